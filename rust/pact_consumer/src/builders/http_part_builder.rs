@@ -1,12 +1,17 @@
 use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
 
 use pact_models::bodies::OptionalBody;
+use pact_models::content_types::{ContentType, detect_content_type_from_bytes};
 use pact_models::expression_parser::DataType;
 use pact_models::generators::{Generator, GeneratorCategory, Generators};
 use pact_models::headers::parse_header;
-use pact_models::matchingrules::MatchingRules;
-use pact_models::path_exp::DocPath;
+use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory, MatchingRules, RuleLogic};
+use pact_models::path_exp::{DocPath, PathToken};
 
+use crate::builders::multipart::{build_multipart_body, multipart_boundary};
+use crate::builders::MultipartPart;
 use crate::prelude::*;
 
 /// Various methods shared between `RequestBuilder` and `ResponseBuilder`.
@@ -110,6 +115,123 @@ pub trait HttpPartBuilder {
       self
     }
 
+    /// Specify a header with multiple values that can be received in any order. Each value is
+    /// added to the header the same way as [`HttpPartBuilder::header`], and the header is marked
+    /// so that the values are compared as a set instead of in the order they were defined.
+    ///
+    /// ```
+    /// use pact_consumer::prelude::*;
+    /// use pact_consumer::*;
+    /// use pact_consumer::builders::RequestBuilder;
+    ///
+    /// RequestBuilder::default()
+    ///     .unordered_header("Vary", "Accept")
+    ///     .unordered_header("Vary", "Origin");
+    /// ```
+    fn unordered_header<N, V>(&mut self, name: N, value: V) -> &mut Self
+      where
+        N: Into<String>,
+        V: Into<StringPattern>,
+    {
+      let name = name.into();
+      self.header(name.as_str(), value);
+      {
+        let (_, rules) = self.headers_and_matching_rules_mut();
+        let mut path = DocPath::root();
+        path.push_field(name);
+        let category = rules.add_category("header");
+        let defined = category.rules.get(&path)
+          .map(|rules| rules.rules.contains(&MatchingRule::UnorderedValues))
+          .unwrap_or(false);
+        if !defined {
+          category.add_rule(path, MatchingRule::UnorderedValues, RuleLogic::And);
+        }
+      }
+      self
+    }
+
+    /// Specify a header with a JSON value. The header will be matched by parsing the values as
+    /// JSON and applying any matching rules from the pattern the same way as for bodies.
+    ///
+    /// ```
+    /// use pact_consumer::prelude::*;
+    /// use pact_consumer::*;
+    /// use pact_consumer::builders::RequestBuilder;
+    ///
+    /// RequestBuilder::default()
+    ///     .json_header("X-Data", json_pattern!({ "id": like!(100), "tags": each_like!("a") }));
+    /// ```
+    fn json_header<N, V>(&mut self, name: N, value: V) -> &mut Self
+      where
+        N: Into<String>,
+        V: Into<JsonPattern>,
+    {
+      let name = name.into();
+      let value = value.into();
+      let example = value.to_example().to_string();
+      {
+        let (headers, rules) = self.headers_and_matching_rules_mut();
+        let entry = headers.keys().cloned().find(|k| k.to_lowercase() == name.to_lowercase());
+        if let Some(key) = entry {
+          headers.insert(key, vec![example]);
+        } else {
+          headers.insert(name.clone(), vec![example]);
+        }
+        let mut path = DocPath::root();
+        path.push_field(name);
+        let category = rules.add_category("header");
+        category.add_rule(path.clone(), MatchingRule::JsonValue, RuleLogic::And);
+        value.extract_matching_rules(path, category)
+      }
+      self
+    }
+
+    /// Add a generator to the part, which will be used to generate the value when the part is
+    /// used in a test (i.e. by the provider verification). The path starts with the category of
+    /// the value to generate (`body`, `header`, `query`, `path` or `status`), followed by the path
+    /// to the value within the category for bodies, or the name for headers and query parameters.
+    /// Panics if the path is not valid.
+    ///
+    /// ```
+    /// use pact_consumer::prelude::*;
+    /// use pact_consumer::builders::RequestBuilder;
+    /// use pact_models::generators::Generator;
+    ///
+    /// RequestBuilder::default()
+    ///     .with_generator("$.body.id", Generator::Uuid(None))
+    ///     .with_generator("$.header.X-Request-Id", Generator::RandomString(10))
+    ///     .with_generator("$.query.page", Generator::RandomInt(1, 10));
+    /// ```
+    fn with_generator<P: AsRef<str>>(&mut self, path: P, generator: Generator) -> &mut Self {
+      let path = path.as_ref();
+      let doc_path = DocPath::new(path)
+        .unwrap_or_else(|err| panic!("'{}' is not a valid generator path - {}", path, err));
+      let category = doc_path.first_field()
+        .and_then(|field| field.parse::<GeneratorCategory>().ok())
+        .unwrap_or_else(|| panic!("'{}' does not start with a valid generator category", path));
+      let remaining = doc_path.tokens().iter().skip(2).cloned().collect::<Vec<_>>();
+      let sub_path = match category {
+        GeneratorCategory::BODY => {
+          let mut sub_path = DocPath::root();
+          for token in remaining {
+            sub_path.push(token);
+          }
+          sub_path
+        }
+        GeneratorCategory::HEADER | GeneratorCategory::QUERY => match remaining.as_slice() {
+          [PathToken::Field(name)] => {
+            let mut sub_path = DocPath::root();
+            sub_path.push_field(name);
+            sub_path
+          }
+          _ => panic!("'{}' must have the name of the header or query parameter", path)
+        }
+        _ => DocPath::empty()
+      };
+      self.generators().add_generator_with_subcategory(&category, sub_path, generator);
+      self
+    }
+
     /// Set the `Content-Type` header.
     fn content_type<CT>(&mut self, content_type: CT) -> &mut Self
     where
@@ -225,20 +347,180 @@ pub trait HttpPartBuilder {
     }
     self
   }
+
+  /// Specify a MIME multipart body (`multipart/form-data`) made up of the given named parts.
+  /// Each part has its own content type, and any matching rules for the part are keyed by the
+  /// part name. Parts are matched by name, so the order of the parts in the actual body does
+  /// not matter.
+  ///
+  /// ```
+  /// use pact_consumer::prelude::*;
+  /// use pact_consumer::*;
+  /// use pact_consumer::builders::{MultipartPart, RequestBuilder};
+  ///
+  /// RequestBuilder::default().multipart_body(vec![
+  ///   MultipartPart::json("metadata", json_pattern!({ "id": like!(100) })),
+  ///   MultipartPart::file("photo", "photo.png", "image/png", &b"\x89PNG\r\n\x1a\n"[..])
+  /// ]);
+  /// ```
+  fn multipart_body(&mut self, parts: Vec<MultipartPart>) -> &mut Self {
+    let boundary = multipart_boundary();
+    let content_type = format!("multipart/form-data; boundary={}", boundary);
+    self.header("Content-Type", term!(r"multipart/form-data;(\s*charset=[^;]*;)?\s*boundary=.*", content_type.as_str()));
+    {
+      let (body_ref, rules) = self.body_and_matching_rules_mut();
+      let body = build_multipart_body(&parts, &boundary, rules.add_category("body"));
+      *body_ref = OptionalBody::Present(body, ContentType::parse(content_type.as_str()).ok(), None);
+    }
+    self
+  }
+
+  /// Specify a body literal loaded from the file at the given path. The file is read when this
+  /// method is called. The content type is inferred from the file extension, or else from the
+  /// contents of the file. This does not allow using patterns.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the file can not be read.
+  ///
+  /// ```no_run
+  /// use pact_consumer::prelude::*;
+  /// use pact_consumer::builders::RequestBuilder;
+  ///
+  /// RequestBuilder::default().body_from_file("tests/fixtures/report.csv");
+  /// ```
+  fn body_from_file<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+    let path = path.as_ref();
+    let data = read_body_file(path);
+    let content_type = content_type_for_file(path, &data);
+    {
+      let (body_ref, _) = self.body_and_matching_rules_mut();
+      *body_ref = OptionalBody::Present(data.into(), content_type, None);
+    }
+    self
+  }
+
+  /// Specify a body literal loaded from the file at the given path, with the given content type.
+  /// The file is read when this method is called. This does not allow using patterns.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the file can not be read.
+  ///
+  /// ```no_run
+  /// use pact_consumer::prelude::*;
+  /// use pact_consumer::builders::RequestBuilder;
+  ///
+  /// RequestBuilder::default().body_from_file2("tests/fixtures/report.data", "text/csv");
+  /// ```
+  fn body_from_file2<P: AsRef<Path>, C: Into<String>>(&mut self, path: P, content_type: C) -> &mut Self {
+    let data = read_body_file(path.as_ref());
+    {
+      let (body_ref, _) = self.body_and_matching_rules_mut();
+      *body_ref = OptionalBody::Present(data.into(), content_type.into().parse().ok(), None);
+    }
+    self
+  }
+
+  /// Specify a JSON body loaded from the file at the given path. The file is read and parsed
+  /// when this method is called.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the file can not be read, or does not contain valid JSON.
+  ///
+  /// ```no_run
+  /// use pact_consumer::prelude::*;
+  /// use pact_consumer::builders::RequestBuilder;
+  ///
+  /// RequestBuilder::default().json_body_from_file("tests/fixtures/order.json");
+  /// ```
+  fn json_body_from_file<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+    self.json_body_from_file_with_rules(path, JsonPattern::null())
+  }
+
+  /// Specify a JSON body loaded from the file at the given path, with the matching rules from the
+  /// given pattern. The file provides the example body, and the pattern is only used for its
+  /// matching rules, so it only needs to contain the parts of the body that require them. The
+  /// file is read and parsed when this method is called.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the file can not be read, or does not contain valid JSON.
+  ///
+  /// ```no_run
+  /// use pact_consumer::prelude::*;
+  /// use pact_consumer::*;
+  /// use pact_consumer::builders::RequestBuilder;
+  ///
+  /// RequestBuilder::default().json_body_from_file_with_rules("tests/fixtures/order.json", json_pattern!({
+  ///     "id": like!(100),
+  /// }));
+  /// ```
+  fn json_body_from_file_with_rules<P: AsRef<Path>, B: Into<JsonPattern>>(&mut self, path: P, rules: B) -> &mut Self {
+    let path = path.as_ref();
+    let data = read_body_file(path);
+    let json: serde_json::Value = serde_json::from_slice(&data)
+      .unwrap_or_else(|err| panic!("Body file '{}' does not contain valid JSON - {}", path.display(), err));
+    let pattern = rules.into();
+    {
+      let (body_ref, rules) = self.body_and_matching_rules_mut();
+      *body_ref = OptionalBody::Present(json.to_string().into(), Some("application/json".into()), None);
+      pattern.extract_matching_rules(DocPath::root(), rules.add_category("body"));
+    }
+    self
+  }
+}
+
+/// Adds the cookie matching rule to the header at the path, if it has not already been added.
+pub(crate) fn add_cookie_matching_rule(category: &mut MatchingRuleCategory, path: &DocPath) {
+  let defined = category.rules.get(path)
+    .map(|rules| rules.rules.contains(&MatchingRule::Cookie))
+    .unwrap_or(false);
+  if !defined {
+    category.add_rule(path.clone(), MatchingRule::Cookie, RuleLogic::And);
+  }
+}
+
+fn read_body_file(path: &Path) -> Vec<u8> {
+  fs::read(path)
+    .unwrap_or_else(|err| panic!("Could not read the body from file '{}' - {}", path.display(), err))
+}
+
+fn content_type_for_file(path: &Path, data: &[u8]) -> Option<ContentType> {
+  let content_type = match path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.to_lowercase()).as_deref() {
+    Some("json") => Some("application/json"),
+    Some("xml") => Some("application/xml"),
+    Some("html") | Some("htm") => Some("text/html"),
+    Some("txt") => Some("text/plain"),
+    Some("csv") => Some("text/csv"),
+    Some("pdf") => Some("application/pdf"),
+    Some("png") => Some("image/png"),
+    Some("jpg") | Some("jpeg") => Some("image/jpeg"),
+    Some("gif") => Some("image/gif"),
+    _ => None
+  };
+  content_type
+    .and_then(|ct| ContentType::parse(ct).ok())
+    .or_else(|| detect_content_type_from_bytes(data))
+    .or_else(|| ContentType::parse("application/octet-stream").ok())
 }
 
 #[cfg(test)]
 mod tests {
   use std::collections::HashMap;
+  use std::fs;
 
   use expectest::prelude::*;
   use maplit::hashmap;
+  use pact_models::generators::{Generator, GeneratorCategory};
   use pact_models::matchingrules::MatchingRule;
   use pact_models::matchingrules_list;
+  use pact_models::path_exp::DocPath;
   use regex::Regex;
   use serde_json::json;
 
-  use crate::builders::{HttpPartBuilder, PactBuilder};
+  use crate::builders::{HttpPartBuilder, MultipartPart, PactBuilder};
   use crate::patterns::{Like, Term};
 
   #[test_log::test]
@@ -297,6 +579,39 @@ mod tests {
     assert_requests_with_context_do_not_match!(actual, expected, bad_context);
   }
 
+  #[test]
+  fn with_generator_stores_the_generator_under_the_category() {
+    let pact = PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.request
+          .json_body(json!({ "id": "abc" }))
+          .with_generator("$.body.id", Generator::Uuid(None))
+          .with_generator("$.header.X-Request-Id", Generator::RandomString(10))
+          .with_generator("$.query.page", Generator::RandomInt(1, 10))
+          .with_generator("$.path", Generator::RandomString(5));
+        i
+      })
+      .build();
+
+    let interaction = pact.interactions().first().unwrap().as_request_response().unwrap();
+    let generators = interaction.request.generators.categories;
+    expect!(generators.get(&GeneratorCategory::BODY).unwrap().get(&DocPath::new_unwrap("$.id")))
+      .to(be_some().value(&Generator::Uuid(None)));
+    expect!(generators.get(&GeneratorCategory::HEADER).unwrap().get(&DocPath::new_unwrap("$['X-Request-Id']")))
+      .to(be_some().value(&Generator::RandomString(10)));
+    expect!(generators.get(&GeneratorCategory::QUERY).unwrap().get(&DocPath::new_unwrap("$.page")))
+      .to(be_some().value(&Generator::RandomInt(1, 10)));
+    expect!(generators.get(&GeneratorCategory::PATH).unwrap().get(&DocPath::empty()))
+      .to(be_some().value(&Generator::RandomString(5)));
+  }
+
+  #[test]
+  #[should_panic(expected = "does not start with a valid generator category")]
+  fn with_generator_panics_with_an_invalid_category() {
+    let mut builder = crate::builders::RequestBuilder::default();
+    builder.with_generator("$.other.id", Generator::Uuid(None));
+  }
+
   #[test]
   fn body_literal() {
     let pattern = PactBuilder::new("C", "P")
@@ -382,6 +697,80 @@ mod tests {
     }));
   }
 
+  #[test]
+  fn unordered_header() {
+    let pattern = PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.request.unordered_header("Vary", "Accept");
+        i.request.unordered_header("Vary", "Origin");
+        i
+      })
+      .build();
+    let interactions = pattern.interactions();
+    let first_interaction = interactions.first().unwrap().as_request_response().unwrap();
+    expect!(first_interaction.request.headers.clone()).to(be_some().value(hashmap!{
+      "Vary".to_string() => vec![ "Accept".to_string(), "Origin".to_string() ]
+    }));
+    expect!(first_interaction.request.matching_rules.rules_for_category("header").unwrap()).to(
+      be_equal_to(matchingrules_list! {
+        "header"; "$.Vary" => [ MatchingRule::UnorderedValues ]
+      })
+    );
+
+    let good = PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.request.header("Vary", "Origin");
+        i.request.header("Vary", "Accept");
+        i
+      })
+      .build();
+    let bad = PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.request.header("Vary", "Origin");
+        i
+      })
+      .build();
+    assert_requests_match!(good, pattern);
+    assert_requests_do_not_match!(bad, pattern);
+  }
+
+  #[test_log::test]
+  fn json_header() {
+    let pattern = PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.request.json_header("X-Data", json_pattern!({ "id": like!(100), "tags": each_like!("a") }));
+        i
+      })
+      .build();
+    let interactions = pattern.interactions();
+    let first_interaction = interactions.first().unwrap().as_request_response().unwrap();
+    expect!(first_interaction.request.headers.clone()).to(be_some().value(hashmap!{
+      "X-Data".to_string() => vec![ r#"{"id":100,"tags":["a"]}"#.to_string() ]
+    }));
+
+    let good = PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.request.json_header("X-Data", json!({ "id": 2, "tags": ["b", "c"] }));
+        i
+      })
+      .build();
+    let bad = PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.request.json_header("X-Data", json!({ "id": "2", "tags": [] }));
+        i
+      })
+      .build();
+    let invalid = PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.request.header("X-Data", "not JSON");
+        i
+      })
+      .build();
+    assert_requests_match!(good, pattern);
+    assert_requests_do_not_match!(bad, pattern);
+    assert_requests_do_not_match!(invalid, pattern);
+  }
+
   #[test]
   fn multi_value_header() {
     let pattern = PactBuilder::new("C", "P")
@@ -401,4 +790,128 @@ mod tests {
       ]
     }));
   }
+
+  #[test]
+  fn multipart_body_pattern() {
+    let png: &[u8] = &[
+      0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a,
+      0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52
+    ];
+    let pattern = PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.request.multipart_body(vec![
+          MultipartPart::json("metadata", json_pattern!({ "id": Like::new(json_pattern!(100)) })),
+          MultipartPart::file("photo", "photo.png", "image/png", png)
+        ]);
+        i
+      })
+      .build();
+    let good = PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.request.multipart_body(vec![
+          MultipartPart::file("photo", "other.png", "image/png", png),
+          MultipartPart::json("metadata", json_pattern!({ "id": 200 }))
+        ]);
+        i
+      })
+      .build();
+    let bad = PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.request.multipart_body(vec![
+          MultipartPart::json("metadata", json_pattern!({ "id": "200" })),
+          MultipartPart::file("photo", "photo.png", "image/png", "not a PNG")
+        ]);
+        i
+      })
+      .build();
+    assert_requests_match!(good, pattern);
+    assert_requests_do_not_match!(bad, pattern);
+
+    let request = pattern.interactions().first().unwrap().as_request_response().unwrap().request;
+    expect!(request.matching_rules.rules_for_category("body").unwrap()).to(
+      be_equal_to(matchingrules_list! {
+        "body";
+        "$.metadata.id" => [ MatchingRule::Type ],
+        "$.photo" => [ MatchingRule::ContentType("image/png".to_string()) ]
+      })
+    );
+  }
+
+  #[test]
+  fn body_from_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("report.csv");
+    fs::write(&path, "id,name\n1,Fred\n").unwrap();
+    let pact = PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.request.body_from_file(&path);
+        i.response.body_from_file2(&path, "text/plain");
+        i
+      })
+      .build();
+    let interaction = pact.interactions().first().unwrap().as_request_response().unwrap();
+    expect!(interaction.request.body.value_as_string().unwrap()).to(be_equal_to("id,name\n1,Fred\n"));
+    expect!(interaction.request.body.content_type().unwrap().to_string()).to(be_equal_to("text/csv"));
+    expect!(interaction.response.body.content_type().unwrap().to_string()).to(be_equal_to("text/plain"));
+  }
+
+  #[test]
+  fn json_body_from_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("order.json");
+    fs::write(&path, r#"{ "id": 100, "items": ["a", "b"] }"#).unwrap();
+    let pattern = PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.request.json_body_from_file_with_rules(&path, json_pattern!({
+          "id": Like::new(json_pattern!(100))
+        }));
+        i
+      })
+      .build();
+    let good = PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.request.json_body(json_pattern!({ "id": 200, "items": ["a", "b"] }));
+        i
+      })
+      .build();
+    let bad = PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.request.json_body(json_pattern!({ "id": 200, "items": ["c"] }));
+        i
+      })
+      .build();
+    assert_requests_match!(good, pattern);
+    assert_requests_do_not_match!(bad, pattern);
+
+    let request = pattern.interactions().first().unwrap().as_request_response().unwrap().request;
+    expect!(request.body.content_type().unwrap().to_string()).to(be_equal_to("application/json"));
+    expect!(request.matching_rules.rules_for_category("body").unwrap()).to(
+      be_equal_to(matchingrules_list! {
+        "body"; "$.id" => [ MatchingRule::Type ]
+      })
+    );
+  }
+
+  #[test]
+  #[should_panic(expected = "Could not read the body from file 'does-not-exist.json'")]
+  fn json_body_from_missing_file() {
+    PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.request.json_body_from_file("does-not-exist.json");
+        i
+      });
+  }
+
+  #[test]
+  #[should_panic(expected = "does not contain valid JSON")]
+  fn json_body_from_file_with_invalid_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("order.json");
+    fs::write(&path, "{ not json").unwrap();
+    PactBuilder::new("C", "P")
+      .interaction("I", "", |mut i| {
+        i.request.json_body_from_file(&path);
+        i
+      });
+  }
 }
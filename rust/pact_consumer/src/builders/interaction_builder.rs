@@ -63,8 +63,8 @@ impl InteractionBuilder {
           self
   }
 
-  /// Sets this interaction as pending. This will permantly mark the interaction as pending in the
-  /// Pact file, and it will not cause a verification failure.
+  /// Sets this interaction as pending. This will permanently mark the interaction as pending in the
+  /// Pact file (V4 only), and it will not cause a verification failure.
   pub fn pending(&mut self, pending: bool) -> &mut Self {
     self.pending = Some(pending);
     self
@@ -260,3 +260,47 @@ mod plugin_tests {
     }));
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use expectest::prelude::*;
+  use pact_models::PactSpecification;
+  use pact_models::v4::interaction::V4Interaction;
+  use serde_json::json;
+
+  use crate::builders::InteractionBuilder;
+
+  #[test]
+  fn given_with_params_keeps_the_types_of_the_parameters() {
+    let mut builder = InteractionBuilder::new("test", "");
+    builder.given_with_params("a user exists", &json!({
+      "id": 100,
+      "name": "Fred",
+      "active": true,
+      "roles": ["admin"]
+    }));
+
+    let expected_states = json!([{
+      "name": "a user exists",
+      "params": {
+        "id": 100,
+        "name": "Fred",
+        "active": true,
+        "roles": ["admin"]
+      }
+    }]);
+    expect!(&builder.build().to_json(&PactSpecification::V3)["providerStates"]).to(be_equal_to(&expected_states));
+    expect!(&builder.build_v4().to_json()["providerStates"]).to(be_equal_to(&expected_states));
+  }
+
+  #[test]
+  fn pending_is_written_to_the_v4_interaction() {
+    let mut builder = InteractionBuilder::new("test", "");
+    expect!(builder.build_v4().pending).to(be_false());
+    expect!(&builder.build_v4().to_json()["pending"]).to(be_equal_to(&json!(false)));
+
+    builder.pending(true);
+    expect!(builder.build_v4().pending).to(be_true());
+    expect!(&builder.build_v4().to_json()["pending"]).to(be_equal_to(&json!(true)));
+  }
+}
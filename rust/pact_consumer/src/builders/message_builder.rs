@@ -191,6 +191,31 @@ impl MessageInteractionBuilder {
     self
   }
 
+  /// Adds a key/value pair to the message metadata, where the value is a `JsonPattern` that may
+  /// include matching rules. The example value will be stored in the metadata, and any matching
+  /// rules will be applied to the metadata value when the message is verified.
+  ///
+  /// ```
+  /// use pact_consumer::prelude::*;
+  /// use pact_consumer::*;
+  /// use pact_consumer::builders::MessageInteractionBuilder;
+  ///
+  /// MessageInteractionBuilder::new("hello message")
+  ///   .metadata_matching("correlationId", term!("^[0-9a-f]{8}(-[0-9a-f]{4}){3}-[0-9a-f]{12}$",
+  ///     "fb5a885f-f7e8-4a50-950f-c1a64a94d500"));
+  /// ```
+  pub fn metadata_matching<S: Into<String>, P: Into<JsonPattern>>(&mut self, key: S, value: P) -> &mut Self {
+    let key = key.into();
+    let value = value.into();
+    let metadata = self.message_contents.metadata
+      .get_or_insert_with(|| hashmap!{});
+    metadata.insert(key.clone(), value.to_example());
+    let rules = self.message_contents.metadata_rules
+      .get_or_insert_with(|| MatchingRuleCategory::empty("metadata"));
+    value.extract_matching_rules(DocPath::root().join(key), rules);
+    self
+  }
+
   /// Specify a unique key for this interaction. This key will be used to determine equality of
   /// the interaction, so must be unique.
   pub fn with_key<G: Into<String>>(&mut self, key: G) -> &mut Self {
@@ -212,6 +237,8 @@ impl MessageInteractionBuilder {
     let mut rules = MatchingRules::default();
     rules.add_category("body")
       .add_rules(self.message_contents.rules.as_ref().cloned().unwrap_or_default());
+    rules.add_category("metadata")
+      .add_rules(self.message_contents.metadata_rules.as_ref().cloned().unwrap_or_default());
 
     #[allow(unused_mut, unused_assignments)] let mut plugin_config = hashmap!{};
     #[cfg(feature = "plugins")]
@@ -264,6 +291,8 @@ impl MessageInteractionBuilder {
     let mut rules = MatchingRules::default();
     rules.add_category("body")
       .add_rules(self.message_contents.rules.as_ref().cloned().unwrap_or_default());
+    rules.add_category("metadata")
+      .add_rules(self.message_contents.metadata_rules.as_ref().cloned().unwrap_or_default());
 
     let metadata = self.message_contents.metadata.as_ref()
       .map(|md| md.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
@@ -464,9 +493,12 @@ impl MessageInteractionBuilder {
 mod tests {
   use expectest::prelude::*;
   use maplit::hashmap;
+  use pact_models::matchingrules::{Category, MatchingRule, RuleList};
+  use pact_models::path_exp::DocPath;
   use serde_json::json;
 
   use crate::builders::MessageInteractionBuilder;
+  use crate::term;
 
   #[test]
   fn supports_setting_metadata_values() {
@@ -481,4 +513,20 @@ mod tests {
       "c".to_string() => json!([1, 2, 3])
     }));
   }
+
+  #[test]
+  fn supports_setting_metadata_values_with_matching_rules() {
+    let message = MessageInteractionBuilder::new("test")
+      .metadata("a", "a")
+      .metadata_matching("correlationId", term!("^[0-9a-f\\-]+$", "fb5a885f-f7e8-4a50-950f-c1a64a94d500"))
+      .build();
+    expect!(message.contents.metadata).to(be_equal_to(hashmap! {
+      "a".to_string() => json!("a"),
+      "correlationId".to_string() => json!("fb5a885f-f7e8-4a50-950f-c1a64a94d500")
+    }));
+    let rules = message.contents.matching_rules.rules_for_category(Category::METADATA).unwrap();
+    expect!(rules.rules).to(be_equal_to(hashmap! {
+      DocPath::new_unwrap("$.correlationId") => RuleList::new(MatchingRule::Regex("^[0-9a-f\\-]+$".to_string()))
+    }));
+  }
 }
@@ -1,8 +1,14 @@
 use std::collections::VecDeque;
 use std::env;
+use std::future::Future;
 use std::panic::RefUnwindSafe;
 use std::path::PathBuf;
+use std::pin::Pin;
 use std::sync::mpsc::channel;
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use futures::{FutureExt, Stream};
 use itertools::Itertools;
 
 use maplit::hashmap;
@@ -17,7 +23,7 @@ use pact_models::v4::V4InteractionType;
 use tokio::runtime::Handle;
 use tracing::{debug, error, info, warn};
 
-use pact_matching::generators::{apply_generators_to_sync_message, generate_message};
+use pact_matching::generators::{apply_generators_to_async_message, apply_generators_to_sync_message, generate_message};
 
 /// Iterator over the messages build with the PactBuilder
 pub struct MessageIterator<MT> {
@@ -147,28 +153,123 @@ impl <MT> Iterator for MessageIterator<MT> {
 impl <MT> Drop for MessageIterator<MT> {
   fn drop(&mut self) {
     if !::std::thread::panicking() {
+      write_pact_file(self.pact.as_ref(), &self.output_dir);
+    }
+  }
+}
+
+/// Stream over the messages built with the PactBuilderAsync. The generators are applied to each
+/// message when it is polled, so any async work (i.e. plugin content generation) is done as each
+/// message is consumed.
+pub struct MessageStream<MT> {
+  pact: Box<dyn Pact + Send + Sync + RefUnwindSafe>,
+  message_list: VecDeque<MT>,
+  generate: fn(MT) -> BoxFuture<'static, MT>,
+  pending: Option<BoxFuture<'static, MT>>,
+  // Output directory to write pact files to when done
+  output_dir: Option<PathBuf>,
+}
+
+/// Construct a new stream over the asynchronous messages in the pact
+pub fn asynchronous_messages_stream(pact: V4Pact, output_dir: &Option<PathBuf>) -> MessageStream<AsynchronousMessage> {
+  MessageStream {
+    pact: pact.boxed(),
+    message_list: pact.filter_interactions(V4InteractionType::Asynchronous_Messages)
+      .iter()
+      .map(|item| item.as_v4_async_message().unwrap())
+      .collect(),
+    generate: |message| async move {
+      let contents = apply_generators_to_async_message(&message, &GeneratorTestMode::Consumer, &hashmap! {}, &vec![], &hashmap! {}).await;
+      AsynchronousMessage {
+        contents,
+        .. message
+      }
+    }.boxed(),
+    pending: None,
+    output_dir: output_dir.clone()
+  }
+}
 
-      // Write out the Pact file
-      let output_dir = self.output_dir.as_ref().map(|dir| dir.to_string_lossy().to_string())
-        .unwrap_or_else(|| {
-          let val = env::var("PACT_OUTPUT_DIR");
-          debug!("env:PACT_OUTPUT_DIR = {:?}", val);
-          val.unwrap_or_else(|_| "target/pacts".to_owned())
-        });
-      let overwrite = env::var("PACT_OVERWRITE");
-      debug!("env:PACT_OVERWRITE = {:?}", overwrite);
-
-      let pact_file_name = self.pact.default_file_name();
-      let mut path = PathBuf::from(output_dir);
-      path.push(pact_file_name);
-
-      info!("Writing pact out to '{}'", path.display());
-      let specification = self.pact.specification_version();
-      if let Err(err) = write_pact(self.pact.boxed(), path.as_path(), specification,
-                                   overwrite.unwrap_or_else(|_| String::default()) == "true") {
-        error!("Failed to write pact to file - {}", err);
-        panic!("Failed to write pact to file - {}", err);
+/// Construct a new stream over the synchronous messages in the pact
+pub fn synchronous_messages_stream(pact: V4Pact, output_dir: &Option<PathBuf>) -> MessageStream<SynchronousMessage> {
+  MessageStream {
+    pact: pact.boxed(),
+    message_list: pact.filter_interactions(V4InteractionType::Synchronous_Messages)
+      .iter()
+      .map(|item| item.as_v4_sync_message().unwrap())
+      .collect(),
+    generate: |message| async move {
+      let (request, response) = apply_generators_to_sync_message(&message, &GeneratorTestMode::Consumer, &hashmap! {}, &vec![], &hashmap! {}).await;
+      SynchronousMessage {
+        request,
+        response,
+        .. message
       }
+    }.boxed(),
+    pending: None,
+    output_dir: output_dir.clone()
+  }
+}
+
+impl <MT> Stream for MessageStream<MT> {
+  type Item = MT;
+
+  fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    if self.pending.is_none() {
+      match self.message_list.pop_front() {
+        Some(message) => {
+          let future = (self.generate)(message);
+          self.pending = Some(future);
+        }
+        None => return Poll::Ready(None)
+      }
+    }
+
+    let result = match self.pending.as_mut() {
+      Some(future) => future.as_mut().poll(cx),
+      None => return Poll::Ready(None)
+    };
+    if result.is_ready() {
+      self.pending = None;
     }
+    result.map(Some)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.message_list.len() + usize::from(self.pending.is_some());
+    (len, Some(len))
+  }
+}
+
+impl <MT> Unpin for MessageStream<MT> {}
+
+impl <MT> Drop for MessageStream<MT> {
+  fn drop(&mut self) {
+    if !::std::thread::panicking() {
+      write_pact_file(self.pact.as_ref(), &self.output_dir);
+    }
+  }
+}
+
+fn write_pact_file(pact: &(dyn Pact + Send + Sync + RefUnwindSafe), output_dir: &Option<PathBuf>) {
+  let output_dir = output_dir.as_ref().map(|dir| dir.to_string_lossy().to_string())
+    .unwrap_or_else(|| {
+      let val = env::var("PACT_OUTPUT_DIR");
+      debug!("env:PACT_OUTPUT_DIR = {:?}", val);
+      val.unwrap_or_else(|_| "target/pacts".to_owned())
+    });
+  let overwrite = env::var("PACT_OVERWRITE");
+  debug!("env:PACT_OVERWRITE = {:?}", overwrite);
+
+  let pact_file_name = pact.default_file_name();
+  let mut path = PathBuf::from(output_dir);
+  path.push(pact_file_name);
+
+  info!("Writing pact out to '{}'", path.display());
+  let specification = pact.specification_version();
+  if let Err(err) = write_pact(pact.boxed(), path.as_path(), specification,
+                               overwrite.unwrap_or_else(|_| String::default()) == "true") {
+    error!("Failed to write pact to file - {}", err);
+    panic!("Failed to write pact to file - {}", err);
   }
 }
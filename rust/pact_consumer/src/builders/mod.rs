@@ -11,6 +11,7 @@ mod request_builder;
 mod response_builder;
 mod message_builder;
 mod message_iter;
+mod multipart;
 mod sync_message_builder;
 
 pub use self::interaction_builder::*;
@@ -20,6 +21,7 @@ pub use self::pact_builder_async::*;
 pub use self::request_builder::*;
 pub use self::response_builder::*;
 pub use self::message_builder::*;
+pub use self::multipart::MultipartPart;
 pub use self::sync_message_builder::*;
 
 #[test]
@@ -0,0 +1,143 @@
+use bytes::{Bytes, BytesMut};
+use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory, RuleLogic};
+use pact_models::path_exp::DocPath;
+use uuid::Uuid;
+
+use crate::patterns::{JsonPattern, Pattern, StringPattern};
+
+/// The contents of a part of a MIME multipart body
+#[derive(Debug)]
+enum PartContents {
+  Json(JsonPattern),
+  Text(StringPattern),
+  Binary(Bytes)
+}
+
+/// A single named part of a MIME multipart body, used with `HttpPartBuilder::multipart_body`.
+/// Each part has its own content type, and any matching rules from the body pattern of the part
+/// are keyed by the part name.
+#[derive(Debug)]
+pub struct MultipartPart {
+  name: String,
+  filename: Option<String>,
+  content_type: String,
+  contents: PartContents
+}
+
+impl MultipartPart {
+  /// Creates a JSON (`application/json`) part with the contents matching the given pattern.
+  ///
+  /// ```
+  /// use pact_consumer::*;
+  /// use pact_consumer::builders::MultipartPart;
+  ///
+  /// MultipartPart::json("metadata", json_pattern!({ "id": like!(100) }));
+  /// ```
+  pub fn json<N: Into<String>, P: Into<JsonPattern>>(name: N, body: P) -> Self {
+    MultipartPart {
+      name: name.into(),
+      filename: None,
+      content_type: "application/json".to_string(),
+      contents: PartContents::Json(body.into())
+    }
+  }
+
+  /// Creates a text (`text/plain`) part with the contents matching the given pattern.
+  ///
+  /// ```
+  /// use pact_consumer::*;
+  /// use pact_consumer::builders::MultipartPart;
+  ///
+  /// MultipartPart::text("age", term!("^\\d+ months?$", "1 month"));
+  /// ```
+  pub fn text<N: Into<String>, P: Into<StringPattern>>(name: N, body: P) -> Self {
+    MultipartPart {
+      name: name.into(),
+      filename: None,
+      content_type: "text/plain".to_string(),
+      contents: PartContents::Text(body.into())
+    }
+  }
+
+  /// Creates a file part with the given contents. The contents of the part will only be matched
+  /// against the content type, which is detected from the actual data of the part.
+  ///
+  /// ```
+  /// use pact_consumer::builders::MultipartPart;
+  ///
+  /// MultipartPart::file("photo", "photo.png", "image/png", &b"\x89PNG\r\n\x1a\n"[..]);
+  /// ```
+  pub fn file<N, F, C, B>(name: N, filename: F, content_type: C, data: B) -> Self
+    where N: Into<String>, F: Into<String>, C: Into<String>, B: Into<Bytes> {
+    MultipartPart {
+      name: name.into(),
+      filename: Some(filename.into()),
+      content_type: content_type.into(),
+      contents: PartContents::Binary(data.into())
+    }
+  }
+
+  /// Sets the filename for the part.
+  pub fn with_filename<S: Into<String>>(mut self, filename: S) -> Self {
+    self.filename = Some(filename.into());
+    self
+  }
+
+  /// Sets the content type of the part.
+  pub fn with_content_type<S: Into<String>>(mut self, content_type: S) -> Self {
+    self.content_type = content_type.into();
+    self
+  }
+
+  fn example_bytes(&self) -> Bytes {
+    match &self.contents {
+      PartContents::Json(pattern) => Bytes::from(pattern.to_example().to_string()),
+      PartContents::Text(pattern) => Bytes::from(pattern.to_example_bytes()),
+      PartContents::Binary(data) => data.clone()
+    }
+  }
+
+  fn write_to(&self, boundary: &str, buffer: &mut BytesMut) {
+    buffer.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
+    buffer.extend_from_slice(format!("Content-Type: {}\r\n", self.content_type).as_bytes());
+    match &self.filename {
+      Some(filename) => buffer.extend_from_slice(
+        format!("Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\r\n", self.name, filename).as_bytes()),
+      None => buffer.extend_from_slice(
+        format!("Content-Disposition: form-data; name=\"{}\"\r\n\r\n", self.name).as_bytes())
+    }
+    buffer.extend_from_slice(&self.example_bytes());
+    buffer.extend_from_slice(b"\r\n");
+  }
+
+  fn extract_matching_rules(&self, rules: &mut MatchingRuleCategory) {
+    let path = DocPath::root().join(self.name.as_str());
+    match &self.contents {
+      PartContents::Json(pattern) => pattern.extract_matching_rules(path, rules),
+      PartContents::Text(pattern) => pattern.extract_matching_rules(path, rules),
+      PartContents::Binary(_) => rules.add_rule(path,
+        MatchingRule::ContentType(self.content_type.clone()), RuleLogic::And)
+    }
+  }
+}
+
+/// Generates a new boundary marker for a multipart body
+pub(crate) fn multipart_boundary() -> String {
+  Uuid::new_v4().simple().to_string()
+}
+
+/// Builds the multipart body from the parts, and adds any matching rules for the parts to the
+/// body rules.
+pub(crate) fn build_multipart_body(
+  parts: &[MultipartPart],
+  boundary: &str,
+  rules: &mut MatchingRuleCategory
+) -> Bytes {
+  let mut buffer = BytesMut::new();
+  for part in parts {
+    part.write_to(boundary, &mut buffer);
+    part.extract_matching_rules(rules);
+  }
+  buffer.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+  buffer.freeze()
+}
@@ -1,4 +1,5 @@
 use std::fmt::{Debug, Formatter};
+use std::net::SocketAddr;
 use std::panic::RefUnwindSafe;
 use std::path::PathBuf;
 use pact_mock_server::mock_server::MockServerConfig;
@@ -18,6 +19,7 @@ use pact_models::v4::sync_message::SynchronousMessage;
 #[cfg(feature = "plugins")] use pact_plugin_driver::plugin_models::PluginDependency;
 use tracing::trace;
 
+use pact_matching::MatchingConfig;
 use pact_matching::metrics::{MetricEvent, send_metrics};
 
 use crate::builders::message_builder::MessageInteractionBuilder;
@@ -204,6 +206,45 @@ impl PactBuilder {
     self.pact.boxed()
   }
 
+  /// Enables the matching settings (i.e. strict header whitespace or case-insensitive keys) that
+  /// are set in the config. These are stored in the Pact metadata, so will also be applied when
+  /// the Pact is verified. See `MatchingConfig` for the settings.
+  pub fn with_matching_config(&mut self, config: MatchingConfig) -> &mut Self {
+    for key in config.metadata_keys() {
+      self.pact.add_md_value(key, "true");
+    }
+    self
+  }
+
+  /// Start a mock server that uses TLS (HTTPS) running in a background thread. The TLS
+  /// configuration is either a self-signed certificate (`MockServerTls::default()`), or the
+  /// provided PEM encoded certificate and private key. The URL of the mock server will be an
+  /// `https://` URL.
+  ///
+  /// Panics:
+  /// Will panic if the TLS configuration is not valid or the mock server can not be started.
+  #[cfg(feature = "tls")]
+  pub fn start_mock_server_tls(
+    &self,
+    tls: MockServerTls,
+    mock_server_config: Option<MockServerConfig>
+  ) -> Box<dyn ValidatingMockServer> {
+    ValidatingHttpMockServer::start_tls(self.build(), self.output_dir.clone(), mock_server_config, tls)
+  }
+
+  /// Start a mock server bound to the given address (interface and port), running in a
+  /// background thread. This is useful when the system under test is configured with a fixed
+  /// URL. An error is returned if the mock server can not bind to the address (i.e. the port is
+  /// already in use), rather than another port being picked. The URL of the mock server will
+  /// reflect the bound address.
+  pub fn start_mock_server_on(
+    &self,
+    address: SocketAddr,
+    mock_server_config: Option<MockServerConfig>
+  ) -> anyhow::Result<Box<dyn ValidatingMockServer>> {
+    ValidatingHttpMockServer::start_on(self.build(), self.output_dir.clone(), address, mock_server_config)
+  }
+
   /// Sets the output directory to write pact files to
   #[deprecated(note = "Use with_output_dir")]
   pub fn output_dir<D: Into<PathBuf>>(&mut self, dir: D) -> &mut Self {
@@ -322,7 +363,9 @@ mod tests {
   use bytes::Bytes;
   use expectest::prelude::*;
   use maplit::hashmap;
+  use pact_matching::MatchingConfig;
   use pact_models::bodies::OptionalBody;
+  use pact_models::PactSpecification;
   use pact_models::matchingrules::{Category, MatchingRuleCategory, MatchingRules};
   use pact_models::provider_states::ProviderState;
   use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
@@ -331,6 +374,24 @@ mod tests {
 
   use crate::builders::{HttpPartBuilder, PactBuilder};
 
+  #[test]
+  fn matching_config_is_stored_in_the_pact_metadata() {
+    let config = MatchingConfig {
+      strict_header_whitespace: true,
+      case_insensitive_keys: true,
+      strict_number_types: true,
+      .. MatchingConfig::default()
+    };
+    let pact = PactBuilder::new_v4("Consumer", "Alice Service")
+      .with_matching_config(config)
+      .build();
+    expect!(MatchingConfig::from_pact(pact.as_ref())).to(be_equal_to(config));
+    let json = pact.to_json(PactSpecification::V4).unwrap();
+    expect!(&json["metadata"]["pactRust"]["strictHeaderWhitespace"]).to(be_equal_to(&Value::String("true".to_string())));
+    expect!(&json["metadata"]["pactRust"]["caseInsensitiveKeys"]).to(be_equal_to(&Value::String("true".to_string())));
+    expect!(&json["metadata"]["pactRust"]["strictContentType"]).to(be_equal_to(&Value::Null));
+  }
+
   #[test]
   fn v4_calc_key_test() {
     let pact = PactBuilder::new_v4("Consumer", "Alice Service")
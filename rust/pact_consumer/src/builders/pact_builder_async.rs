@@ -1,4 +1,5 @@
 use std::future::Future;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
 use async_trait::async_trait;
@@ -16,10 +17,12 @@ use pact_models::v4::sync_message::SynchronousMessage;
 #[cfg(feature = "plugins")] use pact_plugin_driver::plugin_models::{PluginDependency, PluginDependencyType};
 use tracing::trace;
 
+use pact_matching::MatchingConfig;
 use pact_matching::metrics::{MetricEvent, send_metrics};
 
 use crate::builders::message_builder::MessageInteractionBuilder;
-use crate::builders::message_iter::{asynchronous_messages_iter, MessageIterator, synchronous_messages_iter};
+use crate::builders::message_iter::{asynchronous_messages_iter, asynchronous_messages_stream, MessageIterator, MessageStream};
+use crate::builders::message_iter::{synchronous_messages_iter, synchronous_messages_stream};
 use crate::builders::sync_message_builder::SyncMessageInteractionBuilder;
 use crate::mock_server::http_mock_server::ValidatingHttpMockServer;
 #[cfg(feature = "plugins")] use crate::mock_server::plugin_mock_server::PluginMockServer;
@@ -181,6 +184,45 @@ impl PactBuilderAsync {
     self.pact.boxed()
   }
 
+  /// Enables the matching settings (i.e. strict header whitespace or case-insensitive keys) that
+  /// are set in the config. These are stored in the Pact metadata, so will also be applied when
+  /// the Pact is verified. See `MatchingConfig` for the settings.
+  pub fn with_matching_config(&mut self, config: MatchingConfig) -> &mut Self {
+    for key in config.metadata_keys() {
+      self.pact.add_md_value(key, "true");
+    }
+    self
+  }
+
+  /// Start a mock server that uses TLS (HTTPS) running in a task (requires a Tokio runtime to be
+  /// already setup). The TLS configuration is either a self-signed certificate
+  /// (`MockServerTls::default()`), or the provided PEM encoded certificate and private key. The
+  /// URL of the mock server will be an `https://` URL.
+  ///
+  /// Panics:
+  /// Will panic if the TLS configuration is not valid or the mock server can not be started.
+  #[cfg(feature = "tls")]
+  pub async fn start_mock_server_tls_async(
+    &self,
+    tls: MockServerTls,
+    mock_server_config: Option<MockServerConfig>
+  ) -> Box<dyn ValidatingMockServer> {
+    ValidatingHttpMockServer::start_tls_async(self.build(), self.output_dir.clone(), mock_server_config, tls).await
+  }
+
+  /// Start a mock server bound to the given address (interface and port), running in a task
+  /// (requires a Tokio runtime to be already setup). This is useful when the system under test
+  /// is configured with a fixed URL. An error is returned if the mock server can not bind to the
+  /// address (i.e. the port is already in use), rather than another port being picked. The URL
+  /// of the mock server will reflect the bound address.
+  pub async fn start_mock_server_on_async(
+    &self,
+    address: SocketAddr,
+    mock_server_config: Option<MockServerConfig>
+  ) -> anyhow::Result<Box<dyn ValidatingMockServer>> {
+    ValidatingHttpMockServer::start_on_async(self.build(), self.output_dir.clone(), address, mock_server_config).await
+  }
+
   /// Sets the output directory to write pact files to
   pub fn output_dir<D: Into<PathBuf>>(&mut self, dir: D) -> &mut Self {
     self.output_dir = Some(dir.into());
@@ -249,6 +291,31 @@ impl PactBuilderAsync {
     });
     synchronous_messages_iter(self.pact.as_v4_pact().unwrap(), &self.output_dir)
   }
+
+  /// Returns a stream over the asynchronous messages in the Pact. Unlike `messages`, the
+  /// generators are applied to each message as it is polled from the stream.
+  pub fn messages_async(&self) -> MessageStream<AsynchronousMessage> {
+    send_metrics(MetricEvent::ConsumerTestRun {
+      interactions: self.pact.interactions().len(),
+      test_framework: "pact_consumer".to_string(),
+      app_name: "pact_consumer".to_string(),
+      app_version: env!("CARGO_PKG_VERSION").to_string()
+    });
+    asynchronous_messages_stream(self.pact.as_v4_pact().unwrap(), &self.output_dir)
+  }
+
+  /// Returns a stream over the synchronous req/res messages in the Pact. Unlike
+  /// `synchronous_messages`, the generators are applied to each message as it is polled from the
+  /// stream.
+  pub fn synchronous_messages_async(&self) -> MessageStream<SynchronousMessage> {
+    send_metrics(MetricEvent::ConsumerTestRun {
+      interactions: self.pact.interactions().len(),
+      test_framework: "pact_consumer".to_string(),
+      app_name: "pact_consumer".to_string(),
+      app_version: env!("CARGO_PKG_VERSION").to_string()
+    });
+    synchronous_messages_stream(self.pact.as_v4_pact().unwrap(), &self.output_dir)
+  }
 }
 
 impl StartMockServer for PactBuilderAsync {
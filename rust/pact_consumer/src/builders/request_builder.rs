@@ -11,8 +11,9 @@ use pact_models::expression_parser::DataType;
 use pact_models::generators::{Generator, GeneratorCategory, Generators};
 #[cfg(feature = "plugins")] use pact_models::http_parts::HttpPart;
 use pact_models::json_utils::body_from_json;
-use pact_models::matchingrules::{Category, MatchingRules};
+use pact_models::matchingrules::{Category, MatchingRule, MatchingRules, RuleLogic};
 use pact_models::path_exp::DocPath;
+use pact_models::query_strings::parse_query_string;
 use pact_models::request::Request;
 use pact_models::v4::http_parts::HttpRequest;
 use pact_models::v4::interaction::InteractionMarkup;
@@ -25,6 +26,7 @@ use serde_json::json;
 use serde_json::Value;
 #[allow(unused_imports)] use tracing::debug;
 
+use crate::builders::http_part_builder::add_cookie_matching_rule;
 use crate::prelude::*;
 use crate::util::GetDefaulting;
 
@@ -77,7 +79,10 @@ impl RequestBuilder {
     }
 
     /// Specify the request path. Defaults to `"/"`.
+    ///
+    /// Panics if the raw request target has been set with `raw_target`.
     pub fn path<P: Into<StringPattern>>(&mut self, path: P) -> &mut Self {
+        self.check_raw_target_not_defined("path");
         let path = path.into();
         self.request.path = path.to_example();
         path.extract_matching_rules(
@@ -87,6 +92,43 @@ impl RequestBuilder {
         self
     }
 
+    /// Specify the request path from its segments. The example path is built by joining the
+    /// examples of the segments with `/`, and any matching rules from a segment pattern only apply
+    /// to that segment of the path. This replaces any path matching rules that have already been
+    /// set.
+    ///
+    /// Panics if the raw request target has been set with `raw_target`.
+    ///
+    /// ```
+    /// use pact_consumer::*;
+    /// use pact_consumer::builders::RequestBuilder;
+    /// use pact_consumer::patterns::StringPattern;
+    /// use regex::Regex;
+    ///
+    /// RequestBuilder::default()
+    ///     .path_segments(vec![
+    ///         StringPattern::from("users"),
+    ///         term!("^[0-9a-f-]{36}$", "0b5d3c1e-7a4c-4f6f-8d0a-4c1e2b3a4d5e").into(),
+    ///         StringPattern::from("orders")
+    ///     ]);
+    /// ```
+    pub fn path_segments<I, P>(&mut self, segments: I) -> &mut Self
+        where
+          I: IntoIterator<Item = P>,
+          P: Into<StringPattern>
+    {
+        self.check_raw_target_not_defined("path");
+        let segments = segments.into_iter().map(|segment| segment.into()).collect::<Vec<StringPattern>>();
+        self.request.path = format!("/{}", segments.iter().map(|segment| segment.to_example()).collect::<Vec<_>>().join("/"));
+
+        self.request.matching_rules.rules.remove(&Category::PATH);
+        let rules = self.request.matching_rules.add_category(Category::PATH);
+        for (index, segment) in segments.iter().enumerate() {
+            segment.extract_matching_rules(DocPath::root().join_index(index), rules);
+        }
+        self
+    }
+
     /// Specify the request path with generators. Defaults to `"/"`.
     pub fn path_from_provider_state<E, P: Into<StringPattern>>(&mut self, expression: E, path: P) -> &mut Self
         where
@@ -102,6 +144,56 @@ impl RequestBuilder {
         self
     }
 
+    /// Specify the raw request target (the path and query string, i.e. `/orders?page=1`). The
+    /// path and query string will be matched together as a single string with the matching rules
+    /// from the pattern, instead of matching the path and query parameters separately. This
+    /// replaces any path and query parameters that have already been set, and `path` and
+    /// `query_param` can not be used after it for the same request.
+    ///
+    /// The actual target is matched as it was received if it is passed to the matching (see
+    /// `pact_matching::match_request_with_raw_target`). Otherwise it is rebuilt from the path and
+    /// parsed query parameters, so the pattern should expect the query parameters sorted by name.
+    ///
+    /// ```
+    /// use pact_consumer::*;
+    /// use pact_consumer::builders::RequestBuilder;
+    /// use regex::Regex;
+    ///
+    /// RequestBuilder::default()
+    ///     .raw_target(term!("^/orders\\?page=\\d+$", "/orders?page=1"));
+    /// ```
+    pub fn raw_target<P: Into<StringPattern>>(&mut self, target: P) -> &mut Self {
+        let target = target.into();
+        let example = target.to_example();
+        match example.split_once('?') {
+            Some((path, query)) => {
+                self.request.path = path.to_string();
+                self.request.query = parse_query_string(query);
+            }
+            None => {
+                self.request.path = example.clone();
+                self.request.query = None;
+            }
+        }
+
+        self.request.matching_rules.rules.remove(&Category::QUERY);
+        self.request.matching_rules.rules.remove(&Category::PATH);
+        let rules = self.request.matching_rules.add_category(Category::PATH);
+        rules.add_rule(DocPath::empty(), MatchingRule::RawTarget, RuleLogic::And);
+        target.extract_matching_rules(DocPath::empty(), rules);
+        self
+    }
+
+    fn check_raw_target_not_defined(&self, part: &str) {
+        let raw_target_defined = self.request.matching_rules.rules_for_category(Category::PATH)
+            .and_then(|rules| rules.rules.get(&DocPath::empty()).cloned())
+            .map(|rules| rules.rules.contains(&MatchingRule::RawTarget))
+            .unwrap_or(false);
+        if raw_target_defined {
+            panic!("The request {} can not be set when the raw request target is used", part);
+        }
+    }
+
     /// Specify a query parameter. You may pass either a single value or
     /// a list of values to represent a repeated parameter.
     ///
@@ -117,11 +209,14 @@ impl RequestBuilder {
     ///
     /// To pass multiple parameters with the same name, call `query_param` more
     /// than once with the same `key`.
+    ///
+    /// Panics if the raw request target has been set with `raw_target`.
     pub fn query_param<K, V>(&mut self, key: K, value: V) -> &mut Self
     where
         K: Into<String>,
         V: Into<StringPattern>,
     {
+        self.check_raw_target_not_defined("query parameters");
         let key = key.into();
         let value = value.into();
 
@@ -146,6 +241,51 @@ impl RequestBuilder {
         self
     }
 
+    /// Specify a cookie to send with the request in the `Cookie` header. The `Cookie` header will
+    /// be matched as structured cookies, so any other cookies in the actual request are ignored,
+    /// and the matching rules from the pattern are applied to the value of the cookie.
+    ///
+    /// ```
+    /// use pact_consumer::prelude::*;
+    /// use pact_consumer::*;
+    /// use pact_consumer::builders::RequestBuilder;
+    ///
+    /// RequestBuilder::default()
+    ///     .cookie("session", term!("^[a-f0-9]+$", "abc123"))
+    ///     .cookie("theme", "dark");
+    /// ```
+    pub fn cookie<N, V>(&mut self, name: N, value: V) -> &mut Self
+    where
+        N: Into<String>,
+        V: Into<StringPattern>,
+    {
+        let name = name.into();
+        let value = value.into();
+        let cookie = format!("{}={}", name, value.to_example());
+
+        let headers = self.request.headers.get_defaulting();
+        let key = headers.keys()
+            .find(|key| key.eq_ignore_ascii_case("cookie"))
+            .cloned()
+            .unwrap_or_else(|| "Cookie".to_string());
+        let values = headers.entry(key.clone()).or_insert_with(Default::default);
+        match values.first_mut() {
+            Some(existing) => {
+                existing.push_str("; ");
+                existing.push_str(cookie.as_str());
+            }
+            None => values.push(cookie)
+        }
+
+        let mut path = DocPath::root();
+        path.push_field(key);
+        let category = self.request.matching_rules.add_category("header");
+        add_cookie_matching_rule(category, &path);
+        value.extract_matching_rules(path.join(name), category);
+
+        self
+    }
+
     /// Build the specified `Request` object.
     pub fn build(&self) -> Request {
          self.request.as_v3_request()
@@ -418,3 +558,129 @@ fn term_does_not_require_anchors() {
     assert_requests_do_not_match!(bad1, pattern);
     assert_requests_do_not_match!(bad2, pattern);
 }
+
+#[test]
+fn cookie_pattern() {
+    use crate::prelude::*;
+
+    let pattern = PactBuilder::new("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request
+                .cookie("session", term!("^[a-f0-9]+$", "abc123"))
+                .cookie("theme", "dark");
+            i
+        })
+        .build();
+    let interactions = pattern.interactions();
+    let first_interaction = interactions.first().unwrap().as_request_response().unwrap();
+    assert_eq!(first_interaction.request.headers, Some(hashmap!{
+        "Cookie".to_string() => vec!["session=abc123; theme=dark".to_string()]
+    }));
+
+    let good = PactBuilder::new("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request.header("Cookie", "tracking=1; theme=dark; session=ff00");
+            i
+        })
+        .build();
+    let bad = PactBuilder::new("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request.header("Cookie", "session=XYZ; theme=dark");
+            i
+        })
+        .build();
+    let missing = PactBuilder::new("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request.header("Cookie", "session=ff00");
+            i
+        })
+        .build();
+    assert_requests_match!(good, pattern);
+    assert_requests_do_not_match!(bad, pattern);
+    assert_requests_do_not_match!(missing, pattern);
+}
+
+#[test]
+fn raw_target_pattern() {
+    use crate::prelude::*;
+
+    let pattern = PactBuilder::new("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request
+                .query_param("ignored", "1")
+                .raw_target(term!("^/orders/\\d+\\?page=\\d+$", "/orders/100?page=1"));
+            i
+        })
+        .build();
+    let interactions = pattern.interactions();
+    let first_interaction = interactions.first().unwrap().as_request_response().unwrap();
+    assert_eq!(first_interaction.request.path, "/orders/100");
+    assert_eq!(first_interaction.request.query, Some(hashmap!{
+        "page".to_string() => vec![Some("1".to_string())]
+    }));
+    assert!(first_interaction.request.matching_rules.rules_for_category("query").is_none());
+
+    let good = PactBuilder::new("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request.path("/orders/200").query_param("page", "20");
+            i
+        })
+        .build();
+    let bad = PactBuilder::new("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request.path("/orders/200").query_param("size", "20");
+            i
+        })
+        .build();
+    assert_requests_match!(good, pattern);
+    assert_requests_do_not_match!(bad, pattern);
+}
+
+#[test]
+fn path_segments_pattern() {
+    use crate::prelude::*;
+
+    let pattern = PactBuilder::new("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request.path_segments(vec![
+                StringPattern::from("users"),
+                term!("^\\d+$", "100").into(),
+                StringPattern::from("orders")
+            ]);
+            i
+        })
+        .build();
+    let interactions = pattern.interactions();
+    let first_interaction = interactions.first().unwrap().as_request_response().unwrap();
+    assert_eq!(first_interaction.request.path, "/users/100/orders");
+
+    let good = PactBuilder::new("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request.path("/users/200/orders");
+            i
+        })
+        .build();
+    let bad = PactBuilder::new("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request.path("/users/abc/orders");
+            i
+        })
+        .build();
+    let extra = PactBuilder::new("C", "P")
+        .interaction("I", "", |mut i| {
+            i.request.path("/users/200/orders/1");
+            i
+        })
+        .build();
+    assert_requests_match!(good, pattern);
+    assert_requests_do_not_match!(bad, pattern);
+    assert_requests_do_not_match!(extra, pattern);
+}
+
+#[test]
+#[should_panic(expected = "The request path can not be set when the raw request target is used")]
+fn raw_target_can_not_be_combined_with_path() {
+    RequestBuilder::default()
+        .raw_target("/orders?page=1")
+        .path("/orders");
+}
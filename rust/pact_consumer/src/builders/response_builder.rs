@@ -5,7 +5,9 @@ use maplit::*;
 use pact_models::bodies::OptionalBody;
 use pact_models::generators::Generators;
 #[cfg(feature = "plugins")] use pact_models::http_parts::HttpPart;
-use pact_models::matchingrules::MatchingRules;
+use pact_matching::headers::body_hash;
+use pact_models::matchingrules::{MatchingRule, MatchingRules, RuleLogic};
+use pact_models::path_exp::DocPath;
 use pact_models::prelude::ContentType;
 use pact_models::response::Response;
 use pact_models::v4::http_parts::{body_from_json, HttpResponse};
@@ -15,6 +17,7 @@ use pact_models::v4::interaction::InteractionMarkup;
 use serde_json::Value;
 #[allow(unused_imports)] use tracing::debug;
 
+use crate::builders::http_part_builder::add_cookie_matching_rule;
 use crate::prelude::*;
 
 #[cfg(not(feature = "plugins"))]
@@ -77,6 +80,80 @@ impl ResponseBuilder {
         self.status(404)
     }
 
+    /// Require the `ETag` header of the response to be a strong ETag, which is the Base64 encoded
+    /// hash of the body using the given algorithm (`sha256`, `sha512` or `md5`). A header
+    /// mismatch will be reported if the ETag returned by the provider does not match its body.
+    ///
+    /// The example value of the header is calculated from the current body, so this needs to be
+    /// called after the body has been set.
+    ///
+    /// ```
+    /// use pact_consumer::builders::ResponseBuilder;
+    /// use pact_consumer::prelude::*;
+    ///
+    /// let response = ResponseBuilder::default()
+    ///   .body("Hello")
+    ///   .etag_matches_body("sha256")
+    ///   .build();
+    /// assert_eq!(response.headers.unwrap()["ETag"], vec!["\"GF+NsyJx/iX1Yab8k4suJkMG7DBO2lGAB9F2SCY4GWk=\""]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the hash algorithm is not supported.
+    pub fn etag_matches_body(&mut self, algorithm: &str) -> &mut Self {
+      let body = self.response.body.value().unwrap_or_default();
+      let hash = body_hash(algorithm, &body).unwrap_or_else(|err| panic!("{}", err));
+      self.header("ETag", format!("\"{}\"", hash));
+      self.response.matching_rules.add_category("header")
+        .add_rule(DocPath::root().join("ETag"), MatchingRule::BodyHash(algorithm.to_string()), RuleLogic::And);
+      self
+    }
+
+    /// Adds a cookie to the response in a `Set-Cookie` header, with the given attributes (i.e.
+    /// `Path=/`, `Secure` or `HttpOnly`). The `Set-Cookie` headers will be matched as structured
+    /// cookies: the attributes must be present with the same values, the matching rules from the
+    /// pattern are applied to the value of the cookie, and any other cookies or attributes in
+    /// the actual response are ignored.
+    ///
+    /// ```
+    /// use pact_consumer::builders::ResponseBuilder;
+    /// use pact_consumer::prelude::*;
+    /// use pact_consumer::*;
+    ///
+    /// let response = ResponseBuilder::default()
+    ///   .set_cookie("session", term!("^[a-f0-9]+$", "abc123"), &["Path=/", "Secure", "HttpOnly"])
+    ///   .build();
+    /// assert_eq!(response.headers.unwrap()["Set-Cookie"], vec!["session=abc123; Path=/; Secure; HttpOnly"]);
+    /// ```
+    pub fn set_cookie<N, V>(&mut self, name: N, value: V, attributes: &[&str]) -> &mut Self
+    where
+        N: Into<String>,
+        V: Into<StringPattern>,
+    {
+        let name = name.into();
+        let value = value.into();
+        let cookie = std::iter::once(format!("{}={}", name, value.to_example()))
+            .chain(attributes.iter().map(|attribute| attribute.to_string()))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        let headers = self.response.headers.get_or_insert_with(HashMap::new);
+        let key = headers.keys()
+            .find(|key| key.eq_ignore_ascii_case("set-cookie"))
+            .cloned()
+            .unwrap_or_else(|| "Set-Cookie".to_string());
+        headers.entry(key.clone()).or_insert_with(Vec::new).push(cookie);
+
+        let mut path = DocPath::root();
+        path.push_field(key);
+        let category = self.response.matching_rules.add_category("header");
+        add_cookie_matching_rule(category, &path);
+        value.extract_matching_rules(path.join(name).join("value"), category);
+
+        self
+    }
+
     /// Build the specified `Response` object.
     pub fn build(&self) -> Response {
         self.response.as_v3_response()
@@ -549,6 +549,83 @@ impl SyncMessageInteractionBuilder {
     self.response_contents.push(response);
     self
   }
+
+  /// Adds a response message to the interaction, configured with a response builder. This allows
+  /// the body and metadata of each response to be set together. You can call this method multiple
+  /// times, and the responses will be added to the interaction in the order they were added.
+  ///
+  /// ```
+  /// use pact_consumer::prelude::*;
+  /// use pact_consumer::*;
+  /// use pact_consumer::builders::SyncMessageInteractionBuilder;
+  ///
+  /// SyncMessageInteractionBuilder::new("hello message").response(|r| {
+  ///   r.json_body(json_pattern!({ "message": like!("Hello") }));
+  ///   r.metadata("sequence", 1);
+  /// });
+  /// ```
+  pub fn response<F>(&mut self, build_fn: F) -> &mut Self
+    where F: FnOnce(&mut SyncMessageResponseBuilder) {
+    let mut builder = SyncMessageResponseBuilder::default();
+    build_fn(&mut builder);
+    self.response_contents.push(builder.contents);
+    self
+  }
+}
+
+#[derive(Clone, Debug)]
+/// Builder for a single response message of a synchronous message interaction. Normally used via
+/// SyncMessageInteractionBuilder::response.
+pub struct SyncMessageResponseBuilder {
+  contents: InteractionContents
+}
+
+impl Default for SyncMessageResponseBuilder {
+  fn default() -> Self {
+    SyncMessageResponseBuilder {
+      contents: InteractionContents {
+        part_name: "response".to_string(),
+        .. InteractionContents::default()
+      }
+    }
+  }
+}
+
+impl SyncMessageResponseBuilder {
+  /// Specify the response body as `JsonPattern`, possibly including special matching rules.
+  pub fn json_body<B: Into<JsonPattern>>(&mut self, body: B) -> &mut Self {
+    let body = body.into();
+    let mut rules = MatchingRuleCategory::empty("content");
+    body.extract_matching_rules(DocPath::root(), &mut rules);
+    self.contents.body = OptionalBody::Present(body.to_example().to_string().into(),
+      Some("application/json".into()), None);
+    self.contents.rules = if rules.is_not_empty() { Some(rules) } else { None };
+    self
+  }
+
+  /// Specify the response payload and content type
+  pub fn body<B: Into<Bytes>>(&mut self, body: B, content_type: Option<String>) -> &mut Self {
+    self.contents.body = OptionalBody::Present(
+      body.into(),
+      content_type.as_ref().map(|ct| ct.into()),
+      None
+    );
+    if let Some(content_type) = content_type {
+      let metadata = self.contents.metadata.get_or_insert_with(|| hashmap!{});
+      if let Entry::Vacant(entry) = metadata.entry("contentType".to_string()) {
+        entry.insert(Value::String(content_type));
+      }
+    }
+    self
+  }
+
+  /// Adds a key/value pair to the response metadata. The key can be anything that is
+  /// convertible into a string, and the value must be conveyable into a JSON value.
+  pub fn metadata<S: Into<String>, J: Into<Value>>(&mut self, key: S, value: J) -> &mut Self {
+    let metadata = self.contents.metadata.get_or_insert_with(|| hashmap!{});
+    metadata.insert(key.into(), value.into());
+    self
+  }
 }
 
 #[cfg(test)]
@@ -563,6 +640,7 @@ mod tests {
   use pact_models::v4::message_parts::MessageContents;
 
   use crate::builders::SyncMessageInteractionBuilder;
+  use crate::prelude::*;
 
   #[test]
   fn supports_setting_metadata_values() {
@@ -694,4 +772,30 @@ mod tests {
     expect!(message.response.len()).to(be_equal_to(1));
     expect!(message.response[0].clone().matching_rules).to(be_equal_to(rules.clone()));
   }
+
+  #[test]
+  fn supports_multiple_ordered_responses_with_metadata() {
+    let message = SyncMessageInteractionBuilder::new("test")
+      .response(|r| {
+        r.json_body(json_pattern!({ "id": like!(1) }));
+        r.metadata("sequence", 1);
+      })
+      .response(|r| {
+        r.body("second", Some("text/plain".to_string()));
+        r.metadata("sequence", 2);
+      })
+      .build();
+
+    expect!(message.response.len()).to(be_equal_to(2));
+    expect!(message.response[0].contents.value_as_string()).to(be_some().value("{\"id\":1}".to_string()));
+    expect!(message.response[0].metadata.clone()).to(be_equal_to(hashmap! {
+      "sequence".to_string() => json!(1)
+    }));
+    expect!(message.response[0].matching_rules.rules_for_category("body").unwrap().is_not_empty()).to(be_true());
+    expect!(message.response[1].contents.value_as_string()).to(be_some().value("second".to_string()));
+    expect!(message.response[1].metadata.clone()).to(be_equal_to(hashmap! {
+      "contentType".to_string() => json!("text/plain"),
+      "sequence".to_string() => json!(2)
+    }));
+  }
 }
@@ -301,6 +301,11 @@
 //! }
 //! ```
 //!
+//! With the async builder (`PactBuilderAsync`), the messages can also be consumed as a
+//! [`Stream`](futures::Stream) using `messages_async` and `synchronous_messages_async`. With these,
+//! the generators are applied to each message as it is polled, so any async work (like plugin
+//! content generation) is done as each message is consumed.
+//!
 //! ## Using Pact plugins
 //!
 //! The consumer test builders support using Pact plugins. Plugins are defined in the [Pact plugins project](https://github.com/pact-foundation/pact-plugins).
@@ -393,27 +398,52 @@ pub mod prelude {
         like,
         each_like,
         each_like_helper,
+        each_like_examples,
+        tuple_each_like,
         term,
+        one_of,
+        one_of_values,
+        nullable,
+        ip_address,
+        any_body,
+        each_entry,
+        define_matcher,
         json_pattern,
         json_pattern_internal
     };
     pub use crate::builders::{HttpPartBuilder, PactBuilder, PactBuilderAsync};
     #[cfg(feature = "plugins")] pub use crate::builders::plugin_builder::PluginInteractionBuilder;
     pub use crate::mock_server::{StartMockServer, ValidatingMockServer};
+    #[cfg(feature = "tls")] pub use crate::mock_server::http_mock_server::MockServerTls;
     pub use crate::patterns::{
         EachLike,
+        TupleEachLike,
         Like,
         Term,
+        StringLength,
+        Hex,
+        IntegerRange,
+        MultipleOf,
+        Empty,
+        ArrayDistinct,
+        MatchKeys,
+        OneOf,
+        Nullable,
+        IpAddress,
+        IpAddressList,
+        AnyBody,
+        MatcherDefinition,
         ObjectMatching,
         EachKey,
         EachValue,
+        EachEntry,
         JsonPattern,
         Pattern,
         StringPattern,
         each_key,
         each_value
     };
-    #[cfg(feature = "datetime")] pub use crate::patterns::{DateTime};
+    #[cfg(feature = "datetime")] pub use crate::patterns::{DateTime, IsoDuration};
     pub use crate::util::strip_null_fields;
     pub use pact_mock_server::mock_server::MockServerConfig;
 }
@@ -3,6 +3,7 @@
 use async_trait::async_trait;
 use pact_models::pact::Pact;
 use pact_models::sync_pact::RequestResponsePact;
+use pact_models::v4::pact::V4Pact;
 use url::Url;
 
 use pact_mock_server::matching::MatchResult;
@@ -36,6 +37,12 @@ pub trait ValidatingMockServer {
 
   /// Returns the metrics collected by the mock server
   fn metrics(&self) -> MockServerMetrics;
+
+  /// Returns the Pact the mock server is validating the requests against, as a V4 Pact. This is
+  /// the Pact that is written out when the mock server is shutdown without any mismatches, so it
+  /// can be used to inspect the interactions and matching rules in tests (i.e. to test helpers
+  /// that build the interactions) without needing to read the Pact file.
+  fn pact(&self) -> V4Pact;
 }
 
 /// This trait is implemented by types which allow us to start a mock server.
@@ -2,15 +2,17 @@
 
 use std::{env, thread};
 use std::fmt::Write;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use anyhow::anyhow;
+use anyhow::{anyhow, Context};
 use itertools::Itertools;
 use pact_mock_server::builder::MockServerBuilder;
 use pact_mock_server::matching::MatchResult;
 use pact_mock_server::mock_server;
 use pact_mock_server::mock_server::{MockServerConfig, MockServerMetrics};
+#[cfg(feature = "tls")] use pact_mock_server::tls::TlsConfigBuilder;
 #[cfg(feature = "plugins")] use pact_plugin_driver::plugin_manager::{drop_plugin_access, increment_plugin_access};
 #[cfg(feature = "plugins")] use pact_plugin_driver::plugin_models::{PluginDependency, PluginDependencyType};
 use tokio::runtime::Runtime;
@@ -22,10 +24,52 @@ use pact_matching::metrics::{MetricEvent, send_metrics};
 use pact_models::pact::Pact;
 #[cfg(feature = "plugins")] use pact_models::plugins::PluginData;
 use pact_models::v4::http_parts::HttpRequest;
+use pact_models::v4::pact::V4Pact;
 
 use crate::mock_server::ValidatingMockServer;
 use crate::util::panic_or_print_error;
 
+/// TLS configuration for a mock server. The default is to use a self-signed certificate.
+#[cfg(feature = "tls")]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum MockServerTls {
+  /// Use a self-signed certificate
+  #[default]
+  SelfSigned,
+  /// Use the given PEM encoded certificate chain and private key
+  Certificate {
+    /// PEM encoded certificate chain
+    cert_pem: String,
+    /// PEM encoded private key
+    key_pem: String
+  }
+}
+
+#[cfg(feature = "tls")]
+impl MockServerTls {
+  /// TLS configuration using the given PEM encoded certificate chain and private key
+  pub fn certificate<C: Into<String>, K: Into<String>>(cert_pem: C, key_pem: K) -> Self {
+    MockServerTls::Certificate {
+      cert_pem: cert_pem.into(),
+      key_pem: key_pem.into()
+    }
+  }
+
+  fn configure(&self, builder: MockServerBuilder) -> anyhow::Result<MockServerBuilder> {
+    match self {
+      MockServerTls::SelfSigned => builder.with_self_signed_tls(),
+      MockServerTls::Certificate { cert_pem, key_pem } => {
+        let tls_config = TlsConfigBuilder::new()
+          .cert(cert_pem.as_bytes())
+          .key(key_pem.as_bytes())
+          .build()
+          .map_err(|err| anyhow!("Failed to build the TLS configuration - {}", err))?;
+        Ok(builder.with_tls_config(&tls_config))
+      }
+    }
+  }
+}
+
 /// A mock HTTP server that handles the requests described in a `Pact`, intended
 /// for use in tests, and validates that the requests made to that server are
 /// correct. This wraps the standard Pact HTTP mock server.
@@ -58,6 +102,59 @@ impl ValidatingHttpMockServer {
     output_dir: Option<PathBuf>,
     mock_server_config: Option<MockServerConfig>
   ) -> Box<dyn ValidatingMockServer> {
+    Self::start_with(pact, output_dir, mock_server_config, Ok)
+  }
+
+  /// Create a new mock server which handles requests as described in the
+  /// pact using TLS (HTTPS), and runs in a background thread
+  ///
+  /// Panics:
+  /// Will panic if the provided Pact can not be sent to the background thread, or if the TLS
+  /// configuration is not valid.
+  #[cfg(feature = "tls")]
+  pub fn start_tls(
+    pact: Box<dyn Pact + Send + Sync>,
+    output_dir: Option<PathBuf>,
+    mock_server_config: Option<MockServerConfig>,
+    tls: MockServerTls
+  ) -> Box<dyn ValidatingMockServer> {
+    Self::start_with(pact, output_dir, mock_server_config, move |builder| tls.configure(builder))
+  }
+
+  /// Create a new mock server which handles requests as described in the
+  /// pact, bound to the given address, and runs in a background thread. Unlike `start`, this
+  /// will not pick another port if the address is not available, but returns an error.
+  pub fn start_on(
+    pact: Box<dyn Pact + Send + Sync>,
+    output_dir: Option<PathBuf>,
+    address: SocketAddr,
+    mock_server_config: Option<MockServerConfig>
+  ) -> anyhow::Result<Box<dyn ValidatingMockServer>> {
+    Self::try_start_with(pact, output_dir, mock_server_config,
+      move |builder| Ok(builder.bind_to(address.to_string())))
+      .with_context(|| format!("Failed to start the mock server on {}", address))
+  }
+
+  fn start_with<F>(
+    pact: Box<dyn Pact + Send + Sync>,
+    output_dir: Option<PathBuf>,
+    mock_server_config: Option<MockServerConfig>,
+    configure: F
+  ) -> Box<dyn ValidatingMockServer>
+    where F: FnOnce(MockServerBuilder) -> anyhow::Result<MockServerBuilder> + Send + 'static
+  {
+    Self::try_start_with(pact, output_dir, mock_server_config, configure)
+      .expect("Failed to start the mock server")
+  }
+
+  fn try_start_with<F>(
+    pact: Box<dyn Pact + Send + Sync>,
+    output_dir: Option<PathBuf>,
+    mock_server_config: Option<MockServerConfig>,
+    configure: F
+  ) -> anyhow::Result<Box<dyn ValidatingMockServer>>
+    where F: FnOnce(MockServerBuilder) -> anyhow::Result<MockServerBuilder> + Send + 'static
+  {
     debug!("Starting mock server from pact {:?}", pact);
 
     // Start a tokio runtime to drive the mock server
@@ -67,8 +164,9 @@ impl ValidatingHttpMockServer {
       .build()
       .expect("Could not start a new Tokio runtime"));
 
+    #[cfg(feature = "plugins")] let plugin_data = pact.plugin_data();
     #[cfg(feature = "plugins")]
-    Self::increment_plugin_access(&pact.plugin_data());
+    Self::increment_plugin_access(&plugin_data);
 
     // Start a background thread to run the mock server tasks on the runtime
     let tname = format!("test({})-pact-mock-server",
@@ -86,25 +184,31 @@ impl ValidatingHttpMockServer {
         if !builder.address_assigned() {
           builder = builder.bind_to_ip4_port(0)
         };
-        rt.block_on(builder.start())
+        configure(builder).and_then(|builder| rt.block_on(builder.start()))
       })
       .expect("INTERNAL ERROR: Could not spawn a thread to run the mock server")
       .join()
-      .expect("INTERNAL ERROR: Failed to spawn the mock server task onto the runtime")
-      .expect("Failed to start the mock server");
+      .expect("INTERNAL ERROR: Failed to spawn the mock server task onto the runtime");
+    let mock_server = match mock_server {
+      Ok(mock_server) => mock_server,
+      Err(err) => {
+        #[cfg(feature = "plugins")] Self::decrement_plugin_access(&plugin_data);
+        return Err(err);
+      }
+    };
 
     let pact = &mock_server.pact;
     let description = format!("{}/{}", pact.consumer().name, pact.provider().name);
     let url_str = mock_server.url();
 
-    Box::new(ValidatingHttpMockServer {
+    Ok(Box::new(ValidatingHttpMockServer {
       description,
       url: url_str.parse().expect(format!("invalid mock server URL '{}'", url_str).as_str()),
       mock_server,
       output_dir,
       overwrite: false,
       runtime: Some(runtime)
-    })
+    }))
   }
 
   #[cfg(feature = "plugins")]
@@ -141,9 +245,64 @@ impl ValidatingHttpMockServer {
     output_dir: Option<PathBuf>,
     mock_server_config: Option<MockServerConfig>
   ) -> Box<dyn ValidatingMockServer> {
+    Self::start_async_with(pact, output_dir, mock_server_config, Ok).await
+  }
+
+  /// Create a new mock server which handles requests as described in the
+  /// pact using TLS (HTTPS), and runs in a background task in the current Tokio runtime.
+  ///
+  /// Panics:
+  /// Will panic if unable to get the URL to the spawned mock server, or if the TLS
+  /// configuration is not valid.
+  #[cfg(feature = "tls")]
+  pub async fn start_tls_async(
+    pact: Box<dyn Pact + Send + Sync>,
+    output_dir: Option<PathBuf>,
+    mock_server_config: Option<MockServerConfig>,
+    tls: MockServerTls
+  ) -> Box<dyn ValidatingMockServer> {
+    Self::start_async_with(pact, output_dir, mock_server_config, move |builder| tls.configure(builder)).await
+  }
+
+  /// Create a new mock server which handles requests as described in the
+  /// pact, bound to the given address, and runs in a background task in the current Tokio
+  /// runtime. Unlike `start_async`, this will not pick another port if the address is not
+  /// available, but returns an error.
+  pub async fn start_on_async(
+    pact: Box<dyn Pact + Send + Sync>,
+    output_dir: Option<PathBuf>,
+    address: SocketAddr,
+    mock_server_config: Option<MockServerConfig>
+  ) -> anyhow::Result<Box<dyn ValidatingMockServer>> {
+    Self::try_start_async_with(pact, output_dir, mock_server_config,
+      move |builder| Ok(builder.bind_to(address.to_string()))).await
+      .with_context(|| format!("Failed to start the mock server on {}", address))
+  }
+
+  async fn start_async_with<F>(
+    pact: Box<dyn Pact + Send + Sync>,
+    output_dir: Option<PathBuf>,
+    mock_server_config: Option<MockServerConfig>,
+    configure: F
+  ) -> Box<dyn ValidatingMockServer>
+    where F: FnOnce(MockServerBuilder) -> anyhow::Result<MockServerBuilder>
+  {
+    Self::try_start_async_with(pact, output_dir, mock_server_config, configure).await
+      .expect("Could not start the mock server")
+  }
+
+  async fn try_start_async_with<F>(
+    pact: Box<dyn Pact + Send + Sync>,
+    output_dir: Option<PathBuf>,
+    mock_server_config: Option<MockServerConfig>,
+    configure: F
+  ) -> anyhow::Result<Box<dyn ValidatingMockServer>>
+    where F: FnOnce(MockServerBuilder) -> anyhow::Result<MockServerBuilder>
+  {
     debug!("Starting mock server from pact {:?}", pact);
 
-    #[cfg(feature = "plugins")] Self::increment_plugin_access(&pact.plugin_data());
+    #[cfg(feature = "plugins")] let plugin_data = pact.plugin_data();
+    #[cfg(feature = "plugins")] Self::increment_plugin_access(&plugin_data);
 
     let mut builder = MockServerBuilder::new()
       .with_pact(pact);
@@ -153,22 +312,29 @@ impl ValidatingHttpMockServer {
     if !builder.address_assigned() {
       builder = builder.bind_to_ip4_port(0)
     };
-    let mock_server = builder
-      .start()
-      .await
-      .expect("Could not start the mock server");
+    let result = match configure(builder).context("Could not configure the mock server") {
+      Ok(builder) => builder.start().await,
+      Err(err) => Err(err)
+    };
+    let mock_server = match result {
+      Ok(mock_server) => mock_server,
+      Err(err) => {
+        #[cfg(feature = "plugins")] Self::decrement_plugin_access(&plugin_data);
+        return Err(err);
+      }
+    };
 
     let pact = &mock_server.pact;
     let description = format!("{}/{}", pact.consumer().name, pact.provider().name);
     let url_str = mock_server.url();
-    Box::new(ValidatingHttpMockServer {
+    Ok(Box::new(ValidatingHttpMockServer {
       description,
       url: url_str.parse().expect("invalid mock server URL"),
       mock_server,
       output_dir,
       overwrite: false,
       runtime: None
-    })
+    }))
   }
 
   /// Helper function called by our `drop` implementation. This basically exists
@@ -331,6 +497,10 @@ impl ValidatingMockServer for ValidatingHttpMockServer {
   fn metrics(&self) -> MockServerMetrics {
     self.mock_server.metrics.lock().unwrap().clone()
   }
+
+  fn pact(&self) -> V4Pact {
+    self.mock_server.pact.as_v4_pact().expect("could not convert the Pact to a V4 Pact")
+  }
 }
 
 impl Drop for ValidatingHttpMockServer {
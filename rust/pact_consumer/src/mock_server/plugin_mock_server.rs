@@ -9,6 +9,7 @@ use itertools::Itertools;
 use maplit::hashmap;
 use pact_models::pact::{Pact, write_pact};
 use pact_models::PactSpecification;
+use pact_models::v4::pact::V4Pact;
 use pact_plugin_driver::catalogue_manager::CatalogueEntry;
 use pact_plugin_driver::mock_server::{MockServerConfig, MockServerDetails};
 use pact_plugin_driver::plugin_manager::{shutdown_mock_server, start_mock_server_v2};
@@ -145,15 +146,9 @@ impl PluginMockServer {
       .unwrap_or_else(|_| "false".to_owned()) == "true";
     debug!("env:PACT_OVERWRITE = {:?}", overwrite);
 
-    let mut v4_pact = self.pact.as_v4_pact();
-    let pact = if let Ok(ref mut pact) = v4_pact {
-      for interaction in &mut pact.interactions {
-        let catalogue_entry = &self.catalogue_entry;
-        interaction.set_transport(Some(catalogue_entry.key.clone()));
-      }
-      pact.boxed()
-    } else {
-      self.pact.boxed()
+    let pact = match self.v4_pact() {
+      Ok(pact) => pact.boxed(),
+      Err(_) => self.pact.boxed()
     };
 
     let pact_file_name = pact.default_file_name();
@@ -163,6 +158,15 @@ impl PluginMockServer {
     info!("Writing pact out to '{}'", filename.display());
     write_pact(pact, filename.as_path(), PactSpecification::V4, overwrite)
   }
+
+  /// The Pact as a V4 Pact, with the transport of the interactions set to the plugin transport
+  fn v4_pact(&self) -> anyhow::Result<V4Pact> {
+    let mut pact = self.pact.as_v4_pact()?;
+    for interaction in &mut pact.interactions {
+      interaction.set_transport(Some(self.catalogue_entry.key.clone()));
+    }
+    Ok(pact)
+  }
 }
 
 impl ValidatingMockServer for PluginMockServer {
@@ -184,6 +188,10 @@ impl ValidatingMockServer for PluginMockServer {
   fn metrics(&self) -> MockServerMetrics {
     MockServerMetrics::default()
   }
+
+  fn pact(&self) -> V4Pact {
+    self.v4_pact().expect("could not convert the Pact to a V4 Pact")
+  }
 }
 
 impl Drop for PluginMockServer {
@@ -1,10 +1,10 @@
-//! Matchers for matching dates, times and date-times
+//! Matchers for matching dates, times, date-times and durations
 
 use std::marker::PhantomData;
 
 use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory, RuleLogic};
 use pact_models::path_exp::DocPath;
-use pact_models::time_utils::parse_pattern;
+use pact_models::time_utils::{parse_pattern, validate_duration};
 
 use crate::patterns::{JsonPattern, Pattern, StringPattern};
 
@@ -23,11 +23,12 @@ pub struct DateTime<Nested: Pattern> {
 
 impl <Nested: Pattern> DateTime<Nested> {
   /// Construct a new `DateTime`, given a format string and the example string to
-  /// generate.
+  /// generate. Panics if the format string is not a valid datetime format, so that errors are
+  /// reported when the test is written rather than when the pact is verified.
   pub fn new<S: Into<String>>(format: S, example: S) -> Self {
     DateTime {
       example: example.into(),
-      format: format.into(),
+      format: validate_format_string(format.into()),
       phantom: PhantomData
     }
   }
@@ -72,6 +73,12 @@ fn datetime_is_pattern() {
   expect!(rules.to_v3_json()).to(be_equal_to(expected_rules));
 }
 
+#[test]
+#[should_panic(expected = "could not parse datetime format string")]
+fn datetime_with_invalid_format_panics() {
+  let _ = DateTime::<JsonPattern>::new("yyyy-MM-dd aaaa", "2000-01-01 PM");
+}
+
 impl_from_for_pattern!(DateTime<JsonPattern>, JsonPattern);
 impl_from_for_pattern!(DateTime<StringPattern>, StringPattern);
 
@@ -82,7 +89,7 @@ fn datetime_into() {
   let _: StringPattern = DateTime::new("yyy-MM-dd", "2000-01-01").into();
 }
 
-/// Internal helper function called by `datetime!`. Panics if the datetime format string is invalid.
+/// Internal helper function called by `datetime!` and `DateTime::new`. Panics if the datetime format string is invalid.
 #[doc(hidden)]
 pub fn validate_format_string<S: AsRef<str>>(format_str: S) -> String {
   let format_str = format_str.as_ref();
@@ -111,3 +118,105 @@ macro_rules! datetime {
     }
   }
 }
+
+/// Match and generate strings that are ISO-8601 durations (i.e. `PT1H30M`).
+#[derive(Debug)]
+pub struct IsoDuration<Nested: Pattern> {
+  /// The example string we generate when asked.
+  example: String,
+  /// Since we always store `example` as a string, we need to mention our
+  /// `Nested` type somewhere. We can do that using the zero-length
+  /// `PhantomData` type.
+  phantom: PhantomData<Nested>
+}
+
+impl <Nested: Pattern> IsoDuration<Nested> {
+  /// Construct a new `IsoDuration`, given the example string to generate. Panics if the example
+  /// is not a valid ISO-8601 duration, so that errors are reported when the test is written
+  /// rather than when the pact is verified.
+  pub fn new<S: Into<String>>(example: S) -> Self {
+    IsoDuration {
+      example: validate_duration_string(example.into()),
+      phantom: PhantomData
+    }
+  }
+}
+
+impl <Nested> Pattern for IsoDuration<Nested>
+where
+  Nested: Pattern,
+  Nested::Matches: From<String> {
+  type Matches = Nested::Matches;
+
+  fn to_example(&self) -> Self::Matches {
+    From::from(self.example.clone())
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.example.as_bytes().to_vec()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::Duration, RuleLogic::And);
+  }
+}
+
+#[test]
+fn duration_is_pattern() {
+  use serde_json::*;
+  use expectest::prelude::*;
+
+  let matchable = IsoDuration::<JsonPattern>::new("PT1H30M");
+  expect!(matchable.to_example()).to(be_equal_to("PT1H30M"));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  let expected_rules = json!({
+    "$": {
+      "combine": "AND", "matchers": [
+        { "match": "duration" }
+      ]
+    }
+  });
+  expect!(rules.to_v3_json()).to(be_equal_to(expected_rules));
+}
+
+#[test]
+#[should_panic(expected = "Expected 'abc' to be a valid ISO-8601 duration")]
+fn duration_with_invalid_example_panics() {
+  let _ = IsoDuration::<JsonPattern>::new("abc");
+}
+
+impl_from_for_pattern!(IsoDuration<JsonPattern>, JsonPattern);
+impl_from_for_pattern!(IsoDuration<StringPattern>, StringPattern);
+
+/// Internal helper function called by `duration!` and `IsoDuration::new`. Panics if the example
+/// is not a valid ISO-8601 duration.
+#[doc(hidden)]
+pub fn validate_duration_string<S: AsRef<str>>(example: S) -> String {
+  let example = example.as_ref();
+  match validate_duration(example) {
+    Ok(_) => example.to_string(),
+    Err(msg) => panic!("{}", msg),
+  }
+}
+
+/// A pattern which matches an ISO-8601 duration and which generates `$example`.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "timeout": duration!("PT1H30M")
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! duration {
+  ($example:expr) => {
+    {
+      $crate::patterns::IsoDuration::new($crate::patterns::validate_duration_string($example))
+    }
+  }
+}
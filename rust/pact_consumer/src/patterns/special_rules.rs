@@ -4,8 +4,8 @@ use std::iter::repeat;
 use std::marker::PhantomData;
 use itertools::{Either, Itertools};
 
-use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory, RuleLogic};
-use pact_models::matchingrules::expressions::{MatchingRuleDefinition, ValueType};
+use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory, MULTIPLE_OF_TOLERANCE, RuleList, RuleLogic};
+use pact_models::matchingrules::expressions::{MatchingReference, MatchingRuleDefinition, ValueType};
 use pact_models::path_exp::DocPath;
 use regex::Regex;
 use serde_json::Value;
@@ -108,6 +108,7 @@ macro_rules! like {
 pub struct EachLike {
     example_element: JsonPattern,
     min_len: usize,
+    examples: Vec<JsonPattern>,
 }
 
 impl EachLike {
@@ -116,6 +117,7 @@ impl EachLike {
         EachLike {
             example_element,
             min_len: 1,
+            examples: vec![],
         }
     }
 
@@ -124,6 +126,15 @@ impl EachLike {
         self.min_len = min_len;
         self
     }
+
+    /// Use this after `new` to generate the array from the given example elements, instead of
+    /// repeating `example_element`. The matching rules are still only taken from
+    /// `example_element`. If there are fewer examples than the minimum length, the array is
+    /// padded with copies of `example_element` so that it still has the minimum length.
+    pub fn with_examples(mut self, examples: Vec<JsonPattern>) -> EachLike {
+        self.examples = examples;
+        self
+    }
 }
 
 impl_from_for_pattern!(EachLike, JsonPattern);
@@ -133,7 +144,11 @@ impl Pattern for EachLike {
 
     fn to_example(&self) -> serde_json::Value {
         let element = self.example_element.to_example();
-        serde_json::Value::Array(repeat(element).take(self.min_len).collect())
+        let padding = self.min_len.saturating_sub(self.examples.len());
+        serde_json::Value::Array(self.examples.iter()
+            .map(|example| example.to_example())
+            .chain(repeat(element).take(padding))
+            .collect())
     }
 
     fn to_example_bytes(&self) -> Vec<u8> {
@@ -282,6 +297,233 @@ fn each_like_macro_parsing() {
     assert_eq!(with_min.min_len, 3);
 }
 
+/// Matches an array where each element is like `$template` (in the same way as `each_like!`), but
+/// generates the array from the given example elements instead of repeating the template. This
+/// is useful when the generated body needs several distinct, realistic items (i.e. for setting
+/// up provider states), while the matching should still be done by type. The template and each
+/// example are interpreted as a `json_pattern!`, and must each be a single token tree (i.e. an
+/// object, array, literal or parenthesized expression).
+///
+/// The matching rules are only taken from the template, so any matchers in the examples are
+/// ignored. An optional `min` sets the minimum length of the array for matching (defaults to 1)
+/// and does not limit the number of examples. If there are fewer examples than `min`, the
+/// generated array is padded with copies of the template so it still has `min` elements.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "people": each_like_examples!({ "name": "J. Smith", "age": 30 }, [
+///     { "name": "Ann Smith", "age": 42 },
+///     { "name": "Bob Jones", "age": 27 }
+///   ], min = 1)
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! each_like_examples {
+    ($template:tt, [ $($example:tt),* $(,)? ]) => {
+        $crate::patterns::EachLike::new(json_pattern!($template))
+            .with_examples(vec![ $( json_pattern!($example) ),* ])
+    };
+    ($template:tt, [ $($example:tt),* $(,)? ], min = $min_len:expr) => {
+        $crate::patterns::EachLike::new(json_pattern!($template))
+            .with_examples(vec![ $( json_pattern!($example) ),* ])
+            .with_min_len($min_len)
+    };
+}
+
+#[test]
+fn each_like_examples_is_pattern() {
+    use expectest::prelude::*;
+    use pact_models::matchingrules_list;
+    use serde_json::json;
+
+    let matchable = each_like_examples!({ "name": "J. Smith" }, [
+        { "name": "Ann Smith" },
+        { "name": like!("Bob Jones") }
+    ]);
+    expect!(matchable.to_example()).to(be_equal_to(json!([
+        { "name": "Ann Smith" },
+        { "name": "Bob Jones" }
+    ])));
+
+    let mut rules = MatchingRuleCategory::empty("body");
+    matchable.extract_matching_rules(DocPath::root(), &mut rules);
+    expect!(rules).to(be_equal_to(matchingrules_list! {
+        "body";
+        "$" => [ MatchingRule::MinType(1) ],
+        "$[*].*" => [ MatchingRule::Type ]
+    }));
+}
+
+#[test]
+fn each_like_examples_pads_the_examples_to_the_minimum_length() {
+    use expectest::prelude::*;
+    use serde_json::json;
+
+    let matchable = each_like_examples!("tag", ["a", "b"], min = 4);
+    expect!(matchable.to_example()).to(be_equal_to(json!(["a", "b", "tag", "tag"])));
+
+    let matchable = each_like_examples!("tag", ["a", "b", "c"], min = 2);
+    expect!(matchable.to_example()).to(be_equal_to(json!(["a", "b", "c"])));
+
+    let matchable = each_like_examples!("tag", []);
+    expect!(matchable.to_example()).to(be_equal_to(json!(["tag"])));
+}
+
+/// Match an array of tuples, where each tuple is an inner array with a fixed number of elements,
+/// and each position in the tuple is matched with its own pattern.
+#[derive(Debug)]
+pub struct TupleEachLike {
+    elements: Vec<JsonPattern>,
+    min_len: usize,
+}
+
+impl TupleEachLike {
+    /// Match arrays containing tuples with elements like `elements`, by position.
+    pub fn new(elements: Vec<JsonPattern>) -> TupleEachLike {
+        TupleEachLike {
+            elements,
+            min_len: 1,
+        }
+    }
+
+    /// Construct a new `TupleEachLike` from an array pattern. Used by `tuple_each_like!`. Panics
+    /// if the pattern is not an array.
+    #[doc(hidden)]
+    pub fn from_pattern(pattern: JsonPattern) -> TupleEachLike {
+        match pattern {
+            JsonPattern::Array(elements) => TupleEachLike::new(elements),
+            _ => panic!("tuple_each_like! requires an array of tuple elements, got {:?}", pattern)
+        }
+    }
+
+    /// Use this after `new` to set a minimum length for the outer array.
+    pub fn with_min_len(mut self, min_len: usize) -> TupleEachLike {
+        self.min_len = min_len;
+        self
+    }
+}
+
+impl_from_for_pattern!(TupleEachLike, JsonPattern);
+
+impl Pattern for TupleEachLike {
+    type Matches = serde_json::Value;
+
+    fn to_example(&self) -> serde_json::Value {
+        let tuple = serde_json::Value::Array(self.elements.iter()
+            .map(|element| element.to_example())
+            .collect());
+        serde_json::Value::Array(repeat(tuple).take(self.min_len).collect())
+    }
+
+    fn to_example_bytes(&self) -> Vec<u8> {
+        self.to_example().to_string().into_bytes()
+    }
+
+    fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+        rules_out.add_rule(
+            path.clone(),
+            MatchingRule::MinType(self.min_len),
+            RuleLogic::And
+        );
+
+        // Each tuple must have exactly the number of elements given
+        let mut tuple_path = path.clone();
+        tuple_path.push_star_index();
+        rules_out.add_rule(
+            tuple_path.clone(),
+            MatchingRule::MinMaxType(self.elements.len(), self.elements.len()),
+            RuleLogic::And
+        );
+
+        for (index, element) in self.elements.iter().enumerate() {
+            let element_path = tuple_path.join_index(index);
+            element.extract_matching_rules(element_path.clone(), rules_out);
+            // Elements without their own matchers are matched by type
+            if !rules_out.rules.contains_key(&element_path) {
+                rules_out.add_rule(element_path, MatchingRule::Type, RuleLogic::And);
+            }
+        }
+    }
+}
+
+/// Matches an array of tuples (inner arrays with a fixed number of elements, i.e. rows of CSV
+/// like data). The argument is an array with the pattern for each position of the tuple, and is
+/// interpreted as a `json_pattern!`. Elements without their own matchers are matched by type,
+/// and each tuple must have exactly the same number of elements. An optional `min` sets the
+/// minimum number of tuples (defaults to 1).
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   // Expect an array of [name, value] pairs, where the value is a number
+///   "settings": tuple_each_like!([ "timeout", term!("^\\d+$", "30") ], min = 2)
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! tuple_each_like {
+    ([ $($element:tt)* ]) => {
+        $crate::patterns::TupleEachLike::from_pattern(json_pattern!([ $($element)* ]))
+    };
+    ([ $($element:tt)* ], min = $min_len:expr) => {
+        $crate::patterns::TupleEachLike::from_pattern(json_pattern!([ $($element)* ]))
+            .with_min_len($min_len)
+    };
+}
+
+#[test]
+fn tuple_each_like_is_pattern() {
+    use expectest::prelude::*;
+    use pact_models::matchingrules_list;
+    use serde_json::json;
+
+    let matchable = tuple_each_like!([ "timeout", term!("^\\d+$", "30") ], min = 2);
+    expect!(matchable.to_example()).to(be_equal_to(json!([["timeout", "30"], ["timeout", "30"]])));
+
+    let mut rules = MatchingRuleCategory::empty("body");
+    matchable.extract_matching_rules(DocPath::root(), &mut rules);
+    expect!(rules).to(be_equal_to(matchingrules_list! {
+        "body";
+        "$" => [ MatchingRule::MinType(2) ],
+        "$[*]" => [ MatchingRule::MinMaxType(2, 2) ],
+        "$[*][0]" => [ MatchingRule::Type ],
+        "$[*][1]" => [ MatchingRule::Regex("^\\d+$".to_string()) ]
+    }));
+}
+
+#[test]
+fn tuple_each_like_with_ragged_tuples() {
+    use expectest::prelude::*;
+    use maplit::hashmap;
+    use pact_matching::{CoreMatchingContext, DiffConfig};
+    use pact_matching::json::compare_json;
+    use serde_json::json;
+
+    let matchable = tuple_each_like!([ "timeout", like!(30) ]);
+    let mut rules = MatchingRuleCategory::empty("body");
+    matchable.extract_matching_rules(DocPath::root(), &mut rules);
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys, &rules, &hashmap!{});
+
+    let expected = matchable.to_example();
+    expect!(compare_json(&DocPath::root(), &expected, &json!([["retries", 3], ["delay", 100]]), &context)).to(be_ok());
+
+    let mismatches = compare_json(&DocPath::root(), &expected,
+      &json!([["retries", 3], ["delay"], ["limit", 10, "extra"]]), &context).unwrap_err();
+    let mismatches = mismatches.iter()
+      .map(|mismatch| (mismatch.path.clone(), mismatch.to_body_mismatch().description()))
+      .collect::<Vec<_>>();
+    expect!(mismatches.contains(&("$[1]".to_string(),
+      "Expected [\"delay\"] (size 1) to have minimum size of 2".to_string()))).to(be_true());
+    expect!(mismatches.iter().any(|(path, description)| path == "$[2]" &&
+      description.starts_with("Expected [\"limit\", 10, \"extra\"] (size 3) to have maximum size of 2"))).to(be_true());
+}
+
 /// Match and generate strings that match a regular expression.
 #[derive(Debug)]
 pub struct Term<Nested: Pattern> {
@@ -412,416 +654,1871 @@ macro_rules! matching_regex {
     }
 }
 
-/// Match keys and values in an Object based on associated matching rules
+/// Match and generate strings with a length (in characters) within the given bounds.
 #[derive(Debug)]
-pub struct ObjectMatching {
-    example: JsonPattern,
-    rules: Vec<MatchingRule>
+pub struct StringLength<Nested: Pattern> {
+    /// The example string we generate when asked.
+    example: String,
+    /// The minimum length of the string.
+    min: Option<usize>,
+    /// The maximum length of the string.
+    max: Option<usize>,
+    /// Since we always store `example` as a string, we need to mention our
+    /// `Nested` type somewhere. We can do that using the zero-length
+    /// `PhantomData` type.
+    phantom: PhantomData<Nested>,
 }
 
-impl ObjectMatching {
-  /// Create a new ObjectMatching pattern with the provided pattern and list of rules
-  pub fn new(example: JsonPattern, rules: Vec<MatchingRule>) -> Self {
-    Self {
-      example,
-      rules
+impl<Nested: Pattern> StringLength<Nested> {
+    /// Construct a new `StringLength`, given the example string to generate and the optional
+    /// minimum and maximum lengths. Panics if the example is not within the bounds, so that
+    /// errors are reported when the test is written rather than when the pact is verified.
+    pub fn new<S: Into<String>>(example: S, min: Option<usize>, max: Option<usize>) -> Self {
+        let example = example.into();
+        let length = example.chars().count();
+        if min.map(|min| length < min).unwrap_or(false) || max.map(|max| length > max).unwrap_or(false) {
+            panic!("example {:?} has a length of {}, which is not within the bounds {:?} to {:?}",
+                example, length, min, max);
+        }
+        StringLength { example, min, max, phantom: PhantomData }
     }
-  }
 }
 
-impl Pattern for ObjectMatching {
-  type Matches = Value;
-
-  fn to_example(&self) -> Self::Matches {
-      self.example.to_example()
-  }
+impl<Nested> Pattern for StringLength<Nested>
+where
+    Nested: Pattern,
+    Nested::Matches: From<String>,
+{
+    type Matches = Nested::Matches;
 
-  fn to_example_bytes(&self) -> Vec<u8> {
-      self.example.to_example_bytes()
-  }
+    fn to_example(&self) -> Self::Matches {
+        From::from(self.example.clone())
+    }
 
-  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
-    for rule in &self.rules {
-        rules_out.add_rule(path.clone(), rule.clone(), RuleLogic::And);
+    fn to_example_bytes(&self) -> Vec<u8> {
+        self.example.clone().into_bytes()
     }
 
-    let child_path = path.join("*");
-    let mut child_rules = MatchingRuleCategory::empty("body");
-    self.example.extract_matching_rules(DocPath::root(), &mut child_rules);
-    for (path, rules) in child_rules.rules {
-      let path_tokens = path.tokens().iter().dropping(2);
-      let mut rule_path = child_path.clone();
-      for segment in path_tokens {
-        rule_path.push(segment.clone());
-      }
-      for rule in &rules.rules {
-        rules_out.add_rule(rule_path.clone(), rule.clone(), rules.rule_logic);
-      }
+    fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+        rules_out.add_rule(path, MatchingRule::StringLength { min: self.min, max: self.max },
+            RuleLogic::And);
     }
-  }
 }
 
-impl_from_for_pattern!(ObjectMatching, JsonPattern);
+impl_from_for_pattern!(StringLength<JsonPattern>, JsonPattern);
+impl_from_for_pattern!(StringLength<StringPattern>, StringPattern);
 
 #[test]
-fn object_matching_is_pattern() {
-  use serde_json::*;
-  use expectest::prelude::*;
-  use pact_models::matchingrules_list;
+fn string_length_is_pattern() {
+    use maplit::*;
+    use serde_json::*;
 
-  let matchable = ObjectMatching::new(
-    json_pattern!({
-      "key1": "a string we don't care about",
-      "key2": "1",
-    }),
-    vec![
-      MatchingRule::EachKey(MatchingRuleDefinition::new(
-        "key1".to_string(), ValueType::String, MatchingRule::Regex("[a-z]{3,}[0-9]".to_string()), None
-      )),
-      MatchingRule::EachValue(MatchingRuleDefinition::new(
-        "some string".to_string(), ValueType::Unknown, MatchingRule::Type, None
-      ))
-    ]
-  );
-  assert_eq!(matchable.to_example(), json!({
-    "key1": "a string we don't care about",
-    "key2": "1",
-  }));
-  let mut rules = MatchingRuleCategory::empty("body");
-  matchable.extract_matching_rules(DocPath::root(), &mut rules);
-  expect!(rules).to(be_equal_to(matchingrules_list! {
-    "body"; "$" => [
-      MatchingRule::EachKey(MatchingRuleDefinition::new("key1".to_string(), ValueType::String,
-        MatchingRule::Regex("[a-z]{3,}[0-9]".to_string()), None)),
-      MatchingRule::EachValue(MatchingRuleDefinition::new("some string".to_string(), ValueType::Unknown,
-        MatchingRule::Type, None))
-    ]
-  }));
+    let matchable = StringLength::<JsonPattern>::new("password", Some(8), None);
+    assert_eq!(matchable.to_example(), json!("password"));
+
+    let mut rules = MatchingRuleCategory::empty("body");
+    matchable.extract_matching_rules(DocPath::root(), &mut rules);
+    let expected_rules = hashmap!(
+        "$.body".to_string() => json!({ "match": "stringLength", "min": 8 })
+    );
+    assert_eq!(rules.to_v2_json(), expected_rules);
 }
 
 #[test]
-fn object_matching_into() {
-    // Make sure we can convert `ObjectMatching` into different pattern types.
-    let _: JsonPattern = ObjectMatching::new(json_pattern!({}), vec![]).into();
+#[should_panic(expected = "which is not within the bounds")]
+fn string_length_with_invalid_example_panics() {
+    let _ = StringLength::<JsonPattern>::new("short", Some(8), None);
 }
 
-/// A pattern which can take a JSON pattern and then apply a number of matching rules to the
-/// resulting JSON object.
+/// A pattern which matches strings with a length (in characters) within the given bounds, and
+/// which generates `$example`. The length can be given as an exact value, or with `min` and/or
+/// `max` bounds.
 ///
 /// ```
 /// use pact_consumer::*;
-/// use pact_consumer::prelude::{each_key, each_value};
 ///
 /// # fn main() {
-/// object_matching!(
-///   json_pattern!({
-///       "key1": "a string",
-///       "key2": "1",
-///   }),
-///   [
-///       each_key(matching_regex!("[a-z]{3}[0-9]", "key1")),
-///       each_value(like!("value1"))
-///   ]
-/// );
+/// json_pattern!({
+///   "password_hash": string_length!("$2a$12$R9h/cIPz0gi.URNNX3kh2OPST9/PgBkqquzi.Ss7KIUgO2t0jWMUW", 60),
+///   "username": string_length!("fred", min = 3, max = 20),
+///   "nickname": string_length!("f", max = 10)
+/// });
 /// # }
 /// ```
 #[macro_export]
-macro_rules! object_matching {
-  ($example:expr, [ $( $rule:expr ),* ]) => {{
-      let mut _rules: Vec<pact_models::matchingrules::MatchingRule> = vec![];
-
-      $(
-        _rules.push($rule.into());
-      )*
-
-      $crate::patterns::ObjectMatching::new(json_pattern!($example), _rules)
-  }}
+macro_rules! string_length {
+    ($example:expr, min = $min:expr, max = $max:expr) => {
+        $crate::patterns::StringLength::new($example, Some($min), Some($max))
+    };
+    ($example:expr, min = $min:expr) => {
+        $crate::patterns::StringLength::new($example, Some($min), None)
+    };
+    ($example:expr, max = $max:expr) => {
+        $crate::patterns::StringLength::new($example, None, Some($max))
+    };
+    ($example:expr, $length:expr) => {
+        $crate::patterns::StringLength::new($example, Some($length), Some($length))
+    };
 }
 
 #[test]
-fn object_matching_test() {
-  use expectest::prelude::*;
-  use pact_models::matchingrules_list;
-  use serde_json::json;
-  use pretty_assertions::assert_eq;
+fn string_length_macro_parsing() {
+    let exact: StringLength<JsonPattern> = string_length!("abc", 3);
+    assert_eq!((exact.min, exact.max), (Some(3), Some(3)));
+    let bounded: StringLength<JsonPattern> = string_length!("abc", min = 1, max = 5);
+    assert_eq!((bounded.min, bounded.max), (Some(1), Some(5)));
+    let min_only: StringLength<JsonPattern> = string_length!("abc", min = 2);
+    assert_eq!((min_only.min, min_only.max), (Some(2), None));
+}
 
-  let matchable = object_matching!(
-    json_pattern!({
-        "key1": "a string",
-        "key2": "1",
-    }),
-    [
-        each_key(matching_regex!("[a-z]{3}[0-9]", "key1")),
-        each_value(like!("value1"))
-    ]
-  );
-  expect!(matchable.to_example()).to(be_equal_to(json!({
-    "key1": "a string",
-    "key2": "1"
-  })));
+/// Match strings of hexadecimal digits (i.e. hashes or colours), optionally with a required number
+/// of digits. The example is used as the value in the consumer tests and the Pact file, as no
+/// generator is defined for random values.
+#[derive(Debug)]
+pub struct Hex<Nested: Pattern> {
+    /// The example string we generate when asked.
+    example: String,
+    /// The required number of digits.
+    length: Option<usize>,
+    /// Since we always store `example` as a string, we need to mention our
+    /// `Nested` type somewhere. We can do that using the zero-length
+    /// `PhantomData` type.
+    phantom: PhantomData<Nested>,
+}
 
-  let mut rules = MatchingRuleCategory::empty("body");
+impl<Nested: Pattern> Hex<Nested> {
+    /// Construct a new `Hex`, given the example string to generate and the optional number of
+    /// digits. Panics if the example is not a hexadecimal string with the required number of
+    /// digits.
+    pub fn new<S: Into<String>>(example: S, length: Option<usize>) -> Self {
+        let example = example.into();
+        if example.is_empty() || !example.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            panic!("example {:?} is not a hexadecimal string", example);
+        }
+        if let Some(length) = length {
+            if example.len() != length {
+                panic!("example {:?} has {} digits, but {} are required", example, example.len(), length);
+            }
+        }
+        Hex { example, length, phantom: PhantomData }
+    }
+}
+
+impl<Nested> Pattern for Hex<Nested>
+where
+    Nested: Pattern,
+    Nested::Matches: From<String>,
+{
+    type Matches = Nested::Matches;
+
+    fn to_example(&self) -> Self::Matches {
+        From::from(self.example.clone())
+    }
+
+    fn to_example_bytes(&self) -> Vec<u8> {
+        self.example.clone().into_bytes()
+    }
+
+    fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+        rules_out.add_rule(path, MatchingRule::Hex { length: self.length }, RuleLogic::And);
+    }
+}
+
+impl_from_for_pattern!(Hex<JsonPattern>, JsonPattern);
+impl_from_for_pattern!(Hex<StringPattern>, StringPattern);
+
+#[test]
+fn hex_is_pattern() {
+    use maplit::*;
+    use serde_json::*;
+
+    let matchable = Hex::<JsonPattern>::new("00ff00", Some(6));
+    assert_eq!(matchable.to_example(), json!("00ff00"));
+
+    let mut rules = MatchingRuleCategory::empty("body");
+    matchable.extract_matching_rules(DocPath::root(), &mut rules);
+    let expected_rules = hashmap!(
+        "$.body".to_string() => json!({ "match": "hex", "length": 6 })
+    );
+    assert_eq!(rules.to_v2_json(), expected_rules);
+}
+
+#[test]
+#[should_panic(expected = "is not a hexadecimal string")]
+fn hex_with_invalid_example_panics() {
+    let _ = Hex::<JsonPattern>::new("0x00ff", None);
+}
+
+#[test]
+#[should_panic(expected = "digits, but 64 are required")]
+fn hex_with_invalid_example_length_panics() {
+    let _ = Hex::<JsonPattern>::new("00ff", Some(64));
+}
+
+/// A pattern which matches strings of hexadecimal digits, and which uses `$example` as the value.
+/// The number of digits can optionally be given, and it can be applied to each value of an array
+/// with `each_value`.
+///
+/// ```
+/// use pact_consumer::*;
+/// use pact_consumer::prelude::each_value;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "sha256": hex!("9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08", 64),
+///   "colour": hex!("00ff00"),
+///   "colours": each_value(hex!("ff0000", 6))
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! hex {
+    ($example:expr, $length:expr) => {
+        $crate::patterns::Hex::new($example, Some($length))
+    };
+    ($example:expr) => {
+        $crate::patterns::Hex::new($example, None)
+    };
+}
+
+/// Match integers within the given bounds (inclusive). The example is used as the value in the
+/// consumer tests and the Pact file, as no generator is defined for random values.
+#[derive(Debug)]
+pub struct IntegerRange {
+  /// The example integer we generate when asked.
+  example: i64,
+  /// The minimum value of the integer.
+  min: i64,
+  /// The maximum value of the integer.
+  max: i64
+}
+
+impl IntegerRange {
+  /// Construct a new `IntegerRange`, given the example integer to generate and the minimum and
+  /// maximum values. Panics if the example is not within the bounds.
+  pub fn new(example: i64, min: i64, max: i64) -> Self {
+    if example < min || example > max {
+      panic!("example {} is not within the bounds {} to {}", example, min, max);
+    }
+    IntegerRange { example, min, max }
+  }
+}
+
+impl Pattern for IntegerRange {
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+    Value::from(self.example)
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.example.to_string().into_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::IntegerRange { min: self.min, max: self.max },
+      RuleLogic::And);
+  }
+}
+
+impl_from_for_pattern!(IntegerRange, JsonPattern);
+
+#[test]
+fn integer_range_is_pattern() {
+  use maplit::*;
+  use serde_json::*;
+
+  let matchable = IntegerRange::new(50, 0, 100);
+  assert_eq!(matchable.to_example(), json!(50));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  let expected_rules = hashmap!(
+    "$.body".to_string() => json!({ "match": "integerRange", "min": 0, "max": 100 })
+  );
+  assert_eq!(rules.to_v2_json(), expected_rules);
+}
+
+#[test]
+#[should_panic(expected = "is not within the bounds")]
+fn integer_range_with_invalid_example_panics() {
+  let _ = IntegerRange::new(150, 0, 100);
+}
+
+/// A pattern which matches integers between `$min` and `$max` (inclusive), and which uses
+/// `$example` as the value.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "priority": integer_range!(3, 1, 5)
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! integer_range {
+  ($example:expr, $min:expr, $max:expr) => {
+    $crate::patterns::IntegerRange::new($example, $min, $max)
+  };
+}
+
+/// Match numbers that are a multiple of the given base. Values are compared with a tolerance of
+/// `MULTIPLE_OF_TOLERANCE`, so decimal bases (i.e. `0.05`) can be used. The example is used as the
+/// value in the consumer tests and the Pact file, as no generator is defined for random values.
+#[derive(Debug)]
+pub struct MultipleOf {
+  /// The example number we generate when asked.
+  example: Value,
+  /// The base the number must be a multiple of.
+  base: f64
+}
+
+impl MultipleOf {
+  /// Construct a new `MultipleOf`, given the example number to generate and the base. Panics if
+  /// the base is not a positive number, or the example is not a number that is a multiple of
+  /// the base.
+  pub fn new<E: Into<Value>>(example: E, base: f64) -> Self {
+    if !(base > 0.0 && base.is_finite()) {
+      panic!("base {} must be a positive number", base);
+    }
+    let example = example.into();
+    match example.as_f64() {
+      Some(value) if ((value / base) - (value / base).round()).abs() < MULTIPLE_OF_TOLERANCE => {}
+      _ => panic!("example {} is not a multiple of {}", example, base)
+    }
+    MultipleOf { example, base }
+  }
+}
+
+impl Pattern for MultipleOf {
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+    self.example.clone()
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.example.to_string().into_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::MultipleOf(self.base), RuleLogic::And);
+  }
+}
+
+impl_from_for_pattern!(MultipleOf, JsonPattern);
+
+#[test]
+fn multiple_of_is_pattern() {
+  use maplit::*;
+  use serde_json::*;
+
+  let matchable = MultipleOf::new(25, 5.0);
+  assert_eq!(matchable.to_example(), json!(25));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  let expected_rules = hashmap!(
+    "$.body".to_string() => json!({ "match": "multipleOf", "base": 5.0 })
+  );
+  assert_eq!(rules.to_v2_json(), expected_rules);
+}
+
+#[test]
+#[should_panic(expected = "is not a multiple of")]
+fn multiple_of_with_invalid_example_panics() {
+  let _ = MultipleOf::new(7, 5.0);
+}
+
+#[test]
+#[should_panic(expected = "must be a positive number")]
+fn multiple_of_with_zero_base_panics() {
+  let _ = MultipleOf::new(0, 0.0);
+}
+
+/// A pattern which matches numbers that are a multiple of `$base`, and which uses `$example` as
+/// the value.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "priceInCents": multiple_of!(125, 5.0)
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! multiple_of {
+  ($example:expr, $base:expr) => {
+    $crate::patterns::MultipleOf::new($example, $base)
+  };
+}
+
+/// Match values that must be present and empty (an empty object, array or string).
+#[derive(Debug)]
+pub struct Empty {
+  /// The empty example value we generate when asked.
+  example: Value
+}
+
+impl Empty {
+  /// Construct a new `Empty` that matches an empty JSON object.
+  pub fn object() -> Self {
+    Empty { example: Value::Object(Default::default()) }
+  }
+
+  /// Construct a new `Empty` that matches an empty JSON array.
+  pub fn array() -> Self {
+    Empty { example: Value::Array(vec![]) }
+  }
+
+  /// Construct a new `Empty` that matches an empty string.
+  pub fn string() -> Self {
+    Empty { example: Value::String(String::default()) }
+  }
+}
+
+impl Pattern for Empty {
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+    self.example.clone()
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.example.to_string().into_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::Empty, RuleLogic::And);
+  }
+}
+
+impl_from_for_pattern!(Empty, JsonPattern);
+
+#[test]
+fn empty_is_pattern() {
+  use maplit::*;
+  use serde_json::*;
+
+  let matchable = Empty::array();
+  assert_eq!(matchable.to_example(), json!([]));
+  assert_eq!(Empty::object().to_example(), json!({}));
+  assert_eq!(Empty::string().to_example(), json!(""));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  let expected_rules = hashmap!(
+    "$.body".to_string() => json!({ "match": "empty" })
+  );
+  assert_eq!(rules.to_v2_json(), expected_rules);
+}
+
+/// Match keys and values in an Object based on associated matching rules
+#[derive(Debug)]
+pub struct ObjectMatching {
+    example: JsonPattern,
+    rules: Vec<MatchingRule>
+}
+
+impl ObjectMatching {
+  /// Create a new ObjectMatching pattern with the provided pattern and list of rules
+  pub fn new(example: JsonPattern, rules: Vec<MatchingRule>) -> Self {
+    Self {
+      example,
+      rules
+    }
+  }
+}
+
+impl Pattern for ObjectMatching {
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+      self.example.to_example()
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+      self.example.to_example_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    for rule in &self.rules {
+        rules_out.add_rule(path.clone(), rule.clone(), RuleLogic::And);
+    }
+
+    let child_path = path.join("*");
+    let mut child_rules = MatchingRuleCategory::empty("body");
+    self.example.extract_matching_rules(DocPath::root(), &mut child_rules);
+    for (path, rules) in child_rules.rules {
+      let path_tokens = path.tokens().iter().dropping(2);
+      let mut rule_path = child_path.clone();
+      for segment in path_tokens {
+        rule_path.push(segment.clone());
+      }
+      for rule in &rules.rules {
+        rules_out.add_rule(rule_path.clone(), rule.clone(), rules.rule_logic);
+      }
+    }
+  }
+}
+
+impl_from_for_pattern!(ObjectMatching, JsonPattern);
+
+#[test]
+fn object_matching_is_pattern() {
+  use serde_json::*;
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+
+  let matchable = ObjectMatching::new(
+    json_pattern!({
+      "key1": "a string we don't care about",
+      "key2": "1",
+    }),
+    vec![
+      MatchingRule::EachKey(MatchingRuleDefinition::new(
+        "key1".to_string(), ValueType::String, MatchingRule::Regex("[a-z]{3,}[0-9]".to_string()), None
+      )),
+      MatchingRule::EachValue(MatchingRuleDefinition::new(
+        "some string".to_string(), ValueType::Unknown, MatchingRule::Type, None
+      ))
+    ]
+  );
+  assert_eq!(matchable.to_example(), json!({
+    "key1": "a string we don't care about",
+    "key2": "1",
+  }));
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [
+      MatchingRule::EachKey(MatchingRuleDefinition::new("key1".to_string(), ValueType::String,
+        MatchingRule::Regex("[a-z]{3,}[0-9]".to_string()), None)),
+      MatchingRule::EachValue(MatchingRuleDefinition::new("some string".to_string(), ValueType::Unknown,
+        MatchingRule::Type, None))
+    ]
+  }));
+}
+
+#[test]
+fn object_matching_into() {
+    // Make sure we can convert `ObjectMatching` into different pattern types.
+    let _: JsonPattern = ObjectMatching::new(json_pattern!({}), vec![]).into();
+}
+
+/// A pattern which can take a JSON pattern and then apply a number of matching rules to the
+/// resulting JSON object.
+///
+/// ```
+/// use pact_consumer::*;
+/// use pact_consumer::prelude::{each_key, each_value};
+///
+/// # fn main() {
+/// object_matching!(
+///   json_pattern!({
+///       "key1": "a string",
+///       "key2": "1",
+///   }),
+///   [
+///       each_key(matching_regex!("[a-z]{3}[0-9]", "key1")),
+///       each_value(like!("value1"))
+///   ]
+/// );
+/// # }
+/// ```
+#[macro_export]
+macro_rules! object_matching {
+  ($example:expr, [ $( $rule:expr ),* ]) => {{
+      let mut _rules: Vec<pact_models::matchingrules::MatchingRule> = vec![];
+
+      $(
+        _rules.push($rule.into());
+      )*
+
+      $crate::patterns::ObjectMatching::new(json_pattern!($example), _rules)
+  }}
+}
+
+#[test]
+fn object_matching_test() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+  use pretty_assertions::assert_eq;
+
+  let matchable = object_matching!(
+    json_pattern!({
+        "key1": "a string",
+        "key2": "1",
+    }),
+    [
+        each_key(matching_regex!("[a-z]{3}[0-9]", "key1")),
+        each_value(like!("value1"))
+    ]
+  );
+  expect!(matchable.to_example()).to(be_equal_to(json!({
+    "key1": "a string",
+    "key2": "1"
+  })));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  assert_eq!(matchingrules_list! {
+    "body"; "$" => [
+      MatchingRule::EachKey(MatchingRuleDefinition::new("key1".to_string(), ValueType::String,
+        MatchingRule::Regex("[a-z]{3}[0-9]".to_string()), None)),
+      MatchingRule::EachValue(MatchingRuleDefinition::new("\"value1\"".to_string(),
+        ValueType::String, MatchingRule::Type, None))
+    ]
+  }, rules);
+}
+
+#[test]
+fn object_matching_supports_nested_matching_rules() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+  use pretty_assertions::assert_eq;
+
+  let matchable = object_matching!(
+    json_pattern!({
+      "key1": {
+        "id": matching_regex!("[0-9]+", "1000"),
+        "desc": like!("description")
+      }
+    }),
+    [
+        each_key(matching_regex!("[a-z]{3}[0-9]", "key1"))
+    ]
+  );
+  expect!(matchable.to_example()).to(be_equal_to(json!({
+    "key1": {
+      "id": "1000",
+      "desc": "description"
+    }
+  })));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  assert_eq!(matchingrules_list! {
+    "body"; "$" => [
+      MatchingRule::EachKey(MatchingRuleDefinition::new("key1".to_string(), ValueType::String,
+        MatchingRule::Regex("[a-z]{3}[0-9]".to_string()), None))
+    ],
+    "$.*.id" => [ MatchingRule::Regex("[0-9]+".to_string()) ],
+    "$.*.desc" => [ MatchingRule::Type ]
+  }, rules);
+}
+
+/// Apply an associated rule to each key of an Object
+#[derive(Debug)]
+pub struct EachKey {
+  /// The pattern we use to match.
+  pattern: StringPattern,
+  /// Name of the matcher definition to use instead of the pattern
+  reference: Option<String>
+}
+
+impl EachKey {
+  /// Construct a new `EachKey`, given a pattern and example key.
+  pub fn new<Nested: Into<StringPattern>>(pattern: Nested) -> Self {
+    EachKey {
+      pattern: pattern.into(),
+      reference: None
+    }
+  }
+
+  /// Construct a new `EachKey` that uses the named matcher definition (see `MatcherDefinition`),
+  /// given an example key.
+  pub fn reference<N: Into<String>, E: Into<String>>(name: N, example: E) -> Self {
+    EachKey {
+      pattern: StringPattern::String(example.into()),
+      reference: Some(name.into())
+    }
+  }
+}
+
+impl Pattern for EachKey {
+  type Matches = String;
+
+  fn to_example(&self) -> Self::Matches {
+    self.pattern.to_example()
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.to_example().into_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, self.into(), RuleLogic::And);
+  }
+}
+
+impl Into<MatchingRule> for EachKey {
+  fn into(self) -> MatchingRule {
+    (&self).into()
+  }
+}
+
+impl Into<MatchingRule> for &EachKey {
+  fn into(self) -> MatchingRule {
+    MatchingRule::EachKey(MatchingRuleDefinition {
+      value: self.to_example(),
+      value_type: ValueType::String,
+      rules: associated_rules(&self.pattern, self.reference.as_ref()),
+      generator: None
+    })
+  }
+}
+
+/// Returns the rules from the pattern to associate with an `EachKey` or `EachValue` rule, or a
+/// reference to the named matcher definition if there is one.
+fn associated_rules<P: Pattern>(
+  pattern: &P,
+  reference: Option<&String>
+) -> Vec<Either<MatchingRule, MatchingReference>> {
+  match reference {
+    Some(name) => vec![ Either::Right(MatchingReference { name: name.clone() }) ],
+    None => {
+      let mut tmp = MatchingRuleCategory::empty("body");
+      pattern.extract_matching_rules(DocPath::root(), &mut tmp);
+      tmp.rules.values()
+        .flat_map(|list| list.rules.iter())
+        .map(|rule| Either::Left(rule.clone()))
+        .collect()
+    }
+  }
+}
+
+#[test]
+fn each_key_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+
+  let matchable = EachKey::new(
+    matching_regex!("\\d+", "100")
+  );
+  expect!(matchable.to_example()).to(be_equal_to("100"));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [
+      MatchingRule::EachKey(MatchingRuleDefinition::new("100".to_string(), ValueType::String,
+        MatchingRule::Regex("\\d+".to_string()), None))
+    ]
+  }));
+}
+
+/// A pattern which applies another pattern to each key of an object, and which generates an
+/// example key. A regex matcher is the only matcher that makes sense to use on keys.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// // Each key must match the given regex, and an example key is supplied.
+/// use pact_consumer::patterns::each_key;
+/// each_key(matching_regex!("[a-z]{3}[0-9]", "key1"));
+/// # }
+/// ```
+pub fn each_key<P>(pattern: P) -> EachKey where P: Into<StringPattern> {
+  EachKey::new(pattern.into())
+}
+
+#[test]
+fn each_key_test() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+
+  let matchable = each_key(matching_regex!("[a-z]{3}[0-9]", "key1"));
+  expect!(matchable.to_example()).to(be_equal_to("key1"));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [
+      MatchingRule::EachKey(MatchingRuleDefinition::new("key1".to_string(), ValueType::String,
+        MatchingRule::Regex("[a-z]{3}[0-9]".to_string()), None))
+    ]
+  }));
+}
+
+/// Apply an associated rule to each value of an Object
+#[derive(Debug)]
+pub struct EachValue {
+  /// The regex we use to match.
+  rule: JsonPattern,
+  /// Name of the matcher definition to use instead of the pattern
+  reference: Option<String>
+}
+
+impl EachValue {
+  /// Construct a new `EachValue`, given a pattern and example JSON.
+  pub fn new<P: Into<JsonPattern>>(pattern: P) -> Self {
+    EachValue {
+      rule: pattern.into(),
+      reference: None
+    }
+  }
+
+  /// Construct a new `EachValue` that uses the named matcher definition (see
+  /// `MatcherDefinition`), given an example value.
+  pub fn reference<N: Into<String>, E: Into<Value>>(name: N, example: E) -> Self {
+    EachValue {
+      rule: JsonPattern::Json(example.into()),
+      reference: Some(name.into())
+    }
+  }
+}
+
+impl Pattern for EachValue
+{
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+    self.rule.to_example()
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.to_example().to_string().into_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, self.into(), RuleLogic::And);
+  }
+}
+
+impl Into<MatchingRule> for EachValue {
+  fn into(self) -> MatchingRule {
+    (&self).into()
+  }
+}
+
+impl Into<MatchingRule> for &EachValue {
+  fn into(self) -> MatchingRule {
+    MatchingRule::EachValue(MatchingRuleDefinition {
+      value: self.to_example().to_string(),
+      value_type: ValueType::String,
+      rules: associated_rules(&self.rule, self.reference.as_ref()),
+      generator: None
+    })
+  }
+}
+
+#[test]
+fn each_value_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+
+  let matchable = EachValue::new(
+    matching_regex!("\\d+", "100")
+  );
+  expect!(matchable.to_example()).to(be_equal_to("100"));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [
+      MatchingRule::EachValue(MatchingRuleDefinition::new("\"100\"".to_string(), ValueType::String,
+        MatchingRule::Regex("\\d+".to_string()), None))
+    ]
+  }));
+}
+
+/// A pattern which applies another pattern to each value of an object, and which generates an
+/// example value.
+///
+/// ```
+/// use pact_consumer::*;
+/// use pact_consumer::prelude::each_value;
+///
+/// # fn main() {
+/// // Each value must match the given regex, and an example value is supplied.
+/// each_value(matching_regex!("[a-z]{3}[0-9]", "value1"));
+/// # }
+/// ```
+pub fn each_value<P: Into<JsonPattern>>(pattern: P) -> EachValue {
+  EachValue::new(pattern.into())
+}
+
+#[test]
+fn each_value_test() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+
+  let result = each_value(matching_regex!("[a-z]{5}[0-9]", "value1"));
+  expect!(result.to_example()).to(be_equal_to("value1"));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  result.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [
+      MatchingRule::EachValue(MatchingRuleDefinition::new("\"value1\"".to_string(), ValueType::String,
+        MatchingRule::Regex("[a-z]{5}[0-9]".to_string()), None))
+    ]
+  }));
+}
+
+#[test]
+fn each_key_and_each_value_with_references() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+
+  let each_key = EachKey::reference("id", "100");
+  expect!(each_key.to_example()).to(be_equal_to("100"));
+  let each_value = EachValue::reference("item", json!({ "id": 1 }));
+  expect!(each_value.to_example()).to(be_equal_to(json!({ "id": 1 })));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  each_key.extract_matching_rules(DocPath::new_unwrap("$.a"), &mut rules);
+  each_value.extract_matching_rules(DocPath::new_unwrap("$.b"), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body";
+    "$.a" => [ MatchingRule::EachKey(MatchingRuleDefinition {
+      value: "100".to_string(),
+      value_type: ValueType::String,
+      rules: vec![ Either::Right(MatchingReference { name: "id".to_string() }) ],
+      generator: None
+    }) ],
+    "$.b" => [ MatchingRule::EachValue(MatchingRuleDefinition {
+      value: "{\"id\":1}".to_string(),
+      value_type: ValueType::String,
+      rules: vec![ Either::Right(MatchingReference { name: "item".to_string() }) ],
+      generator: None
+    }) ]
+  }));
+}
+
+/// Apply a pattern to each key and another pattern to each value of an Object. This is the same as
+/// applying both an `EachKey` and an `EachValue` rule to the object.
+#[derive(Debug)]
+pub struct EachEntry {
+  /// The rule to apply to each key.
+  key: EachKey,
+  /// The rule to apply to each value.
+  value: EachValue
+}
+
+impl EachEntry {
+  /// Construct a new `EachEntry`, given the patterns for the keys and values. The example object
+  /// will have a single entry made from the example key and value.
+  pub fn new<K: Into<StringPattern>, V: Into<JsonPattern>>(key_pattern: K, value_pattern: V) -> Self {
+    EachEntry {
+      key: EachKey::new(key_pattern),
+      value: EachValue::new(value_pattern)
+    }
+  }
+}
+
+impl Pattern for EachEntry {
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+    let mut map = serde_json::Map::new();
+    map.insert(self.key.to_example(), self.value.to_example());
+    Value::Object(map)
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.to_example().to_string().into_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    self.key.extract_matching_rules(path.clone(), rules_out);
+    self.value.extract_matching_rules(path, rules_out);
+  }
+}
+
+impl_from_for_pattern!(EachEntry, JsonPattern);
+
+#[test]
+fn each_entry_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+
+  let matchable = EachEntry::new(
+    matching_regex!("[0-9a-f]{8}(-[0-9a-f]{4}){3}-[0-9a-f]{12}", "a3e1c2d4-7b6f-4e3a-9c1d-2f8b5a6e7d90"),
+    like!({ "name": "Mary" })
+  );
+  expect!(matchable.to_example()).to(be_equal_to(json!({
+    "a3e1c2d4-7b6f-4e3a-9c1d-2f8b5a6e7d90": { "name": "Mary" }
+  })));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::new_unwrap("$.people"), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$.people" => [
+      MatchingRule::EachKey(MatchingRuleDefinition::new("a3e1c2d4-7b6f-4e3a-9c1d-2f8b5a6e7d90".to_string(),
+        ValueType::String, MatchingRule::Regex("[0-9a-f]{8}(-[0-9a-f]{4}){3}-[0-9a-f]{12}".to_string()), None)),
+      MatchingRule::EachValue(MatchingRuleDefinition::new("{\"name\":\"Mary\"}".to_string(),
+        ValueType::String, MatchingRule::Type, None))
+    ]
+  }));
+}
+
+/// A pattern which applies a pattern to each key and another pattern to each value of an object,
+/// and which generates an example object with a single entry made from the example key and value.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// // Each key must be a UUID, and each value must be an object with a name.
+/// each_entry!(
+///   matching_regex!("[0-9a-f]{8}(-[0-9a-f]{4}){3}-[0-9a-f]{12}", "a3e1c2d4-7b6f-4e3a-9c1d-2f8b5a6e7d90"),
+///   like!({ "name": "Mary" })
+/// );
+/// # }
+/// ```
+#[macro_export]
+macro_rules! each_entry {
+  ($key_pattern:expr, $value_pattern:expr) => {
+    $crate::patterns::EachEntry::new($key_pattern, $value_pattern)
+  };
+}
+
+#[test]
+fn each_entry_test() {
+  use expectest::prelude::*;
+  use serde_json::json;
+
+  let pattern = json_pattern!({
+    "people": each_entry!(matching_regex!("[a-z]+[0-9]", "key1"), matching_regex!("\\d+", "100"))
+  });
+  expect!(pattern.to_example()).to(be_equal_to(json!({ "people": { "key1": "100" } })));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  pattern.extract_matching_rules(DocPath::root(), &mut rules);
+  let rule_list = rules.rules.get(&DocPath::new_unwrap("$.people")).unwrap();
+  expect!(rule_list.rules.iter().map(|rule| rule.name()).collect::<Vec<_>>()).to(be_equal_to(vec![
+    "each-key".to_string(), "each-value".to_string()
+  ]));
+}
+
+/// Defines a named matcher from the rules of the nested pattern, which can then be referenced by
+/// name from `EachKey` and `EachValue` (see `EachKey::reference` and `EachValue::reference`). The
+/// nested pattern is also applied to the value and used to generate the example.
+#[derive(Debug)]
+pub struct MatcherDefinition {
+  /// Name of the matcher definition.
+  name: String,
+  /// The pattern that defines the matcher.
+  pattern: JsonPattern
+}
+
+impl MatcherDefinition {
+  /// Construct a new `MatcherDefinition`, given the name and the pattern that defines the matcher.
+  pub fn new<N: Into<String>, P: Into<JsonPattern>>(name: N, pattern: P) -> Self {
+    MatcherDefinition {
+      name: name.into(),
+      pattern: pattern.into()
+    }
+  }
+}
+
+impl Pattern for MatcherDefinition {
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+    self.pattern.to_example()
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.pattern.to_example_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    let mut tmp = MatchingRuleCategory::empty(rules_out.name.clone());
+    self.pattern.extract_matching_rules(DocPath::root(), &mut tmp);
+    rules_out.add_definition(self.name.as_str(), RuleList {
+      rules: tmp.rules.values().flat_map(|list| list.rules.iter()).cloned().collect(),
+      rule_logic: RuleLogic::And,
+      cascaded: false
+    });
+    self.pattern.extract_matching_rules(path, rules_out);
+  }
+}
+
+impl_from_for_pattern!(MatcherDefinition, JsonPattern);
+
+/// A pattern which defines a named matcher from the nested pattern, which can then be referenced
+/// from `EachKey` and `EachValue`. The nested pattern is also applied to the value.
+///
+/// ```
+/// use pact_consumer::*;
+/// use pact_consumer::patterns::{EachKey, EachValue};
+/// use serde_json::json;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "id": define_matcher!("id", matching_regex!("^\\d+$", "100")),
+///   "related": object_matching!(json_pattern!({ "200": "a" }), [ EachKey::reference("id", "200") ]),
+///   "counts": object_matching!(json_pattern!({ "a": "300" }), [ EachValue::reference("id", json!("300")) ])
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! define_matcher {
+  ($name:expr, $($json_pattern:tt)+) => {
+    $crate::patterns::MatcherDefinition::new($name, json_pattern!($($json_pattern)+))
+  };
+}
+
+#[test]
+fn matcher_definition_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+
+  let matchable = define_matcher!("id", matching_regex!("^\\d+$", "100"));
+  expect!(matchable.to_example()).to(be_equal_to(json!("100")));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::new_unwrap("$.id"), &mut rules);
+  let mut expected = matchingrules_list! {
+    "body"; "$.id" => [ MatchingRule::Regex("^\\d+$".to_string()) ]
+  };
+  expected.add_definition("id", RuleList::new(MatchingRule::Regex("^\\d+$".to_string())));
+  expect!(rules).to(be_equal_to(expected));
+}
+
+/// Match arrays where all the items are unique, optionally comparing the value at a path relative
+/// to each item. The nested pattern is used to generate the example and to match the items.
+#[derive(Debug)]
+pub struct ArrayDistinct {
+  /// The pattern for the array.
+  pattern: JsonPattern,
+  /// Path relative to each item to compare by.
+  by: Option<String>
+}
+
+impl ArrayDistinct {
+  /// Construct a new `ArrayDistinct`, given the pattern for the array and the optional path to
+  /// compare the items by (i.e. `$.id`).
+  pub fn new<P: Into<JsonPattern>>(pattern: P, by: Option<String>) -> Self {
+    ArrayDistinct {
+      pattern: pattern.into(),
+      by
+    }
+  }
+}
+
+impl Pattern for ArrayDistinct {
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+    self.pattern.to_example()
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.to_example().to_string().into_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    self.pattern.extract_matching_rules(path.clone(), rules_out);
+    rules_out.add_rule(path, MatchingRule::ArrayDistinct(self.by.clone()), RuleLogic::And);
+  }
+}
+
+impl_from_for_pattern!(ArrayDistinct, JsonPattern);
+
+#[test]
+fn array_distinct_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+
+  let matchable = ArrayDistinct::new(json_pattern!(["a", "b"]), None);
+  expect!(matchable.to_example()).to(be_equal_to(json!(["a", "b"])));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [ MatchingRule::ArrayDistinct(None) ]
+  }));
+}
+
+/// A pattern which matches arrays where all the items are unique. The items can be compared by
+/// the value at a path relative to each item by using the `by` form. Any rules from the nested
+/// pattern are also applied, so it can be combined with `each_like!` to match each item. Note
+/// that the `min` of `each_like!` repeats the example item, so it should not be used here.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "tags": array_distinct!(json_pattern!(["red", "green"])),
+///   "users": array_distinct!(each_like!({ "id": 1, "name": "Fred" }), by = "$.id")
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! array_distinct {
+  ($pattern:expr, by = $by:expr) => {
+    $crate::patterns::ArrayDistinct::new($pattern, Some($by.to_string()))
+  };
+  ($pattern:expr) => {
+    $crate::patterns::ArrayDistinct::new($pattern, None)
+  };
+}
+
+#[test]
+fn array_distinct_with_each_like() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+
+  let matchable = array_distinct!(each_like!({ "id": 1 }), by = "$.id");
+  expect!(matchable.to_example()).to(be_equal_to(json!([{ "id": 1 }])));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body";
+    "$" => [ MatchingRule::MinType(1), MatchingRule::ArrayDistinct(Some("$.id".to_string())) ],
+    "$[*].*" => [ MatchingRule::Type ]
+  }));
+}
+
+/// Match objects that contain exactly the keys of the example object, ignoring the values.
+#[derive(Debug)]
+pub struct MatchKeys {
+  /// The example object.
+  example: JsonPattern
+}
+
+impl MatchKeys {
+  /// Construct a new `MatchKeys`, given an example object. The keys of the example are the keys
+  /// the actual object must have.
+  pub fn new<P: Into<JsonPattern>>(example: P) -> Self {
+    let example = example.into();
+    if !example.to_example().is_object() {
+      panic!("MatchKeys example must be an object, got {}", example.to_example());
+    }
+    MatchKeys { example }
+  }
+
+  fn keys(&self) -> Vec<String> {
+    match self.example.to_example() {
+      Value::Object(map) => map.keys().cloned().collect(),
+      _ => vec![]
+    }
+  }
+}
+
+impl Pattern for MatchKeys {
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+    self.example.to_example()
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.to_example().to_string().into_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::Keys(self.keys()), RuleLogic::And);
+  }
+}
+
+impl_from_for_pattern!(MatchKeys, JsonPattern);
+
+#[test]
+fn match_keys_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+
+  let matchable = MatchKeys::new(json_pattern!({ "a": 1, "b": like!("x") }));
+  expect!(matchable.to_example()).to(be_equal_to(json!({ "a": 1, "b": "x" })));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [ MatchingRule::Keys(vec!["a".to_string(), "b".to_string()]) ]
+  }));
+}
+
+#[test]
+#[should_panic]
+fn match_keys_with_a_non_object_example_panics() {
+  MatchKeys::new(json_pattern!([1, 2]));
+}
+
+/// A pattern which matches objects that have exactly the same keys as the example object,
+/// regardless of the values. Any matching rules in the example are not applied, as the values
+/// are not compared.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "flags": match_keys!({ "enabled": true, "beta": false })
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! match_keys {
+  ($($json:tt)+) => {
+    $crate::patterns::MatchKeys::new(json_pattern!($($json)+))
+  };
+}
+
+/// Match values that match any one of several alternative patterns. The first alternative is
+/// used to generate the example. If all the alternatives are literal strings, a single `OneOf`
+/// matching rule with the values is used. Otherwise, the rules of each alternative are combined
+/// using `OR` logic, and alternatives without any rules (i.e. literal values) are matched with a
+/// regex for the exact value, as an equality rule would compare against the generated example.
+#[derive(Debug)]
+pub struct OneOf {
+  /// The alternative patterns.
+  alternatives: Vec<JsonPattern>
+}
+
+impl OneOf {
+  /// Construct a new `OneOf`, given the alternative patterns. Panics if no alternatives are
+  /// provided.
+  pub fn new(alternatives: Vec<JsonPattern>) -> Self {
+    if alternatives.is_empty() {
+      panic!("one_of! requires at least one alternative");
+    }
+    OneOf { alternatives }
+  }
+}
+
+impl Pattern for OneOf {
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+    self.alternatives[0].to_example()
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.alternatives[0].to_example_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    let values = self.alternatives.iter()
+      .map(|alternative| match alternative {
+        JsonPattern::Json(Value::String(value)) => Some(value.clone()),
+        _ => None
+      })
+      .collect::<Option<Vec<_>>>();
+    if let Some(values) = values {
+      rules_out.add_rule(path, MatchingRule::OneOf(values), RuleLogic::And);
+      return;
+    }
+
+    let mut alternative_rules = vec![];
+    for alternative in &self.alternatives {
+      let mut rules = MatchingRuleCategory::empty(rules_out.name.clone());
+      alternative.extract_matching_rules(path.clone(), &mut rules);
+      match rules.rules.remove(&path) {
+        Some(list) if !list.is_empty() => alternative_rules.extend(list.rules),
+        _ => {
+          let value = match alternative.to_example() {
+            Value::String(s) => s,
+            value => value.to_string()
+          };
+          alternative_rules.push(MatchingRule::Regex(format!("^{}$", regex::escape(value.as_str()))));
+        }
+      }
+      rules_out.add_rules(rules);
+    }
+    for rule in alternative_rules {
+      rules_out.add_rule(path.clone(), rule, RuleLogic::Or);
+    }
+  }
+}
+
+impl_from_for_pattern!(OneOf, JsonPattern);
+
+/// A pattern which matches any one of the given alternative patterns, and which generates the
+/// example from the first alternative. The alternatives can be literal values or other patterns.
+/// Combine it with `each_value` to apply it to each item of an array.
+///
+/// ```
+/// use pact_consumer::*;
+/// use pact_consumer::prelude::each_value;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "status": one_of!("active", "pending"),
+///   "colours": each_value(one_of!("RED", "GREEN", "BLUE")),
+///   "id": one_of!(like!(100), term!("^[a-z]+$", "abc"))
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! one_of {
+  ($($alternative:expr),+ $(,)?) => {
+    $crate::patterns::OneOf::new(vec![ $( $crate::patterns::JsonPattern::from($alternative) ),+ ])
+  };
+}
+
+#[test]
+fn one_of_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules::RuleList;
+  use serde_json::json;
+
+  let matchable = one_of!("active", "pending");
+  expect!(matchable.to_example()).to(be_equal_to(json!("active")));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::new_unwrap("$.status"), &mut rules);
+  let expected = RuleList {
+    rules: vec![
+      MatchingRule::OneOf(vec!["active".to_string(), "pending".to_string()])
+    ],
+    rule_logic: RuleLogic::And,
+    cascaded: false
+  };
+  expect!(rules.rules.get(&DocPath::new_unwrap("$.status"))).to(be_some().value(&expected));
+
+  let matchable = one_of!("active", 100);
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::new_unwrap("$.status"), &mut rules);
+  let expected = RuleList {
+    rules: vec![
+      MatchingRule::Regex("^active$".to_string()),
+      MatchingRule::Regex("^100$".to_string())
+    ],
+    rule_logic: RuleLogic::Or,
+    cascaded: false
+  };
+  expect!(rules.rules.get(&DocPath::new_unwrap("$.status"))).to(be_some().value(&expected));
+}
+
+#[test]
+fn one_of_with_patterns() {
+  use expectest::prelude::*;
+  use serde_json::json;
+
+  let matchable = one_of!(like!(100), term!("^[a-z]+$", "abc"));
+  expect!(matchable.to_example()).to(be_equal_to(json!(100)));
+
+  let mut rules = MatchingRuleCategory::empty("body");
   matchable.extract_matching_rules(DocPath::root(), &mut rules);
-  assert_eq!(matchingrules_list! {
-    "body"; "$" => [
-      MatchingRule::EachKey(MatchingRuleDefinition::new("key1".to_string(), ValueType::String,
-        MatchingRule::Regex("[a-z]{3}[0-9]".to_string()), None)),
-      MatchingRule::EachValue(MatchingRuleDefinition::new("\"value1\"".to_string(),
-        ValueType::String, MatchingRule::Type, None))
-    ]
-  }, rules);
+  let list = rules.rules.get(&DocPath::root()).unwrap();
+  expect!(list.rule_logic).to(be_equal_to(RuleLogic::Or));
+  expect!(list.rules.clone()).to(be_equal_to(vec![
+    MatchingRule::Type,
+    MatchingRule::Regex("^[a-z]+$".to_string())
+  ]));
+}
+
+/// A pattern which matches a string value that is one of the given values, and which uses the
+/// first value as the example. The values are converted to strings.
+#[macro_export]
+#[deprecated(note = "Use one_of! instead, which uses a OneOf matching rule for string values")]
+macro_rules! one_of_values {
+  ($($value:expr),+ $(,)?) => {
+    $crate::one_of!($( $value.to_string() ),+)
+  };
 }
 
 #[test]
-fn object_matching_supports_nested_matching_rules() {
+#[allow(deprecated)]
+fn one_of_values_is_an_alias_for_one_of() {
   use expectest::prelude::*;
   use pact_models::matchingrules_list;
   use serde_json::json;
-  use pretty_assertions::assert_eq;
 
-  let matchable = object_matching!(
-    json_pattern!({
-      "key1": {
-        "id": matching_regex!("[0-9]+", "1000"),
-        "desc": like!("description")
-      }
-    }),
-    [
-        each_key(matching_regex!("[a-z]{3}[0-9]", "key1"))
+  let matchable = one_of_values!("RED", "GREEN", "BLUE");
+  expect!(matchable.to_example()).to(be_equal_to(json!("RED")));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$" => [
+      MatchingRule::OneOf(vec!["RED".to_string(), "GREEN".to_string(), "BLUE".to_string()])
     ]
-  );
-  expect!(matchable.to_example()).to(be_equal_to(json!({
-    "key1": {
-      "id": "1000",
-      "desc": "description"
-    }
-  })));
+  }));
+}
+
+#[test]
+fn one_of_with_each_value() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+
+  let matchable = each_value(one_of!("RED", "GREEN"));
 
   let mut rules = MatchingRuleCategory::empty("body");
   matchable.extract_matching_rules(DocPath::root(), &mut rules);
-  assert_eq!(matchingrules_list! {
+  expect!(rules).to(be_equal_to(matchingrules_list! {
     "body"; "$" => [
-      MatchingRule::EachKey(MatchingRuleDefinition::new("key1".to_string(), ValueType::String,
-        MatchingRule::Regex("[a-z]{3}[0-9]".to_string()), None))
-    ],
-    "$.*.id" => [ MatchingRule::Regex("[0-9]+".to_string()) ],
-    "$.*.desc" => [ MatchingRule::Type ]
-  }, rules);
+      MatchingRule::EachValue(MatchingRuleDefinition::new("\"RED\"".to_string(), ValueType::String,
+        MatchingRule::OneOf(vec!["RED".to_string(), "GREEN".to_string()]), None))
+    ]
+  }));
 }
 
-/// Apply an associated rule to each key of an Object
+/// Match values that are either null, or match the nested pattern. The nested pattern is used to
+/// generate the example.
 #[derive(Debug)]
-pub struct EachKey {
-  /// The pattern we use to match.
-  pattern: StringPattern
+pub struct Nullable {
+  /// The pattern for non-null values.
+  pattern: JsonPattern
 }
 
-impl EachKey {
-  /// Construct a new `EachKey`, given a pattern and example key.
-  pub fn new<Nested: Into<StringPattern>>(pattern: Nested) -> Self {
-    EachKey {
+impl Nullable {
+  /// Construct a new `Nullable`, given the pattern that non-null values must match.
+  pub fn new<P: Into<JsonPattern>>(pattern: P) -> Self {
+    Nullable {
       pattern: pattern.into()
     }
   }
 }
 
-impl Pattern for EachKey {
-  type Matches = String;
+impl Pattern for Nullable {
+  type Matches = Value;
 
   fn to_example(&self) -> Self::Matches {
     self.pattern.to_example()
   }
 
   fn to_example_bytes(&self) -> Vec<u8> {
-    self.to_example().into_bytes()
+    self.pattern.to_example_bytes()
   }
 
   fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
-    rules_out.add_rule(path, self.into(), RuleLogic::And);
+    let mut rules = MatchingRuleCategory::empty(rules_out.name.clone());
+    self.pattern.extract_matching_rules(path.clone(), &mut rules);
+    let (nested_rules, rule_logic) = match rules.rules.remove(&path) {
+      Some(list) if !list.is_empty() => (list.rules, list.rule_logic),
+      // Objects and arrays without a rule are matched by type, so that the rules of their
+      // children still apply
+      _ => match self.pattern.to_example() {
+        Value::Object(_) | Value::Array(_) => (vec![ MatchingRule::Type ], RuleLogic::And),
+        _ => (vec![ MatchingRule::Equality ], RuleLogic::And)
+      }
+    };
+    rules_out.add_rules(rules);
+    for rule in nested_rules {
+      rules_out.add_rule(path.clone(), MatchingRule::Nullable(Box::new(rule)), rule_logic);
+    }
   }
 }
 
-impl Into<MatchingRule> for EachKey {
-  fn into(self) -> MatchingRule {
-    (&self).into()
-  }
-}
+impl_from_for_pattern!(Nullable, JsonPattern);
 
-impl Into<MatchingRule> for &EachKey {
-  fn into(self) -> MatchingRule {
-    let mut tmp = MatchingRuleCategory::empty("body");
-    self.pattern.extract_matching_rules(DocPath::root(), &mut tmp);
-    MatchingRule::EachKey(MatchingRuleDefinition {
-      value: self.to_example(),
-      value_type: ValueType::String,
-      rules: tmp.rules.values()
-        .flat_map(|list| list.rules.iter())
-        .map(|rule| Either::Left(rule.clone()))
-        .collect(),
-      generator: None
-    })
-  }
+/// A pattern which matches either a null value or the nested pattern, and which generates the
+/// example from the nested pattern. A literal value is matched by equality, and a literal object
+/// or array by type.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "nickname": nullable!(like!("Freddy")),
+///   "address": nullable!({ "street": like!("Main St") })
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! nullable {
+  ($($json_pattern:tt)+) => {
+    $crate::patterns::Nullable::new(json_pattern!($($json_pattern)+))
+  };
 }
 
 #[test]
-fn each_key_is_pattern() {
+fn nullable_is_pattern() {
   use expectest::prelude::*;
   use pact_models::matchingrules_list;
+  use serde_json::json;
 
-  let matchable = EachKey::new(
-    matching_regex!("\\d+", "100")
-  );
-  expect!(matchable.to_example()).to(be_equal_to("100"));
+  let matchable = nullable!(like!("Freddy"));
+  expect!(matchable.to_example()).to(be_equal_to(json!("Freddy")));
 
   let mut rules = MatchingRuleCategory::empty("body");
-  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  matchable.extract_matching_rules(DocPath::new_unwrap("$.nickname"), &mut rules);
   expect!(rules).to(be_equal_to(matchingrules_list! {
-    "body"; "$" => [
-      MatchingRule::EachKey(MatchingRuleDefinition::new("100".to_string(), ValueType::String,
-        MatchingRule::Regex("\\d+".to_string()), None))
-    ]
+    "body"; "$.nickname" => [ MatchingRule::Nullable(Box::new(MatchingRule::Type)) ]
   }));
 }
 
-/// A pattern which applies another pattern to each key of an object, and which generates an
-/// example key. A regex matcher is the only matcher that makes sense to use on keys.
-///
-/// ```
-/// use pact_consumer::*;
-///
-/// # fn main() {
-/// // Each key must match the given regex, and an example key is supplied.
-/// use pact_consumer::patterns::each_key;
-/// each_key(matching_regex!("[a-z]{3}[0-9]", "key1"));
-/// # }
-/// ```
-pub fn each_key<P>(pattern: P) -> EachKey where P: Into<StringPattern> {
-  EachKey::new(pattern.into())
-}
-
 #[test]
-fn each_key_test() {
+fn nullable_with_nested_rules() {
   use expectest::prelude::*;
   use pact_models::matchingrules_list;
+  use serde_json::json;
 
-  let matchable = each_key(matching_regex!("[a-z]{3}[0-9]", "key1"));
-  expect!(matchable.to_example()).to(be_equal_to("key1"));
+  let matchable = nullable!({ "street": like!("Main St"), "number": 10 });
+  expect!(matchable.to_example()).to(be_equal_to(json!({ "street": "Main St", "number": 10 })));
 
   let mut rules = MatchingRuleCategory::empty("body");
-  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  matchable.extract_matching_rules(DocPath::new_unwrap("$.address"), &mut rules);
   expect!(rules).to(be_equal_to(matchingrules_list! {
-    "body"; "$" => [
-      MatchingRule::EachKey(MatchingRuleDefinition::new("key1".to_string(), ValueType::String,
-        MatchingRule::Regex("[a-z]{3}[0-9]".to_string()), None))
-    ]
+    "body";
+    "$.address" => [ MatchingRule::Nullable(Box::new(MatchingRule::Type)) ],
+    "$.address.street" => [ MatchingRule::Type ]
   }));
 }
 
-/// Apply an associated rule to each value of an Object
+/// Match and generate strings that are IP addresses, optionally of a particular version (4 or 6).
 #[derive(Debug)]
-pub struct EachValue {
-  /// The regex we use to match.
-  rule: JsonPattern
+pub struct IpAddress<Nested: Pattern> {
+  /// The example address we generate when asked.
+  example: String,
+  /// The IP version the address must be, if any.
+  version: Option<u8>,
+  /// Since we always store `example` as a string, we need to mention our
+  /// `Nested` type somewhere. We can do that using the zero-length
+  /// `PhantomData` type.
+  phantom: PhantomData<Nested>
 }
 
-impl EachValue {
-  /// Construct a new `EachValue`, given a pattern and example JSON.
-  pub fn new<P: Into<JsonPattern>>(pattern: P) -> Self {
-    EachValue {
-      rule: pattern.into()
+impl <Nested: Pattern> IpAddress<Nested> {
+  /// Construct a new `IpAddress`, given the example address to generate and an optional IP
+  /// version. Panics if the version is not 4 or 6, or if the example is not a valid address of
+  /// that version, so that errors are reported when the test is written rather than when the
+  /// pact is verified.
+  pub fn new<S: Into<String>>(example: S, version: Option<u8>) -> Self {
+    IpAddress {
+      example: validate_ip_address(example.into(), version),
+      version,
+      phantom: PhantomData
     }
   }
 }
 
-impl Pattern for EachValue
-{
-  type Matches = Value;
+impl <Nested> Pattern for IpAddress<Nested>
+where
+  Nested: Pattern,
+  Nested::Matches: From<String> {
+  type Matches = Nested::Matches;
 
   fn to_example(&self) -> Self::Matches {
-    self.rule.to_example()
+    From::from(self.example.clone())
   }
 
   fn to_example_bytes(&self) -> Vec<u8> {
-    self.to_example().to_string().into_bytes()
+    self.example.as_bytes().to_vec()
   }
 
   fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
-    rules_out.add_rule(path, self.into(), RuleLogic::And);
+    rules_out.add_rule(path, MatchingRule::IpAddress { version: self.version }, RuleLogic::And);
   }
 }
 
-impl Into<MatchingRule> for EachValue {
-  fn into(self) -> MatchingRule {
-    (&self).into()
+impl_from_for_pattern!(IpAddress<JsonPattern>, JsonPattern);
+impl_from_for_pattern!(IpAddress<StringPattern>, StringPattern);
+
+/// Internal helper function called by `IpAddress::new`. Panics if the version is not 4 or 6, or
+/// the example is not a valid IP address of that version.
+#[doc(hidden)]
+pub fn validate_ip_address<S: AsRef<str>>(example: S, version: Option<u8>) -> String {
+  let example = example.as_ref();
+  match (example.parse::<std::net::IpAddr>(), version) {
+    (_, Some(version)) if version != 4 && version != 6 =>
+      panic!("IP version must be either 4 or 6, got {}", version),
+    (Ok(address), Some(4)) if !address.is_ipv4() => panic!("Expected '{}' to be an IPv4 address", example),
+    (Ok(address), Some(6)) if !address.is_ipv6() => panic!("Expected '{}' to be an IPv6 address", example),
+    (Ok(_), _) => example.to_string(),
+    (Err(err), _) => panic!("Expected '{}' to be a valid IP address - {}", example, err)
   }
 }
 
-impl Into<MatchingRule> for &EachValue {
-  fn into(self) -> MatchingRule {
-    let mut tmp = MatchingRuleCategory::empty("body");
-    self.rule.extract_matching_rules(DocPath::root(), &mut tmp);
-    MatchingRule::EachValue(MatchingRuleDefinition {
-      value: self.to_example().to_string(),
-      value_type: ValueType::String,
-      rules: tmp.rules.values()
-        .flat_map(|list| list.rules.iter())
-        .map(|rule| Either::Left(rule.clone()))
-        .collect(),
-      generator: None
-    })
+/// A pattern which matches a string that is an IP address, and which generates `$example`. An
+/// optional version (4 or 6) can be given, in which case only addresses of that version will
+/// match. When applied to an array, each item of the array must be an IP address.
+///
+/// ```
+/// use pact_consumer::*;
+///
+/// # fn main() {
+/// json_pattern!({
+///   "gateway": ip_address!("10.0.0.1", 4),
+///   "nameservers": ip_address!(["2001:4860:4860::8888", "8.8.8.8"])
+/// });
+/// # }
+/// ```
+#[macro_export]
+macro_rules! ip_address {
+  ([ $($example:expr),* $(,)? ]) => {
+    $crate::patterns::IpAddressList::new(vec![ $( $example.to_string() ),* ], None)
+  };
+  ([ $($example:expr),* $(,)? ], $version:expr) => {
+    $crate::patterns::IpAddressList::new(vec![ $( $example.to_string() ),* ], Some($version))
+  };
+  ($example:expr) => {
+    $crate::patterns::IpAddress::new($example, None)
+  };
+  ($example:expr, $version:expr) => {
+    $crate::patterns::IpAddress::new($example, Some($version))
+  };
+}
+
+/// Match and generate arrays where each item is an IP address, optionally of a particular
+/// version (4 or 6). The matching rule is placed on the array, so it is applied to every item.
+#[derive(Debug)]
+pub struct IpAddressList {
+  /// The example addresses we generate when asked.
+  examples: Vec<String>,
+  /// The IP version the addresses must be, if any.
+  version: Option<u8>
+}
+
+impl IpAddressList {
+  /// Construct a new `IpAddressList`, given the example addresses to generate and an optional
+  /// IP version. Panics if any example is not a valid address of that version.
+  pub fn new<S: Into<String>>(examples: Vec<S>, version: Option<u8>) -> Self {
+    IpAddressList {
+      examples: examples.into_iter().map(|example| validate_ip_address(example.into(), version)).collect(),
+      version
+    }
+  }
+}
+
+impl Pattern for IpAddressList {
+  type Matches = Value;
+
+  fn to_example(&self) -> Self::Matches {
+    Value::Array(self.examples.iter().map(|example| Value::String(example.clone())).collect())
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.to_example().to_string().into_bytes()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::IpAddress { version: self.version }, RuleLogic::And);
   }
 }
 
+impl_from_for_pattern!(IpAddressList, JsonPattern);
+
 #[test]
-fn each_value_is_pattern() {
+fn ip_address_is_pattern() {
   use expectest::prelude::*;
   use pact_models::matchingrules_list;
+  use serde_json::json;
 
-  let matchable = EachValue::new(
-    matching_regex!("\\d+", "100")
-  );
-  expect!(matchable.to_example()).to(be_equal_to("100"));
+  let matchable: IpAddress<JsonPattern> = ip_address!("10.0.0.1", 4);
+  expect!(matchable.to_example()).to(be_equal_to(json!("10.0.0.1")));
 
   let mut rules = MatchingRuleCategory::empty("body");
-  matchable.extract_matching_rules(DocPath::root(), &mut rules);
+  matchable.extract_matching_rules(DocPath::new_unwrap("$.gateway"), &mut rules);
   expect!(rules).to(be_equal_to(matchingrules_list! {
-    "body"; "$" => [
-      MatchingRule::EachValue(MatchingRuleDefinition::new("\"100\"".to_string(), ValueType::String,
-        MatchingRule::Regex("\\d+".to_string()), None))
-    ]
+    "body"; "$.gateway" => [ MatchingRule::IpAddress { version: Some(4) } ]
   }));
 }
 
-/// A pattern which applies another pattern to each value of an object, and which generates an
-/// example value.
+#[test]
+fn ip_address_list_is_pattern() {
+  use expectest::prelude::*;
+  use pact_models::matchingrules_list;
+  use serde_json::json;
+
+  let matchable = ip_address!(["2001:4860:4860::8888", "8.8.8.8"]);
+  expect!(matchable.to_example()).to(be_equal_to(json!(["2001:4860:4860::8888", "8.8.8.8"])));
+
+  let mut rules = MatchingRuleCategory::empty("body");
+  matchable.extract_matching_rules(DocPath::new_unwrap("$.nameservers"), &mut rules);
+  expect!(rules).to(be_equal_to(matchingrules_list! {
+    "body"; "$.nameservers" => [ MatchingRule::IpAddress { version: None } ]
+  }));
+}
+
+#[test]
+#[should_panic(expected = "Expected '::1' to be an IPv4 address")]
+fn ip_address_with_example_of_the_wrong_version_panics() {
+  let _ = IpAddress::<JsonPattern>::new("::1", Some(4));
+}
+
+#[test]
+#[should_panic(expected = "Expected 'localhost' to be a valid IP address")]
+fn ip_address_with_invalid_example_panics() {
+  let _ = IpAddress::<JsonPattern>::new("localhost", None);
+}
+
+/// Match any value, ignoring its contents. This is intended to be applied at the root of a body
+/// for opaque payloads (i.e. an encrypted blob), where only the presence of the body and its
+/// content type are important.
+#[derive(Debug)]
+pub struct AnyBody<Nested: Pattern> {
+  /// The example value we generate when asked.
+  example: String,
+  /// Since we always store `example` as a string, we need to mention our
+  /// `Nested` type somewhere. We can do that using the zero-length
+  /// `PhantomData` type.
+  phantom: PhantomData<Nested>
+}
+
+impl <Nested: Pattern> AnyBody<Nested> {
+  /// Construct a new `AnyBody`, given the example value to generate.
+  pub fn new<S: Into<String>>(example: S) -> Self {
+    AnyBody {
+      example: example.into(),
+      phantom: PhantomData
+    }
+  }
+}
+
+impl <Nested> Pattern for AnyBody<Nested>
+where
+  Nested: Pattern,
+  Nested::Matches: From<String> {
+  type Matches = Nested::Matches;
+
+  fn to_example(&self) -> Self::Matches {
+    From::from(self.example.clone())
+  }
+
+  fn to_example_bytes(&self) -> Vec<u8> {
+    self.example.as_bytes().to_vec()
+  }
+
+  fn extract_matching_rules(&self, path: DocPath, rules_out: &mut MatchingRuleCategory) {
+    rules_out.add_rule(path, MatchingRule::Any, RuleLogic::And);
+  }
+}
+
+impl_from_for_pattern!(AnyBody<JsonPattern>, JsonPattern);
+impl_from_for_pattern!(AnyBody<StringPattern>, StringPattern);
+
+/// A pattern which matches any body, and which generates `$example`. The contents of the actual
+/// body are not compared, but the body must still be present and have a matching content type.
 ///
 /// ```
-/// use pact_consumer::*;
-/// use pact_consumer::prelude::each_value;
+/// use pact_consumer::prelude::*;
+/// use pact_consumer::builders::RequestBuilder;
 ///
-/// # fn main() {
-/// // Each value must match the given regex, and an example value is supplied.
-/// each_value(matching_regex!("[a-z]{3}[0-9]", "value1"));
-/// # }
+/// RequestBuilder::default()
+///   .body_matching2(any_body!("b3BhcXVlIGJsb2I="), "application/octet-stream");
 /// ```
-pub fn each_value<P: Into<JsonPattern>>(pattern: P) -> EachValue {
-  EachValue::new(pattern.into())
+#[macro_export]
+macro_rules! any_body {
+  ($example:expr) => {
+    $crate::patterns::AnyBody::new($example)
+  };
 }
 
 #[test]
-fn each_value_test() {
+fn any_body_is_pattern() {
   use expectest::prelude::*;
   use pact_models::matchingrules_list;
 
-  let result = each_value(matching_regex!("[a-z]{5}[0-9]", "value1"));
-  expect!(result.to_example()).to(be_equal_to("value1"));
+  let matchable: AnyBody<StringPattern> = any_body!("opaque");
+  expect!(matchable.to_example()).to(be_equal_to("opaque".to_string()));
+  expect!(matchable.to_example_bytes()).to(be_equal_to(b"opaque".to_vec()));
 
   let mut rules = MatchingRuleCategory::empty("body");
-  result.extract_matching_rules(DocPath::root(), &mut rules);
+  matchable.extract_matching_rules(DocPath::root(), &mut rules);
   expect!(rules).to(be_equal_to(matchingrules_list! {
-    "body"; "$" => [
-      MatchingRule::EachValue(MatchingRuleDefinition::new("\"value1\"".to_string(), ValueType::String,
-        MatchingRule::Regex("[a-z]{5}[0-9]".to_string()), None))
-    ]
+    "body"; "$" => [ MatchingRule::Any ]
   }));
 }
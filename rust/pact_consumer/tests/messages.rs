@@ -120,3 +120,34 @@ fn test_req_res_message_client() {
     expect!(processed.state).to(be_equal_to("VA"));
   }
 }
+
+// This is the same test for async messages, but using the async builder and consuming the messages
+// as a stream, where any generators are applied as each message is polled
+#[test_log::test(tokio::test)]
+async fn test_message_client_with_stream() {
+  use futures::{Stream, StreamExt};
+
+  let mut pact_builder = PactBuilderAsync::new_v4("message-consumer", "message-provider-stream");
+
+  pact_builder.message_interaction("hello message", |mut i| async move {
+      i.test_name("test_message_client_with_stream");
+      i.json_body(json_pattern!({
+          "name": like!("mai"),
+          "street": like!("5th"),
+          "state": like!("VA"),
+      }));
+      i
+    }).await;
+
+  let handler = MessageHandler::new();
+  let mut messages = pact_builder.messages_async();
+  expect!(messages.size_hint()).to(be_equal_to((1, Some(1))));
+  while let Some(message) = messages.next().await {
+    let bytes = message.contents.contents.value().unwrap();
+    let processed = handler.process(bytes).unwrap();
+
+    expect!(processed.name).to(be_equal_to("mai"));
+    expect!(processed.street).to(be_equal_to("5th"));
+    expect!(processed.state).to(be_equal_to("VA"));
+  }
+}
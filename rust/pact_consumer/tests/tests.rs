@@ -131,6 +131,29 @@ fn mock_server_passing_validation_blocking() -> anyhow::Result<()> {
   Ok(())
 }
 
+#[test_log::test]
+fn mock_server_returns_the_pact_it_validates_against() {
+  let alice_service = PactBuilder::new("Consumer", "Alice Service")
+    .interaction("a retrieve Mallory request", "", |mut i| {
+      i.request.path(term!("^/mallory/\\d+$", "/mallory/1"));
+      i.response.ok();
+      i
+    })
+    .start_mock_server(None, None);
+
+  let response = reqwest::blocking::get(alice_service.path("/mallory/2"))
+    .expect("could not fetch URL");
+  assert_eq!(response.status(), 200);
+
+  let pact = alice_service.pact();
+  assert_eq!(pact.consumer.name, "Consumer");
+  assert_eq!(pact.interactions.len(), 1);
+  let interaction = pact.interactions[0].as_v4_http().unwrap();
+  assert_eq!(interaction.description, "a retrieve Mallory request");
+  assert_eq!(interaction.request.path, "/mallory/1");
+  assert!(interaction.request.matching_rules.rules_for_category("path").is_some());
+}
+
 fn output_dir(path: &str) -> PathBuf {
   match Path::new(path).canonicalize() {
     Ok(path) => {
@@ -176,6 +199,66 @@ async fn mock_server_passing_validation_async_version() {
   assert_eq!(body, "That is some good Mallory.");
 }
 
+#[cfg(feature = "tls")]
+#[test_log::test(tokio::test)]
+async fn mock_server_with_self_signed_tls() {
+  let alice_service = PactBuilderAsync::new("Consumer", "Alice Service TLS")
+    .interaction("a retrieve Mallory request", "", |mut i| async move {
+      i.request.path("/mallory");
+      i.response
+        .ok()
+        .content_type("text/plain")
+        .body("That is some good Mallory.");
+      i.clone()
+    })
+    .await
+    .start_mock_server_tls_async(MockServerTls::default(), None)
+    .await;
+
+  let mallory_url = alice_service.path("/mallory");
+  expect!(mallory_url.scheme()).to(be_equal_to("https"));
+  let client = Client::builder()
+    .danger_accept_invalid_certs(true)
+    .build()
+    .expect("could not build the client");
+  let response = client.get(mallory_url).send().await.expect("could not fetch URL");
+  let body = response.text().await.expect("could not read response body");
+  assert_eq!(body, "That is some good Mallory.");
+}
+
+#[test_log::test(tokio::test)]
+async fn mock_server_bound_to_a_fixed_address() {
+  let address = std::net::TcpListener::bind("127.0.0.1:0")
+    .and_then(|listener| listener.local_addr())
+    .expect("could not find a free port");
+  let mut builder = PactBuilderAsync::new("Consumer", "Alice Service Fixed Port");
+  builder
+    .interaction("a retrieve Mallory request", "", |mut i| async move {
+      i.request.path("/mallory");
+      i.response
+        .ok()
+        .content_type("text/plain")
+        .body("That is some good Mallory.");
+      i.clone()
+    })
+    .await;
+  let alice_service = builder.start_mock_server_on_async(address, None)
+    .await
+    .expect("could not start the mock server");
+
+  expect!(alice_service.url().port()).to(be_some().value(address.port()));
+  expect!(alice_service.url().host_str()).to(be_some().value("127.0.0.1"));
+
+  let second = PactBuilderAsync::new("Consumer", "Alice Service Fixed Port 2")
+    .start_mock_server_on_async(address, None)
+    .await;
+  expect!(second.is_err()).to(be_true());
+
+  let response = reqwest::get(alice_service.path("/mallory")).await.expect("could not fetch URL");
+  let body = response.text().await.expect("could not read response body");
+  assert_eq!(body, "That is some good Mallory.");
+}
+
 #[test_log::test]
 #[should_panic]
 fn mock_server_failing_validation() {
@@ -10,7 +10,8 @@ use std::panic::RefUnwindSafe;
 use std::str::FromStr;
 
 use lazy_static::lazy_static;
-use libc::c_char;
+use bytes::Bytes;
+use libc::{c_char, c_uchar, size_t};
 use tracing::{debug, error, info, trace, warn};
 use tracing_core::{Level, LevelFilter};
 use tracing_log::AsLog;
@@ -50,6 +51,27 @@ pub extern "C" fn pactffi_version() -> *const c_char {
     VERSION.as_ptr() as *const c_char
 }
 
+/// Returns the major version number of the library. This can be used to check the
+/// compatibility of the library without having to parse the version string.
+#[no_mangle]
+pub extern "C" fn pactffi_version_major() -> u32 {
+    env!("CARGO_PKG_VERSION_MAJOR").parse().unwrap_or_default()
+}
+
+/// Returns the minor version number of the library. This can be used to check the
+/// compatibility of the library without having to parse the version string.
+#[no_mangle]
+pub extern "C" fn pactffi_version_minor() -> u32 {
+    env!("CARGO_PKG_VERSION_MINOR").parse().unwrap_or_default()
+}
+
+/// Returns the patch version number of the library. This can be used to check the
+/// compatibility of the library without having to parse the version string.
+#[no_mangle]
+pub extern "C" fn pactffi_version_patch() -> u32 {
+    env!("CARGO_PKG_VERSION_PATCH").parse().unwrap_or_default()
+}
+
 /// Initialise the mock server library, can provide an environment variable name to use to
 /// set the log levels. This function should only be called once, as it tries to install a global
 /// tracing subscriber.
@@ -228,6 +250,26 @@ ffi_fn! {
     }
 }
 
+ffi_fn! {
+    /// Combine two collections of mismatches into a new collection, with the mismatches from `a`
+    /// followed by the mismatches from `b`. A NULL pointer is treated as an empty collection.
+    ///
+    /// The mismatches are copied, so `a` and `b` are not modified and must still be deleted. The
+    /// returned collection must be deleted with `pactffi_mismatches_delete` when done.
+    fn pactffi_mismatches_concat(a: *const Mismatches, b: *const Mismatches) -> *const Mismatches {
+        let mut mismatches = vec![];
+        if !a.is_null() {
+            mismatches.extend_from_slice(&as_ref!(a).0);
+        }
+        if !b.is_null() {
+            mismatches.extend_from_slice(&as_ref!(b).0);
+        }
+        ptr::raw_to(Mismatches(mismatches)) as *const Mismatches
+    } {
+        std::ptr::null() as *const Mismatches
+    }
+}
+
 ffi_fn! {
     /// Get the next mismatch from a mismatches iterator.
     ///
@@ -310,6 +352,75 @@ ffi_fn! {
     }
 }
 
+ffi_fn! {
+    /// Get the raw bytes of the expected body of a body mismatch (`BodyMismatch` or
+    /// `BodyTypeMismatch`). The number of bytes is written to `len`. Unlike `pactffi_mismatch_to_json`,
+    /// the bytes are not converted to a UTF-8 string, so this can be used with binary bodies.
+    ///
+    /// # Safety
+    ///
+    /// `len` must be a valid pointer. The returned pointer is only valid while the mismatch is not
+    /// deleted, and must not be freed by the caller.
+    ///
+    /// # Error Handling
+    ///
+    /// Returns NULL (and sets `len` to zero) if the mismatch is not a body mismatch, or does not have
+    /// an expected body.
+    fn pactffi_mismatch_expected_bytes(mismatch: *const Mismatch, len: *mut size_t) -> *const c_uchar {
+        let mismatch = as_ref!(mismatch);
+        let len = as_mut!(len);
+        let expected = match mismatch {
+            Mismatch::BodyMismatch { expected, .. } => expected.as_ref(),
+            Mismatch::BodyTypeMismatch { expected_body, .. } => expected_body.as_ref(),
+            _ => None
+        };
+        body_bytes_to_c(expected, len)
+    } {
+        std::ptr::null()
+    }
+}
+
+ffi_fn! {
+    /// Get the raw bytes of the actual body of a body mismatch (`BodyMismatch` or
+    /// `BodyTypeMismatch`). The number of bytes is written to `len`. Unlike `pactffi_mismatch_to_json`,
+    /// the bytes are not converted to a UTF-8 string, so this can be used with binary bodies.
+    ///
+    /// # Safety
+    ///
+    /// `len` must be a valid pointer. The returned pointer is only valid while the mismatch is not
+    /// deleted, and must not be freed by the caller.
+    ///
+    /// # Error Handling
+    ///
+    /// Returns NULL (and sets `len` to zero) if the mismatch is not a body mismatch, or does not have
+    /// an actual body.
+    fn pactffi_mismatch_actual_bytes(mismatch: *const Mismatch, len: *mut size_t) -> *const c_uchar {
+        let mismatch = as_ref!(mismatch);
+        let len = as_mut!(len);
+        let actual = match mismatch {
+            Mismatch::BodyMismatch { actual, .. } => actual.as_ref(),
+            Mismatch::BodyTypeMismatch { actual_body, .. } => actual_body.as_ref(),
+            _ => None
+        };
+        body_bytes_to_c(actual, len)
+    } {
+        std::ptr::null()
+    }
+}
+
+fn body_bytes_to_c(bytes: Option<&Bytes>, len: &mut size_t) -> *const c_uchar {
+    match bytes {
+        Some(bytes) => {
+            *len = bytes.len() as size_t;
+            bytes.as_ptr()
+        }
+        None => {
+            *len = 0;
+            std::ptr::null()
+        }
+    }
+}
+
 /// A collection of mismatches from a matching comparison.
 #[allow(missing_copy_implementations)]
 #[allow(missing_debug_implementations)]
@@ -341,6 +452,13 @@ mod tests {
   use super::*;
   use tracing_core::LevelFilter;
 
+  #[test]
+  fn version_numbers_match_the_version_string() {
+    let version = unsafe { CStr::from_ptr(pactffi_version()) }.to_str().unwrap();
+    let numbers = format!("{}.{}.{}", pactffi_version_major(), pactffi_version_minor(), pactffi_version_patch());
+    expect!(version.starts_with(numbers.as_str())).to(be_true());
+  }
+
   #[rstest]
   #[case("trace", LevelFilter::TRACE)]
   #[case("TRACE", LevelFilter::TRACE)]
@@ -384,4 +502,73 @@ mod tests {
     let result = unsafe { log_level_from_c_char(value.as_ptr()) };
     expect!(result).to(be_equal_to(level));
   }
+
+  #[test]
+  fn pactffi_mismatch_bytes_test() {
+    let mismatch = Mismatch::BodyMismatch {
+      path: "$".to_string(),
+      expected: Some(Bytes::from_static(&[0x89, 0x50, 0x4E, 0x47])),
+      actual: Some(Bytes::from_static(&[0xFF, 0xD8])),
+      mismatch: "Expected the same image".to_string()
+    };
+    let mut len: size_t = 0;
+
+    let expected = pactffi_mismatch_expected_bytes(&mismatch, &mut len);
+    let bytes = unsafe { std::slice::from_raw_parts(expected, len) };
+    expect!(bytes).to(be_equal_to(&[0x89, 0x50, 0x4E, 0x47][..]));
+
+    let actual = pactffi_mismatch_actual_bytes(&mismatch, &mut len);
+    let bytes = unsafe { std::slice::from_raw_parts(actual, len) };
+    expect!(bytes).to(be_equal_to(&[0xFF, 0xD8][..]));
+
+    let mismatch = Mismatch::BodyTypeMismatch {
+      expected: "image/png".to_string(),
+      actual: "image/jpeg".to_string(),
+      mismatch: "Expected a body of 'image/png'".to_string(),
+      expected_body: Some(Bytes::from_static(&[0x89])),
+      actual_body: None
+    };
+    let expected = pactffi_mismatch_expected_bytes(&mismatch, &mut len);
+    expect!(expected.is_null()).to(be_false());
+    expect!(len).to(be_equal_to(1));
+    let actual = pactffi_mismatch_actual_bytes(&mismatch, &mut len);
+    expect!(actual.is_null()).to(be_true());
+    expect!(len).to(be_equal_to(0));
+
+    let mismatch = Mismatch::MethodMismatch { expected: "GET".to_string(), actual: "POST".to_string() };
+    expect!(pactffi_mismatch_expected_bytes(&mismatch, &mut len).is_null()).to(be_true());
+  }
+
+  #[test]
+  fn pactffi_mismatches_concat_test() {
+    let method_mismatch = Mismatch::MethodMismatch { expected: "GET".to_string(), actual: "POST".to_string() };
+    let body_mismatch = Mismatch::BodyMismatch {
+      path: "$.a".to_string(),
+      expected: None,
+      actual: None,
+      mismatch: "Expected a".to_string()
+    };
+    let a = ptr::raw_to(Mismatches(vec![method_mismatch.clone()])) as *const Mismatches;
+    let b = ptr::raw_to(Mismatches(vec![body_mismatch.clone()])) as *const Mismatches;
+
+    let combined = pactffi_mismatches_concat(a, b);
+    pactffi_mismatches_delete(a);
+    pactffi_mismatches_delete(b);
+    let iter = pactffi_mismatches_get_iter(combined);
+    let mut result = vec![];
+    loop {
+      let mismatch = pactffi_mismatches_iter_next(iter);
+      if mismatch.is_null() {
+        break;
+      }
+      result.push(unsafe { &*mismatch }.clone());
+    }
+    pactffi_mismatches_iter_delete(iter);
+    expect!(result).to(be_equal_to(vec![method_mismatch.clone(), body_mismatch]));
+
+    let with_null = pactffi_mismatches_concat(std::ptr::null(), combined);
+    expect!(unsafe { &*with_null }.0.len()).to(be_equal_to(2));
+    pactffi_mismatches_delete(combined);
+    pactffi_mismatches_delete(with_null);
+  }
 }
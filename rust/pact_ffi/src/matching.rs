@@ -1,10 +1,17 @@
 //! Module provides FFI functions to match values using Pact matching rules
 
+use std::ffi::{CStr, CString};
+use std::sync::Arc;
+
 use bytes::Bytes;
-use libc::{c_char, c_uchar};
+use libc::{c_char, c_int, c_uchar, c_void};
+use pact_models::content_types::ContentType;
+use pact_models::json_utils::json_to_string;
 use pact_models::matchingrules::MatchingRule;
 use serde_json::Value;
+use tracing::error;
 
+use pact_matching::{BodyMatcherFn, Mismatch, register_body_matcher, unregister_body_matcher};
 use pact_matching::matchers::Matches;
 
 use crate::{as_ref, ffi_fn, safe_str};
@@ -260,14 +267,210 @@ ffi_fn! {
     }
 }
 
+/// Callback function used to match bodies of a content type, registered with
+/// `pactffi_register_body_matcher`. It is passed the content type as a NULL terminated string,
+/// the expected and actual bodies with their lengths, and the user data pointer given when the
+/// callback was registered.
+///
+/// It must return NULL if the bodies match, otherwise a NULL terminated string containing a JSON
+/// array of mismatches. Each mismatch is a JSON object with a `mismatch` description, and
+/// optional `path`, `expected` and `actual` attributes. The returned string is copied as soon as
+/// the callback returns, and the memory for it remains owned by the calling code.
+pub type BodyMatcherCallback = extern "C" fn(
+  content_type: *const c_char,
+  expected: *const c_uchar,
+  expected_len: usize,
+  actual: *const c_uchar,
+  actual_len: usize,
+  user_data: *mut c_void
+) -> *const c_char;
+
+/// User data pointer passed to a body matcher callback
+#[derive(Debug, Clone, Copy)]
+struct UserData(*mut c_void);
+
+impl UserData {
+  fn ptr(&self) -> *mut c_void {
+    self.0
+  }
+}
+
+// The calling code is responsible for the user data being safe to use from any thread, as the
+// callback will be invoked from the threads that the matching is done on.
+unsafe impl Send for UserData {}
+unsafe impl Sync for UserData {}
+
+fn ffi_body_matcher(callback: BodyMatcherCallback, user_data: *mut c_void) -> Arc<BodyMatcherFn> {
+  let user_data = UserData(user_data);
+  Arc::new(move |content_type: &ContentType, expected: &Bytes, actual: &Bytes| {
+    let content_type = CString::new(content_type.to_string()).unwrap_or_default();
+    let result = callback(content_type.as_ptr(), expected.as_ptr(), expected.len(),
+      actual.as_ptr(), actual.len(), user_data.ptr());
+    if result.is_null() {
+      vec![]
+    } else {
+      let json = unsafe { CStr::from_ptr(result) }.to_string_lossy().to_string();
+      callback_mismatches(&json, expected, actual)
+    }
+  })
+}
+
+fn callback_mismatches(json: &str, expected: &Bytes, actual: &Bytes) -> Vec<Mismatch> {
+  match serde_json::from_str::<Value>(json) {
+    Ok(Value::Array(items)) => items.iter().map(|item| Mismatch::BodyMismatch {
+      path: item.get("path").map(json_to_string).unwrap_or_else(|| "$".to_string()),
+      expected: Some(item.get("expected").map(|value| Bytes::from(json_to_string(value))).unwrap_or_else(|| expected.clone())),
+      actual: Some(item.get("actual").map(|value| Bytes::from(json_to_string(value))).unwrap_or_else(|| actual.clone())),
+      mismatch: item.get("mismatch").map(json_to_string).unwrap_or_default()
+    }).collect(),
+    _ => vec![Mismatch::BodyMismatch {
+      path: "$".to_string(),
+      expected: Some(expected.clone()),
+      actual: Some(actual.clone()),
+      mismatch: format!("Body matcher callback did not return a JSON array of mismatches: '{}'", json)
+    }]
+  }
+}
+
+ffi_fn! {
+    /// Registers a callback function to match bodies with the given content type. The callback is
+    /// used in preference to any plugin or core matcher for the content type, and replaces any
+    /// callback already registered for it. Parameters on the content type (i.e. charset) are
+    /// ignored. See `BodyMatcherCallback` for the details of the callback.
+    ///
+    /// * content_type - content type as a NULL terminated string
+    /// * callback - function to call to match the bodies
+    /// * user_data - pointer that is passed to the callback, can be NULL
+    ///
+    /// Returns 0 if the callback was registered, 1 if the content type is not valid, or 2 if the
+    /// callback is NULL.
+    ///
+    /// # Safety
+    ///
+    /// The content type must be a valid pointer to a NULL terminated string. The callback will be
+    /// called from the threads that the matching is done on, so it and the user data must be
+    /// safe to use from any thread, and the callback must remain valid until it is unregistered.
+    fn pactffi_register_body_matcher(
+      content_type: *const c_char,
+      callback: Option<BodyMatcherCallback>,
+      user_data: *mut c_void
+    ) -> c_int {
+      let content_type = safe_str!(content_type);
+      match (ContentType::parse(content_type), callback) {
+        (Ok(content_type), Some(callback)) => {
+          register_body_matcher(&content_type, ffi_body_matcher(callback, user_data));
+          0
+        }
+        (Err(err), _) => {
+          error!("'{}' is not a valid content type - {}", content_type, err);
+          1
+        }
+        (_, None) => {
+          error!("Body matcher callback for '{}' is NULL", content_type);
+          2
+        }
+      }
+    } {
+      -1
+    }
+}
+
+ffi_fn! {
+    /// Removes the callback function registered to match bodies with the given content type.
+    /// Returns 0 if a callback was removed, 1 if there was no callback registered for the
+    /// content type, or 2 if the content type is not valid.
+    ///
+    /// # Safety
+    ///
+    /// The content type must be a valid pointer to a NULL terminated string.
+    fn pactffi_unregister_body_matcher(content_type: *const c_char) -> c_int {
+      let content_type = safe_str!(content_type);
+      match ContentType::parse(content_type) {
+        Ok(content_type) => if unregister_body_matcher(&content_type) { 0 } else { 1 },
+        Err(err) => {
+          error!("'{}' is not a valid content type - {}", content_type, err);
+          2
+        }
+      }
+    } {
+      -1
+    }
+}
+
 #[cfg(test)]
 mod tests {
   use std::ffi::{c_char, CString};
 
+  use bytes::Bytes;
   use expectest::prelude::*;
+  use libc::{c_uchar, c_void};
+  use pact_models::content_types::ContentType;
   use pact_models::matchingrules::MatchingRule;
 
-  use crate::matching::{pactffi_matches_binary_value, pactffi_matches_bool_value, pactffi_matches_f64_value, pactffi_matches_i64_value, pactffi_matches_json_value, pactffi_matches_string_value, pactffi_matches_u64_value};
+  use crate::matching::{
+    ffi_body_matcher,
+    pactffi_matches_binary_value,
+    pactffi_matches_bool_value,
+    pactffi_matches_f64_value,
+    pactffi_matches_i64_value,
+    pactffi_matches_json_value,
+    pactffi_matches_string_value,
+    pactffi_matches_u64_value,
+    pactffi_register_body_matcher,
+    pactffi_unregister_body_matcher
+  };
+
+  extern "C" fn length_body_matcher(
+    _content_type: *const c_char,
+    _expected: *const c_uchar,
+    expected_len: usize,
+    _actual: *const c_uchar,
+    actual_len: usize,
+    user_data: *mut c_void
+  ) -> *const c_char {
+    let result = unsafe { &*(user_data as *const CString) };
+    if expected_len == actual_len {
+      std::ptr::null()
+    } else {
+      result.as_ptr()
+    }
+  }
+
+  #[test]
+  fn ffi_body_matcher_test() {
+    let result = CString::new(r#"[{"path": "$.a", "mismatch": "Lengths are different"}]"#).unwrap();
+    let matcher = ffi_body_matcher(length_body_matcher, &result as *const CString as *mut c_void);
+    let content_type = ContentType::parse("application/cbor").unwrap();
+
+    let mismatches = matcher(&content_type, &Bytes::from("abc"), &Bytes::from("xyz"));
+    expect!(mismatches.iter()).to(be_empty());
+
+    let mismatches = matcher(&content_type, &Bytes::from("abc"), &Bytes::from("abcd"));
+    expect!(mismatches.len()).to(be_equal_to(1));
+    expect!(mismatches[0].description()).to(be_equal_to("$.a -> Lengths are different"));
+
+    let invalid = CString::new("not JSON").unwrap();
+    let matcher = ffi_body_matcher(length_body_matcher, &invalid as *const CString as *mut c_void);
+    let mismatches = matcher(&content_type, &Bytes::from("abc"), &Bytes::from("abcd"));
+    expect!(mismatches[0].description()).to(
+      be_equal_to("$ -> Body matcher callback did not return a JSON array of mismatches: 'not JSON'"));
+  }
+
+  #[test]
+  fn pactffi_register_body_matcher_test() {
+    let content_type = CString::new("application/x-ffi-test; charset=utf-8").unwrap();
+    let base_content_type = CString::new("application/x-ffi-test").unwrap();
+    let invalid_content_type = CString::new("not a content type").unwrap();
+
+    expect!(pactffi_register_body_matcher(content_type.as_ptr(), Some(length_body_matcher), std::ptr::null_mut()))
+      .to(be_equal_to(0));
+    expect!(pactffi_register_body_matcher(content_type.as_ptr(), None, std::ptr::null_mut())).to(be_equal_to(2));
+    expect!(pactffi_register_body_matcher(invalid_content_type.as_ptr(), Some(length_body_matcher), std::ptr::null_mut()))
+      .to(be_equal_to(1));
+
+    expect!(pactffi_unregister_body_matcher(base_content_type.as_ptr())).to(be_equal_to(0));
+    expect!(pactffi_unregister_body_matcher(base_content_type.as_ptr())).to(be_equal_to(1));
+  }
 
   #[test_log::test]
   fn pactffi_matches_string_value_test() {
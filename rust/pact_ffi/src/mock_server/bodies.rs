@@ -425,8 +425,6 @@ fn format_multipart_error(e: std::io::Error) -> String {
 
 #[cfg(test)]
 mod test {
-  use std::collections::HashMap;
-
 use expectest::prelude::*;
   use maplit::hashmap;
   use pact_models::prelude::Category;
@@ -437,7 +435,7 @@ use pretty_assertions::assert_eq;
   use pact_models::{generators, HttpStatus, matchingrules_list};
   use pact_models::content_types::ContentType;
   use pact_models::generators::{Generator, Generators};
-  use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory, RuleList};
+  use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory};
   use pact_models::matchingrules::expressions::{MatchingRuleDefinition, ValueType};
   use pact_models::path_exp::DocPath;
 
@@ -867,8 +865,8 @@ use pretty_assertions::assert_eq;
   #[case(json!({ "pact:matcher:type": "array-contains", "variants": [] }), vec![MatchingRule::ArrayContains(vec![])])]
   #[case(json!({ "pact:matcher:type": "array-contains", "variants": ["Thing1", "Thing2"] }), vec![
     MatchingRule::ArrayContains(vec![
-      (0, MatchingRuleCategory{name: Category::BODY, rules: HashMap::from([ (DocPath::empty(), RuleList::equality()) ]) }, std::collections::HashMap::default()),
-      (0, MatchingRuleCategory{name: Category::BODY, rules: HashMap::from([ (DocPath::empty(), RuleList::equality()) ]) }, std::collections::HashMap::default())
+      (0, MatchingRuleCategory::equality(Category::BODY), std::collections::HashMap::default()),
+      (0, MatchingRuleCategory::equality(Category::BODY), std::collections::HashMap::default())
     ])
   ])]
   #[case(json!({ "pact:matcher:type": "values" }), vec![MatchingRule::Values])]
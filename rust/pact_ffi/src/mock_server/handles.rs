@@ -105,6 +105,7 @@ use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::collections::hash_map::Entry;
 use std::ffi::{CStr, CString};
+use std::fmt::Write;
 use std::path::PathBuf;
 use std::ptr::null_mut;
 use std::str::from_utf8;
@@ -120,7 +121,8 @@ use maplit::*;
 use pact_models::{Consumer, PactSpecification, Provider};
 use pact_models::bodies::OptionalBody;
 use pact_models::content_types::{detect_content_type_from_string, ContentType, TEXT};
-use pact_models::generators::{generators_from_json, Generator, Generators};
+use pact_models::expression_parser::DataType;
+use pact_models::generators::{generators_from_json, Generator, GeneratorCategory, Generators};
 use pact_models::headers::parse_header;
 use pact_models::http_parts::HttpPart;
 use pact_models::interaction::Interaction;
@@ -131,6 +133,7 @@ use pact_models::path_exp::DocPath;
 use pact_models::prelude::Pact;
 use pact_models::prelude::v4::V4Pact;
 use pact_models::provider_states::ProviderState;
+use pact_models::query_strings::parse_query_string;
 use pact_models::v4::async_message::AsynchronousMessage;
 use pact_models::v4::interaction::V4Interaction;
 use pact_models::v4::message_parts::MessageContents;
@@ -139,7 +142,8 @@ use pact_models::v4::synch_http::SynchronousHttp;
 use serde_json::{json, Value};
 use tracing::*;
 
-use pact_matching::generators::generate_message;
+use pact_matching::{generate_request, generate_response};
+use pact_matching::generators::{apply_generators_to_async_message, apply_generators_to_sync_message, generate_message};
 use pact_models::generators::GeneratorTestMode;
 use futures::executor::block_on;
 
@@ -162,6 +166,7 @@ use crate::mock_server::bodies::{
 };
 use crate::mock_server::form_urlencoded::process_form_urlencoded_json;
 use crate::models::iterators::{PactAsyncMessageIterator, PactMessageIterator, PactSyncHttpIterator, PactSyncMessageIterator};
+use crate::models::matching_rules::InteractionMatchingRulesIterator;
 use crate::ptr;
 
 #[derive(Debug, Clone)]
@@ -539,6 +544,43 @@ pub extern fn pactffi_new_sync_message_interaction(pact: PactHandle, description
   }
 }
 
+/// Creates a copy of an interaction in the same Pact and returns a handle to the copy. The copy
+/// includes the request, response and message contents, provider states, matching rules and
+/// generators, and can be modified without affecting the original interaction. As interaction
+/// descriptions need to be unique, the copy should be given a new description with
+/// `pactffi_upon_receiving` (or different provider states with `pactffi_given`).
+///
+/// * `interaction` - Handle to the interaction to copy.
+///
+/// The copy is owned by the Pact, like any other interaction, and will be freed when the Pact
+/// handle is freed. There is nothing to free for the returned handle.
+///
+/// Returns a new `InteractionHandle`. The handle will not be valid (and any functions called with
+/// it will fail) if the interaction was not found, or the Pact can't be modified (i.e. the mock
+/// server for it has already started).
+#[no_mangle]
+pub extern fn pactffi_interaction_clone(interaction: InteractionHandle) -> InteractionHandle {
+  let pact = PactHandle { pact_ref: (interaction.interaction_ref >> 16) as u16 };
+  let index = (interaction.interaction_ref & 0x0000FFFF) as usize;
+  interaction.with_pact(&|_, inner| {
+    if inner.mock_server_started {
+      error!("pactffi_interaction_clone: The interaction can not be cloned as the mock server has already started");
+      return InteractionHandle::new(pact, 0);
+    }
+    match index.checked_sub(1).and_then(|index| inner.pact.interactions.get(index)) {
+      Some(existing) => {
+        let copy = existing.boxed_v4();
+        inner.pact.interactions.push(copy);
+        InteractionHandle::new(pact, inner.pact.interactions.len() as u16)
+      }
+      None => {
+        error!("pactffi_interaction_clone: Did not find the interaction to clone");
+        InteractionHandle::new(pact, 0)
+      }
+    }
+  }).unwrap_or_else(|| InteractionHandle::new(pact, 0))
+}
+
 /// Sets the description for the Interaction. Returns false if the interaction or Pact can't be
 /// modified (i.e. the mock server for it has already started)
 ///
@@ -924,6 +966,47 @@ pub extern fn pactffi_with_query_parameter_v2(
   }
 }
 
+/// Configures the query parameters for the Interaction from a raw query string (i.e.
+/// `id=1&id=2&name=Fred`). The names and values are percent-decoded, and repeated parameters
+/// will have multiple values in the order they appear. A leading `?` is ignored. The parsed
+/// values are added to any query parameters already configured. Returns false if the interaction
+/// or Pact can't be modified (i.e. the mock server for it has already started), or the
+/// interaction is not an HTTP interaction.
+///
+/// Parameters without a value (i.e. `?foo&bar`) will be set with no value, while parameters with
+/// a blank value (i.e. `?foo=&bar=`) will be set with an empty string.
+///
+/// # Safety
+/// The query parameter must be a valid pointer to a NULL terminated string.
+#[no_mangle]
+pub extern fn pactffi_with_query_string(
+  interaction: InteractionHandle,
+  query: *const c_char
+) -> bool {
+  if let Some(query) = convert_cstr("query", query) {
+    trace!(?interaction, query, "pactffi_with_query_string called");
+    let query = query.strip_prefix('?').unwrap_or(query);
+    interaction.with_interaction(&|_, mock_server_started, inner| {
+      if let Some(reqres) = inner.as_v4_http_mut() {
+        if let Some(parsed) = parse_query_string(query) {
+          let mut query_map = reqres.request.query.clone().unwrap_or_default();
+          for (name, values) in parsed {
+            query_map.entry(name).or_default().extend(values);
+          }
+          reqres.request.query = Some(query_map);
+        }
+        !mock_server_started
+      } else {
+        error!("Interaction is not an HTTP interaction, is {}", inner.type_of());
+        false
+      }
+    }).unwrap_or(false)
+  } else {
+    warn!("Ignoring null query string");
+    false
+  }
+}
+
 fn update_query_map(
   index: size_t,
   name: &str,
@@ -1315,6 +1398,88 @@ pub extern fn pactffi_with_metadata(
   }).unwrap_or(false)
 }
 
+/// Adds a matching rule for a metadata key of a message interaction. The rule is registered
+/// under the metadata category, and will be applied when the metadata value for the key is
+/// compared.
+///
+/// * `key` - metadata key
+/// * `matcher_json` - the matching rule as JSON. This can either be in the integration JSON
+///   format (i.e. `{"pact:matcher:type": "datetime", "format": "yyyy-MM-dd'T'HH:mm:ss"}`) or in
+///   the format used in Pact files (i.e. `{"match": "datetime", "format": "yyyy-MM-dd'T'HH:mm:ss"}`).
+///
+/// Returns `true` if the matching rule was added, `false` if the interaction is not an
+/// asynchronous message, the mock server has already started or the matcher JSON is not valid.
+///
+/// Note that this only adds the matching rule, the metadata value needs to be set with
+/// [`pactffi_with_metadata`].
+///
+/// # Safety
+///
+/// The key and matcher JSON parameters must be valid pointers to NULL terminated strings.
+#[no_mangle]
+pub extern fn pactffi_message_with_metadata_matcher(
+  interaction: InteractionHandle,
+  key: *const c_char,
+  matcher_json: *const c_char
+) -> bool {
+  trace!("pactffi_message_with_metadata_matcher(interaction: {:?}, key: {:?}, matcher_json: {:?})", interaction, key, matcher_json);
+  let key = match convert_cstr("key", key) {
+    Some(key) => key,
+    None => {
+      error!("Failed to convert key to a string");
+      return false;
+    }
+  };
+  let rules = match convert_cstr("matcher_json", matcher_json) {
+    Some(matcher_json) => match metadata_matchers_from_json(matcher_json) {
+      Ok(rules) => rules,
+      Err(err) => {
+        error!("Failed to parse matcher JSON for metadata key '{}' - {}", key, err);
+        return false;
+      }
+    },
+    None => {
+      error!("Failed to convert matcher JSON to a string");
+      return false;
+    }
+  };
+
+  interaction.with_interaction(&|_, mock_server_started, inner| {
+    if mock_server_started {
+      error!("Mock server is already started, can not add metadata matching rules");
+      return false;
+    }
+
+    if let Some(message) = inner.as_v4_async_message_mut() {
+      let matching_rules = message.contents.matching_rules.add_category(Category::METADATA);
+      let path = DocPath::root().join(key);
+      for rule in &rules {
+        matching_rules.add_rule(path.clone(), rule.clone(), RuleLogic::And);
+      }
+      true
+    } else {
+      warn!("Metadata matching rules can only be added to asynchronous message interactions, interaction is {}", inner.type_of());
+      false
+    }
+  }).unwrap_or(false)
+}
+
+fn metadata_matchers_from_json(matcher_json: &str) -> anyhow::Result<Vec<MatchingRule>> {
+  match serde_json::from_str(matcher_json)? {
+    Value::Object(map) => if map.contains_key("pact:matcher:type") {
+      let (rules, _) = matchers_from_integration_json(&map)?;
+      if rules.is_empty() {
+        Err(anyhow!("No matching rules were found in '{}'", matcher_json))
+      } else {
+        Ok(rules)
+      }
+    } else {
+      MatchingRule::from_json(&Value::Object(map)).map(|rule| vec![rule])
+    },
+    _ => Err(anyhow!("Matcher JSON '{}' is not a JSON object", matcher_json))
+  }
+}
+
 /// Configures a header for the Interaction. Returns false if the interaction or Pact can't be
 /// modified (i.e. the mock server for it has already started)
 ///
@@ -1672,6 +1837,108 @@ pub extern fn pactffi_response_status_v2(interaction: InteractionHandle, status:
   }).unwrap_or(false)
 }
 
+ffi_fn! {
+    /// Configures the response for the Interaction from a raw HTTP/1.1 response (i.e. one
+    /// captured from real traffic), consisting of the status line, the headers and an optional
+    /// body separated from the headers by a blank line. Lines can be terminated with either CRLF
+    /// or LF. This replaces any status, headers and body already configured for the response.
+    /// The values are used as is, and no matching rules or generators are configured.
+    ///
+    /// If no `Content-Type` header is present, the content type of the body will be detected
+    /// from the body contents.
+    ///
+    /// # Safety
+    ///
+    /// The raw response parameter must be a valid pointer to a NULL terminated string.
+    ///
+    /// # Error Handling
+    ///
+    /// If the response can not be set, this will return a positive value and the interaction
+    /// will not be modified.
+    ///
+    /// * `1` - Function panicked or the raw response was NULL. Error message will be available by calling `pactffi_get_error_message`.
+    /// * `2` - Handle was not valid.
+    /// * `3` - Mock server was already started and the integration can not be modified.
+    /// * `4` - Not an HTTP interaction.
+    /// * `5` - The raw response could not be parsed. Error message will be available by calling `pactffi_get_error_message`.
+    fn pactffi_response_from_raw(interaction: InteractionHandle, raw_http: *const c_char) -> c_uint {
+      let raw_http = safe_str!(raw_http);
+      let (status, headers, body) = match parse_raw_response(raw_http) {
+        Ok(response) => response,
+        Err(err) => {
+          error!("Failed to parse the raw HTTP response: {}", err);
+          set_error_msg(err.to_string());
+          return Ok(5);
+        }
+      };
+      interaction.with_interaction(&|_, started, inner| {
+        if !started {
+          if let Some(reqres) = inner.as_v4_http_mut() {
+            reqres.response.status = status;
+            reqres.response.headers = if headers.is_empty() { None } else { Some(headers.clone()) };
+            reqres.response.body = body.clone();
+            0
+          } else {
+            4
+          }
+        } else {
+          3
+        }
+      }).unwrap_or(2)
+    } {
+      1
+    }
+}
+
+/// Parses a raw HTTP/1.1 response into the status, headers and body
+fn parse_raw_response(raw: &str) -> anyhow::Result<(u16, HashMap<String, Vec<String>>, OptionalBody)> {
+  let separator = ["\r\n\r\n", "\n\n"].iter()
+    .filter_map(|separator| raw.find(separator).map(|index| (index, separator.len())))
+    .min_by_key(|(index, _)| *index);
+  let (head, body) = match separator {
+    Some((index, len)) => (&raw[..index], &raw[index + len..]),
+    None => (raw, "")
+  };
+
+  let mut lines = head.lines();
+  let status_line = lines.next()
+    .filter(|line| !line.trim().is_empty())
+    .ok_or_else(|| anyhow!("The raw response does not have a status line"))?;
+  let mut parts = status_line.split_whitespace();
+  match parts.next() {
+    Some(version) if version.starts_with("HTTP/") => {}
+    _ => return Err(anyhow!("Expected the status line to start with the HTTP version, got '{}'", status_line))
+  }
+  let status = parts.next()
+    .and_then(|status| status.parse::<u16>().ok())
+    .filter(|status| (100..=999).contains(status))
+    .ok_or_else(|| anyhow!("The status line '{}' does not have a valid status code", status_line))?;
+
+  let mut headers: HashMap<String, Vec<String>> = hashmap!{};
+  for line in lines {
+    match line.split_once(':') {
+      Some((name, value)) if !name.trim().is_empty() && !name.starts_with(char::is_whitespace) => {
+        let name = name.trim();
+        headers.entry(name.to_string()).or_default().extend(parse_header(name, value.trim()));
+      }
+      _ => return Err(anyhow!("'{}' is not a valid header line", line))
+    }
+  }
+
+  let body = if body.is_empty() {
+    OptionalBody::Missing
+  } else {
+    let content_type = headers.iter()
+      .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+      .and_then(|(_, values)| values.first())
+      .and_then(|value| ContentType::parse(value).ok())
+      .or_else(|| detect_content_type_from_string(body));
+    OptionalBody::Present(Bytes::from(body.to_string()), content_type, None)
+  };
+
+  Ok((status, headers, body))
+}
+
 /// Process the body, including generators and matching rules.
 ///
 /// As the body may contain embedded generators and matching rules, we must use
@@ -2333,6 +2600,187 @@ ffi_fn!{
   }
 }
 
+ffi_fn!{
+  /// Adds a provider state generator to the interaction. When the Pact is verified, the value will
+  /// be replaced with the result of evaluating the expression against the values returned from
+  /// the provider state callback (i.e. `${id}` will be replaced with the `id` value).
+  ///
+  /// * `interaction` - Interaction handle to add the generator to.
+  /// * `part` - Request or response part (if applicable).
+  /// * `category` - Category of the value the generator applies to (`header`, `query`, `body`,
+  ///   `path`, `status` or `metadata`).
+  /// * `path` - Header name, query parameter name or metadata key for the `header`, `query` and
+  ///   `metadata` categories, or the JSON path (i.e. `$.id`) of the value for the `body` category.
+  ///   It is ignored for the other categories, and can be NULL.
+  /// * `expression` - Provider state expression (i.e. `/orders/${id}`).
+  /// * `data_type` - Optional data type to convert the generated value to (`STRING`, `INTEGER`,
+  ///   `DECIMAL`, `FLOAT`, `BOOLEAN` or `RAW`). Can be NULL.
+  ///
+  /// For synchronous messages which allow multiple responses, the generator will be added to all
+  /// the responses.
+  ///
+  /// Returns `true` if the generator was added, and `false` if the parameters are not valid or an
+  /// error occurred.
+  ///
+  /// # Safety
+  ///
+  /// The category, path, expression and data_type parameters must be valid pointers to NULL
+  /// terminated UTF-8 strings (path and data_type may be NULL).
+  fn pactffi_with_provider_state_generator(
+    interaction: InteractionHandle,
+    part: InteractionPart,
+    category: *const c_char,
+    path: *const c_char,
+    expression: *const c_char,
+    data_type: *const c_char
+  ) -> bool {
+    let category = match convert_cstr("category", category).map(|category| category.parse::<GeneratorCategory>()) {
+      Some(Ok(category)) => category,
+      Some(Err(err)) => {
+        error!("with_provider_state_generator: {}", err);
+        return Ok(false);
+      }
+      None => {
+        error!("with_provider_state_generator: Category is not valid (NULL or non-UTF-8)");
+        return Ok(false);
+      }
+    };
+    let expression = match convert_cstr("expression", expression) {
+      Some(expression) => expression,
+      None => {
+        error!("with_provider_state_generator: Expression is not valid (NULL or non-UTF-8)");
+        return Ok(false);
+      }
+    };
+    let path = match category {
+      GeneratorCategory::BODY => match convert_cstr("path", path).map(DocPath::new) {
+        Some(Ok(path)) => path,
+        Some(Err(err)) => {
+          error!("with_provider_state_generator: Path is not a valid JSON path - {}", err);
+          return Ok(false);
+        }
+        None => DocPath::root()
+      },
+      GeneratorCategory::HEADER | GeneratorCategory::QUERY | GeneratorCategory::METADATA => match convert_cstr("path", path) {
+        Some(name) => DocPath::root().join(name),
+        None => {
+          error!("with_provider_state_generator: A name is required for the {:?} category", category);
+          return Ok(false);
+        }
+      },
+      _ => DocPath::empty()
+    };
+    let data_type = convert_cstr("data_type", data_type)
+      .map(|data_type| DataType::from(Value::String(data_type.to_string())));
+    let generator = Generator::ProviderStateGenerator(expression.to_string(), data_type);
+
+    interaction.with_interaction(&|_, _, inner| {
+      if let Some(reqres) = inner.as_v4_http_mut() {
+        match part {
+          InteractionPart::Request => reqres.request.generators_mut()
+            .add_generator_with_subcategory(&category, path.clone(), generator.clone()),
+          InteractionPart::Response => reqres.response.generators_mut()
+            .add_generator_with_subcategory(&category, path.clone(), generator.clone())
+        };
+        Ok(())
+      } else if let Some(message) = inner.as_v4_async_message_mut() {
+        message.generators_mut().add_generator_with_subcategory(&category, path.clone(), generator.clone());
+        Ok(())
+      } else if let Some(sync_message) = inner.as_v4_sync_message_mut() {
+        match part {
+          InteractionPart::Request => sync_message.request.generators_mut()
+            .add_generator_with_subcategory(&category, path.clone(), generator.clone()),
+          InteractionPart::Response => sync_message.response.iter_mut().for_each(|response| response.generators_mut()
+            .add_generator_with_subcategory(&category, path.clone(), generator.clone()))
+        };
+        Ok(())
+      } else {
+        error!("Interaction is an unknown type, is {}", inner.type_of());
+        Err(())
+      }
+    }).unwrap_or(Err(())).is_ok()
+  }
+  // Failure block
+  {
+    false
+  }
+}
+
+
+ffi_fn!{
+  /// Adds a content type matcher to the root of the body, so that the body will be matched by
+  /// the content type detected from the actual contents (using the magic bytes of the contents
+  /// for binary bodies) instead of comparing the contents. This is useful for binary bodies, like
+  /// file uploads and downloads. Any existing content type matcher at the root of the body will
+  /// be replaced.
+  ///
+  /// * `interaction` - Interaction handle to add the matcher to.
+  /// * `part` - Request or response part (if applicable).
+  /// * `expected_content_type` - Expected content type of the body (i.e. `image/png`).
+  ///
+  /// For synchronous messages which allow multiple responses, the matcher will be added to all
+  /// the responses.
+  ///
+  /// Returns `true` if the matcher was added, and `false` if the content type is not valid or an
+  /// error occurred.
+  ///
+  /// # Safety
+  ///
+  /// The expected_content_type parameter must be a valid pointer to a NULL terminated UTF-8
+  /// string.
+  fn pactffi_with_content_type_matcher(
+    interaction: InteractionHandle,
+    part: InteractionPart,
+    expected_content_type: *const c_char
+  ) -> bool {
+    let content_type = match convert_cstr("expected_content_type", expected_content_type) {
+      Some(content_type) => content_type,
+      None => {
+        error!("with_content_type_matcher: Content type is not valid (NULL or non-UTF-8)");
+        return Ok(false);
+      }
+    };
+    if let Err(err) = ContentType::parse(content_type) {
+      error!("with_content_type_matcher: '{}' is not a valid content type - {}", content_type, err);
+      return Ok(false);
+    }
+
+    interaction.with_interaction(&|_, _, inner| {
+      if let Some(reqres) = inner.as_v4_http_mut() {
+        match part {
+          InteractionPart::Request => set_content_type_matching_rule_for_body(reqres.request.matching_rules_mut(), content_type),
+          InteractionPart::Response => set_content_type_matching_rule_for_body(reqres.response.matching_rules_mut(), content_type)
+        };
+        Ok(())
+      } else if let Some(message) = inner.as_v4_async_message_mut() {
+        set_content_type_matching_rule_for_body(message.matching_rules_mut(), content_type);
+        Ok(())
+      } else if let Some(sync_message) = inner.as_v4_sync_message_mut() {
+        match part {
+          InteractionPart::Request => set_content_type_matching_rule_for_body(sync_message.request.matching_rules_mut(), content_type),
+          InteractionPart::Response => sync_message.response.iter_mut()
+            .for_each(|response| set_content_type_matching_rule_for_body(response.matching_rules_mut(), content_type))
+        };
+        Ok(())
+      } else {
+        error!("Interaction is an unknown type, is {}", inner.type_of());
+        Err(())
+      }
+    }).unwrap_or(Err(())).is_ok()
+  }
+  // Failure block
+  {
+    false
+  }
+}
+
+fn set_content_type_matching_rule_for_body(matching_rules: &mut MatchingRules, content_type: &str) {
+  let category = matching_rules.add_category("body");
+  if let Some(rules) = category.rules.get_mut(&DocPath::root()) {
+    rules.rules.retain(|rule| !matches!(rule, MatchingRule::ContentType(_)));
+  }
+  category.add_rule(DocPath::root(), MatchingRule::ContentType(content_type.into()), RuleLogic::And);
+}
 
 fn add_content_type_matching_rule_to_body(is_supported: bool, matching_rules: &mut MatchingRules, content_type: &str) {
   if is_supported {
@@ -2605,6 +3053,29 @@ ffi_fn!{
   }
 }
 
+ffi_fn!{
+  /// Set an arbitrary comment on the V4 interaction's comments map.
+  ///
+  /// * `interaction` - Interaction handle to set the comment for.
+  /// * `key` - Key of the comment.
+  /// * `value` - Comment value. If it parses as JSON, the JSON value is stored, otherwise it is
+  ///   stored as a string. A NULL pointer will remove the comment.
+  ///
+  /// This function will return `true` if the comments were successfully updated. It behaves
+  /// the same as [`pactffi_set_comment`].
+  ///
+  /// # Safety
+  ///
+  /// The key parameter must be a valid pointer to a NULL terminated UTF-8.
+  /// The value parameter must be a valid pointer to a NULL terminated UTF-8,
+  /// or NULL if the comment is to be removed.
+  fn pactffi_interaction_set_comment(interaction: InteractionHandle, key: *const c_char, value: *const c_char) -> bool {
+    pactffi_set_comment(interaction, key, value)
+  } {
+    false
+  }
+}
+
 ffi_fn!{
   /// Add a text comment to the interaction.
   ///
@@ -2780,21 +3251,293 @@ ffi_fn! {
     }
 }
 
-/// Creates a new Pact Message model and returns a handle to it.
-///
-/// * `consumer_name` - The name of the consumer for the pact.
-/// * `provider_name` - The name of the provider for the pact.
-///
-/// Returns a new `MessagePactHandle`. The handle will need to be freed with the `pactffi_free_message_pact_handle`
-/// function to release its resources.
-#[no_mangle]
-pub extern fn pactffi_new_message_pact(consumer_name: *const c_char, provider_name: *const c_char) -> MessagePactHandle {
-  let consumer = convert_cstr("consumer_name", consumer_name).unwrap_or("Consumer");
-  let provider = convert_cstr("provider_name", provider_name).unwrap_or("Provider");
-  MessagePactHandle::new(consumer, provider)
+ffi_fn! {
+    /// Get an iterator over all the matching rules of the interaction. Each entry returned by the
+    /// iterator contains the category, path and JSON form of a matching rule. The returned
+    /// iterator needs to be freed with `pactffi_interaction_matching_rules_iter_delete`.
+    ///
+    /// # Safety
+    ///
+    /// The iterator contains a copy of the matching rules, so it is always safe to use.
+    ///
+    /// # Error Handling
+    ///
+    /// On failure (for instance, the interaction handle is not valid), this function will return
+    /// a NULL pointer.
+    fn pactffi_interaction_matching_rules_iter(interaction: InteractionHandle) -> *mut InteractionMatchingRulesIterator {
+        let iter = interaction.with_interaction(&|_, _, inner| {
+          InteractionMatchingRulesIterator::new(inner)
+        }).ok_or_else(|| anyhow!("Interaction handle is not valid"))?;
+        ptr::raw_to(iter)
+    } {
+        std::ptr::null_mut()
+    }
 }
 
-/// Creates a new Message and returns a handle to it.
+ffi_fn! {
+    /// Returns a human-readable, multi-line description of the interaction. For HTTP interactions
+    /// this includes the request method, path, query parameters, headers and body, and the
+    /// response status, headers and body. For message interactions, this includes the metadata
+    /// and contents of each message. Bodies longer than 512 characters are truncated. The
+    /// matching rules of the interaction are listed at the end.
+    ///
+    /// The returned string must be freed with the `pactffi_string_delete` function.
+    ///
+    /// # Error Handling
+    ///
+    /// On failure (for instance, the interaction handle is not valid), this function will return
+    /// a NULL pointer.
+    fn pactffi_interaction_pretty_print(interaction: InteractionHandle) -> *const c_char {
+        let description = interaction.with_interaction(&|_, _, inner| {
+          pretty_print_interaction(inner)
+        }).ok_or_else(|| anyhow!("Interaction handle is not valid"))?;
+        let description = CString::new(description)?;
+        description.into_raw() as *const c_char
+    } {
+        std::ptr::null()
+    }
+}
+
+ffi_fn! {
+    /// Returns the matching rules that would be selected for the given path when matching the
+    /// request or response part of the interaction, as a JSON document. This is intended as a
+    /// debugging aid to see which rules apply to a value.
+    ///
+    /// * `interaction` - Interaction handle.
+    /// * `part` - Request or response part. For asynchronous messages, this is ignored.
+    /// * `category` - Matching rule category (`body`, `header`, `query`, `path`, `metadata`, etc.).
+    /// * `path` - Path to select the matchers for. For the body and metadata categories this is a
+    ///   JSON path expression (i.e. `$.items[0].id`), otherwise it is the header or query parameter
+    ///   name.
+    ///
+    /// The returned JSON has the form:
+    ///
+    /// ```json
+    /// {
+    ///   "path": "$.items[*].id",
+    ///   "weight": 32,
+    ///   "cascaded": false,
+    ///   "combine": "AND",
+    ///   "matchers": [ { "match": "integer" } ]
+    /// }
+    /// ```
+    ///
+    /// where `path` is the path expression the selected rules were defined for and `weight` is the
+    /// weighting calculated for it. Only the body and metadata categories select matchers by
+    /// weighting, so `path` and `weight` will be null for the other categories. If no matchers
+    /// apply to the path, `matchers` will be empty.
+    ///
+    /// The returned string must be freed with the `pactffi_string_delete` function.
+    ///
+    /// # Safety
+    ///
+    /// The category and path parameters must be valid pointers to NULL terminated UTF-8 strings.
+    ///
+    /// # Error Handling
+    ///
+    /// On failure (for instance, the interaction handle or category is not valid), this function
+    /// will return a NULL pointer.
+    fn pactffi_interaction_select_best_matcher(
+      interaction: InteractionHandle,
+      part: InteractionPart,
+      category: *const c_char,
+      path: *const c_char
+    ) -> *const c_char {
+        let category = safe_str!(category).parse::<Category>().map_err(|err| anyhow!(err))?;
+        let path = safe_str!(path);
+        let path_tokens = match category {
+          Category::BODY | Category::METADATA | Category::CONTENTS => DocPath::new(path)?.to_vec(),
+          _ => vec![ path.to_string() ]
+        };
+        let path_slice = path_tokens.iter().map(|p| p.as_str()).collect_vec();
+
+        let matching_rules = interaction.with_interaction(&|_, _, inner| {
+          if let Some(http) = inner.as_v4_http() {
+            match part {
+              InteractionPart::Request => Some(http.request.matching_rules.clone()),
+              InteractionPart::Response => Some(http.response.matching_rules.clone())
+            }
+          } else if let Some(message) = inner.as_v4_async_message() {
+            Some(message.contents.matching_rules.clone())
+          } else if let Some(message) = inner.as_v4_sync_message() {
+            match part {
+              InteractionPart::Request => Some(message.request.matching_rules.clone()),
+              InteractionPart::Response => message.response.first().map(|response| response.matching_rules.clone())
+            }
+          } else {
+            None
+          }
+        }).ok_or_else(|| anyhow!("Interaction handle is not valid"))?.unwrap_or_default();
+        let rules = matching_rules.rules_for_category(category.clone())
+          .unwrap_or_else(|| MatchingRuleCategory::empty(category));
+
+        let (matcher_path, weight, rule_list) = match rules.select_best_matcher_with_weighting(&path_slice) {
+          Some((matcher_path, weight, rule_list)) => (json!(matcher_path.to_string()), json!(weight), rule_list),
+          None => (Value::Null, Value::Null, rules.select_best_matcher(&path_slice))
+        };
+        let json = json!({
+          "path": matcher_path,
+          "weight": weight,
+          "cascaded": rule_list.cascaded,
+          "combine": match rule_list.rule_logic {
+            RuleLogic::And => "AND",
+            RuleLogic::Or => "OR"
+          },
+          "matchers": rule_list.rules.iter().map(|rule| rule.to_json()).collect::<Vec<_>>()
+        });
+        let json = CString::new(json.to_string())?;
+        json.into_raw() as *const c_char
+    } {
+        std::ptr::null()
+    }
+}
+
+/// Maximum number of characters of a body that will be included by `pretty_print_interaction`
+const PRETTY_PRINT_BODY_LIMIT: usize = 512;
+
+fn pretty_print_interaction(interaction: &dyn V4Interaction) -> String {
+  let mut buffer = String::new();
+  let _ = writeln!(buffer, "Interaction: \"{}\" ({})", interaction.description(), interaction.v4_type());
+  for state in interaction.provider_states() {
+    let _ = writeln!(buffer, "  Given: {}", state.name);
+  }
+
+  if let Some(http) = interaction.as_v4_http() {
+    let request = &http.request;
+    let _ = writeln!(buffer, "Request:");
+    let _ = writeln!(buffer, "  {} {}", request.method.to_uppercase(), request.path);
+    if let Some(query) = &request.query {
+      let _ = writeln!(buffer, "  Query:");
+      for (name, values) in query.iter().sorted_by(|(a, _), (b, _)| a.cmp(b)) {
+        for value in values {
+          match value {
+            Some(value) => { let _ = writeln!(buffer, "    {}={}", name, value); },
+            None => { let _ = writeln!(buffer, "    {}", name); }
+          }
+        }
+      }
+    }
+    pretty_print_headers(&mut buffer, &request.headers);
+    pretty_print_body(&mut buffer, "Body", &request.body, request.content_type());
+
+    let response = &http.response;
+    let _ = writeln!(buffer, "Response:");
+    let _ = writeln!(buffer, "  Status: {}", response.status);
+    pretty_print_headers(&mut buffer, &response.headers);
+    pretty_print_body(&mut buffer, "Body", &response.body, response.content_type());
+
+    pretty_print_matching_rules(&mut buffer, &[
+      ("request.", &request.matching_rules),
+      ("response.", &response.matching_rules)
+    ]);
+  } else if let Some(message) = interaction.as_v4_async_message() {
+    let _ = writeln!(buffer, "Message:");
+    pretty_print_message_contents(&mut buffer, &message.contents);
+    pretty_print_matching_rules(&mut buffer, &[("", &message.contents.matching_rules)]);
+  } else if let Some(message) = interaction.as_v4_sync_message() {
+    let _ = writeln!(buffer, "Request:");
+    pretty_print_message_contents(&mut buffer, &message.request);
+    let mut rules = vec![("request.".to_string(), &message.request.matching_rules)];
+    for (index, response) in message.response.iter().enumerate() {
+      if message.response.len() == 1 {
+        let _ = writeln!(buffer, "Response:");
+        rules.push(("response.".to_string(), &response.matching_rules));
+      } else {
+        let _ = writeln!(buffer, "Response {}:", index + 1);
+        rules.push((format!("response[{}].", index), &response.matching_rules));
+      }
+      pretty_print_message_contents(&mut buffer, response);
+    }
+    let rules = rules.iter()
+      .map(|(prefix, rules)| (prefix.as_str(), *rules))
+      .collect_vec();
+    pretty_print_matching_rules(&mut buffer, rules.as_slice());
+  }
+
+  buffer.trim_end().to_string()
+}
+
+fn pretty_print_headers(buffer: &mut String, headers: &Option<HashMap<String, Vec<String>>>) {
+  if let Some(headers) = headers {
+    if !headers.is_empty() {
+      let _ = writeln!(buffer, "  Headers:");
+      for (name, values) in headers.iter().sorted_by(|(a, _), (b, _)| a.cmp(b)) {
+        let _ = writeln!(buffer, "    {}: {}", name, values.join(", "));
+      }
+    }
+  }
+}
+
+fn pretty_print_message_contents(buffer: &mut String, contents: &MessageContents) {
+  if !contents.metadata.is_empty() {
+    let _ = writeln!(buffer, "  Metadata:");
+    for (key, value) in contents.metadata.iter().sorted_by(|(a, _), (b, _)| a.cmp(b)) {
+      let _ = writeln!(buffer, "    {}: {}", key, json_to_string(value));
+    }
+  }
+  pretty_print_body(buffer, "Contents", &contents.contents, contents.message_content_type());
+}
+
+fn pretty_print_body(buffer: &mut String, label: &str, body: &OptionalBody, content_type: Option<ContentType>) {
+  let content_type = content_type
+    .map(|ct| format!(" ({})", ct))
+    .unwrap_or_default();
+  match body {
+    OptionalBody::Missing => {},
+    OptionalBody::Empty => { let _ = writeln!(buffer, "  {}{}: <empty>", label, content_type); },
+    OptionalBody::Null => { let _ = writeln!(buffer, "  {}{}: null", label, content_type); },
+    OptionalBody::Present(bytes, _, _) => {
+      let display = body.display_string();
+      let display = if display.chars().count() > PRETTY_PRINT_BODY_LIMIT {
+        format!("{}... ({} bytes)", display.chars().take(PRETTY_PRINT_BODY_LIMIT).collect::<String>(), bytes.len())
+      } else {
+        display
+      };
+      let _ = writeln!(buffer, "  {}{}:", label, content_type);
+      for line in display.lines() {
+        let _ = writeln!(buffer, "    {}", line);
+      }
+    }
+  }
+}
+
+fn pretty_print_matching_rules(buffer: &mut String, rules: &[(&str, &MatchingRules)]) {
+  let entries = rules.iter()
+    .flat_map(|(prefix, rules)| {
+      rules.rules.iter()
+        .flat_map(move |(category, rules)| rules.rules.iter()
+          .map(move |(path, rule_list)| (format!("{}{}", prefix, category), path.to_string(), rule_list)))
+    })
+    .filter(|(_, _, rule_list)| !rule_list.is_empty())
+    .sorted_by(|(c1, p1, _), (c2, p2, _)| c1.cmp(c2).then_with(|| p1.cmp(p2)))
+    .collect_vec();
+  if !entries.is_empty() {
+    let _ = writeln!(buffer, "Matching rules:");
+    for (category, path, rule_list) in entries {
+      let separator = if rule_list.rule_logic == RuleLogic::Or { " OR " } else { " AND " };
+      let rules = rule_list.rules.iter()
+        .map(|rule| rule.to_json().to_string())
+        .join(separator);
+      let _ = writeln!(buffer, "  {} {}: {}", category, path, rules);
+    }
+  }
+}
+
+/// Creates a new Pact Message model and returns a handle to it.
+///
+/// * `consumer_name` - The name of the consumer for the pact.
+/// * `provider_name` - The name of the provider for the pact.
+///
+/// Returns a new `MessagePactHandle`. The handle will need to be freed with the `pactffi_free_message_pact_handle`
+/// function to release its resources.
+#[no_mangle]
+pub extern fn pactffi_new_message_pact(consumer_name: *const c_char, provider_name: *const c_char) -> MessagePactHandle {
+  let consumer = convert_cstr("consumer_name", consumer_name).unwrap_or("Consumer");
+  let provider = convert_cstr("provider_name", provider_name).unwrap_or("Provider");
+  MessagePactHandle::new(consumer, provider)
+}
+
+/// Creates a new Message and returns a handle to it.
 ///
 /// * `description` - The message description. It needs to be unique for each Message.
 ///
@@ -3152,6 +3895,104 @@ ffi_fn! {
   }
 }
 
+ffi_fn! {
+  /// Returns the JSON form of the current state of the Pact, as it would be written to the pact
+  /// file for the given specification version. If `PactSpecification::Unknown` is passed, the
+  /// specification version set for the Pact is used. This can be used to preview the Pact before
+  /// the mock server is started.
+  ///
+  /// The returned string must be freed with `pactffi_string_delete`.
+  ///
+  /// # Safety
+  ///
+  /// This function is safe.
+  ///
+  /// # Error Handling
+  ///
+  /// Returns a NULL pointer if the Pact handle is not valid, or the Pact can not be converted to
+  /// JSON for the specification version (i.e. a V4 Pact with mixed interaction types can not be
+  /// downgraded).
+  fn pactffi_pact_handle_to_json(pact: PactHandle, spec_version: PactSpecification) -> *const c_char {
+    let (v4_pact, pact_spec) = pact.with_pact(&|_, inner| {
+      (inner.pact.clone(), inner.specification_version)
+    }).ok_or_else(|| anyhow!("Pact handle is not valid"))?;
+    let spec_version = if spec_version == PactSpecification::Unknown {
+      pact_spec
+    } else {
+      spec_version
+    };
+    let json = v4_pact.to_json(spec_version)?;
+    CString::new(json.to_string())?.into_raw() as *const c_char
+  } {
+    std::ptr::null()
+  }
+}
+
+ffi_fn! {
+  /// Returns the JSON form of the Pact, where all the matching rules and generators have been
+  /// materialized into concrete example values. Generators are applied in consumer mode, and then
+  /// the matching rules and generators are removed from the interactions. This is useful for
+  /// generating documentation fixtures or golden files.
+  ///
+  /// The returned string must be freed with `pactffi_string_delete`.
+  ///
+  /// # Safety
+  ///
+  /// This function must only ever be called from a foreign language. Calling it from a Rust
+  /// function that has a Tokio runtime in its call stack can result in a deadlock.
+  ///
+  /// # Error Handling
+  ///
+  /// Returns a NULL pointer if the Pact handle is not valid, or the Pact can not be converted to
+  /// JSON for its specification version.
+  fn pactffi_pact_to_examples(pact: PactHandle) -> *const c_char {
+    let (v4_pact, spec_version) = pact.with_pact(&|_, inner| {
+      (inner.pact.clone(), inner.specification_version)
+    }).ok_or_else(|| anyhow!("Pact handle is not valid"))?;
+    let examples = block_on(pact_examples(v4_pact));
+    let json = examples.to_json(spec_version)?;
+    CString::new(json.to_string())?.into_raw() as *const c_char
+  } {
+    std::ptr::null()
+  }
+}
+
+/// Applies all the generators to the interactions of the Pact, and then removes any matching
+/// rules and generators
+async fn pact_examples(mut pact: V4Pact) -> V4Pact {
+  let mode = GeneratorTestMode::Consumer;
+  let context = hashmap!{};
+  for interaction in pact.interactions.iter_mut() {
+    if let Some(http) = interaction.as_v4_http_mut() {
+      let mut request = generate_request(&http.request, &mode, &context).await;
+      request.matching_rules = MatchingRules::default();
+      request.generators = Generators::default();
+      http.request = request;
+
+      let mut response = generate_response(&http.response, &mode, &context).await;
+      response.matching_rules = MatchingRules::default();
+      response.generators = Generators::default();
+      http.response = response;
+    } else if let Some(message) = interaction.as_v4_async_message_mut() {
+      let mut contents = apply_generators_to_async_message(message, &mode, &context,
+        &vec![], &hashmap!{}).await;
+      contents.matching_rules = MatchingRules::default();
+      contents.generators = Generators::default();
+      message.contents = contents;
+    } else if let Some(message) = interaction.as_v4_sync_message_mut() {
+      let (mut request, mut responses) = apply_generators_to_sync_message(message, &mode,
+        &context, &vec![], &hashmap!{}).await;
+      for contents in std::iter::once(&mut request).chain(responses.iter_mut()) {
+        contents.matching_rules = MatchingRules::default();
+        contents.generators = Generators::default();
+      }
+      message.request = request;
+      message.response = responses;
+    }
+  }
+  pact
+}
+
 /// Creates a new V4 asynchronous message and returns a handle to it.
 ///
 /// * `description` - The message description. It needs to be unique for each Message.
@@ -3215,7 +4056,7 @@ pub fn pact_default_file_name(handle: &PactHandle) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-  use std::ffi::CString;
+  use std::ffi::{CStr, CString};
 
   use either::Either;
   use expectest::prelude::*;
@@ -3229,6 +4070,12 @@ mod tests {
   use rstest::rstest;
 
   use crate::mock_server::handles::*;
+  use crate::models::matching_rules::{
+    MatchingRuleCategory as FfiMatchingRuleCategory,
+    pactffi_interaction_matching_rules_entry_delete,
+    pactffi_interaction_matching_rules_iter_delete,
+    pactffi_interaction_matching_rules_iter_next
+  };
 
   use super::from_integration_json_v2;
 
@@ -3289,6 +4136,110 @@ mod tests {
     expect!(interaction.request.matching_rules.rules.get(&Category::QUERY).cloned().unwrap_or_default().is_empty()).to(be_true());
   }
 
+  #[test]
+  fn query_parameters_from_a_query_string() {
+    let pact_handle = PactHandle::new("TestC1", "TestP");
+    let description = CString::new("query_parameters_from_a_query_string").unwrap();
+    let handle = pactffi_new_interaction(pact_handle, description.as_ptr());
+
+    let name = CString::new("page").unwrap();
+    let value = CString::new("1").unwrap();
+    pactffi_with_query_parameter_v2(handle, name.as_ptr(), 0, value.as_ptr());
+    let query = CString::new("?id=1&id=2&config=delete.topic.enable%3Dtrue&flag&blank=").unwrap();
+    let result = pactffi_with_query_string(handle, query.as_ptr());
+
+    let interaction = handle.with_interaction(&|_, _, inner| {
+      inner.as_v4_http().unwrap()
+    }).unwrap();
+
+    pactffi_free_pact_handle(pact_handle);
+
+    expect!(result).to(be_true());
+    expect!(interaction.request.query.clone()).to(be_some().value(hashmap!{
+      "page".to_string() => vec![Some("1".to_string())],
+      "id".to_string() => vec![Some("1".to_string()), Some("2".to_string())],
+      "config".to_string() => vec![Some("delete.topic.enable=true".to_string())],
+      "flag".to_string() => vec![None],
+      "blank".to_string() => vec![Some("".to_string())]
+    }));
+  }
+
+  #[test]
+  fn response_from_raw_http() {
+    let pact_handle = PactHandle::new("TestC1", "TestP");
+    let description = CString::new("response_from_raw_http").unwrap();
+    let handle = pactffi_new_interaction(pact_handle, description.as_ptr());
+
+    let raw = CString::new("HTTP/1.1 201 Created\r\nContent-Type: application/json\r\n\
+      X-Request-Id: 1234\r\nVary: Accept, Origin\r\n\r\n{\"id\": 100}").unwrap();
+    let result = pactffi_response_from_raw(handle, raw.as_ptr());
+
+    let interaction = handle.with_interaction(&|_, _, inner| {
+      inner.as_v4_http().unwrap()
+    }).unwrap();
+
+    pactffi_free_pact_handle(pact_handle);
+
+    expect!(result).to(be_equal_to(0));
+    expect!(interaction.response.status).to(be_equal_to(201));
+    expect!(interaction.response.headers).to(be_some().value(hashmap!{
+      "Content-Type".to_string() => vec!["application/json".to_string()],
+      "X-Request-Id".to_string() => vec!["1234".to_string()],
+      "Vary".to_string() => vec!["Accept".to_string(), "Origin".to_string()]
+    }));
+    expect!(interaction.response.body.value_as_string()).to(be_some().value("{\"id\": 100}".to_string()));
+    expect!(interaction.response.body.content_type()).to(be_some().value(JSON.clone()));
+  }
+
+  #[test]
+  fn response_from_raw_http_without_a_body() {
+    let pact_handle = PactHandle::new("TestC1", "TestP");
+    let description = CString::new("response_from_raw_http_without_a_body").unwrap();
+    let handle = pactffi_new_interaction(pact_handle, description.as_ptr());
+
+    let raw = CString::new("HTTP/1.1 204 No Content\nX-Request-Id: 1234\n\n").unwrap();
+    let result = pactffi_response_from_raw(handle, raw.as_ptr());
+
+    let interaction = handle.with_interaction(&|_, _, inner| {
+      inner.as_v4_http().unwrap()
+    }).unwrap();
+
+    pactffi_free_pact_handle(pact_handle);
+
+    expect!(result).to(be_equal_to(0));
+    expect!(interaction.response.status).to(be_equal_to(204));
+    expect!(interaction.response.headers).to(be_some().value(hashmap!{
+      "X-Request-Id".to_string() => vec!["1234".to_string()]
+    }));
+    expect!(interaction.response.body).to(be_equal_to(OptionalBody::Missing));
+  }
+
+  #[rstest]
+  #[case("")]
+  #[case("200 OK\r\n\r\n")]
+  #[case("HTTP/1.1 OK\r\n\r\n")]
+  #[case("HTTP/1.1 2000 OK\r\n\r\n")]
+  #[case("HTTP/1.1 200 OK\r\nNot a header\r\n\r\n")]
+  #[case("HTTP/1.1 200 OK\r\n: no-name\r\n\r\n")]
+  fn response_from_raw_http_with_invalid_response(#[case] raw: &str) {
+    let pact_handle = PactHandle::new("TestC1", "TestP");
+    let description = CString::new("response_from_raw_http_with_invalid_response").unwrap();
+    let handle = pactffi_new_interaction(pact_handle, description.as_ptr());
+    pactffi_response_status(handle, 400);
+
+    let raw = CString::new(raw).unwrap();
+    let result = pactffi_response_from_raw(handle, raw.as_ptr());
+
+    let interaction = handle.with_interaction(&|_, _, inner| {
+      inner.as_v4_http().unwrap()
+    }).unwrap();
+
+    pactffi_free_pact_handle(pact_handle);
+
+    expect!(result).to(be_equal_to(5));
+    expect!(interaction.response.status).to(be_equal_to(400));
+  }
+
   #[test]
   fn query_parameter_with_matcher() {
     let pact_handle = PactHandle::new("TestC2", "TestP");
@@ -3435,6 +4386,62 @@ mod tests {
     );
   }
 
+  #[test]
+  fn pactffi_message_with_metadata_matcher_test() {
+    let pact_handle = PactHandle::new("metadata-consumer", "metadata-provider");
+    let description = CString::new("metadata matcher").unwrap();
+    let handle = pactffi_new_message_interaction(pact_handle, description.as_ptr());
+
+    let key = CString::new("timestamp").unwrap();
+    let value = CString::new("\"2024-01-02T03:04:05\"").unwrap();
+    let matcher = CString::new(r#"{"pact:matcher:type": "datetime", "format": "yyyy-MM-dd'T'HH:mm:ss"}"#).unwrap();
+    let key2 = CString::new("id").unwrap();
+    let value2 = CString::new("100").unwrap();
+    let matcher2 = CString::new(r#"{"match": "integer"}"#).unwrap();
+    let invalid = CString::new(r#"{"match": "not-a-matcher"}"#).unwrap();
+    let not_json = CString::new("not json").unwrap();
+    assert!(pactffi_with_metadata(handle, key.as_ptr(), value.as_ptr(), InteractionPart::Request));
+    assert!(pactffi_message_with_metadata_matcher(handle, key.as_ptr(), matcher.as_ptr()));
+    assert!(pactffi_with_metadata(handle, key2.as_ptr(), value2.as_ptr(), InteractionPart::Request));
+    assert!(pactffi_message_with_metadata_matcher(handle, key2.as_ptr(), matcher2.as_ptr()));
+    assert!(!pactffi_message_with_metadata_matcher(handle, key2.as_ptr(), invalid.as_ptr()));
+    assert!(!pactffi_message_with_metadata_matcher(handle, key2.as_ptr(), not_json.as_ptr()));
+    assert!(!pactffi_message_with_metadata_matcher(handle, key2.as_ptr(), std::ptr::null()));
+
+    let interaction = handle.with_interaction(&|_, _, inner| {
+      inner.as_v4_async_message().unwrap()
+    }).unwrap();
+
+    let http_description = CString::new("http").unwrap();
+    let http_handle = pactffi_new_interaction(pact_handle, http_description.as_ptr());
+    assert!(!pactffi_message_with_metadata_matcher(http_handle, key.as_ptr(), matcher.as_ptr()));
+
+    pactffi_free_pact_handle(pact_handle);
+
+    let metadata_rules = interaction.contents.matching_rules.rules_for_category("metadata").unwrap();
+    assert_eq!(metadata_rules.rules, hashmap!{
+      DocPath::root().join("timestamp") => matchingrules::RuleList::new(MatchingRule::Timestamp("yyyy-MM-dd'T'HH:mm:ss".to_string())),
+      DocPath::root().join("id") => matchingrules::RuleList::new(MatchingRule::Integer)
+    });
+
+    let context = pact_matching::CoreMatchingContext::new(pact_matching::DiffConfig::AllowUnexpectedKeys,
+      &metadata_rules, &hashmap!{});
+    let mut actual = interaction.contents.clone();
+    actual.metadata = hashmap!{
+      "timestamp".to_string() => json!("2025-12-31T23:59:59"),
+      "id".to_string() => json!(12345)
+    };
+    expect!(pact_matching::match_message_metadata(&interaction.contents, &actual, &context)
+      .values().all(|mismatches| mismatches.is_empty())).to(be_true());
+
+    actual.metadata = hashmap!{
+      "timestamp".to_string() => json!("31/12/2025"),
+      "id".to_string() => json!(12345)
+    };
+    let result = pact_matching::match_message_metadata(&interaction.contents, &actual, &context);
+    expect!(result.get("timestamp").unwrap().is_empty()).to(be_false());
+  }
+
   #[test]
   fn pactffi_with_metadata_sync() {
     let pact_handle = PactHandle::new("metadata-consumer", "metadata-provider");
@@ -4359,6 +5366,120 @@ mod tests {
     });
     }
 
+  #[test]
+  fn pactffi_with_provider_state_generator_test() {
+    let pact_handle = PactHandle::new("Consumer", "Provider");
+    let description = CString::new("Provider State Generator Test").unwrap();
+    let i_handle = pactffi_new_interaction(pact_handle, description.as_ptr());
+
+    let header = CString::new("header").unwrap();
+    let header_name = CString::new("se-token").unwrap();
+    let header_expression = CString::new("${seToken}").unwrap();
+    let body = CString::new("body").unwrap();
+    let body_path = CString::new("$.id").unwrap();
+    let body_expression = CString::new("${id}").unwrap();
+    let integer = CString::new("INTEGER").unwrap();
+    let path = CString::new("path").unwrap();
+    let path_expression = CString::new("/orders/${id}").unwrap();
+    let invalid = CString::new("invalid").unwrap();
+
+    assert!(pactffi_with_provider_state_generator(i_handle, InteractionPart::Request,
+      header.as_ptr(), header_name.as_ptr(), header_expression.as_ptr(), std::ptr::null()));
+    assert!(pactffi_with_provider_state_generator(i_handle, InteractionPart::Request,
+      path.as_ptr(), std::ptr::null(), path_expression.as_ptr(), std::ptr::null()));
+    assert!(pactffi_with_provider_state_generator(i_handle, InteractionPart::Response,
+      body.as_ptr(), body_path.as_ptr(), body_expression.as_ptr(), integer.as_ptr()));
+    assert!(!pactffi_with_provider_state_generator(i_handle, InteractionPart::Request,
+      invalid.as_ptr(), header_name.as_ptr(), header_expression.as_ptr(), std::ptr::null()));
+    assert!(!pactffi_with_provider_state_generator(i_handle, InteractionPart::Request,
+      header.as_ptr(), std::ptr::null(), header_expression.as_ptr(), std::ptr::null()));
+
+    let interaction = i_handle.with_interaction(&|_, _, inner| {
+      inner.as_v4_http().unwrap()
+    }).unwrap();
+
+    pactffi_free_pact_handle(pact_handle);
+    let mut expected_request_generators = generators!{
+      "header" => {
+        "$['se-token']" => Generator::ProviderStateGenerator("${seToken}".to_string(), None)
+      }
+    };
+    expected_request_generators.add_generator(&GeneratorCategory::PATH,
+      Generator::ProviderStateGenerator("/orders/${id}".to_string(), None));
+    assert_eq!(interaction.request.generators, expected_request_generators);
+    assert_eq!(interaction.response.generators, generators!{
+      "body" => {
+        "$.id" => Generator::ProviderStateGenerator("${id}".to_string(), Some(DataType::INTEGER))
+      }
+    });
+  }
+
+  #[test]
+  fn pactffi_interaction_clone_test() {
+    let pact_handle = PactHandle::new("Consumer", "Provider");
+    let description = CString::new("original").unwrap();
+    let i_handle = pactffi_new_interaction(pact_handle, description.as_ptr());
+    let path = CString::new("/orders").unwrap();
+    let method = CString::new("GET").unwrap();
+    pactffi_with_request(i_handle, method.as_ptr(), path.as_ptr());
+    let gif = CString::new("image/gif").unwrap();
+    pactffi_with_content_type_matcher(i_handle, InteractionPart::Response, gif.as_ptr());
+
+    let clone_handle = pactffi_interaction_clone(i_handle);
+    let clone_description = CString::new("copy").unwrap();
+    let clone_path = CString::new("/orders/100").unwrap();
+    expect!(pactffi_upon_receiving(clone_handle, clone_description.as_ptr())).to(be_true());
+    expect!(pactffi_with_request(clone_handle, method.as_ptr(), clone_path.as_ptr())).to(be_true());
+
+    let original = i_handle.with_interaction(&|_, _, inner| {
+      inner.as_v4_http().unwrap()
+    }).unwrap();
+    let copy = clone_handle.with_interaction(&|_, _, inner| {
+      inner.as_v4_http().unwrap()
+    }).unwrap();
+    let invalid = pactffi_interaction_clone(InteractionHandle::new(pact_handle, 100));
+    let interactions = pact_handle.with_pact(&|_, inner| inner.pact.interactions.len()).unwrap();
+
+    pactffi_free_pact_handle(pact_handle);
+
+    expect!(interactions).to(be_equal_to(2));
+    expect!(original.description).to(be_equal_to("original"));
+    expect!(original.request.path).to(be_equal_to("/orders"));
+    expect!(copy.description).to(be_equal_to("copy"));
+    expect!(copy.request.path).to(be_equal_to("/orders/100"));
+    expect!(copy.response.matching_rules).to(be_equal_to(original.response.matching_rules));
+    expect!(invalid.interaction_ref & 0x0000FFFF).to(be_equal_to(0));
+  }
+
+  #[test]
+  fn pactffi_with_content_type_matcher_test() {
+    let pact_handle = PactHandle::new("Consumer", "Provider");
+    let description = CString::new("Content Type Matcher Test").unwrap();
+    let i_handle = pactffi_new_interaction(pact_handle, description.as_ptr());
+
+    let gif = CString::new("image/gif").unwrap();
+    let png = CString::new("image/png").unwrap();
+    let invalid = CString::new("not a content type").unwrap();
+
+    assert!(pactffi_with_content_type_matcher(i_handle, InteractionPart::Request, gif.as_ptr()));
+    assert!(pactffi_with_content_type_matcher(i_handle, InteractionPart::Response, gif.as_ptr()));
+    assert!(pactffi_with_content_type_matcher(i_handle, InteractionPart::Response, png.as_ptr()));
+    assert!(!pactffi_with_content_type_matcher(i_handle, InteractionPart::Response, invalid.as_ptr()));
+    assert!(!pactffi_with_content_type_matcher(i_handle, InteractionPart::Response, std::ptr::null()));
+
+    let interaction = i_handle.with_interaction(&|_, _, inner| {
+      inner.as_v4_http().unwrap()
+    }).unwrap();
+
+    pactffi_free_pact_handle(pact_handle);
+    assert_eq!(interaction.request.matching_rules, matchingrules!{
+      "body" => { "$" => [ MatchingRule::ContentType("image/gif".to_string()) ] }
+    });
+    assert_eq!(interaction.response.matching_rules, matchingrules!{
+      "body" => { "$" => [ MatchingRule::ContentType("image/png".to_string()) ] }
+    });
+  }
+
     // See https://github.com/pact-foundation/pact-php/pull/626
     // and https://github.com/pact-foundation/pact-reference/pull/461
     #[rstest]
@@ -4480,4 +5601,295 @@ mod tests {
       None
     )
   }
+
+  #[test]
+  fn pactffi_interaction_matching_rules_iter_test() {
+    let pact_handle = PactHandle::new("Consumer", "Provider");
+    let description = CString::new("Matching rules iterator test").unwrap();
+    let i_handle = pactffi_new_interaction(pact_handle, description.as_ptr());
+
+    let content_type = CString::new("application/json").unwrap();
+    let request_body = CString::new(r#"{"id": {"pact:matcher:type": "regex", "regex": "\\d+", "value": "100"}}"#).unwrap();
+    let response_body = CString::new(r#"{"name": {"pact:matcher:type": "type", "value": "Fred"}}"#).unwrap();
+    pactffi_with_body(i_handle, InteractionPart::Request, content_type.as_ptr(), request_body.as_ptr());
+    pactffi_with_body(i_handle, InteractionPart::Response, content_type.as_ptr(), response_body.as_ptr());
+
+    let iter = pactffi_interaction_matching_rules_iter(i_handle);
+    expect!(iter.is_null()).to(be_false());
+
+    let mut entries = vec![];
+    loop {
+      let entry = pactffi_interaction_matching_rules_iter_next(iter);
+      if entry.is_null() {
+        break;
+      }
+      let e = unsafe { &*entry };
+      let path = unsafe { CStr::from_ptr(e.path) }.to_string_lossy().to_string();
+      let rule = unsafe { CStr::from_ptr(e.rule_json) }.to_string_lossy().to_string();
+      entries.push((e.category, path, rule));
+      pactffi_interaction_matching_rules_entry_delete(entry);
+    }
+    pactffi_interaction_matching_rules_iter_delete(iter);
+    pactffi_free_pact_handle(pact_handle);
+
+    expect!(entries).to(be_equal_to(vec![
+      (FfiMatchingRuleCategory::BODY, "$.id".to_string(),
+        r#"{"match":"regex","regex":"\\d+"}"#.to_string()),
+      (FfiMatchingRuleCategory::BODY, "$.name".to_string(),
+        r#"{"match":"type"}"#.to_string())
+    ]));
+  }
+
+  #[test]
+  fn pactffi_interaction_set_comment_test() {
+    let pact_handle = PactHandle::new("Consumer", "Provider");
+    let description = CString::new("set comment test").unwrap();
+    let i_handle = pactffi_new_interaction(pact_handle, description.as_ptr());
+
+    let key = CString::new("tool").unwrap();
+    let string_value = CString::new("some tool").unwrap();
+    let key2 = CString::new("metadata").unwrap();
+    let json_value = CString::new(r#"{"version": 1}"#).unwrap();
+    expect!(pactffi_interaction_set_comment(i_handle, key.as_ptr(), string_value.as_ptr())).to(be_true());
+    expect!(pactffi_interaction_set_comment(i_handle, key2.as_ptr(), json_value.as_ptr())).to(be_true());
+
+    let comments = i_handle.with_interaction(&|_, _, inner| inner.comments()).unwrap();
+    expect!(comments.get("tool").cloned()).to(be_some().value(json!("some tool")));
+    expect!(comments.get("metadata").cloned()).to(be_some().value(json!({"version": 1})));
+
+    expect!(pactffi_interaction_set_comment(i_handle, key.as_ptr(), std::ptr::null())).to(be_true());
+    let comments = i_handle.with_interaction(&|_, _, inner| inner.comments()).unwrap();
+    expect!(comments.get("tool")).to(be_none());
+    expect!(comments.get("metadata").cloned()).to(be_some().value(json!({"version": 1})));
+
+    expect!(pactffi_interaction_set_comment(i_handle, std::ptr::null(), string_value.as_ptr())).to(be_false());
+    pactffi_free_pact_handle(pact_handle);
+  }
+
+  #[test]
+  fn pactffi_interaction_select_best_matcher_test() {
+    let pact_handle = PactHandle::new("Consumer", "Provider");
+    let description = CString::new("select best matcher test").unwrap();
+    let i_handle = pactffi_new_interaction(pact_handle, description.as_ptr());
+    i_handle.with_interaction(&|_, _, inner| {
+      inner.as_v4_http_mut().unwrap().response.matching_rules = matchingrules! {
+        "body" => {
+          "$.items" => [ MatchingRule::MinType(1) ],
+          "$.items[*].id" => [ MatchingRule::Integer ]
+        },
+        "header" => {
+          "X-Id" => [ MatchingRule::Regex("\\d+".to_string()) ]
+        }
+      };
+    });
+
+    let body = CString::new("body").unwrap();
+    let header = CString::new("header").unwrap();
+    let id_path = CString::new("$.items[0].id").unwrap();
+    let name_path = CString::new("$.items[0].name").unwrap();
+    let header_name = CString::new("X-Id").unwrap();
+
+    let result = pactffi_interaction_select_best_matcher(i_handle, InteractionPart::Response,
+      body.as_ptr(), id_path.as_ptr());
+    let json = unsafe { CString::from_raw(result as *mut c_char) };
+    let json: Value = serde_json::from_str(json.to_str().unwrap()).unwrap();
+    expect!(json).to(be_equal_to(json!({
+      "path": "$.items[*].id",
+      "weight": 32,
+      "cascaded": false,
+      "combine": "AND",
+      "matchers": [ { "match": "integer" } ]
+    })));
+
+    let result = pactffi_interaction_select_best_matcher(i_handle, InteractionPart::Response,
+      body.as_ptr(), name_path.as_ptr());
+    let json = unsafe { CString::from_raw(result as *mut c_char) };
+    let json: Value = serde_json::from_str(json.to_str().unwrap()).unwrap();
+    expect!(json).to(be_equal_to(json!({
+      "path": "$.items",
+      "weight": 8,
+      "cascaded": true,
+      "combine": "AND",
+      "matchers": [ { "match": "type", "min": 1 } ]
+    })));
+
+    let result = pactffi_interaction_select_best_matcher(i_handle, InteractionPart::Response,
+      header.as_ptr(), header_name.as_ptr());
+    let json = unsafe { CString::from_raw(result as *mut c_char) };
+    let json: Value = serde_json::from_str(json.to_str().unwrap()).unwrap();
+    expect!(json["path"].clone()).to(be_equal_to(Value::Null));
+    expect!(json["matchers"].clone()).to(be_equal_to(json!([ { "match": "regex", "regex": "\\d+" } ])));
+
+    let result = pactffi_interaction_select_best_matcher(i_handle, InteractionPart::Request,
+      body.as_ptr(), id_path.as_ptr());
+    let json = unsafe { CString::from_raw(result as *mut c_char) };
+    let json: Value = serde_json::from_str(json.to_str().unwrap()).unwrap();
+    expect!(json["matchers"].clone()).to(be_equal_to(json!([])));
+
+    let invalid = CString::new("invalid").unwrap();
+    let result = pactffi_interaction_select_best_matcher(i_handle, InteractionPart::Response,
+      invalid.as_ptr(), id_path.as_ptr());
+    expect!(result.is_null()).to(be_true());
+
+    pactffi_free_pact_handle(pact_handle);
+  }
+
+  #[test]
+  fn pactffi_interaction_matching_rules_iter_with_invalid_handle() {
+    let iter = pactffi_interaction_matching_rules_iter(InteractionHandle::new(PactHandle::new("C", "P"), 1234));
+    expect!(iter.is_null()).to(be_true());
+  }
+
+  #[test]
+  fn pactffi_interaction_pretty_print_test() {
+    let pact_handle = PactHandle::new("Consumer", "Provider");
+    let description = CString::new("pretty print test").unwrap();
+    let i_handle = pactffi_new_interaction(pact_handle, description.as_ptr());
+
+    let given = CString::new("an order exists").unwrap();
+    let method = CString::new("GET").unwrap();
+    let path = CString::new("/orders").unwrap();
+    let query_name = CString::new("status").unwrap();
+    let query_value = CString::new("good").unwrap();
+    let header_name = CString::new("X-Test").unwrap();
+    let header_value = CString::new("a").unwrap();
+    let json = CString::new("application/json").unwrap();
+    let text = CString::new("text/plain").unwrap();
+    let request_body = CString::new(r#"{"id": {"pact:matcher:type": "regex", "regex": "\\d+", "value": "100"}}"#).unwrap();
+    let response_body = CString::new("OK").unwrap();
+    pactffi_given(i_handle, given.as_ptr());
+    pactffi_with_request(i_handle, method.as_ptr(), path.as_ptr());
+    pactffi_with_query_parameter_v2(i_handle, query_name.as_ptr(), 0, query_value.as_ptr());
+    pactffi_with_header_v2(i_handle, InteractionPart::Request, header_name.as_ptr(), 0, header_value.as_ptr());
+    pactffi_with_body(i_handle, InteractionPart::Request, json.as_ptr(), request_body.as_ptr());
+    pactffi_response_status(i_handle, 200);
+    pactffi_with_body(i_handle, InteractionPart::Response, text.as_ptr(), response_body.as_ptr());
+
+    let result = pactffi_interaction_pretty_print(i_handle);
+    pactffi_free_pact_handle(pact_handle);
+
+    expect!(result.is_null()).to(be_false());
+    let string = unsafe { CString::from_raw(result as *mut c_char) };
+    assert_eq!(string.to_string_lossy(), r#"Interaction: "pretty print test" (Synchronous/HTTP)
+  Given: an order exists
+Request:
+  GET /orders
+  Query:
+    status=good
+  Headers:
+    Content-Type: application/json
+    X-Test: a
+  Body (application/json):
+    {"id":"100"}
+Response:
+  Status: 200
+  Headers:
+    Content-Type: text/plain
+  Body (text/plain):
+    OK
+Matching rules:
+  request.body $.id: {"match":"regex","regex":"\\d+"}"#);
+  }
+
+  #[test]
+  fn pactffi_interaction_pretty_print_message_test() {
+    let pact_handle = PactHandle::new("Consumer", "Provider");
+    let description = CString::new("pretty print message test").unwrap();
+    let i_handle = pactffi_new_message_interaction(pact_handle, description.as_ptr());
+
+    let key = CString::new("queue").unwrap();
+    let value = CString::new("orders").unwrap();
+    let json = CString::new("application/json").unwrap();
+    let body = CString::new(format!(r#"{{"data":"{}"}}"#, "x".repeat(600))).unwrap();
+    pactffi_with_metadata(i_handle, key.as_ptr(), value.as_ptr(), InteractionPart::Request);
+    pactffi_with_body(i_handle, InteractionPart::Request, json.as_ptr(), body.as_ptr());
+
+    let result = pactffi_interaction_pretty_print(i_handle);
+    pactffi_free_pact_handle(pact_handle);
+
+    expect!(result.is_null()).to(be_false());
+    let string = unsafe { CString::from_raw(result as *mut c_char) };
+    let expected_body = format!(r#"{{"data":"{}"#, "x".repeat(503));
+    assert_eq!(string.to_string_lossy(), format!(r#"Interaction: "pretty print message test" (Asynchronous/Messages)
+Message:
+  Metadata:
+    contentType: application/json
+    queue: orders
+  Contents (application/json):
+    {}... (611 bytes)"#, expected_body));
+  }
+
+  #[test]
+  fn pactffi_interaction_pretty_print_with_invalid_handle() {
+    let result = pactffi_interaction_pretty_print(InteractionHandle::new(PactHandle::new("C", "P"), 1234));
+    expect!(result.is_null()).to(be_true());
+  }
+
+  #[test]
+  fn pactffi_pact_to_examples_test() {
+    let pact_handle = PactHandle::new("Consumer", "Provider");
+    let description = CString::new("Examples Test").unwrap();
+    let i_handle = pactffi_new_interaction(pact_handle, description.as_ptr());
+
+    let content_type = CString::new("application/json").unwrap();
+    let body = CString::new(r#"{
+      "id": { "pact:matcher:type": "integer", "pact:generator:type": "RandomInt", "value": 300 },
+      "name": { "pact:matcher:type": "type", "value": "Fred" }
+    }"#).unwrap();
+    pactffi_with_body(i_handle, InteractionPart::Response, content_type.as_ptr(), body.as_ptr());
+
+    let json_ptr = pactffi_pact_to_examples(pact_handle);
+    pactffi_free_pact_handle(pact_handle);
+
+    expect!(json_ptr.is_null()).to(be_false());
+    let json_str = unsafe { CString::from_raw(json_ptr as *mut c_char) };
+    let json: Value = serde_json::from_str(json_str.to_str().unwrap()).unwrap();
+    let response = &json["interactions"][0]["response"];
+    let id = response["body"]["id"].as_u64().unwrap();
+    expect!(id).to_not(be_equal_to(300));
+    expect!(response["body"]["name"].clone()).to(be_equal_to(json!("Fred")));
+    expect!(response.get("matchingRules")).to(be_none());
+    expect!(response.get("generators")).to(be_none());
+  }
+
+  #[test]
+  fn pactffi_pact_handle_to_json_test() {
+    let pact_handle = PactHandle::new("Consumer", "Provider");
+    let description = CString::new("JSON Test").unwrap();
+    let i_handle = pactffi_new_interaction(pact_handle, description.as_ptr());
+    let method = CString::new("GET").unwrap();
+    let path = CString::new("/test").unwrap();
+    pactffi_with_request(i_handle, method.as_ptr(), path.as_ptr());
+
+    let json_ptr = pactffi_pact_handle_to_json(pact_handle, PactSpecification::Unknown);
+    expect!(json_ptr.is_null()).to(be_false());
+    let json_str = unsafe { CString::from_raw(json_ptr as *mut c_char) };
+    let json: Value = serde_json::from_str(json_str.to_str().unwrap()).unwrap();
+    expect!(json["consumer"]["name"].clone()).to(be_equal_to(json!("Consumer")));
+    expect!(json["interactions"][0]["description"].clone()).to(be_equal_to(json!("JSON Test")));
+    expect!(json["interactions"][0]["request"]["path"].clone()).to(be_equal_to(json!("/test")));
+    expect!(json["metadata"]["pactSpecification"]["version"].clone()).to(be_equal_to(json!("3.0.0")));
+
+    let json_ptr = pactffi_pact_handle_to_json(pact_handle, PactSpecification::V4);
+    expect!(json_ptr.is_null()).to(be_false());
+    let json_str = unsafe { CString::from_raw(json_ptr as *mut c_char) };
+    let json: Value = serde_json::from_str(json_str.to_str().unwrap()).unwrap();
+    expect!(json["interactions"][0]["type"].clone()).to(be_equal_to(json!("Synchronous/HTTP")));
+    expect!(json["metadata"]["pactSpecification"]["version"].clone()).to(be_equal_to(json!("4.0")));
+
+    pactffi_free_pact_handle(pact_handle);
+    expect!(pactffi_pact_handle_to_json(pact_handle, PactSpecification::V4).is_null()).to(be_true());
+  }
+
+  #[test]
+  fn pactffi_handle_get_pact_spec_version_test() {
+    let pact_handle = PactHandle::new("Consumer", "Provider");
+    expect!(pactffi_handle_get_pact_spec_version(pact_handle)).to(be_equal_to(PactSpecification::V3));
+
+    pactffi_with_specification(pact_handle, PactSpecification::V4);
+    expect!(pactffi_handle_get_pact_spec_version(pact_handle)).to(be_equal_to(PactSpecification::V4));
+
+    pactffi_free_pact_handle(pact_handle);
+    expect!(pactffi_handle_get_pact_spec_version(pact_handle)).to(be_equal_to(PactSpecification::Unknown));
+  }
 }
@@ -144,6 +144,28 @@ ffi_fn! {
     }
 }
 
+ffi_fn! {
+    /// Get the content type of the message contents.
+    ///
+    /// # Safety
+    ///
+    /// The returned string must be deleted with `pactffi_string_delete`.
+    ///
+    /// # Error Handling
+    ///
+    /// If the message contents is NULL or the content type can not be determined, returns NULL.
+    fn pactffi_message_contents_get_content_type(contents: *const MessageContents) -> *const c_char {
+        let contents = as_ref!(contents);
+
+        match contents.message_content_type() {
+            Some(content_type) => string::to_c(content_type.to_string().as_str())? as *const c_char,
+            None => std::ptr::null()
+        }
+    } {
+        std::ptr::null()
+    }
+}
+
 ffi_fn! {
   /// Sets the contents of the message as an array of bytes.
   ///
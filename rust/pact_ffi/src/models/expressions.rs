@@ -226,6 +226,38 @@ ffi_fn! {
 /// | Semver | 21 |
 /// | EachKey | 22 |
 /// | EachValue | 23 |
+/// | AllowedKeys | 24 |
+/// | BodyHash | 25 |
+/// | Correlation | 26 |
+/// | PathExpression | 27 |
+/// | Base64 | 28 |
+/// | RetryAfter | 29 |
+/// | ImageSimilarity | 30 |
+/// | Duration | 31 |
+/// | StringLength | 32 |
+/// | ArrayContainsInOrder | 33 |
+/// | ArrayDistinct | 34 |
+/// | IntegerRange | 35 |
+/// | JsonSchema | 36 |
+/// | DateTimeAfter | 37 |
+/// | DateTimeBefore | 38 |
+/// | Keys | 39 |
+/// | OneOf | 40 |
+/// | Nullable | 41 |
+/// | IpAddress | 42 |
+/// | Format | 43 |
+/// | Any | 44 |
+/// | JsonRegex | 45 |
+/// | NoCascade | 46 |
+/// | MultipleOf | 47 |
+/// | Cookie | 48 |
+/// | RawTarget | 49 |
+/// | Empty | 50 |
+/// | UnorderedValues | 51 |
+/// | NumericString | 52 |
+/// | Hex | 53 |
+/// | Sensitive | 54 |
+/// | JsonValue | 55 |
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MatchingRuleResult {
   /// The matching rule from the expression.
@@ -291,7 +323,39 @@ impl MatchingRuleIterator {
               MatchingRule::NotEmpty => None,
               MatchingRule::Semver => None,
               MatchingRule::EachKey(_) => None,
-              MatchingRule::EachValue(_) => None
+              MatchingRule::EachValue(_) => None,
+              MatchingRule::AllowedKeys(keys) => Some(CString::new(keys.join(",")).unwrap()),
+              MatchingRule::BodyHash(algorithm) => Some(CString::new(algorithm.as_str()).unwrap()),
+              MatchingRule::Correlation(header, _) => Some(CString::new(header.as_str()).unwrap()),
+              MatchingRule::PathExpression => None,
+              MatchingRule::Base64(_, _) => None,
+              MatchingRule::RetryAfter(_, _) => None,
+              MatchingRule::ImageSimilarity { threshold } => Some(CString::new(threshold.to_string()).unwrap()),
+              MatchingRule::Duration => None,
+              MatchingRule::StringLength { .. } => None,
+              MatchingRule::ArrayContainsInOrder => None,
+              MatchingRule::ArrayDistinct(by) => by.as_ref().map(|by| CString::new(by.as_str()).unwrap()),
+              MatchingRule::IntegerRange { .. } => None,
+              MatchingRule::JsonSchema(schema) => Some(CString::new(schema.to_string()).unwrap()),
+              MatchingRule::DateTimeAfter { reference, .. } => Some(CString::new(reference.as_str()).unwrap()),
+              MatchingRule::DateTimeBefore { reference, .. } => Some(CString::new(reference.as_str()).unwrap()),
+              MatchingRule::Keys(keys) => Some(CString::new(keys.join(",")).unwrap()),
+              MatchingRule::OneOf(values) => Some(CString::new(values.join(",")).unwrap()),
+              MatchingRule::Nullable(rule) => Some(CString::new(rule.to_json().to_string()).unwrap()),
+              MatchingRule::IpAddress { version } => version.map(|version| CString::new(version.to_string()).unwrap()),
+              MatchingRule::Format(format) => Some(CString::new(format.as_str()).unwrap()),
+              MatchingRule::Any => None,
+              MatchingRule::JsonRegex(regex) => Some(CString::new(regex.as_str()).unwrap()),
+              MatchingRule::NoCascade => None,
+              MatchingRule::MultipleOf(base) => Some(CString::new(base.to_string()).unwrap()),
+              MatchingRule::Cookie => None,
+              MatchingRule::RawTarget => None,
+              MatchingRule::Empty => None,
+              MatchingRule::UnorderedValues => None,
+              MatchingRule::NumericString { tolerance } => tolerance.map(|tolerance| CString::new(tolerance.to_string()).unwrap()),
+              MatchingRule::Hex { length } => length.map(|length| CString::new(length.to_string()).unwrap()),
+              MatchingRule::Sensitive => None,
+              MatchingRule::JsonValue => None
             };
             let rule_value = val.as_ref().map(|v| v.as_ptr()).unwrap_or_else(|| null());
             let rule_result = MatchingRuleResult::MatchingRule(rule_id(rule), rule_value, rule.clone());
@@ -345,7 +409,39 @@ fn rule_id(rule: &MatchingRule) -> u16 {
     MatchingRule::NotEmpty => 20,
     MatchingRule::Semver => 21,
     MatchingRule::EachKey(_) => 22,
-    MatchingRule::EachValue(_) => 23
+    MatchingRule::EachValue(_) => 23,
+    MatchingRule::AllowedKeys(_) => 24,
+    MatchingRule::BodyHash(_) => 25,
+    MatchingRule::Correlation(_, _) => 26,
+    MatchingRule::PathExpression => 27,
+    MatchingRule::Base64(_, _) => 28,
+    MatchingRule::RetryAfter(_, _) => 29,
+    MatchingRule::ImageSimilarity { .. } => 30,
+    MatchingRule::Duration => 31,
+    MatchingRule::StringLength { .. } => 32,
+    MatchingRule::ArrayContainsInOrder => 33,
+    MatchingRule::ArrayDistinct(_) => 34,
+    MatchingRule::IntegerRange { .. } => 35,
+    MatchingRule::JsonSchema(_) => 36,
+    MatchingRule::DateTimeAfter { .. } => 37,
+    MatchingRule::DateTimeBefore { .. } => 38,
+    MatchingRule::Keys(_) => 39,
+    MatchingRule::OneOf(_) => 40,
+    MatchingRule::Nullable(_) => 41,
+    MatchingRule::IpAddress { .. } => 42,
+    MatchingRule::Format(_) => 43,
+    MatchingRule::Any => 44,
+    MatchingRule::JsonRegex(_) => 45,
+    MatchingRule::NoCascade => 46,
+    MatchingRule::MultipleOf(_) => 47,
+    MatchingRule::Cookie => 48,
+    MatchingRule::RawTarget => 49,
+    MatchingRule::Empty => 50,
+    MatchingRule::UnorderedValues => 51,
+    MatchingRule::NumericString { .. } => 52,
+    MatchingRule::Hex { .. } => 53,
+    MatchingRule::Sensitive => 54,
+    MatchingRule::JsonValue => 55
   }
 }
 
@@ -424,6 +520,38 @@ ffi_fn! {
     /// | Semver | 21 |
     /// | EachKey | 22 |
     /// | EachValue | 23 |
+    /// | AllowedKeys | 24 |
+    /// | BodyHash | 25 |
+    /// | Correlation | 26 |
+    /// | PathExpression | 27 |
+    /// | Base64 | 28 |
+    /// | RetryAfter | 29 |
+    /// | ImageSimilarity | 30 |
+    /// | Duration | 31 |
+    /// | StringLength | 32 |
+    /// | ArrayContainsInOrder | 33 |
+    /// | ArrayDistinct | 34 |
+    /// | IntegerRange | 35 |
+    /// | JsonSchema | 36 |
+    /// | DateTimeAfter | 37 |
+    /// | DateTimeBefore | 38 |
+    /// | Keys | 39 |
+    /// | OneOf | 40 |
+    /// | Nullable | 41 |
+    /// | IpAddress | 42 |
+    /// | Format | 43 |
+    /// | Any | 44 |
+    /// | JsonRegex | 45 |
+    /// | NoCascade | 46 |
+    /// | MultipleOf | 47 |
+    /// | Cookie | 48 |
+    /// | RawTarget | 49 |
+    /// | Empty | 50 |
+    /// | UnorderedValues | 51 |
+    /// | NumericString | 52 |
+    /// | Hex | 53 |
+    /// | Sensitive | 54 |
+    /// | JsonValue | 55 |
     ///
     /// # Safety
     ///
@@ -470,6 +598,38 @@ ffi_fn! {
     /// | Semver | 21 | NULL |
     /// | EachKey | 22 | NULL |
     /// | EachValue | 23 | NULL |
+    /// | AllowedKeys | 24 | Comma-separated list of keys |
+    /// | BodyHash | 25 | Hash algorithm |
+    /// | Correlation | 26 | Request header name |
+    /// | PathExpression | 27 | NULL |
+    /// | Base64 | 28 | NULL |
+    /// | RetryAfter | 29 | NULL |
+    /// | ImageSimilarity | 30 | Threshold |
+    /// | Duration | 31 | NULL |
+    /// | StringLength | 32 | NULL |
+    /// | ArrayContainsInOrder | 33 | NULL |
+    /// | ArrayDistinct | 34 | Path to compare by (if set) |
+    /// | IntegerRange | 35 | NULL |
+    /// | JsonSchema | 36 | JSON Schema (as a JSON string) |
+    /// | DateTimeAfter | 37 | Reference date/time |
+    /// | DateTimeBefore | 38 | Reference date/time |
+    /// | Keys | 39 | Comma-separated list of keys |
+    /// | OneOf | 40 | Comma-separated list of values |
+    /// | Nullable | 41 | Wrapped matching rule (as a JSON string) |
+    /// | IpAddress | 42 | IP version (4 or 6), or NULL if any version is accepted |
+    /// | Format | 43 | Name of the format |
+    /// | Any | 44 | NULL |
+    /// | JsonRegex | 45 | Regex value |
+    /// | NoCascade | 46 | NULL |
+    /// | MultipleOf | 47 | Base value |
+    /// | Cookie | 48 | NULL |
+    /// | RawTarget | 49 | NULL |
+    /// | Empty | 50 | NULL |
+    /// | UnorderedValues | 51 | NULL |
+    /// | NumericString | 52 | Tolerance (NULL if not set) |
+    /// | Hex | 53 | Number of digits (NULL if not set) |
+    /// | Sensitive | 54 | NULL |
+    /// | JsonValue | 55 | NULL |
     ///
     /// Will return a NULL pointer if the matching rule was a reference or does not have an
     /// associated value.
@@ -1,11 +1,14 @@
 //! FFI wrapper code for iterating over Pact interactions
 
+use std::ffi::CString;
 use std::panic::RefUnwindSafe;
 
+use libc::{c_char, size_t};
 use tracing::trace;
 
 use pact_models::message::Message;
 use pact_models::message_pact::MessagePact;
+use pact_models::provider_states::ProviderState;
 use pact_models::v4::pact::V4Pact;
 use pact_models::v4::async_message::AsynchronousMessage;
 use pact_models::v4::sync_message::SynchronousMessage;
@@ -339,3 +342,178 @@ ffi_fn! {
         ptr::drop_raw(iter);
     }
 }
+
+/// Summary of an interaction in a pact, returned by `pactffi_pact_interactions_iter_next`.
+#[derive(Debug)]
+#[repr(C)]
+#[allow(missing_copy_implementations)]
+pub struct PactInteractionSummary {
+  /// Index of the interaction in the pact.
+  pub index: size_t,
+  /// Description of the interaction as a NULL terminated string.
+  pub description: *const c_char,
+  /// Key of the interaction as a NULL terminated string. This will be NULL if the interaction
+  /// is not a V4 interaction, or does not have a key.
+  pub key: *const c_char
+}
+
+/// An iterator over the index, description and key of the interactions in a pact.
+#[derive(Debug)]
+#[allow(missing_copy_implementations)]
+pub struct PactInteractionSummaryIterator {
+  current: usize,
+  /// Owns the strings that the summaries point to
+  _strings: Vec<(CString, Option<CString>)>,
+  summaries: Vec<PactInteractionSummary>
+}
+
+impl PactInteractionSummaryIterator {
+  /// Create a new iterator over the summaries of all the interactions in the pact
+  pub fn new(pact: Box<dyn pact_models::pact::Pact + Send + Sync + RefUnwindSafe>) -> Self {
+    let strings = pact.interactions().iter()
+      .map(|interaction| {
+        let description = CString::new(interaction.description()).unwrap_or_default();
+        let key = Some(interaction)
+          .filter(|interaction| interaction.is_v4())
+          .and_then(|interaction| interaction.as_v4())
+          .and_then(|interaction| interaction.key())
+          .and_then(|key| CString::new(key).ok());
+        (description, key)
+      })
+      .collect::<Vec<_>>();
+    let summaries = strings.iter()
+      .enumerate()
+      .map(|(index, (description, key))| PactInteractionSummary {
+        index,
+        description: description.as_ptr(),
+        key: key.as_ref().map(|key| key.as_ptr()).unwrap_or(std::ptr::null())
+      })
+      .collect();
+    PactInteractionSummaryIterator {
+      current: 0,
+      _strings: strings,
+      summaries
+    }
+  }
+
+  /// Get the summary of the next interaction in the pact.
+  fn next(&mut self) -> Option<&PactInteractionSummary> {
+    let idx = self.current;
+    self.current += 1;
+    self.summaries.get(idx)
+  }
+}
+
+ffi_fn! {
+    /// Get the summary (index, description and key) of the next interaction from the pact. As
+    /// the summaries returned are owned by the iterator, they do not need to be deleted but will
+    /// be cleaned up when the iterator is deleted. The strings in the summary are only valid
+    /// while the iterator exists.
+    ///
+    /// Will return a NULL pointer when the iterator has advanced past the end of the list.
+    ///
+    /// # Safety
+    ///
+    /// This function is safe.
+    ///
+    /// Deleting a summary returned by the iterator can lead to undefined behaviour.
+    ///
+    /// # Error Handling
+    ///
+    /// This function will return a NULL pointer if passed a NULL pointer or if an error occurs.
+    fn pactffi_pact_interactions_iter_next(iter: *mut PactInteractionSummaryIterator) -> *const PactInteractionSummary {
+        let iter = as_mut!(iter);
+        match iter.next() {
+          Some(summary) => summary as *const PactInteractionSummary,
+          None => {
+            trace!("iter past the end of interactions");
+            std::ptr::null()
+          }
+        }
+    } {
+        std::ptr::null()
+    }
+}
+
+ffi_fn! {
+    /// Free the iterator when you're done using it.
+    fn pactffi_pact_interactions_iter_delete(iter: *mut PactInteractionSummaryIterator) {
+        ptr::drop_raw(iter);
+    }
+}
+
+/// An iterator over the unique provider states of all the interactions in a pact.
+#[derive(Debug)]
+#[allow(missing_copy_implementations)]
+pub struct PactProviderStateIterator {
+  current: usize,
+  provider_states: Vec<ProviderState>
+}
+
+impl PactProviderStateIterator {
+  /// Create a new iterator over the provider states of all the interactions in the pact. Provider
+  /// states with the same name are only returned once, with the parameters of all of them merged
+  /// together (where the first value found for a parameter is kept).
+  pub fn new(pact: Box<dyn pact_models::pact::Pact + Send + Sync + RefUnwindSafe>) -> Self {
+    let mut provider_states: Vec<ProviderState> = vec![];
+    for interaction in pact.interactions() {
+      for state in interaction.provider_states() {
+        match provider_states.iter_mut().find(|ps| ps.name == state.name) {
+          Some(existing) => for (key, value) in state.params {
+            existing.params.entry(key).or_insert(value);
+          },
+          None => provider_states.push(state)
+        }
+      }
+    }
+    PactProviderStateIterator {
+      current: 0,
+      provider_states
+    }
+  }
+
+  /// Get the next provider state in the pact.
+  fn next(&mut self) -> Option<&ProviderState> {
+    let idx = self.current;
+    self.current += 1;
+    self.provider_states.get(idx)
+  }
+}
+
+ffi_fn! {
+    /// Get the next provider state from the pact. As the provider states returned are owned by the
+    /// iterator, they do not need to be deleted but will be cleaned up when the iterator is
+    /// deleted. The name and parameters can be accessed with the `pactffi_provider_state_get_name`
+    /// and `pactffi_provider_state_get_param_iter` functions.
+    ///
+    /// Will return a NULL pointer when the iterator has advanced past the end of the list.
+    ///
+    /// # Safety
+    ///
+    /// This function is safe.
+    ///
+    /// Deleting a provider state returned by the iterator can lead to undefined behaviour.
+    ///
+    /// # Error Handling
+    ///
+    /// This function will return a NULL pointer if passed a NULL pointer or if an error occurs.
+    fn pactffi_pact_provider_states_iter_next(iter: *mut PactProviderStateIterator) -> *const ProviderState {
+        let iter = as_mut!(iter);
+        match iter.next() {
+          Some(provider_state) => provider_state as *const ProviderState,
+          None => {
+            trace!("iter past the end of provider states");
+            std::ptr::null()
+          }
+        }
+    } {
+        std::ptr::null()
+    }
+}
+
+ffi_fn! {
+    /// Free the iterator when you're done using it.
+    fn pactffi_pact_provider_states_iter_delete(iter: *mut PactProviderStateIterator) {
+        ptr::drop_raw(iter);
+    }
+}
@@ -2,9 +2,10 @@
 
 use itertools::Itertools;
 use libc::c_char;
-use pact_models::matchingrules::{Category, MatchingRule};
+use pact_models::matchingrules::{Category, MatchingRule, MatchingRules};
 use pact_models::path_exp::DocPath;
 use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
+use pact_models::v4::interaction::V4Interaction;
 use pact_models::v4::message_parts::MessageContents;
 use tracing::trace;
 
@@ -203,6 +204,133 @@ ffi_fn! {
     }
 }
 
+/// An iterator that enables FFI iteration over all the matching rules of an interaction, across
+/// all matching rule categories.
+#[derive(Debug)]
+pub struct InteractionMatchingRulesIterator {
+  rules: Vec<(Category, DocPath, MatchingRule)>,
+  current_idx: usize
+}
+
+impl InteractionMatchingRulesIterator {
+  /// Creates a new iterator over the matching rules of the interaction. For HTTP interactions
+  /// and synchronous messages, the request rules are returned before the response rules.
+  pub fn new(interaction: &dyn V4Interaction) -> Self {
+    let matching_rules = if let Some(http) = interaction.as_v4_http() {
+      vec![http.request.matching_rules, http.response.matching_rules]
+    } else if let Some(message) = interaction.as_v4_async_message() {
+      vec![message.contents.matching_rules]
+    } else if let Some(message) = interaction.as_v4_sync_message() {
+      let mut rules = vec![message.request.matching_rules];
+      rules.extend(message.response.iter().map(|response| response.matching_rules.clone()));
+      rules
+    } else {
+      vec![]
+    };
+    InteractionMatchingRulesIterator {
+      rules: matching_rules.iter().flat_map(Self::flatten_rules).collect(),
+      current_idx: 0
+    }
+  }
+
+  fn flatten_rules(rules: &MatchingRules) -> Vec<(Category, DocPath, MatchingRule)> {
+    rules.rules.iter()
+      .sorted_by(|(a, _), (b, _)| Ord::cmp(a, b))
+      .flat_map(|(category, rules)| {
+        rules.rules.iter()
+          .sorted_by(|(a, _), (b, _)| Ord::cmp(a.to_string().as_str(), b.to_string().as_str()))
+          .flat_map(|(path, list)| list.rules.iter()
+            .map(|rule| (category.clone(), path.clone(), rule.clone())))
+      })
+      .collect()
+  }
+
+  fn next(&mut self) -> Option<&(Category, DocPath, MatchingRule)> {
+    let value = self.rules.get(self.current_idx);
+    self.current_idx += 1;
+    value
+  }
+}
+
+/// A single matching rule entry of an interaction exported to the C-side. The matching rule is
+/// provided in its JSON form.
+#[derive(Debug)]
+#[repr(C)]
+pub struct InteractionMatchingRuleEntry {
+  /// The category the matching rule applies to
+  pub category: MatchingRuleCategory,
+  /// The matching rule path
+  pub path: *const c_char,
+  /// The JSON form of the matching rule
+  pub rule_json: *const c_char
+}
+
+impl InteractionMatchingRuleEntry {
+  fn new(
+    category: &Category,
+    path: &DocPath,
+    rule: &MatchingRule
+  ) -> anyhow::Result<InteractionMatchingRuleEntry> {
+    Ok(InteractionMatchingRuleEntry {
+      category: category.clone().into(),
+      path: string::to_c(&path.to_string())? as *const c_char,
+      rule_json: string::to_c(&rule.to_json().to_string())? as *const c_char
+    })
+  }
+}
+
+// Ensure that the owned values are freed when the entry is dropped.
+impl Drop for InteractionMatchingRuleEntry {
+  fn drop(&mut self) {
+    string::pactffi_string_delete(self.path as *mut c_char);
+    string::pactffi_string_delete(self.rule_json as *mut c_char);
+  }
+}
+
+ffi_fn! {
+    /// Get the next matching rule entry out of the iterator, if possible.
+    ///
+    /// The returned pointer must be deleted with `pactffi_interaction_matching_rules_entry_delete`.
+    ///
+    /// # Safety
+    ///
+    /// The underlying data is owned by the `InteractionMatchingRuleEntry`, so is always safe to use.
+    ///
+    /// # Error Handling
+    ///
+    /// If no further data is present, returns NULL.
+    fn pactffi_interaction_matching_rules_iter_next(iter: *mut InteractionMatchingRulesIterator) -> *const InteractionMatchingRuleEntry {
+        let iter = as_mut!(iter);
+
+        match iter.next() {
+          Some((category, path, rule)) => {
+            let entry = InteractionMatchingRuleEntry::new(category, path, rule)?;
+            ptr::raw_to(entry)
+          }
+          None => {
+            trace!("iter past the end of the interaction matching rules");
+            std::ptr::null_mut()
+          }
+      }
+    } {
+        std::ptr::null_mut()
+    }
+}
+
+ffi_fn! {
+    /// Free the iterator when you're done using it.
+    fn pactffi_interaction_matching_rules_iter_delete(iter: *mut InteractionMatchingRulesIterator) {
+        ptr::drop_raw(iter);
+    }
+}
+
+ffi_fn! {
+    /// Free an entry returned from `pactffi_interaction_matching_rules_iter_next`.
+    fn pactffi_interaction_matching_rules_entry_delete(entry: *const InteractionMatchingRuleEntry) {
+        ptr::drop_raw(entry as *mut InteractionMatchingRuleEntry);
+    }
+}
+
 #[cfg(test)]
 mod tests {
   use std::ffi::CString;
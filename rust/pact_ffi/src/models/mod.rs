@@ -9,7 +9,7 @@ use serde_json::Value;
 use tracing::error;
 
 use crate::{ffi_fn, safe_str, as_ref};
-use crate::models::iterators::PactInteractionIterator;
+use crate::models::iterators::{PactInteractionIterator, PactInteractionSummaryIterator, PactProviderStateIterator};
 use crate::models::pact_specification::PactSpecification;
 use crate::util::ptr;
 
@@ -28,6 +28,7 @@ pub mod pact_specification;
 pub mod provider;
 pub mod provider_state;
 pub mod sync_message;
+pub mod validation;
 
 /// Opaque type for use as a pointer to a Pact model
 #[derive(Debug)]
@@ -95,6 +96,48 @@ ffi_fn! {
   }
 }
 
+ffi_fn! {
+  /// Returns an iterator over the index, description and key (for V4 interactions) of all the
+  /// interactions in the Pact, which can be used to correlate verification results with the
+  /// interactions without having to parse the Pact JSON. The iterator will have to be deleted
+  /// using the `pactffi_pact_interactions_iter_delete` function. The iterator will contain a copy
+  /// of the values, so it will still function if the Pact model is deleted.
+  ///
+  /// # Safety
+  /// This function is safe as long as the Pact pointer is a valid pointer.
+  ///
+  /// # Errors
+  /// On any error, this function will return a NULL pointer.
+  fn pactffi_pact_interactions_iter(pact: *const Pact) -> *mut PactInteractionSummaryIterator {
+    let pact = as_ref!(pact);
+    let inner = pact.inner.lock().unwrap();
+    ptr::raw_to(PactInteractionSummaryIterator::new(inner.boxed()))
+  } {
+    std::ptr::null_mut()
+  }
+}
+
+ffi_fn! {
+  /// Returns an iterator over the unique provider states of all the interactions in the Pact.
+  /// Provider states with the same name are only returned once, with their parameters merged.
+  /// The iterator will have to be deleted using the `pactffi_pact_provider_states_iter_delete`
+  /// function. The iterator will contain a copy of the provider states, so it will still function
+  /// if the Pact model is deleted.
+  ///
+  /// # Safety
+  /// This function is safe as long as the Pact pointer is a valid pointer.
+  ///
+  /// # Errors
+  /// On any error, this function will return a NULL pointer.
+  fn pactffi_pact_provider_states_iter(pact: *const Pact) -> *mut PactProviderStateIterator {
+    let pact = as_ref!(pact);
+    let inner = pact.inner.lock().unwrap();
+    ptr::raw_to(PactProviderStateIterator::new(inner.boxed()))
+  } {
+    std::ptr::null_mut()
+  }
+}
+
 ffi_fn! {
   /// Returns the Pact specification enum that the Pact is for.
   fn pactffi_pact_spec_version(pact: *const Pact) -> PactSpecification {
@@ -130,7 +173,7 @@ ffi_fn! {
 
 #[cfg(test)]
 mod tests {
-  use std::ffi::CString;
+  use std::ffi::{CStr, CString};
 
   use expectest::prelude::*;
   use libc::c_char;
@@ -139,7 +182,9 @@ mod tests {
     pactffi_pact_model_delete,
     pactffi_parse_pact_json,
     pactffi_pact_spec_version,
-    pactffi_pact_model_interaction_iterator
+    pactffi_pact_model_interaction_iterator,
+    pactffi_pact_interactions_iter,
+    pactffi_pact_provider_states_iter
   };
   use crate::models::consumer::{
     pactffi_consumer_get_name,
@@ -155,7 +200,11 @@ mod tests {
   };
   use crate::models::iterators::{
     pactffi_pact_interaction_iter_delete,
-    pactffi_pact_interaction_iter_next
+    pactffi_pact_interaction_iter_next,
+    pactffi_pact_interactions_iter_delete,
+    pactffi_pact_interactions_iter_next,
+    pactffi_pact_provider_states_iter_delete,
+    pactffi_pact_provider_states_iter_next
   };
   use crate::models::pact_specification::PactSpecification;
   use crate::models::provider::{
@@ -163,6 +212,7 @@ mod tests {
     pactffi_pact_provider_delete,
     pactffi_provider_get_name
   };
+  use crate::models::provider_state::pactffi_provider_state_get_name;
 
   #[test]
   fn load_pact_from_json() {
@@ -291,4 +341,137 @@ mod tests {
     expect!(provider_name.to_string_lossy()).to(be_equal_to("load_pact_from_json Provider"));
     expect!(spec_version).to(be_equal_to(PactSpecification::V3));
   }
+
+  #[test]
+  fn pact_provider_states_iter() {
+    let json = CString::new(r#"{
+      "provider": { "name": "pact_provider_states_iter Provider" },
+      "consumer": { "name": "pact_provider_states_iter Consumer" },
+      "interactions": [
+        {
+          "description": "request one",
+          "providerStates": [
+            { "name": "a user exists", "params": { "id": 100 } },
+            { "name": "the user is an admin" }
+          ],
+          "request": { "method": "GET", "path": "/one" },
+          "response": { "status": 200 }
+        },
+        {
+          "description": "request two",
+          "providerStates": [
+            { "name": "a user exists", "params": { "id": 200, "name": "Fred" } }
+          ],
+          "request": { "method": "GET", "path": "/two" },
+          "response": { "status": 200 }
+        }
+      ],
+      "metadata": { "pactSpecification": { "version": "3.0.0" } }
+    }"#).unwrap();
+    let pact = pactffi_parse_pact_json(json.as_ptr());
+    expect!(pact.is_null()).to(be_false());
+
+    let iter = pactffi_pact_provider_states_iter(pact);
+    pactffi_pact_model_delete(pact);
+    expect!(iter.is_null()).to(be_false());
+
+    let mut names = vec![];
+    let mut params = vec![];
+    loop {
+      let state = pactffi_pact_provider_states_iter_next(iter);
+      if state.is_null() {
+        break;
+      }
+      let name_ptr = pactffi_provider_state_get_name(state);
+      let name = unsafe { CString::from_raw(name_ptr as *mut c_char) };
+      names.push(name.to_string_lossy().to_string());
+      let mut keys = unsafe { (*state).params.keys().cloned().collect::<Vec<_>>() };
+      keys.sort();
+      params.push(keys);
+    }
+    pactffi_pact_provider_states_iter_delete(iter);
+
+    expect!(names).to(be_equal_to(vec!["a user exists".to_string(), "the user is an admin".to_string()]));
+    expect!(params).to(be_equal_to(vec![vec!["id".to_string(), "name".to_string()], vec![]]));
+  }
+
+  #[test]
+  fn pact_interactions_iter() {
+    let json = CString::new(r#"{
+      "provider": { "name": "pact_interactions_iter Provider" },
+      "consumer": { "name": "pact_interactions_iter Consumer" },
+      "interactions": [
+        {
+          "type": "Synchronous/HTTP",
+          "key": "1234abcd",
+          "description": "request one",
+          "request": { "method": "GET", "path": "/one" },
+          "response": { "status": 200 }
+        },
+        {
+          "type": "Asynchronous/Messages",
+          "key": "5678efab",
+          "description": "message two",
+          "contents": { "content": "hello" }
+        }
+      ],
+      "metadata": { "pactSpecification": { "version": "4.0" } }
+    }"#).unwrap();
+    let pact = pactffi_parse_pact_json(json.as_ptr());
+    expect!(pact.is_null()).to(be_false());
+
+    let iter = pactffi_pact_interactions_iter(pact);
+    pactffi_pact_model_delete(pact);
+    expect!(iter.is_null()).to(be_false());
+
+    let mut summaries = vec![];
+    loop {
+      let summary = pactffi_pact_interactions_iter_next(iter);
+      if summary.is_null() {
+        break;
+      }
+      let summary = unsafe { &*summary };
+      let description = unsafe { CStr::from_ptr(summary.description) }.to_string_lossy().to_string();
+      let key = if summary.key.is_null() {
+        None
+      } else {
+        Some(unsafe { CStr::from_ptr(summary.key) }.to_string_lossy().to_string())
+      };
+      summaries.push((summary.index, description, key));
+    }
+    pactffi_pact_interactions_iter_delete(iter);
+
+    expect!(summaries).to(be_equal_to(vec![
+      (0, "request one".to_string(), Some("1234abcd".to_string())),
+      (1, "message two".to_string(), Some("5678efab".to_string()))
+    ]));
+  }
+
+  #[test]
+  fn pact_interactions_iter_with_v3_pact() {
+    let json = CString::new(r#"{
+      "provider": { "name": "pact_interactions_iter Provider" },
+      "consumer": { "name": "pact_interactions_iter Consumer" },
+      "interactions": [
+        {
+          "description": "request one",
+          "request": { "method": "GET", "path": "/one" },
+          "response": { "status": 200 }
+        }
+      ],
+      "metadata": { "pactSpecification": { "version": "3.0.0" } }
+    }"#).unwrap();
+    let pact = pactffi_parse_pact_json(json.as_ptr());
+    let iter = pactffi_pact_interactions_iter(pact);
+    pactffi_pact_model_delete(pact);
+
+    let summary = pactffi_pact_interactions_iter_next(iter);
+    expect!(summary.is_null()).to(be_false());
+    let summary = unsafe { &*summary };
+    expect!(summary.index).to(be_equal_to(0));
+    expect!(unsafe { CStr::from_ptr(summary.description) }.to_str().unwrap()).to(be_equal_to("request one"));
+    expect!(summary.key.is_null()).to(be_true());
+    expect!(pactffi_pact_interactions_iter_next(iter).is_null()).to(be_true());
+    pactffi_pact_interactions_iter_delete(iter);
+  }
 }
@@ -0,0 +1,330 @@
+//! FFI functions to validate Pact files against the Pact specification
+
+use std::str::FromStr;
+
+use libc::c_char;
+use pact_models::matchingrules::{Category, MatchingRule};
+use pact_models::pact::{determine_spec_version, load_pact_from_json, parse_meta_data};
+use pact_models::path_exp::DocPath;
+use pact_models::PactSpecification;
+use serde_json::{json, Map, Value};
+
+use crate::{ffi_fn, safe_str};
+use crate::models::pact_specification::PactSpecification as FfiPactSpecification;
+use crate::util::string;
+
+/// Matcher types that are supported by the V2 specification
+const V2_MATCHERS: [&str; 6] = ["equality", "regex", "type", "min-type", "max-type", "min-max-type"];
+
+/// Interaction attributes that are only supported by V4 pacts
+const V4_INTERACTION_ATTRIBUTES: [&str; 6] = [
+  "type", "key", "pending", "comments", "pluginConfiguration", "interactionMarkup"
+];
+
+ffi_fn! {
+  /// Validates that the provided Pact JSON conforms to the given specification version. If the
+  /// specification version is `Unknown`, the version declared in the Pact metadata is used.
+  ///
+  /// Returns a JSON array of the problems found, where each problem is a JSON object with a
+  /// `path` to the offending part of the document and a `message`. An empty array means no
+  /// problems were found. A Pact file without any interactions is reported as a problem.
+  ///
+  /// The returned string must be freed with the `pactffi_string_delete` function.
+  ///
+  /// # Error Handling
+  ///
+  /// This function will return a NULL pointer if passed a NULL pointer or if an error occurs.
+  fn pactffi_validate_pact_json(
+    json: *const c_char,
+    spec_version: FfiPactSpecification
+  ) -> *const c_char {
+    let json_str = safe_str!(json);
+    let problems = match serde_json::from_str::<Value>(json_str) {
+      Ok(pact_json) => validate_pact_json(&pact_json, spec_version.into()),
+      Err(err) => vec![ problem("$", format!("Pact JSON is not valid - {}", err)) ]
+    };
+    string::to_c(&Value::Array(problems).to_string())? as *const c_char
+  } {
+    std::ptr::null()
+  }
+}
+
+fn problem<S: Into<String>>(path: &str, message: S) -> Value {
+  json!({ "path": path, "message": message.into() })
+}
+
+/// Validates the Pact JSON against the given specification version, returning the problems found.
+/// If the specification version is `Unknown`, the version declared in the Pact metadata is used.
+pub fn validate_pact_json(pact_json: &Value, spec_version: PactSpecification) -> Vec<Value> {
+  let mut problems = vec![];
+
+  let map = match pact_json {
+    Value::Object(map) => map,
+    _ => return vec![ problem("$", "Pact JSON is not a JSON object") ]
+  };
+
+  let metadata = parse_meta_data(pact_json);
+  let declared_version = determine_spec_version("<FFI>", &metadata);
+  let spec_version = if spec_version == PactSpecification::Unknown {
+    declared_version
+  } else {
+    if declared_version != spec_version {
+      problems.push(problem("$.metadata", format!("Pact declares specification version {} but {} was expected",
+        declared_version, spec_version)));
+    }
+    spec_version
+  };
+
+  if let Err(err) = load_pact_from_json("<FFI>", pact_json) {
+    problems.push(problem("$", format!("Pact could not be loaded - {}", err)));
+  }
+
+  for party in ["consumer", "provider"] {
+    if map.get(party).and_then(|v| v.get("name")).and_then(|v| v.as_str()).is_none() {
+      problems.push(problem(&format!("$.{}.name", party), format!("Pact is missing the {} name", party)));
+    }
+  }
+
+  let key = if spec_version < PactSpecification::V4 && map.contains_key("messages") {
+    "messages"
+  } else {
+    "interactions"
+  };
+  let path = format!("$.{}", key);
+  match map.get(key) {
+    Some(Value::Array(interactions)) => {
+      if interactions.is_empty() {
+        problems.push(problem(&path, "Pact has no interactions"));
+      }
+      for (index, interaction) in interactions.iter().enumerate() {
+        validate_interaction(&format!("{}[{}]", path, index), interaction, spec_version, &mut problems);
+      }
+    }
+    Some(_) => problems.push(problem(&path, "Interactions must be a JSON array")),
+    None => problems.push(problem(&path, "Pact has no interactions"))
+  }
+
+  if spec_version < PactSpecification::V4 && pact_json.pointer("/metadata/plugins").is_some() {
+    problems.push(problem("$.metadata.plugins", format!("Plugins are not supported by {} pacts", spec_version)));
+  }
+
+  problems
+}
+
+fn validate_interaction(
+  path: &str,
+  interaction: &Value,
+  spec_version: PactSpecification,
+  problems: &mut Vec<Value>
+) {
+  let map = match interaction {
+    Value::Object(map) => map,
+    _ => {
+      problems.push(problem(path, "Interaction is not a JSON object"));
+      return;
+    }
+  };
+
+  if spec_version < PactSpecification::V4 {
+    for attribute in V4_INTERACTION_ATTRIBUTES {
+      if map.contains_key(attribute) {
+        problems.push(problem(&format!("{}.{}", path, attribute),
+          format!("'{}' is not supported by {} pacts", attribute, spec_version)));
+      }
+    }
+  }
+  if spec_version < PactSpecification::V3 && map.contains_key("providerStates") {
+    problems.push(problem(&format!("{}.providerStates", path),
+      format!("'providerStates' is not supported by {} pacts", spec_version)));
+  }
+
+  validate_part(path, map, spec_version, problems);
+  for part in ["request", "response"] {
+    match map.get(part) {
+      Some(Value::Object(part_map)) => validate_part(&format!("{}.{}", path, part), part_map, spec_version, problems),
+      Some(Value::Array(responses)) => for (index, response) in responses.iter().enumerate() {
+        if let Value::Object(part_map) = response {
+          validate_part(&format!("{}.{}[{}]", path, part, index), part_map, spec_version, problems);
+        }
+      },
+      _ => {}
+    }
+  }
+}
+
+fn validate_part(
+  path: &str,
+  part: &Map<String, Value>,
+  spec_version: PactSpecification,
+  problems: &mut Vec<Value>
+) {
+  if spec_version < PactSpecification::V3 && part.contains_key("generators") {
+    problems.push(problem(&format!("{}.generators", path),
+      format!("Generators are not supported by {} pacts", spec_version)));
+  }
+
+  if let Some(rules) = part.get("matchingRules") {
+    let path = format!("{}.matchingRules", path);
+    if spec_version < PactSpecification::V2 {
+      problems.push(problem(&path, format!("Matching rules are not supported by {} pacts", spec_version)));
+    } else if let Value::Object(rules) = rules {
+      if spec_version == PactSpecification::V2 {
+        validate_v2_matching_rules(&path, rules, problems);
+      } else {
+        validate_v3_matching_rules(&path, rules, problems);
+      }
+    } else {
+      problems.push(problem(&path, "Matching rules must be a JSON object"));
+    }
+  }
+}
+
+fn validate_v2_matching_rules(path: &str, rules: &Map<String, Value>, problems: &mut Vec<Value>) {
+  for (key, rule) in rules {
+    let rule_path = format!("{}['{}']", path, key);
+    if let Err(err) = DocPath::new(key) {
+      problems.push(problem(&rule_path, format!("Matching rule path '{}' is not valid - {}", key, err)));
+    }
+    match MatchingRule::from_json(rule) {
+      Ok(rule) => if !V2_MATCHERS.contains(&rule.name().as_str()) {
+        problems.push(problem(&rule_path, format!("'{}' matcher is not supported by V2 pacts", rule.name())));
+      },
+      Err(err) => problems.push(problem(&rule_path, format!("Matching rule is not valid - {}", err)))
+    }
+  }
+}
+
+fn validate_v3_matching_rules(path: &str, rules: &Map<String, Value>, problems: &mut Vec<Value>) {
+  for (category, rules) in rules {
+    let category_path = format!("{}.{}", path, category);
+    let category = match Category::from_str(category) {
+      Ok(category) => category,
+      Err(err) => {
+        problems.push(problem(&category_path, err));
+        continue;
+      }
+    };
+    match rules {
+      Value::Object(map) if map.contains_key("matchers") => {
+        validate_rule_list(&category_path, rules, problems);
+      }
+      Value::Object(map) => for (key, rule_list) in map {
+        let rule_path = format!("{}['{}']", category_path, key);
+        if category == Category::BODY || category == Category::CONTENTS {
+          if let Err(err) = DocPath::new(key) {
+            problems.push(problem(&rule_path, format!("Matching rule path '{}' is not valid - {}", key, err)));
+          }
+        }
+        validate_rule_list(&rule_path, rule_list, problems);
+      },
+      _ => problems.push(problem(&category_path, "Matching rules must be a JSON object"))
+    }
+  }
+}
+
+fn validate_rule_list(path: &str, rule_list: &Value, problems: &mut Vec<Value>) {
+  match rule_list.get("matchers") {
+    Some(Value::Array(matchers)) => for (index, matcher) in matchers.iter().enumerate() {
+      if let Err(err) = MatchingRule::from_json(matcher) {
+        problems.push(problem(&format!("{}.matchers[{}]", path, index), format!("Matching rule is not valid - {}", err)));
+      }
+    },
+    _ => problems.push(problem(path, "Matching rule definition must have a 'matchers' array"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::ffi::{CStr, CString};
+
+  use expectest::prelude::*;
+  use serde_json::json;
+
+  use crate::models::pact_specification::PactSpecification;
+  use crate::util::string::pactffi_string_delete;
+
+  use super::*;
+
+  #[test]
+  fn validate_pact_json_with_a_valid_pact() {
+    let pact = json!({
+      "consumer": { "name": "Consumer" },
+      "provider": { "name": "Provider" },
+      "interactions": [
+        {
+          "description": "a request",
+          "request": { "method": "GET", "path": "/" },
+          "response": {
+            "status": 200,
+            "body": { "id": 1 },
+            "matchingRules": { "body": { "$.id": { "matchers": [ { "match": "integer" } ] } } }
+          }
+        }
+      ],
+      "metadata": { "pactSpecification": { "version": "3.0.0" } }
+    });
+    expect!(validate_pact_json(&pact, pact_models::PactSpecification::Unknown)).to(be_empty());
+  }
+
+  #[test]
+  fn validate_pact_json_reports_structural_problems() {
+    let pact = json!({
+      "consumer": { "name": "Consumer" },
+      "provider": {},
+      "interactions": [
+        {
+          "description": "a request",
+          "pending": true,
+          "request": { "method": "GET", "path": "/", "generators": { "body": {} } },
+          "response": {
+            "status": 200,
+            "matchingRules": {
+              "$.body.id": { "match": "integer" },
+              "$.body.": { "match": "type" },
+              "$.body.name": { "match": "unknown" }
+            }
+          }
+        }
+      ],
+      "metadata": { "pactSpecification": { "version": "3.0.0" } }
+    });
+    let problems = validate_pact_json(&pact, pact_models::PactSpecification::V2);
+    let paths = problems.iter()
+      .map(|p| p["path"].as_str().unwrap_or_default().to_string())
+      .collect::<Vec<_>>();
+    expect!(paths.iter()).to(contain(&"$.metadata".to_string()));
+    expect!(paths.iter()).to(contain(&"$.provider.name".to_string()));
+    expect!(paths.iter()).to(contain(&"$.interactions[0].pending".to_string()));
+    expect!(paths.iter()).to(contain(&"$.interactions[0].request.generators".to_string()));
+    expect!(paths.iter()).to(contain(&"$.interactions[0].response.matchingRules['$.body.id']".to_string()));
+    expect!(paths.iter()).to(contain(&"$.interactions[0].response.matchingRules['$.body.']".to_string()));
+    expect!(paths.iter()).to(contain(&"$.interactions[0].response.matchingRules['$.body.name']".to_string()));
+  }
+
+  #[test]
+  fn validate_pact_json_warns_when_there_are_no_interactions() {
+    let pact = json!({
+      "consumer": { "name": "Consumer" },
+      "provider": { "name": "Provider" },
+      "interactions": [],
+      "metadata": { "pactSpecification": { "version": "4.0" } }
+    });
+    expect!(validate_pact_json(&pact, pact_models::PactSpecification::V4)).to(be_equal_to(vec![
+      json!({ "path": "$.interactions", "message": "Pact has no interactions" })
+    ]));
+  }
+
+  #[test]
+  fn pactffi_validate_pact_json_returns_a_json_array() {
+    let json = CString::new("{ not json").unwrap();
+    let result = pactffi_validate_pact_json(json.as_ptr(), PactSpecification::V3);
+    expect!(result.is_null()).to(be_false());
+    let problems: Value = serde_json::from_str(unsafe { CStr::from_ptr(result) }.to_str().unwrap()).unwrap();
+    pactffi_string_delete(result as *mut c_char);
+    expect!(problems.as_array().unwrap().len()).to(be_equal_to(1));
+    expect!(problems[0]["path"].clone()).to(be_equal_to(json!("$")));
+
+    let result = pactffi_validate_pact_json(std::ptr::null(), PactSpecification::V3);
+    expect!(result.is_null()).to(be_true());
+  }
+}
@@ -1,14 +1,17 @@
 //! The `plugins` module provides exported functions using C bindings for using plugins with
 //! Pact tests.
 
+use std::collections::HashMap;
 use std::time::Duration;
 
 use anyhow::anyhow;
 use bytes::Bytes;
 use itertools::Itertools;
 use libc::{c_char, c_uint};
+use pact_matching::generators::{apply_generators_to_async_message, apply_generators_to_sync_message};
 use pact_models::bodies::OptionalBody;
 use pact_models::content_types::ContentType;
+use pact_models::generators::GeneratorTestMode;
 use pact_models::http_parts::HttpPart;
 use pact_models::json_utils::body_from_json;
 use pact_models::pact::Pact;
@@ -25,7 +28,7 @@ use tokio::runtime::Builder;
 use tokio::time::sleep;
 use tracing::{debug, error};
 
-use crate::{ffi_fn, safe_str, RUNTIME};
+use crate::{ffi_fn, ptr, safe_str, RUNTIME};
 use crate::error::{catch_panic, set_error_msg};
 use crate::mock_server::handles::{InteractionHandle, InteractionPart, PactHandle};
 use crate::string::if_null;
@@ -257,6 +260,67 @@ pub extern fn pactffi_interaction_contents(
   }).unwrap_or(1)
 }
 
+ffi_fn! {
+  /// Generates the contents for a message interaction, as the message handler under test would
+  /// receive them. If the contents were configured using a plugin (i.e. with
+  /// `pactffi_interaction_contents`), the plugin will be invoked to generate any values. For
+  /// synchronous messages, the contents of the request message are generated.
+  ///
+  /// The generated bytes can be retrieved with `pactffi_message_contents_get_contents_bin` and
+  /// `pactffi_message_contents_get_contents_length`, and the content type with
+  /// `pactffi_message_contents_get_content_type`.
+  ///
+  /// # Safety
+  ///
+  /// The returned contents must be deleted with `pactffi_message_contents_delete`.
+  ///
+  /// This function must only ever be called from a foreign language. Calling it from a Rust
+  /// function that has a Tokio runtime in its call stack can result in a deadlock.
+  ///
+  /// # Error Handling
+  ///
+  /// Returns NULL if the interaction handle is not valid, or is not for a message interaction.
+  fn pactffi_message_contents_generated(interaction: InteractionHandle) -> *const MessageContents {
+    let messages = interaction.with_interaction(&|_, _, inner| {
+      (inner.as_v4_async_message(), inner.as_v4_sync_message())
+    });
+    let plugin_data = interaction.with_pact(&|_, inner| inner.pact.plugin_data())
+      .unwrap_or_default();
+    let context = HashMap::new();
+
+    let contents = match messages {
+      Some((Some(message), _)) => RUNTIME.block_on(apply_generators_to_async_message(
+        &message,
+        &GeneratorTestMode::Consumer,
+        &context,
+        &plugin_data,
+        &message.plugin_config
+      )),
+      Some((_, Some(message))) => RUNTIME.block_on(apply_generators_to_sync_message(
+        &message,
+        &GeneratorTestMode::Consumer,
+        &context,
+        &plugin_data,
+        &message.plugin_config
+      )).0,
+      Some(_) => {
+        error!("pactffi_message_contents_generated: interaction is not a message interaction");
+        set_error_msg("Interaction is not a message interaction".to_string());
+        return Ok(std::ptr::null());
+      }
+      None => {
+        error!("pactffi_message_contents_generated: interaction handle is not valid");
+        set_error_msg("Interaction handle is not valid".to_string());
+        return Ok(std::ptr::null());
+      }
+    };
+
+    ptr::raw_to(contents) as *const MessageContents
+  } {
+    std::ptr::null()
+  }
+}
+
 fn setup_sync_message_contents(
   interaction: &mut dyn V4Interaction,
   contents: Vec<InteractionContents>,
@@ -409,9 +473,18 @@ mod tests {
   use pact_models::matchingrules::MatchingRule;
   use pact_models::v4::sync_message::SynchronousMessage;
 
-  use crate::mock_server::handles::{InteractionHandle, InteractionPart, PactHandle};
+  use pact_models::v4::message_parts::MessageContents;
+
+  use crate::mock_server::handles::{
+    InteractionHandle,
+    InteractionPart,
+    PactHandle,
+    pactffi_new_interaction,
+    pactffi_new_message_interaction,
+    pactffi_with_body
+  };
 
-  use super::{pactffi_interaction_contents, setup_sync_message_contents};
+  use super::{pactffi_interaction_contents, pactffi_message_contents_generated, setup_sync_message_contents};
 
   #[test]
   fn pactffi_interaction_contents_with_invalid_content_type() {
@@ -434,6 +507,26 @@ mod tests {
     expect!(pactffi_interaction_contents(i_handle, InteractionPart::Request, content_type.as_ptr(), contents.as_ptr())).to(be_equal_to(5));
   }
 
+  #[test]
+  fn pactffi_message_contents_generated_test() {
+    let pact_handle = PactHandle::new("Test", "Test");
+    let description = CString::new("a message").unwrap();
+    let i_handle = pactffi_new_message_interaction(pact_handle, description.as_ptr());
+    let content_type = CString::new("application/json").unwrap();
+    let body = CString::new("{\"id\":100}").unwrap();
+    pactffi_with_body(i_handle, InteractionPart::Request, content_type.as_ptr(), body.as_ptr());
+
+    let contents = pactffi_message_contents_generated(i_handle);
+    expect!(contents.is_null()).to(be_false());
+    let contents = unsafe { Box::from_raw(contents as *mut MessageContents) };
+    expect!(contents.contents.value_as_string()).to(be_some().value("{\"id\":100}"));
+    expect!(contents.message_content_type().map(|ct| ct.to_string())).to(be_some().value("application/json"));
+
+    let description = CString::new("an HTTP interaction").unwrap();
+    let i_handle = pactffi_new_interaction(pact_handle, description.as_ptr());
+    expect!(pactffi_message_contents_generated(i_handle).is_null()).to(be_true());
+  }
+
   #[test]
   fn setup_sync_message_contents_handles_matching_rules_on_metadata() {
     let mut interaction = SynchronousMessage {
@@ -1,16 +1,22 @@
 //! Handle interface to creating a verifier
 
-use std::sync::Arc;
+use std::io;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
 
 use itertools::Itertools;
 use serde_json::Value;
-use tracing::{debug, error};
+use tracing::{debug, Dispatch, error};
+use tracing::instrument::WithSubscriber;
+use tracing_log::AsTrace;
+use tracing_subscriber::fmt::MakeWriter;
 use pact_matching::logging::LOG_ID;
+use pact_matching::MatchingConfig;
 use pact_models::prelude::HttpAuth;
 use pact_verifier::{ConsumerVersionSelector, FilterInfo, NullRequestFilterExecutor, PactSource, ProviderInfo, ProviderTransport, PublishOptions, VerificationOptions, verify_provider_async};
-use pact_verifier::callback_executors::HttpRequestProviderStateExecutor;
+use pact_verifier::callback_executors::{HttpRequestProviderStateExecutor, InteractionResultCallback};
 use pact_verifier::metrics::VerificationMetrics;
-use pact_verifier::verification_result::VerificationExecutionResult;
+use pact_verifier::verification_result::{VerificationExecutionResult, VerificationInteractionResult};
 
 use crate::RUNTIME;
 
@@ -27,7 +33,44 @@ pub struct VerifierHandle {
   /// Calling application name and version
   calling_app: Option<(String, String)>,
   /// Output captured from the verifier
-  verifier_output: VerificationExecutionResult
+  verifier_output: VerificationExecutionResult,
+  /// Level to capture the log entries from the verification run at, if log capturing is enabled
+  log_capture_level: Option<log::LevelFilter>,
+  /// Log entries captured from the last verification run
+  captured_logs: LogCapture
+}
+
+/// Writer that accumulates the log entries for a verification run in memory
+#[derive(Debug, Clone, Default)]
+struct LogCapture(Arc<Mutex<Vec<u8>>>);
+
+impl LogCapture {
+  fn clear(&self) {
+    self.0.lock().unwrap().clear();
+  }
+
+  fn contents(&self) -> String {
+    String::from_utf8_lossy(&self.0.lock().unwrap()).to_string()
+  }
+}
+
+impl Write for LogCapture {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    self.0.lock().unwrap().extend_from_slice(buf);
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+impl <'a> MakeWriter<'a> for LogCapture {
+  type Writer = LogCapture;
+
+  fn make_writer(&'a self) -> Self::Writer {
+    self.clone()
+  }
 }
 
 impl VerifierHandle {
@@ -43,7 +86,9 @@ impl VerifierHandle {
       publish_options: None,
       consumers: vec![],
       calling_app: None,
-      verifier_output: VerificationExecutionResult::new()
+      verifier_output: VerificationExecutionResult::new(),
+      log_capture_level: None,
+      captured_logs: LogCapture::default()
     }
   }
 
@@ -58,7 +103,9 @@ impl VerifierHandle {
       publish_options: None,
       consumers: vec![],
       calling_app: Some((calling_app_name.to_string(), calling_app_version.to_string())),
-      verifier_output: VerificationExecutionResult::new()
+      verifier_output: VerificationExecutionResult::new(),
+      log_capture_level: None,
+      captured_logs: LogCapture::default()
     }
   }
 
@@ -226,6 +273,11 @@ impl VerifierHandle {
     request_timeout: u64
   ) {
     self.verification_options.disable_ssl_verification = disable_ssl_verification;
+    self.set_request_timeout(request_timeout);
+  }
+
+  /// Sets the timeout (in milliseconds) for all requests made to the provider
+  pub fn set_request_timeout(&mut self, request_timeout: u64) {
     self.verification_options.request_timeout = request_timeout;
   }
 
@@ -242,6 +294,20 @@ impl VerifierHandle {
     self.verification_options.no_pacts_is_error = is_error;
   }
 
+  /// Enables or disables strict matching of the responses from the provider
+  pub fn set_strict_mode(&mut self, strict: bool) {
+    self.verification_options.matching_config = if strict {
+      MatchingConfig::strict()
+    } else {
+      MatchingConfig::default()
+    };
+  }
+
+  /// Sets the callback invoked with the result of each interaction as soon as it has been verified
+  pub fn set_interaction_result_callback(&mut self, callback: Option<InteractionResultCallback>) {
+    self.verification_options.interaction_result_callback = callback;
+  }
+
   /// Update the details used when publishing results
   /// 
   /// # Args
@@ -277,16 +343,27 @@ impl VerifierHandle {
   /// * 1 - verification was not successful
   /// * 2 - failed to run the verification
   ///
-  /// Anu captured output from the verification will be stored against this handle
+  /// Anu captured output from the verification will be stored against this handle. If log
+  /// capturing is enabled, the log entries from any previous run are cleared and the log entries
+  /// from this run are stored against the handle.
   pub fn execute(&mut self) -> i32 {
     for s in &self.sources {
       debug!("Pact source to verify = {s}");
     };
 
+    self.captured_logs.clear();
+    let dispatch = self.log_capture_level.map(|level| {
+      Dispatch::new(tracing_subscriber::fmt()
+        .with_max_level(level.as_trace())
+        .with_ansi(false)
+        .with_writer(self.captured_logs.clone())
+        .finish())
+    });
+
     let (calling_app_name, calling_app_version) = self.calling_app.clone().unwrap_or_else(|| {
       ("pact_ffi".to_string(), env!("CARGO_PKG_VERSION").to_string())
     });
-    match RUNTIME.block_on(LOG_ID.scope(format!("verify:{}", self.provider.name), async {
+    let verification = LOG_ID.scope(format!("verify:{}", self.provider.name), async {
       verify_provider_async(
         self.provider.clone(),
         self.sources.clone(),
@@ -301,19 +378,41 @@ impl VerifierHandle {
           app_version: calling_app_version.clone()
         })
       ).await
-    })) {
+    });
+    let result = match &dispatch {
+      Some(dispatch) => RUNTIME.block_on(verification.with_subscriber(dispatch.clone())),
+      None => RUNTIME.block_on(verification)
+    };
+    match result {
       Ok(result) => {
         self.verifier_output = result.clone();
         if result.result { 0 } else { 1 }
       }
       Err(err) => {
-        error!("Verification execution failed: {}", err);
+        match &dispatch {
+          Some(dispatch) => tracing::dispatcher::with_default(dispatch, || {
+            error!("Verification execution failed: {}", err);
+          }),
+          None => error!("Verification execution failed: {}", err)
+        }
         self.verifier_output.output.push(format!("Verification execution failed: {}", err));
         2
       }
     }
   }
 
+  /// Enables capturing the log entries from the verification run at the given level. The captured
+  /// log entries can be retrieved with the `logs` function.
+  pub fn capture_logs(&mut self, level: log::LevelFilter) {
+    self.log_capture_level = Some(level);
+  }
+
+  /// Return the log entries captured from the last verification run, or `None` if log capturing
+  /// has not been enabled
+  pub fn logs(&self) -> Option<String> {
+    self.log_capture_level.map(|_| self.captured_logs.contents())
+  }
+
   /// Return the captured standard output from the verification execution
   pub fn output(&self) -> String {
     self.verifier_output.output.iter().join("\n")
@@ -325,6 +424,23 @@ impl VerifierHandle {
     json.to_string()
   }
 
+  /// Return the result for each interaction that was verified as a JSON document. This includes
+  /// the result for each part of the interaction (status, headers, metadata and body).
+  pub fn interaction_results_json(&self) -> String {
+    let json = self.verifier_output.interaction_results.iter()
+      .map(|result| {
+        let json: Value = result.into();
+        json
+      })
+      .collect();
+    Value::Array(json).to_string()
+  }
+
+  #[cfg(test)]
+  pub fn set_interaction_results(&mut self, results: Vec<VerificationInteractionResult>) {
+    self.verifier_output.interaction_results = results;
+  }
+
   #[cfg(test)]
   pub fn set_output(&mut self, out: &str) {
     self.verifier_output.output = out.split('\n').map(|s| s.to_string()).collect();
@@ -7,19 +7,23 @@ use std::env;
 use std::ffi::{CStr, CString, OsStr, OsString};
 use std::panic::catch_unwind;
 use std::str::from_utf8;
+use std::sync::Mutex;
 
 use clap::ArgSettings;
 use lazy_static::lazy_static;
-use libc::{c_char, c_int, c_uchar, c_ulong, c_ushort, EXIT_FAILURE, EXIT_SUCCESS};
+use libc::{c_char, c_int, c_uchar, c_ulong, c_ushort, c_void, EXIT_FAILURE, EXIT_SUCCESS};
 use log::*;
 use pact_models::prelude::HttpAuth;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
+use pact_verifier::callback_executors::InteractionResultCallback;
 use pact_verifier::selectors::{consumer_tags_to_selectors, json_to_selectors};
+use pact_verifier::verification_result::VerificationInteractionResult;
 
 use crate::{as_mut, as_ref, ffi_fn, RUNTIME, safe_str};
-use crate::log::fetch_buffer_contents;
+use crate::log::{fetch_buffer_contents, LevelFilter};
 use crate::ptr;
 use crate::util::string::{if_null, optional_str};
 
@@ -277,6 +281,104 @@ ffi_fn! {
     }
 }
 
+/// Callback function invoked by the verifier with the result of each interaction as soon as it
+/// has been verified, registered with `pactffi_verifier_set_interaction_result_callback`. It is
+/// passed the result as a NULL terminated JSON string (in the same format as the items returned
+/// by `pactffi_verifier_interaction_results`) and the user data pointer given when the callback
+/// was registered. The JSON string is only valid for the duration of the call.
+pub type VerificationResultCallback = extern "C" fn(
+  result_json: *const c_char,
+  user_data: *mut c_void
+);
+
+/// User data pointer passed to a verification result callback
+#[derive(Debug, Clone, Copy)]
+struct UserData(*mut c_void);
+
+impl UserData {
+  fn ptr(&self) -> *mut c_void {
+    self.0
+  }
+}
+
+// The calling code is responsible for the user data being safe to use from the verifier threads.
+unsafe impl Send for UserData {}
+unsafe impl Sync for UserData {}
+
+fn ffi_result_callback(callback: VerificationResultCallback, user_data: *mut c_void) -> InteractionResultCallback {
+  let user_data = UserData(user_data);
+  // The lock serialises the calls to the foreign callback, so it is never re-entered
+  let lock = Mutex::new(());
+  InteractionResultCallback::new(move |result: &VerificationInteractionResult| {
+    let json: Value = result.into();
+    let json = CString::new(json.to_string()).unwrap_or_default();
+    let _guard = lock.lock().unwrap_or_else(|err| err.into_inner());
+    callback(json.as_ptr(), user_data.ptr());
+  })
+}
+
+ffi_fn! {
+    /// Registers a callback function that is invoked with the result of each interaction as soon
+    /// as it has been verified, which can be used to report progress while the verification is
+    /// running. Calls to the callback are serialised, so it will not be re-entered. See
+    /// `VerificationResultCallback` for the details of the callback. Passing a NULL callback
+    /// removes any previously registered callback.
+    ///
+    /// * handle - the verifier handle
+    /// * callback - function to call with each interaction result, can be NULL
+    /// * user_data - pointer that is passed to the callback, can be NULL
+    ///
+    /// # Safety
+    ///
+    /// This function is safe as long as the handle pointer points to a valid handle. The callback
+    /// will be called from the threads that the verification is run on, so it and the user data
+    /// must be safe to use from any thread, and must remain valid until the verification is
+    /// complete.
+    fn pactffi_verifier_set_interaction_result_callback(
+      handle: *mut handle::VerifierHandle,
+      callback: Option<VerificationResultCallback>,
+      user_data: *mut c_void
+    ) -> c_int {
+      let handle = as_mut!(handle);
+
+      handle.set_interaction_result_callback(callback.map(|callback| ffi_result_callback(callback, user_data)));
+
+      EXIT_SUCCESS
+    } {
+      EXIT_FAILURE
+    }
+}
+
+ffi_fn! {
+    /// Set the timeout (in milliseconds) for all requests made by the verifier to the provider.
+    /// If a request times out, the interaction will fail with a verification error. The default
+    /// timeout is 5000 milliseconds.
+    ///
+    /// Returns EXIT_FAILURE (1) if the timeout is zero.
+    ///
+    /// # Safety
+    ///
+    /// This function is safe as long as the handle pointer points to a valid handle.
+    ///
+    fn pactffi_verifier_set_request_timeout(
+      handle: *mut handle::VerifierHandle,
+      timeout: c_ulong
+    ) -> c_int {
+      let handle = as_mut!(handle);
+
+      if timeout == 0 {
+        error!("pactffi_verifier_set_request_timeout: timeout must be greater than zero");
+        return Ok(EXIT_FAILURE);
+      }
+
+      handle.set_request_timeout(timeout as u64);
+
+      EXIT_SUCCESS
+    } {
+      EXIT_FAILURE
+    }
+}
+
 ffi_fn! {
     /// Enables or disables coloured output using ANSI escape codes in the verifier output. By default,
     /// coloured output is enabled.
@@ -301,6 +403,35 @@ ffi_fn! {
     }
 }
 
+ffi_fn! {
+    /// Enables capturing the log entries from the verification run into a memory buffer attached
+    /// to the handle. The captured log entries can then be retrieved with `pactffi_verifier_logs`
+    /// after the verification has been executed. The buffer is cleared each time the verification
+    /// is executed.
+    ///
+    /// While the verification is running, the log entries will only be written to this buffer
+    /// and not to any log sinks setup with the `pactffi_log_*` functions.
+    ///
+    /// `level_filter` is the level to capture the log entries at.
+    ///
+    /// # Safety
+    ///
+    /// This function is safe as long as the handle pointer points to a valid handle.
+    ///
+    fn pactffi_verifier_capture_logs(
+      handle: *mut handle::VerifierHandle,
+      level_filter: LevelFilter
+    ) -> c_int {
+      let handle = as_mut!(handle);
+
+      handle.capture_logs(level_filter.into());
+
+      EXIT_SUCCESS
+    } {
+      EXIT_FAILURE
+    }
+}
+
 ffi_fn! {
     /// Enables or disables if no pacts are found to verify results in an error.
     ///
@@ -325,6 +456,34 @@ ffi_fn! {
     }
 }
 
+ffi_fn! {
+    /// Enables or disables strict matching of the responses from the provider. With strict
+    /// matching, unexpected keys in the response bodies are mismatches, header values and content
+    /// types must match exactly and integer and decimal numbers are treated as different types.
+    /// This applies to all the interactions, without needing to configure each Pact. For message
+    /// interactions, only the unexpected keys and number types settings apply to the message contents.
+    ///
+    /// `strict` is a boolean value. Set it to greater than zero to enable strict matching, and set
+    /// it to zero to disable it. Strict matching is disabled by default.
+    ///
+    /// # Safety
+    ///
+    /// This function is safe as long as the handle pointer points to a valid handle.
+    ///
+    fn pactffi_verifier_set_strict_mode(
+      handle: *mut handle::VerifierHandle,
+      strict: c_uchar
+    ) -> c_int {
+      let handle = as_mut!(handle);
+
+      handle.set_strict_mode(strict > 0);
+
+      EXIT_SUCCESS
+    } {
+      EXIT_FAILURE
+    }
+}
+
 ffi_fn! {
   /// Set the options used when publishing verification results to the Pact Broker. By default,
   /// verification results will not be published unless this function is called.
@@ -849,14 +1008,19 @@ fn extract_verifier_logs(name: &str) -> *const c_char {
 }
 
 ffi_fn! {
-    /// Extracts the logs for the verification run. This needs the memory buffer log sink to be
+    /// Extracts the logs for the verification run. If log capturing has been enabled with
+    /// `pactffi_verifier_capture_logs`, this returns the log entries captured for the handle from
+    /// the last verification run. Otherwise, this needs the memory buffer log sink to be
     /// setup before the verification is executed. The returned string will need to be freed with
     /// the `free_string` function call to avoid leaking memory.
     ///
     /// Will return a NULL pointer if the logs for the verification can not be retrieved.
     fn pactffi_verifier_logs(handle: *const handle::VerifierHandle) -> *const c_char {
       let handle = as_ref!(handle);
-      extract_verifier_logs(&handle.provider_info().name)
+      match handle.logs() {
+        Some(logs) => CString::new(logs)?.into_raw() as *const c_char,
+        None => extract_verifier_logs(&handle.provider_info().name)
+      }
     } {
       std::ptr::null()
     }
@@ -917,16 +1081,72 @@ ffi_fn! {
     }
 }
 
+ffi_fn! {
+    /// Extracts the result for each interaction that was verified as a JSON document. This must
+    /// be called after `pactffi_verifier_execute`. The returned string will need to be freed with
+    /// the `free_string` function call to avoid leaking memory.
+    ///
+    /// The document is an array with an entry for each interaction, with the following attributes:
+    /// * `description` - Description of the verification that was performed
+    /// * `interactionDescription` - Description of the interaction from the Pact file
+    /// * `interactionId` - ID of the interaction (only set if the Pact was loaded from a Pact broker)
+    /// * `interactionKey` - Key of the interaction (only set for V4 Pacts)
+    /// * `pending` - If the interaction is pending
+    /// * `duration` - Duration of the verification in milliseconds
+    /// * `result` - One of `success`, `failure` or `error`
+    /// * `error` - The error message, if the result was an error
+    /// * `parts` - Result for each part of the interaction that was checked. Each part has a
+    ///   `part` attribute (one of `status`, `header`, `metadata` or `body`), a `name` attribute
+    ///   for headers and metadata, a `result` (`success` or `failure`) and any `mismatches`.
+    ///
+    /// Will return a NULL pointer if the handle is invalid.
+    fn pactffi_verifier_interaction_results(handle: *const handle::VerifierHandle) -> *const c_char {
+      let handle = as_ref!(handle);
+      let output = CString::new(handle.interaction_results_json()).unwrap();
+      output.into_raw() as *const c_char
+    } {
+      std::ptr::null()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-  use std::ffi::CString;
+  use std::ffi::{CStr, CString};
   use std::ptr::null;
 
   use expectest::prelude::*;
-  use libc::c_char;
+  use libc::{c_char, c_void};
+
+  use pact_verifier::verification_result::{InteractionPart, VerificationInteractionResult};
+  use serde_json::{json, Value};
 
   use crate::verifier::handle::VerifierHandle;
-  use crate::verifier::pactffi_verifier_output;
+  use crate::log::LevelFilter;
+  use crate::verifier::{
+    pactffi_verifier_capture_logs,
+    pactffi_verifier_interaction_results,
+    pactffi_verifier_logs,
+    pactffi_verifier_output
+  };
+
+  #[test]
+  fn pactffi_verifier_logs_returns_the_captured_logs_from_the_last_run() {
+    std::env::set_var("PACT_DO_NOT_TRACK", "true");
+    let mut handle = VerifierHandle::new_for_application("tests", "1.0.0");
+    handle.add_file_source("/does/not/exist/pact.json");
+
+    let result = pactffi_verifier_capture_logs(&mut handle, LevelFilter::Info);
+    expect!(result).to(be_equal_to(0));
+
+    handle.execute();
+    handle.execute();
+
+    let result = pactffi_verifier_logs(&handle);
+    expect!(result.is_null()).to(be_false());
+    let logs = unsafe { CString::from_raw(result as *mut c_char) }.into_string().unwrap();
+    expect!(logs.lines().filter(|line| line.contains("ERROR") && line.contains("Failed to load pact")).count())
+      .to(be_equal_to(1));
+  }
 
   #[test]
   fn pactffi_verifier_output_test() {
@@ -976,6 +1196,87 @@ We are tracking events anonymously to gather important usage statistics like Pac
 \n\nThere were 1 pact failures\n\n"));
   }
 
+  #[test]
+  fn pactffi_verifier_interaction_results_test() {
+    let mut handle = VerifierHandle::new_for_application("tests", "1.0.0");
+    handle.set_interaction_results(vec![
+      VerificationInteractionResult {
+        interaction_id: None,
+        interaction_key: None,
+        description: "Verifying a pact between test_consumer and test_provider".to_string(),
+        interaction_description: "test interaction".to_string(),
+        result: Ok(()),
+        pending: false,
+        duration: Default::default(),
+        parts: vec![InteractionPart::Status, InteractionPart::Body]
+      }
+    ]);
+
+    let result = pactffi_verifier_interaction_results(&handle);
+    let json = unsafe { CString::from_raw(result as *mut c_char) }.into_string().unwrap();
+    let json: Value = serde_json::from_str(json.as_str()).unwrap();
+
+    expect!(json).to(be_equal_to(json!([
+      {
+        "description": "Verifying a pact between test_consumer and test_provider",
+        "interactionDescription": "test interaction",
+        "interactionId": "",
+        "interactionKey": "",
+        "pending": false,
+        "duration": 0,
+        "result": "success",
+        "parts": [
+          { "part": "status", "result": "success" },
+          { "part": "body", "result": "success" }
+        ]
+      }
+    ])));
+  }
+
+  extern "C" fn collect_result(result_json: *const c_char, user_data: *mut c_void) {
+    let results = unsafe { &mut *(user_data as *mut Vec<String>) };
+    results.push(unsafe { CStr::from_ptr(result_json) }.to_string_lossy().to_string());
+  }
+
+  #[test]
+  fn verification_result_callback_test() {
+    let mut results: Vec<String> = vec![];
+    let callback = super::ffi_result_callback(collect_result,
+      &mut results as *mut Vec<String> as *mut c_void);
+
+    callback.call(&VerificationInteractionResult {
+      interaction_id: Some("1234".to_string()),
+      interaction_key: None,
+      description: "Verifying a pact between test_consumer and test_provider".to_string(),
+      interaction_description: "test interaction".to_string(),
+      result: Ok(()),
+      pending: false,
+      duration: Default::default(),
+      parts: vec![InteractionPart::Status]
+    });
+
+    expect!(results.len()).to(be_equal_to(1));
+    let json: Value = serde_json::from_str(results[0].as_str()).unwrap();
+    expect!(&json["interactionId"]).to(be_equal_to(&json!("1234")));
+    expect!(&json["interactionDescription"]).to(be_equal_to(&json!("test interaction")));
+    expect!(&json["result"]).to(be_equal_to(&json!("success")));
+  }
+
+  #[test]
+  fn pactffi_verifier_set_request_timeout_test() {
+    let mut handle = VerifierHandle::new_for_application("test", "0.0.0");
+    expect!(super::pactffi_verifier_set_request_timeout(&mut handle, 10000)).to(be_equal_to(0));
+    expect!(super::pactffi_verifier_set_request_timeout(&mut handle, 0)).to(be_equal_to(1));
+  }
+
+  #[test]
+  fn pactffi_verifier_set_strict_mode_test() {
+    let mut handle = VerifierHandle::new_for_application("test", "0.0.0");
+    expect!(super::pactffi_verifier_set_strict_mode(&mut handle, 1)).to(be_equal_to(0));
+    expect!(super::pactffi_verifier_set_strict_mode(&mut handle, 0)).to(be_equal_to(0));
+    expect!(super::pactffi_verifier_set_strict_mode(std::ptr::null_mut(), 1)).to(be_equal_to(1));
+  }
+
   #[test]
   fn pactffi_verifier_broker_source_with_selectors_test() {
     let mut handle = VerifierHandle::new_for_application("test", "0.0.0");
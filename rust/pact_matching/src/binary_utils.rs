@@ -187,6 +187,48 @@ pub fn match_octet_stream(
   }
 }
 
+/// Calculates a perceptual (difference) hash of the image data. The image is reduced to a 9x8
+/// grayscale thumbnail, and each bit of the hash is set if a pixel is brighter than the pixel to
+/// its right.
+#[cfg(feature = "image")]
+pub fn image_hash(data: &[u8]) -> anyhow::Result<u64> {
+  let image = image::load_from_memory(data)
+    .map_err(|err| anyhow!("Could not decode the image - {}", err))?;
+  let thumbnail = image.resize_exact(9, 8, image::imageops::FilterType::Triangle).to_luma8();
+  let mut hash = 0_u64;
+  for y in 0..8 {
+    for x in 0..8 {
+      hash <<= 1;
+      if thumbnail.get_pixel(x, y)[0] > thumbnail.get_pixel(x + 1, y)[0] {
+        hash |= 1;
+      }
+    }
+  }
+  Ok(hash)
+}
+
+/// Matches the actual image against the expected one. The difference is the fraction of bits that
+/// differ between the perceptual hashes of the two images, and must be within the threshold.
+#[cfg(feature = "image")]
+pub(crate) fn match_image_similarity(expected: &[u8], actual: &[u8], threshold: f64) -> anyhow::Result<()> {
+  let expected_hash = image_hash(expected)
+    .map_err(|err| anyhow!("Expected image is not valid: {}", err))?;
+  let actual_hash = image_hash(actual)
+    .map_err(|err| anyhow!("Actual image is not valid: {}", err))?;
+  let difference = (expected_hash ^ actual_hash).count_ones() as f64 / 64.0;
+  if difference <= threshold {
+    Ok(())
+  } else {
+    Err(anyhow!("Expected the image to be similar to the expected image within a threshold of {}, but the difference was {}",
+      threshold, difference))
+  }
+}
+
+#[cfg(not(feature = "image"))]
+pub(crate) fn match_image_similarity(_expected: &[u8], _actual: &[u8], _threshold: f64) -> anyhow::Result<()> {
+  Err(anyhow!("Image similarity matchers require the image feature to be enabled"))
+}
+
 fn display_bytes(bytes: &Bytes, max_bytes: usize) -> String {
   if bytes.len() <= max_bytes {
     bytes.iter().map(|b| format!("{:02x}", b)).collect()
@@ -247,6 +289,25 @@ impl MimeField {
       Ok(self.data.clone())
     }
   }
+
+  /// Content type of the field from the part headers
+  fn content_type(&self) -> Option<mime::Mime> {
+    self.headers.get("Content-Type")
+      .and_then(|value| value.to_str().ok())
+      .and_then(|value| value.parse::<mime::Mime>().ok())
+  }
+
+  /// Converts the field into a file without a filename, so the contents can be compared as a body
+  fn to_file(&self) -> MimeFile {
+    MimeFile {
+      index: self.index,
+      name: self.name.clone(),
+      content_type: self.content_type(),
+      filename: String::default(),
+      data: self.data.clone(),
+      headers: self.headers.clone()
+    }
+  }
 }
 
 #[derive(Debug)]
@@ -426,7 +487,11 @@ async fn match_mime_part(
 
   match (expected, actual) {
     (MimePart::Field(expected_field), MimePart::Field(actual_field)) => {
-      match_field(key, &expected_field, &actual_field, context)
+      if compare_field_as_body(key, expected_field, context) {
+        match_file_part(key, &expected_field.to_file(), &actual_field.to_file(), context).await
+      } else {
+        match_field(key, &expected_field, &actual_field, context)
+      }
     },
     (MimePart::File(expected_file), MimePart::File(actual_file)) => {
       match_file_part(key, expected_file, actual_file, context).await
@@ -450,6 +515,25 @@ async fn match_mime_part(
   }
 }
 
+/// Fields with a non-text content type (i.e. JSON) that have matching rules for the contents of
+/// the field (i.e. `$.metadata.id`) are compared as bodies, so that the matching rules are applied.
+/// All other fields are compared as strings.
+#[cfg(feature = "multipart")]
+fn compare_field_as_body(
+  key: &str,
+  expected: &MimeField,
+  context: &(dyn MatchingContext + Send + Sync)
+) -> bool {
+  let path = if key.is_empty() {
+    DocPath::root().join(expected.index.to_string())
+  } else {
+    DocPath::root().join(key)
+  };
+  expected.content_type().map(|ct| ct.type_() != mime::TEXT).unwrap_or(false) &&
+    context.matchers().rules.keys()
+      .any(|p| p.len() > path.len() && p.tokens().starts_with(path.tokens()))
+}
+
 #[cfg(feature = "multipart")]
 fn match_field(
   key: &str,
@@ -813,19 +897,58 @@ fn get_http_header_map(h: &Option<HashMap<String, Vec<String>>>) -> HeaderMap {
 mod tests {
   #[cfg(feature = "multipart")] use std::str;
 
-  #[cfg(feature = "multipart")] use bytes::{Bytes, BytesMut};
+  #[cfg(any(feature = "multipart", feature = "image"))] use bytes::Bytes;
+  #[cfg(feature = "multipart")] use bytes::BytesMut;
   #[allow(unused_imports)]  use expectest::prelude::*;
   #[allow(unused_imports)]  use hamcrest2::prelude::*;
   #[cfg(feature = "multipart")] use http::header::HeaderMap;
   #[allow(unused_imports)]  use maplit::*;
   #[cfg(feature = "multipart")] use pact_models::{matchingrules, matchingrules_list};
   #[cfg(feature = "multipart")] use pact_models::bodies::OptionalBody;
-  #[cfg(feature = "multipart")] use pact_models::matchingrules::MatchingRule;
+  #[cfg(any(feature = "multipart", feature = "image"))] use pact_models::matchingrules::MatchingRule;
   #[cfg(feature = "multipart")] use pact_models::path_exp::DocPath;
   #[cfg(feature = "multipart")] use pact_models::request::Request;
 
   #[cfg(feature = "multipart")] use crate::{CoreMatchingContext, DiffConfig, Mismatch};
   #[cfg(feature = "multipart")] use crate::binary_utils::{match_content_type, match_mime_multipart};
+  #[cfg(feature = "image")] use crate::binary_utils::match_image_similarity;
+  #[cfg(feature = "image")] use crate::matchers::Matches;
+
+  #[cfg(feature = "image")]
+  fn gradient_png(inverted: bool, noise: bool) -> Vec<u8> {
+    let image = image::GrayImage::from_fn(16, 16, |x, y| {
+      let value = if inverted { 255 - x * 16 } else { x * 16 };
+      let value = if noise && (x + y) % 3 == 0 { value.saturating_sub(3) } else { value };
+      image::Luma([value as u8])
+    });
+    let mut buffer = std::io::Cursor::new(vec![]);
+    image.write_to(&mut buffer, image::ImageFormat::Png).unwrap();
+    buffer.into_inner()
+  }
+
+  #[test]
+  #[cfg(feature = "image")]
+  fn match_image_similarity_with_a_similar_image() {
+    let expected = gradient_png(false, false);
+    let actual = gradient_png(false, true);
+    expect!(match_image_similarity(&expected, &actual, 0.1)).to(be_ok());
+    expect!(match_image_similarity(&expected, &expected, 0.0)).to(be_ok());
+
+    let matcher = MatchingRule::ImageSimilarity { threshold: 0.1 };
+    expect!(Bytes::from(expected).matches_with(&Bytes::from(actual), &matcher, false)).to(be_ok());
+  }
+
+  #[test]
+  #[cfg(feature = "image")]
+  fn match_image_similarity_with_a_different_image() {
+    let expected = gradient_png(false, false);
+    let actual = gradient_png(true, false);
+    let result = match_image_similarity(&expected, &actual, 0.1);
+    expect!(result.as_ref()).to(be_err());
+    expect!(result.unwrap_err().to_string()).to(be_equal_to(
+      "Expected the image to be similar to the expected image within a threshold of 0.1, but the difference was 1"));
+    expect!(match_image_similarity(&expected, b"not an image", 0.1)).to(be_err());
+  }
 
   #[cfg(feature = "multipart")]
   fn mismatch(m: &Mismatch) -> &str {
@@ -1034,6 +1157,105 @@ mod tests {
     expect!(result).to(be_ok());
   }
 
+  #[test]
+  #[cfg(feature = "multipart")]
+  fn match_mime_multipart_with_matching_rules_on_json_parts() {
+    let expected_body = Bytes::from("--1234\r\n\
+      Content-Type: application/json\r\n\
+      Content-Disposition: form-data; name=\"metadata\"\r\n\r\n\
+      {\"id\":100,\"name\":\"Baxter\"}\r\n\
+      --1234\r\n\
+      Content-Type: text/csv\r\n\
+      Content-Disposition: form-data; name=\"file\"; filename=\"008.csv\"\r\n\r\n\
+      1,2,3,4\r\n\
+      --1234--\r\n");
+    let expected = Request {
+      headers: Some(hashmap!{ "Content-Type".into() => vec![ "multipart/form-data; boundary=1234".into() ] }),
+      body: OptionalBody::Present(expected_body, None, None),
+      matching_rules: matchingrules! {
+        "body" => {
+          "$.metadata.id" => [ MatchingRule::Integer ],
+          "$.metadata.name" => [ MatchingRule::Type ]
+        }
+      },
+      ..Request::default()
+    };
+    // Parts are in a different order, and the JSON part has different values
+    let actual_body = Bytes::from("--4567\r\n\
+      Content-Type: text/csv\r\n\
+      Content-Disposition: form-data; name=\"file\"; filename=\"008.csv\"\r\n\r\n\
+      1,2,3,4\r\n\
+      --4567\r\n\
+      Content-Type: application/json\r\n\
+      Content-Disposition: form-data; name=\"metadata\"\r\n\r\n\
+      {\"id\":200,\"name\":\"Fred\"}\r\n\
+      --4567--\r\n");
+    let actual = Request {
+      headers: Some(hashmap!{ "Content-Type".into() => vec![ "multipart/form-data; boundary=4567".into() ] }),
+      body: OptionalBody::Present(actual_body, None, None),
+      ..Request::default()
+    };
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
+      &expected.matching_rules.rules_for_category("body").unwrap(), &hashmap!{});
+
+    let result = match_mime_multipart(&expected, &actual, &context);
+    expect!(result).to(be_ok());
+
+    let actual_body = Bytes::from("--4567\r\n\
+      Content-Type: application/json\r\n\
+      Content-Disposition: form-data; name=\"metadata\"\r\n\r\n\
+      {\"id\":\"200\",\"name\":\"Fred\"}\r\n\
+      --4567\r\n\
+      Content-Type: text/csv\r\n\
+      Content-Disposition: form-data; name=\"file\"; filename=\"008.csv\"\r\n\r\n\
+      1,2,3,4\r\n\
+      --4567--\r\n");
+    let actual = Request {
+      headers: Some(hashmap!{ "Content-Type".into() => vec![ "multipart/form-data; boundary=4567".into() ] }),
+      body: OptionalBody::Present(actual_body, None, None),
+      ..Request::default()
+    };
+
+    let result = match_mime_multipart(&expected, &actual, &context);
+    expect!(result).to(be_err());
+  }
+
+  #[test]
+  #[cfg(feature = "multipart")]
+  fn match_mime_multipart_compares_json_parts_without_matching_rules_as_fields() {
+    let expected_body = Bytes::from("--1234\r\n\
+      Content-Type: application/json\r\n\
+      Content-Disposition: form-data; name=\"metadata\"\r\n\r\n\
+      {\"id\":100}\r\n\
+      --1234--\r\n");
+    let expected = Request {
+      headers: Some(hashmap!{ "Content-Type".into() => vec![ "multipart/form-data; boundary=1234".into() ] }),
+      body: OptionalBody::Present(expected_body, None, None),
+      matching_rules: matchingrules! {
+        "body" => {
+          "$.metadata" => [ MatchingRule::Regex("^\\{.*\\}$".to_string()) ]
+        }
+      },
+      ..Request::default()
+    };
+    // The actual part is sent as text, and would not match the expected JSON as a body
+    let actual_body = Bytes::from("--4567\r\n\
+      Content-Type: text/plain\r\n\
+      Content-Disposition: form-data; name=\"metadata\"\r\n\r\n\
+      {\"id\":\"abc\"}\r\n\
+      --4567--\r\n");
+    let actual = Request {
+      headers: Some(hashmap!{ "Content-Type".into() => vec![ "multipart/form-data; boundary=4567".into() ] }),
+      body: OptionalBody::Present(actual_body, None, None),
+      ..Request::default()
+    };
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
+      &expected.matching_rules.rules_for_category("body").unwrap(), &hashmap!{});
+
+    let result = match_mime_multipart(&expected, &actual, &context);
+    expect!(result).to(be_ok());
+  }
+
   #[test]
   #[cfg(feature = "multipart")]
   fn match_mime_multipart_different_content_type() {
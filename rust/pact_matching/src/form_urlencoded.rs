@@ -1,5 +1,7 @@
 //! Functions for matching `multipart/form` bodies
 
+use std::collections::HashMap;
+
 use itertools::Itertools;
 use pact_models::bodies::OptionalBody;
 use pact_models::http_parts::HttpPart;
@@ -55,22 +57,23 @@ pub(crate) fn match_form_urlencoded(
         (Ok(_), Err(m2)) => Err(vec![m2]),
         (Ok(e), Ok(a)) => {
           debug!(expected=?e, actual=?a, "Comparing form post body");
-          let expected_params = super::group_by(e, |(k, _)| k.clone())
-            .iter()
-            .map(|(k, v)| (k.clone(), v.iter().map(|(_, v)| Some(v.clone())).collect_vec()))
-            .collect();
-          let actual_params = super::group_by(a, |(k, _)| k.clone())
-            .iter()
-            .map(|(k, v)| (k.clone(), v.iter().map(|(_, v)| Some(v.clone())).collect_vec()))
-            .collect();
+          let bracket_notation = context.matching_config().form_bracket_notation;
+          let (expected_params, expected_keys) = group_form_params(e, bracket_notation);
+          let (actual_params, actual_keys) = group_form_params(a, bracket_notation);
           let result: Vec<_> = match_query_maps(expected_params, actual_params, context)
             .values().flat_map(|m| m.iter().map(|mismatch| {
             if let Mismatch::QueryMismatch { parameter, expected, actual, mismatch } = mismatch {
+              let mismatch = mismatch.replace("query parameter", "form post parameter");
+              let original_key = expected_keys.get(parameter).or_else(|| actual_keys.get(parameter));
               Mismatch::BodyMismatch {
                 path: format!("$.{}", parameter),
                 expected: Some(expected.clone().into()),
                 actual: Some(actual.clone().into()),
-                mismatch: mismatch.replace("query parameter", "form post parameter")
+                mismatch: match original_key {
+                  Some(key) if key != parameter => mismatch.replace(
+                    format!("parameter '{}'", parameter).as_str(), format!("parameter '{}'", key).as_str()),
+                  _ => mismatch
+                }
               }
             } else {
               Mismatch::BodyMismatch {
@@ -92,6 +95,54 @@ pub(crate) fn match_form_urlencoded(
   }
 }
 
+/// Groups the form parameters by name. If bracket notation is enabled, parameters named `a[]` or
+/// `a[0]` are grouped with any named `a`, and values with an index are ordered by it. Also
+/// returns the names as they appear in the form, keyed by the grouped name.
+fn group_form_params(
+  params: Vec<(String, String)>,
+  bracket_notation: bool
+) -> (HashMap<String, Vec<Option<String>>>, HashMap<String, String>) {
+  let mut keys = HashMap::new();
+  let grouped = super::group_by(params.iter().map(|(key, value)| {
+    let (name, index) = if bracket_notation {
+      parse_bracket_notation(key)
+    } else {
+      (key.as_str(), None)
+    };
+    keys.entry(name.to_string()).or_insert_with(|| key.clone());
+    (name.to_string(), index, value.clone())
+  }).collect_vec(), |(name, _, _)| name.clone());
+  let params = grouped.into_iter()
+    .map(|(name, values)| {
+      let values = values.into_iter()
+        .sorted_by_key(|(_, index, _)| *index)
+        .map(|(_, _, value)| Some(value))
+        .collect();
+      (name, values)
+    })
+    .collect();
+  (params, keys)
+}
+
+/// Splits a parameter name using bracket notation (`a[]` or `a[0]`) into the name and the index
+fn parse_bracket_notation(key: &str) -> (&str, Option<usize>) {
+  if let Some(name) = key.strip_suffix("[]") {
+    if !name.is_empty() {
+      return (name, None);
+    }
+  }
+  if let Some(stripped) = key.strip_suffix(']') {
+    if let Some((name, index)) = stripped.rsplit_once('[') {
+      if let Ok(index) = index.parse::<usize>() {
+        if !name.is_empty() {
+          return (name, Some(index));
+        }
+      }
+    }
+  }
+  (key, None)
+}
+
 #[cfg(test)]
 mod tests {
   use expectest::prelude::*;
@@ -103,7 +154,7 @@ mod tests {
   use pact_models::request::Request;
   use pretty_assertions::assert_eq;
 
-  use crate::{CoreMatchingContext, DiffConfig, Mismatch};
+  use crate::{CoreMatchingContext, DiffConfig, MatchingConfig, Mismatch};
 
   use super::match_form_urlencoded;
 
@@ -414,4 +465,62 @@ mod tests {
     });
     assert_eq!(mismatches[0].description(), "$.a -> Expected 'hgjhghgh' to match '\\d+'");
   }
+
+  #[test_log::test]
+  fn match_form_with_bracket_notation_treats_the_encodings_as_the_same_parameter() {
+    let context = CoreMatchingContext::default()
+      .with_matching_config(MatchingConfig { form_bracket_notation: true, .. MatchingConfig::default() });
+    let expected = Request {
+      body: OptionalBody::Present("a=1&a=2".bytes().collect(), Some(FORM_URLENCODED.clone()), Some(ContentTypeHint::TEXT)),
+      .. Request::default()
+    };
+    for body in ["a=1&a=2", "a[]=1&a[]=2", "a[1]=2&a[0]=1"] {
+      let actual = Request {
+        body: OptionalBody::Present(body.bytes().collect(), Some(FORM_URLENCODED.clone()), Some(ContentTypeHint::TEXT)),
+        .. Request::default()
+      };
+      expect!(match_form_urlencoded(&expected, &actual, &context)).to(be_ok());
+      expect!(match_form_urlencoded(&actual, &expected, &context)).to(be_ok());
+    }
+  }
+
+  #[test_log::test]
+  fn match_form_without_bracket_notation_compares_the_parameter_names() {
+    let expected = Request {
+      body: OptionalBody::Present("a=1&a=2".bytes().collect(), Some(FORM_URLENCODED.clone()), Some(ContentTypeHint::TEXT)),
+      .. Request::default()
+    };
+    let actual = Request {
+      body: OptionalBody::Present("a[]=1&a[]=2".bytes().collect(), Some(FORM_URLENCODED.clone()), Some(ContentTypeHint::TEXT)),
+      .. Request::default()
+    };
+    expect!(match_form_urlencoded(&expected, &actual, &CoreMatchingContext::default())).to(be_err());
+  }
+
+  #[test_log::test]
+  fn match_form_with_bracket_notation_uses_the_original_parameter_name_in_mismatches() {
+    let context = CoreMatchingContext::default()
+      .with_matching_config(MatchingConfig { form_bracket_notation: true, .. MatchingConfig::default() });
+    let expected = Request {
+      body: OptionalBody::Present("a[]=1&a[]=2".bytes().collect(), Some(FORM_URLENCODED.clone()), Some(ContentTypeHint::TEXT)),
+      .. Request::default()
+    };
+    let actual = Request {
+      body: OptionalBody::Present("a[0]=1&a[1]=3".bytes().collect(), Some(FORM_URLENCODED.clone()), Some(ContentTypeHint::TEXT)),
+      .. Request::default()
+    };
+    let mismatches = match_form_urlencoded(&expected, &actual, &context).unwrap_err();
+    expect!(mismatches.iter().map(|m| m.description()).collect::<Vec<_>>()).to(be_equal_to(vec![
+      "$.a -> Expected form post parameter 'a[]' with value '2' but was '3'".to_string()
+    ]));
+  }
+
+  #[test]
+  fn parse_bracket_notation_test() {
+    expect!(super::parse_bracket_notation("a")).to(be_equal_to(("a", None)));
+    expect!(super::parse_bracket_notation("a[]")).to(be_equal_to(("a", None)));
+    expect!(super::parse_bracket_notation("a[2]")).to(be_equal_to(("a", Some(2))));
+    expect!(super::parse_bracket_notation("a[b]")).to(be_equal_to(("a[b]", None)));
+    expect!(super::parse_bracket_notation("[]")).to(be_equal_to(("[]", None)));
+  }
 }
@@ -3,14 +3,21 @@
 use std::collections::HashMap;
 use std::iter::FromIterator;
 
+use anyhow::anyhow;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
 use itertools::Itertools;
 use maplit::hashmap;
+use pact_models::bodies::OptionalBody;
 use pact_models::headers::PARAMETERISED_HEADERS;
-use pact_models::matchingrules::MatchingRule;
-use pact_models::path_exp::DocPath;
+use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory};
+use pact_models::path_exp::{DocPath, PathToken};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256, Sha512};
 use tracing::{instrument, debug};
 
-use crate::{matchers, MatchingContext, Mismatch, CommonMismatch};
+use crate::{matchers, CoreMatchingContext, DiffConfig, MatchingContext, Mismatch, CommonMismatch};
+use crate::json::compare_json;
 use crate::matchers::Matches;
 use crate::matchingrules::compare_lists_with_matchingrules;
 
@@ -83,8 +90,12 @@ pub(crate) fn match_header_value(
 ) -> Result<(), Vec<CommonMismatch>> {
   let path = DocPath::root().join(key.to_lowercase());
   let indexed_path = path.join(index.to_string());
-  let expected = expected.trim();
-  let actual = actual.trim();
+  let strict = context.matching_config().strict_header_whitespace;
+  let (expected, actual) = if strict {
+    (expected, actual)
+  } else {
+    (expected.trim(), actual.trim())
+  };
 
   let matcher_result = if context.matcher_is_defined(&path) {
     let result = matchers::match_values(&path, &context.select_best_matcher(&path), expected, actual);
@@ -100,7 +111,7 @@ pub(crate) fn match_header_value(
     } else {
       result.map_err(|err| err.iter().map(|e| format!("{} for value at index {}", e, index)).collect())
     }
-  } else if PARAMETERISED_HEADERS.contains(&key.to_lowercase().as_str()) {
+  } else if !strict && PARAMETERISED_HEADERS.contains(&key.to_lowercase().as_str()) {
     match_parameter_header(expected, actual, key, "header", index, single_value)
   } else {
     Matches::matches_with(&expected.to_string(), &actual.to_string(), &MatchingRule::Equality, false)
@@ -119,12 +130,227 @@ pub(crate) fn match_header_value(
         path: key.to_string(),
         expected: expected.to_string(),
         actual: actual.to_string(),
-        description: format!("Mismatch with header '{}': {}", key, message)
+        description: if strict {
+          format!("Mismatch with header '{}' (strict whitespace): {}", key, message)
+        } else {
+          format!("Mismatch with header '{}': {}", key, message)
+        }
       }
     }).collect()
   })
 }
 
+/// If the header has been marked as having a JSON value (with a JSON value matching rule),
+/// returns the matching rules to apply to the JSON value. These are the rules defined for paths
+/// under the header name, relative to the root of the JSON value.
+fn json_header_matchers(key: &str, context: &dyn MatchingContext) -> Option<MatchingRuleCategory> {
+  let path = DocPath::root().join(key.to_lowercase());
+  if context.select_best_matcher(&path).rules.contains(&MatchingRule::JsonValue) {
+    Some(header_value_matchers(key, context))
+  } else {
+    None
+  }
+}
+
+/// If the header has been marked as a cookie header (with a cookie matching rule), returns the
+/// matching rules to apply to the parsed cookies. These are the rules defined for paths under the
+/// header name, relative to the root of the parsed cookies.
+fn cookie_header_matchers(key: &str, context: &dyn MatchingContext) -> Option<MatchingRuleCategory> {
+  let path = DocPath::root().join(key.to_lowercase());
+  if context.select_best_matcher(&path).rules.contains(&MatchingRule::Cookie) {
+    Some(header_value_matchers(key, context))
+  } else {
+    None
+  }
+}
+
+/// If the header has been marked as having values that can be in any order (with an unordered
+/// values matching rule), returns the context to use to compare the individual values, which has
+/// the marker removed.
+fn unordered_header_context(key: &str, context: &dyn MatchingContext) -> Option<Box<dyn MatchingContext + Send + Sync>> {
+  let path = DocPath::root().join(key.to_lowercase());
+  if context.select_best_matcher(&path).rules.contains(&MatchingRule::UnorderedValues) {
+    let mut matchers = context.matchers().clone();
+    if let Some(rule_list) = matchers.rules.get_mut(&path) {
+      rule_list.rules.retain(|rule| *rule != MatchingRule::UnorderedValues);
+      if rule_list.rules.is_empty() {
+        matchers.rules.remove(&path);
+      }
+    }
+    Some(context.clone_with(&matchers))
+  } else {
+    None
+  }
+}
+
+/// Compares the header values ignoring their order. Each expected value must match a different
+/// actual value, and there must not be any additional actual values.
+fn match_unordered_header_values(
+  key: &str,
+  expected: &[String],
+  actual: &[String],
+  context: &dyn MatchingContext
+) -> Result<(), Vec<CommonMismatch>> {
+  let mut unmatched = actual.iter().collect_vec();
+  let mut mismatches = vec![];
+  for (index, expected_value) in expected.iter().enumerate() {
+    let found = unmatched.iter().position(|actual_value| {
+      match_header_value(key, index, expected_value, actual_value, context, false).is_ok()
+    });
+    match found {
+      Some(position) => {
+        unmatched.remove(position);
+      }
+      None => mismatches.push(CommonMismatch {
+        path: key.to_string(),
+        expected: expected_value.clone(),
+        actual: actual.join(", "),
+        description: format!("Mismatch with header '{}': Expected value '{}' but it was not found in the actual values '{}' (ignoring order)",
+          key, expected_value, actual.join(", "))
+      })
+    }
+  }
+  for actual_value in unmatched {
+    mismatches.push(CommonMismatch {
+      path: key.to_string(),
+      expected: expected.join(", "),
+      actual: actual_value.clone(),
+      description: format!("Mismatch with header '{}': Unexpected value '{}' received (ignoring order)",
+        key, actual_value)
+    });
+  }
+
+  if mismatches.is_empty() {
+    Ok(())
+  } else {
+    Err(mismatches)
+  }
+}
+
+/// Returns the matching rules defined for paths under the header name, relative to the root of
+/// the header value. The rules that mark how the header value is parsed are not included.
+fn header_value_matchers(key: &str, context: &dyn MatchingContext) -> MatchingRuleCategory {
+  let mut value_matchers = MatchingRuleCategory::empty("body");
+  for (rule_path, rule_list) in &context.matchers().rules {
+    if rule_path.first_field().map(|field| field.to_lowercase()) == Some(key.to_lowercase()) {
+      let value_path = rule_path.tokens().iter()
+        .skip_while(|token| !matches!(token, PathToken::Field(_)))
+        .skip(1)
+        .fold(DocPath::root(), |mut value_path, token| {
+          value_path.push(token.clone());
+          value_path
+        });
+      for rule in &rule_list.rules {
+        if !matches!(rule, MatchingRule::JsonValue | MatchingRule::Cookie) {
+          value_matchers.add_rule(value_path.clone(), rule.clone(), rule_list.rule_logic);
+        }
+      }
+    }
+  }
+  value_matchers
+}
+
+/// Parses the values of a `Cookie` request header into a JSON object of the cookie names mapped
+/// to their values.
+pub fn parse_cookie_header(values: &[String]) -> Value {
+  let cookies = values.iter()
+    .flat_map(|value| value.split(';'))
+    .map(|cookie| cookie.trim())
+    .filter(|cookie| !cookie.is_empty())
+    .map(|cookie| match cookie.split_once('=') {
+      Some((name, value)) => (name.trim().to_string(), Value::String(value.trim().to_string())),
+      None => (cookie.to_string(), Value::String(String::new()))
+    })
+    .collect();
+  Value::Object(cookies)
+}
+
+/// Parses the values of `Set-Cookie` response headers (one cookie per value) into a JSON object
+/// of the cookie names mapped to the attributes of the cookie. The value of the cookie is stored
+/// in the `value` attribute. The `secure` and `httpOnly` flags are only added if they are present
+/// (with a value of `true`), so an expected cookie without the flags matches one that has them.
+pub fn parse_set_cookie_headers(values: &[String]) -> Value {
+  let cookies = values.iter()
+    .filter_map(|value| {
+      let mut parts = value.split(';').map(|part| part.trim());
+      let (name, cookie_value) = parts.next()?.split_once('=')?;
+      let mut attributes = Map::new();
+      attributes.insert("value".to_string(), Value::String(cookie_value.trim().to_string()));
+      for attribute in parts.filter(|part| !part.is_empty()) {
+        let (attribute_name, attribute_value) = match attribute.split_once('=') {
+          Some((attribute_name, attribute_value)) =>
+            (attribute_name.trim(), Value::String(attribute_value.trim().to_string())),
+          None => (attribute, Value::Bool(true))
+        };
+        let attribute_name = match attribute_name.to_lowercase().as_str() {
+          "max-age" => "maxAge".to_string(),
+          "samesite" => "sameSite".to_string(),
+          "httponly" => "httpOnly".to_string(),
+          name => name.to_string()
+        };
+        attributes.insert(attribute_name, attribute_value);
+      }
+      Some((name.trim().to_string(), Value::Object(attributes)))
+    })
+    .collect();
+  Value::Object(cookies)
+}
+
+/// Matches cookie header values by parsing them into structured cookies and comparing them the
+/// same way as for bodies. Additional cookies and attributes in the actual values are allowed.
+fn match_cookie_header_value(
+  key: &str,
+  expected: &[String],
+  actual: &[String],
+  matchers: &MatchingRuleCategory,
+  context: &dyn MatchingContext
+) -> Result<(), Vec<CommonMismatch>> {
+  let (expected_cookies, actual_cookies) = if key.eq_ignore_ascii_case("set-cookie") {
+    (parse_set_cookie_headers(expected), parse_set_cookie_headers(actual))
+  } else {
+    (parse_cookie_header(expected), parse_cookie_header(actual))
+  };
+
+  let cookie_context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys, matchers,
+    context.plugin_configuration());
+  compare_json(&DocPath::root(), &expected_cookies, &actual_cookies, &cookie_context)
+    .map_err(|mismatches| mismatches.iter().map(|m| CommonMismatch {
+      path: key.to_string(),
+      expected: m.expected.clone(),
+      actual: m.actual.clone(),
+      description: format!("Mismatch with header '{}': {} -> {}", key, m.path, m.description)
+    }).collect())
+}
+
+/// Matches a header value as JSON, applying the matching rules the same way as for bodies
+fn match_json_header_value(
+  key: &str,
+  expected: &str,
+  actual: &str,
+  matchers: &MatchingRuleCategory,
+  context: &dyn MatchingContext
+) -> Result<(), Vec<CommonMismatch>> {
+  let mismatch = |description: String| vec![ CommonMismatch {
+    path: key.to_string(),
+    expected: expected.to_string(),
+    actual: actual.to_string(),
+    description
+  } ];
+  let expected_json = serde_json::from_str(expected)
+    .map_err(|err| mismatch(format!("Mismatch with header '{}': Failed to parse the expected value as JSON - {}", key, err)))?;
+  let actual_json = serde_json::from_str(actual)
+    .map_err(|err| mismatch(format!("Mismatch with header '{}': Failed to parse the actual value '{}' as JSON - {}", key, actual, err)))?;
+
+  let json_context = CoreMatchingContext::new(context.config(), matchers, context.plugin_configuration());
+  compare_json(&DocPath::root(), &expected_json, &actual_json, &json_context)
+    .map_err(|mismatches| mismatches.iter().map(|m| CommonMismatch {
+      path: key.to_string(),
+      expected: m.expected.clone(),
+      actual: m.actual.clone(),
+      description: format!("Mismatch with header '{}': {} -> {}", key, m.path, m.description)
+    }).collect())
+}
+
 fn find_entry<T>(map: &HashMap<String, T>, key: &str) -> Option<(String, T)> where T: Clone {
   match map.keys().find(|k| k.to_lowercase() == key.to_lowercase() ) {
     Some(k) => map.get(k).map(|v| (key.to_string(), v.clone()) ),
@@ -148,8 +374,27 @@ fn match_header_maps(
       } else {
         let mut mismatches = vec![];
 
-        // Special case when the headers only have 1 value to improve messaging
-        if value.len() == 1 && actual_values.len() == 1 {
+        if let Some(cookie_matchers) = cookie_header_matchers(key, context) {
+          let comparison_result = match_cookie_header_value(key, value, &actual_values,
+            &cookie_matchers, context)
+            .err()
+            .unwrap_or_default();
+          mismatches.extend(comparison_result.iter().cloned());
+        } else if let Some(json_matchers) = json_header_matchers(key, context) {
+          // JSON values may have been split on the commas, so need to be joined before parsing
+          let comparison_result = match_json_header_value(key, &value.join(","),
+            &actual_values.join(","), &json_matchers, context)
+            .err()
+            .unwrap_or_default();
+          mismatches.extend(comparison_result.iter().cloned());
+        } else if let Some(values_context) = unordered_header_context(key, context) {
+          let comparison_result = match_unordered_header_values(key, value, &actual_values,
+            values_context.as_ref())
+            .err()
+            .unwrap_or_default();
+          mismatches.extend(comparison_result.iter().cloned());
+        } else if value.len() == 1 && actual_values.len() == 1 {
+          // Special case when the headers only have 1 value to improve messaging
           let comparison_result = match_header_value(key, 0, value.first().unwrap(),
             actual_values.first().unwrap(), context, true)
             .err()
@@ -219,17 +464,230 @@ pub fn match_headers(
   }
 }
 
+/// Calculates the hash of the body using the given algorithm (sha256, sha512 or md5), Base64
+/// encoded.
+pub fn body_hash(algorithm: &str, body: &[u8]) -> anyhow::Result<String> {
+  let digest = match algorithm.to_lowercase().as_str() {
+    "sha256" | "sha-256" => Sha256::digest(body).to_vec(),
+    "sha512" | "sha-512" => Sha512::digest(body).to_vec(),
+    "md5" => md5::compute(body).0.to_vec(),
+    _ => return Err(anyhow!("'{}' is not a supported hash algorithm", algorithm))
+  };
+  Ok(BASE64.encode(digest))
+}
+
+/// Matches any headers that have a body hash matching rule against the hash of the actual body.
+/// The header must be a strong ETag (i.e. not prefixed with `W/`), with the value being the
+/// Base64 encoded hash of the body. Missing headers are not checked, as they will already be
+/// reported as a header mismatch.
+pub(crate) fn match_body_hash_headers(
+  rules: &MatchingRuleCategory,
+  headers: &Option<HashMap<String, Vec<String>>>,
+  body: &OptionalBody
+) -> Vec<Mismatch> {
+  let mut mismatches = vec![];
+  for (path, rule_list) in rules.rules.iter().sorted_by(|(a, _), (b, _)| Ord::cmp(&a.to_string(), &b.to_string())) {
+    let key = match path.first_field() {
+      Some(key) => key,
+      None => continue
+    };
+    let actual = match headers.as_ref().and_then(|headers| find_entry(headers, key)) {
+      Some((_, values)) => values.join(", "),
+      None => continue
+    };
+    for rule in &rule_list.rules {
+      if let MatchingRule::BodyHash(algorithm) = rule {
+        let body = body.value().unwrap_or_default();
+        let expected = body_hash(algorithm, &body).map(|hash| format!("\"{}\"", hash));
+        let value = actual.trim();
+        let result = match &expected {
+          Ok(expected) => if value.starts_with("W/") {
+            Err(format!("Expected a strong ETag of {} but was a weak ETag '{}'", expected, value))
+          } else if value.trim_matches('"') != expected.trim_matches('"') {
+            Err(format!("Expected '{}' to be the {} hash of the body {}", value, algorithm, expected))
+          } else {
+            Ok(())
+          },
+          Err(err) => Err(err.to_string())
+        };
+        if let Err(err) = result {
+          mismatches.push(Mismatch::HeaderMismatch {
+            key: key.to_string(),
+            expected: expected.unwrap_or_default(),
+            actual: actual.clone(),
+            mismatch: format!("Mismatch with header '{}': {}", key, err)
+          });
+        }
+      }
+    }
+  }
+  mismatches
+}
+
+/// Applies a header correlation transform to the value. Supported transforms are `add:n`,
+/// `subtract:n` and `multiply:n` for numeric values, and `prefix:s`, `suffix:s`, `uppercase`
+/// and `lowercase` for string values.
+pub fn apply_header_transform(value: &str, transform: &str) -> anyhow::Result<String> {
+  let (operation, argument) = transform.split_once(':').unwrap_or((transform, ""));
+  let operation = operation.trim();
+  match operation {
+    "add" | "subtract" | "multiply" => {
+      let value = value.trim();
+      let argument = argument.trim();
+      if let (Ok(a), Ok(b)) = (value.parse::<i64>(), argument.parse::<i64>()) {
+        let result = match operation {
+          "add" => a.checked_add(b),
+          "subtract" => a.checked_sub(b),
+          _ => a.checked_mul(b)
+        };
+        result.map(|result| result.to_string())
+          .ok_or_else(|| anyhow!("Applying '{}' to '{}' overflows", transform, value))
+      } else {
+        let a = value.parse::<f64>()
+          .map_err(|_| anyhow!("Can not apply '{}' to '{}' as it is not a number", transform, value))?;
+        let b = argument.parse::<f64>()
+          .map_err(|_| anyhow!("'{}' is not a valid transform, '{}' is not a number", transform, argument))?;
+        let result = match operation {
+          "add" => a + b,
+          "subtract" => a - b,
+          _ => a * b
+        };
+        Ok(result.to_string())
+      }
+    }
+    "prefix" => Ok(format!("{}{}", argument, value)),
+    "suffix" => Ok(format!("{}{}", value, argument)),
+    "uppercase" => Ok(value.to_uppercase()),
+    "lowercase" => Ok(value.to_lowercase()),
+    _ => Err(anyhow!("'{}' is not a valid header transform", transform))
+  }
+}
+
+/// Matches any response headers that have a correlation matching rule against the value of the
+/// request header they correlate with, after applying any transform. Missing response headers
+/// are not checked, as they will already be reported as a header mismatch.
+pub(crate) fn match_header_correlations(
+  rules: &MatchingRuleCategory,
+  request_headers: &Option<HashMap<String, Vec<String>>>,
+  response_headers: &Option<HashMap<String, Vec<String>>>
+) -> Vec<Mismatch> {
+  let mut mismatches = vec![];
+  for (path, rule_list) in rules.rules.iter().sorted_by(|(a, _), (b, _)| Ord::cmp(&a.to_string(), &b.to_string())) {
+    let key = match path.first_field() {
+      Some(key) => key,
+      None => continue
+    };
+    let actual = match response_headers.as_ref().and_then(|headers| find_entry(headers, key)) {
+      Some((_, values)) => values.join(", "),
+      None => continue
+    };
+    for rule in &rule_list.rules {
+      if let MatchingRule::Correlation(request_header, transform) = rule {
+        let request_value = request_headers.as_ref()
+          .and_then(|headers| find_entry(headers, request_header))
+          .map(|(_, values)| values.join(", "));
+        let result = match request_value {
+          Some(request_value) => {
+            let expected = match transform {
+              Some(transform) => apply_header_transform(request_value.trim(), transform),
+              None => Ok(request_value.trim().to_string())
+            };
+            match expected {
+              Ok(expected) => if actual.trim() == expected {
+                Ok(())
+              } else {
+                let description = match transform {
+                  Some(transform) => format!("request header '{}' with '{}' applied", request_header, transform),
+                  None => format!("request header '{}'", request_header)
+                };
+                Err((expected.clone(), format!("Expected '{}' to be equal to '{}' (from {})",
+                  actual.trim(), expected, description)))
+              },
+              Err(err) => Err((String::default(), err.to_string()))
+            }
+          }
+          None => Err((String::default(), format!("Expected a request header '{}' to correlate with but was missing",
+            request_header)))
+        };
+        if let Err((expected, message)) = result {
+          mismatches.push(Mismatch::HeaderMismatch {
+            key: key.to_string(),
+            expected,
+            actual: actual.clone(),
+            mismatch: format!("Mismatch with header '{}': {}", key, message)
+          });
+        }
+      }
+    }
+  }
+  mismatches
+}
+
+/// Parses a `Retry-After` header value, returning the delay in seconds. The value can either be a
+/// number of seconds, or an HTTP date, in which case the delay is the number of seconds from now
+/// until that date (which will be negative if the date is in the past).
+pub fn parse_retry_after(value: &str) -> anyhow::Result<i64> {
+  let value = value.trim();
+  if !value.is_empty() && value.chars().all(|ch| ch.is_ascii_digit()) {
+    value.parse::<i64>()
+      .map_err(|err| anyhow!("'{}' is not a valid number of seconds - {}", value, err))
+  } else {
+    parse_retry_after_date(value)
+  }
+}
+
+#[cfg(feature = "datetime")]
+fn parse_retry_after_date(value: &str) -> anyhow::Result<i64> {
+  let date = chrono::DateTime::parse_from_rfc2822(value)
+    .map_err(|err| anyhow!("'{}' is not a valid number of seconds or HTTP date - {}", value, err))?;
+  Ok(date.signed_duration_since(chrono::Utc::now()).num_seconds())
+}
+
+#[cfg(not(feature = "datetime"))]
+fn parse_retry_after_date(value: &str) -> anyhow::Result<i64> {
+  Err(anyhow!("'{}' is not a valid number of seconds (HTTP dates require the datetime feature to be enabled)", value))
+}
+
+/// Matches that the value is a valid `Retry-After` header value, with the delay within the
+/// optional minimum and maximum number of seconds
+pub(crate) fn match_retry_after(value: &str, min: Option<usize>, max: Option<usize>) -> anyhow::Result<()> {
+  let delay = parse_retry_after(value)?;
+  if let Some(min) = min {
+    if delay < min as i64 {
+      return Err(anyhow!("Expected a Retry-After of at least {} second(s), but '{}' was {} second(s)", min, value, delay));
+    }
+  }
+  if let Some(max) = max {
+    if delay > max as i64 {
+      return Err(anyhow!("Expected a Retry-After of at most {} second(s), but '{}' was {} second(s)", max, value, delay));
+    }
+  }
+  Ok(())
+}
+
 #[cfg(test)]
 mod tests {
   use expectest::prelude::*;
+  use itertools::Itertools;
   use maplit::*;
+  use pact_models::bodies::OptionalBody;
   use pact_models::matchingrules;
-  use pact_models::matchingrules::MatchingRule;
+  use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory};
   use pact_models::matchingrules::expressions::{MatchingRuleDefinition, ValueType};
   use pretty_assertions::assert_eq;
-
-  use crate::{CoreMatchingContext, DiffConfig, HeaderMatchingContext, Mismatch, CommonMismatch};
-  use crate::headers::{match_header_value, match_headers, parse_charset_parameters};
+  use serde_json::json;
+
+  use crate::{CoreMatchingContext, DiffConfig, HeaderMatchingContext, MatchingConfig, Mismatch, CommonMismatch};
+  use crate::headers::{
+    apply_header_transform,
+    match_body_hash_headers,
+    match_header_value,
+    match_headers,
+    parse_charset_parameters,
+    parse_cookie_header,
+    parse_retry_after,
+    parse_set_cookie_headers
+  };
 
   #[test]
   fn matching_headers_be_true_when_headers_are_equal() {
@@ -436,6 +894,257 @@ mod tests {
     expect!(mismatches).to(be_ok());
   }
 
+  #[test]
+  fn match_header_value_with_strict_whitespace() {
+    let context = HeaderMatchingContext::default()
+      .with_matching_config(MatchingConfig { strict_header_whitespace: true, .. MatchingConfig::default() });
+
+    expect!(match_header_value("x-test", 0, "a value", "a value", &context, true)).to(be_ok());
+    let mismatches = match_header_value("x-test", 0, "a value", "a value ", &context, true);
+    expect!(mismatches.unwrap_err()[0].description.as_str()).to(be_equal_to(
+      "Mismatch with header 'x-test' (strict whitespace): Expected 'a value ' to be equal to 'a value'"));
+
+    expect!(match_header_value("content-type", 0, "application/json; charset=UTF-8",
+      "application/json;charset=UTF-8", &HeaderMatchingContext::default(), true)).to(be_ok());
+    expect!(match_header_value("content-type", 0, "application/json; charset=UTF-8",
+      "application/json;charset=UTF-8", &context, true)).to(be_err());
+  }
+
+  #[test]
+  fn matching_headers_with_json_values() {
+    let context = HeaderMatchingContext::new(&CoreMatchingContext::new(
+      DiffConfig::AllowUnexpectedKeys,
+      &matchingrules! {
+        "header" => {
+          "$['X-Data']" => [ MatchingRule::JsonValue ],
+          "$['X-Data'].id" => [ MatchingRule::Integer ],
+          "$['X-Data'].tags" => [ MatchingRule::MinType(1) ]
+        }
+      }.rules_for_category("header").unwrap_or_default(), &hashmap!{}
+    ));
+    let expected = hashmap! {
+      "X-Data".to_string() => vec![r#"{"id": 1, "tags": ["a"]}"#.to_string()]
+    };
+
+    let actual = hashmap! {
+      "x-data".to_string() => vec![r#"{"id": 100"#.to_string(), r#""tags": ["b""#.to_string(), r#""c"]}"#.to_string()]
+    };
+    let result = match_headers(Some(expected.clone()), Some(actual), &context);
+    expect!(result.values().flatten()).to(be_empty());
+
+    let actual = hashmap! {
+      "x-data".to_string() => vec![r#"{"id": "100", "tags": []}"#.to_string()]
+    };
+    let result = match_headers(Some(expected.clone()), Some(actual), &context);
+    let mismatches = result.values().flatten().map(|m| m.description()).sorted().collect::<Vec<_>>();
+    expect!(mismatches.len()).to(be_equal_to(2));
+    expect!(mismatches[0].starts_with("Mismatch with header 'X-Data': $.id -> ")).to(be_true());
+    expect!(mismatches[1].starts_with("Mismatch with header 'X-Data': $.tags -> ")).to(be_true());
+
+    let actual = hashmap! {
+      "x-data".to_string() => vec!["not JSON".to_string()]
+    };
+    let result = match_headers(Some(expected.clone()), Some(actual), &context);
+    let mismatches = result.values().flatten().map(|m| m.description()).collect::<Vec<_>>();
+    expect!(mismatches.len()).to(be_equal_to(1));
+    expect!(mismatches[0].starts_with("Mismatch with header 'X-Data': Failed to parse the actual value 'not JSON' as JSON")).to(be_true());
+    let context = HeaderMatchingContext::new(&CoreMatchingContext::new(
+      DiffConfig::AllowUnexpectedKeys,
+      &matchingrules! {
+        "header" => {
+          "$['X-Data']" => [ MatchingRule::ContentType("application/json".to_string()) ]
+        }
+      }.rules_for_category("header").unwrap_or_default(), &hashmap!{}
+    ));
+    let actual = hashmap! {
+      "x-data".to_string() => vec!["not JSON".to_string()]
+    };
+    let result = match_headers(Some(expected), Some(actual), &context);
+    expect!(result.values().flatten().any(|m| m.description().contains("as JSON"))).to(be_false());
+  }
+
+  #[test]
+  fn parse_cookie_header_test() {
+    expect!(parse_cookie_header(&[])).to(be_equal_to(json!({})));
+    expect!(parse_cookie_header(&["session=abc; theme=dark".to_string(), "flag".to_string()])).to(be_equal_to(json!({
+      "session": "abc",
+      "theme": "dark",
+      "flag": ""
+    })));
+  }
+
+  #[test]
+  fn parse_set_cookie_headers_test() {
+    let values = vec![
+      "session=abc; Path=/; Domain=example.com; Max-Age=3600; SameSite=Lax; Secure; HttpOnly".to_string(),
+      "theme=dark; Expires=Wed, 21 Oct 2026 07:28:00 GMT".to_string(),
+      "invalid".to_string()
+    ];
+    expect!(parse_set_cookie_headers(&values)).to(be_equal_to(json!({
+      "session": {
+        "value": "abc",
+        "path": "/",
+        "domain": "example.com",
+        "maxAge": "3600",
+        "sameSite": "Lax",
+        "secure": true,
+        "httpOnly": true
+      },
+      "theme": {
+        "value": "dark",
+        "expires": "Wed, 21 Oct 2026 07:28:00 GMT"
+      }
+    })));
+  }
+
+  #[test]
+  fn match_cookie_request_header_with_matchers() {
+    let context = HeaderMatchingContext::new(&CoreMatchingContext::new(
+      DiffConfig::NoUnexpectedKeys,
+      &matchingrules! {
+        "header" => {
+          "$.Cookie" => [ MatchingRule::Cookie ],
+          "$.Cookie.session" => [ MatchingRule::Regex("^[a-f0-9]+$".to_string()) ]
+        }
+      }.rules_for_category("header").unwrap_or_default(), &hashmap!{}
+    ));
+    let expected = hashmap! {
+      "Cookie".to_string() => vec!["session=abc123; theme=dark".to_string()]
+    };
+
+    let actual = hashmap! {
+      "cookie".to_string() => vec!["tracking=xyz; theme=dark; session=fff000".to_string()]
+    };
+    let result = match_headers(Some(expected.clone()), Some(actual), &context);
+    expect!(result.values().flatten()).to(be_empty());
+
+    let actual = hashmap! {
+      "cookie".to_string() => vec!["session=XYZ".to_string()]
+    };
+    let result = match_headers(Some(expected), Some(actual), &context);
+    let mismatches = result.values().flatten().map(|m| m.description()).sorted().collect::<Vec<_>>();
+    expect!(mismatches.len()).to(be_equal_to(2));
+    expect!(mismatches[0].starts_with("Mismatch with header 'Cookie': $ -> ")).to(be_true());
+    expect!(mismatches[1].starts_with("Mismatch with header 'Cookie': $.session -> ")).to(be_true());
+  }
+
+  #[test]
+  fn match_set_cookie_response_header_with_matchers() {
+    let context = HeaderMatchingContext::new(&CoreMatchingContext::new(
+      DiffConfig::NoUnexpectedKeys,
+      &matchingrules! {
+        "header" => {
+          "$['Set-Cookie']" => [ MatchingRule::Cookie ],
+          "$['Set-Cookie'].session.value" => [ MatchingRule::Type ],
+          "$['Set-Cookie'].session.maxAge" => [ MatchingRule::Regex("^\\d+$".to_string()) ]
+        }
+      }.rules_for_category("header").unwrap_or_default(), &hashmap!{}
+    ));
+    let expected = hashmap! {
+      "Set-Cookie".to_string() => vec!["session=abc; Path=/; Max-Age=3600; Secure; HttpOnly".to_string()]
+    };
+
+    let actual = hashmap! {
+      "set-cookie".to_string() => vec![
+        "session=other; Path=/; Max-Age=60; Secure; HttpOnly; SameSite=Strict".to_string(),
+        "theme=dark".to_string()
+      ]
+    };
+    let result = match_headers(Some(expected.clone()), Some(actual), &context);
+    expect!(result.values().flatten()).to(be_empty());
+
+    let actual = hashmap! {
+      "set-cookie".to_string() => vec!["session=other; Path=/api; Max-Age=60".to_string()]
+    };
+    let result = match_headers(Some(expected), Some(actual), &context);
+    let mismatches = result.values().flatten().map(|m| m.description()).sorted().collect::<Vec<_>>();
+    expect!(mismatches.len()).to(be_equal_to(2));
+    expect!(mismatches[0].as_str()).to(be_equal_to("Mismatch with header 'Set-Cookie': $.session -> Actual map is missing the following keys: httpOnly, secure"));
+    expect!(mismatches[1].starts_with("Mismatch with header 'Set-Cookie': $.session.path -> ")).to(be_true());
+  }
+
+  #[test]
+  fn match_set_cookie_response_header_only_compares_the_expected_flags() {
+    let context = HeaderMatchingContext::new(&CoreMatchingContext::new(
+      DiffConfig::NoUnexpectedKeys,
+      &matchingrules! {
+        "header" => {
+          "$['Set-Cookie']" => [ MatchingRule::Cookie ],
+          "$['Set-Cookie'].session.value" => [ MatchingRule::Type ]
+        }
+      }.rules_for_category("header").unwrap_or_default(), &hashmap!{}
+    ));
+    let expected = hashmap! {
+      "Set-Cookie".to_string() => vec!["session=abc; Path=/".to_string()]
+    };
+
+    let actual = hashmap! {
+      "set-cookie".to_string() => vec!["session=other; Path=/; Secure; HttpOnly".to_string()]
+    };
+    let result = match_headers(Some(expected), Some(actual), &context);
+    expect!(result.values().flatten()).to(be_empty());
+  }
+
+  #[test]
+  fn match_retry_after_with_seconds_in_range() {
+    let context = HeaderMatchingContext::new(&CoreMatchingContext::new(
+      DiffConfig::AllowUnexpectedKeys,
+      &matchingrules! {
+        "header" => {
+          "Retry-After" => [ MatchingRule::RetryAfter(Some(30), Some(120)) ]
+        }
+      }.rules_for_category("header").unwrap_or_default(), &hashmap!{}
+    ));
+    expect!(match_header_value("Retry-After", 0, "60", "90", &context, true)).to(be_ok());
+    expect!(match_header_value("Retry-After", 0, "60", "30", &context, true)).to(be_ok());
+    expect!(match_header_value("Retry-After", 0, "60", "120", &context, true)).to(be_ok());
+  }
+
+  #[test]
+  fn match_retry_after_with_seconds_out_of_range() {
+    let context = HeaderMatchingContext::new(&CoreMatchingContext::new(
+      DiffConfig::AllowUnexpectedKeys,
+      &matchingrules! {
+        "header" => {
+          "Retry-After" => [ MatchingRule::RetryAfter(Some(30), Some(120)) ]
+        }
+      }.rules_for_category("header").unwrap_or_default(), &hashmap!{}
+    ));
+    let mismatches = match_header_value("Retry-After", 0, "60", "3600", &context, true).unwrap_err();
+    expect!(mismatches[0].description.as_str()).to(be_equal_to(
+      "Mismatch with header 'Retry-After': Expected a Retry-After of at most 120 second(s), but '3600' was 3600 second(s)"));
+    expect!(match_header_value("Retry-After", 0, "60", "5", &context, true)).to(be_err());
+    expect!(match_header_value("Retry-After", 0, "60", "soon", &context, true)).to(be_err());
+  }
+
+  #[test]
+  #[cfg(feature = "datetime")]
+  fn match_retry_after_with_a_date() {
+    let context = HeaderMatchingContext::new(&CoreMatchingContext::new(
+      DiffConfig::AllowUnexpectedKeys,
+      &matchingrules! {
+        "header" => {
+          "Retry-After" => [ MatchingRule::RetryAfter(Some(30), Some(120)) ]
+        }
+      }.rules_for_category("header").unwrap_or_default(), &hashmap!{}
+    ));
+    let format = "%a, %d %b %Y %H:%M:%S GMT";
+    let in_range = (chrono::Utc::now() + chrono::Duration::seconds(60)).format(format).to_string();
+    let out_of_range = (chrono::Utc::now() + chrono::Duration::hours(2)).format(format).to_string();
+    expect!(match_header_value("Retry-After", 0, "60", in_range.as_str(), &context, true)).to(be_ok());
+    expect!(match_header_value("Retry-After", 0, "60", out_of_range.as_str(), &context, true)).to(be_err());
+    expect!(match_header_value("Retry-After", 0, "60", "Wed, 21 Oct 2015 07:28:00 GMT", &context, true)).to(be_err());
+  }
+
+  #[test]
+  fn parse_retry_after_test() {
+    expect!(parse_retry_after("120")).to(be_ok().value(120));
+    expect!(parse_retry_after(" 0 ")).to(be_ok().value(0));
+    expect!(parse_retry_after("")).to(be_err());
+    expect!(parse_retry_after("-1")).to(be_err());
+    expect!(parse_retry_after("1.5")).to(be_err());
+  }
+
   #[test]
   fn parse_charset_parameters_test() {
     expect!(parse_charset_parameters(&[])).to(be_equal_to(hashmap!{}));
@@ -846,4 +1555,127 @@ mod tests {
       }
     ]));
   }
+
+  #[test]
+  fn match_body_hash_headers_test() {
+    let rules = matchingrules! {
+      "header" => { "ETag" => [ MatchingRule::BodyHash("sha256".to_string()) ] }
+    }.rules_for_category("header").unwrap();
+    let body = OptionalBody::from("{\"id\":1}");
+
+    let headers = Some(hashmap! {
+      "ETag".to_string() => vec!["\"A3ySFO73TMOIfzpPCFtOF9digNr9JzsO4WDAnEuhz9Q=\"".to_string()]
+    });
+    expect!(match_body_hash_headers(&rules, &headers, &body).iter()).to(be_empty());
+
+    let headers = Some(hashmap! {
+      "etag".to_string() => vec!["\"A3ySFO73TMOIfzpPCFtOF9digNr9JzsO4WDAnEuhz9Q=\"".to_string()]
+    });
+    expect!(match_body_hash_headers(&rules, &headers, &body).iter()).to(be_empty());
+
+    expect!(match_body_hash_headers(&rules, &None, &body).iter()).to(be_empty());
+  }
+
+  #[test]
+  fn match_body_hash_headers_with_stale_etag() {
+    let rules = matchingrules! {
+      "header" => { "ETag" => [ MatchingRule::BodyHash("sha256".to_string()) ] }
+    }.rules_for_category("header").unwrap();
+    let body = OptionalBody::from("{\"id\":1}");
+
+    // ETag calculated from the previous version of the body
+    let headers = Some(hashmap! {
+      "ETag".to_string() => vec!["\"nn5lRTc5u8ZLoVXu0Yo3vV+xGWx/Kd7R2mYLlEFNetg=\"".to_string()]
+    });
+    let mismatches = match_body_hash_headers(&rules, &headers, &body);
+    expect!(mismatches.iter().map(|m| m.description()).collect::<Vec<_>>()).to(be_equal_to(vec![
+      "Mismatch with header 'ETag': Expected '\"nn5lRTc5u8ZLoVXu0Yo3vV+xGWx/Kd7R2mYLlEFNetg=\"' to be the sha256 hash of the body \"A3ySFO73TMOIfzpPCFtOF9digNr9JzsO4WDAnEuhz9Q=\"".to_string()
+    ]));
+
+    let headers = Some(hashmap! {
+      "ETag".to_string() => vec!["W/\"A3ySFO73TMOIfzpPCFtOF9digNr9JzsO4WDAnEuhz9Q=\"".to_string()]
+    });
+    let mismatches = match_body_hash_headers(&rules, &headers, &body);
+    expect!(mismatches.iter().map(|m| m.description()).collect::<Vec<_>>()).to(be_equal_to(vec![
+      "Mismatch with header 'ETag': Expected a strong ETag of \"A3ySFO73TMOIfzpPCFtOF9digNr9JzsO4WDAnEuhz9Q=\" but was a weak ETag 'W/\"A3ySFO73TMOIfzpPCFtOF9digNr9JzsO4WDAnEuhz9Q=\"'".to_string()
+    ]));
+  }
+
+  #[test]
+  fn apply_header_transform_test() {
+    expect!(apply_header_transform("5", "add:1")).to(be_ok().value("6".to_string()));
+    expect!(apply_header_transform("5", "subtract:2")).to(be_ok().value("3".to_string()));
+    expect!(apply_header_transform("5", "multiply:3")).to(be_ok().value("15".to_string()));
+    expect!(apply_header_transform("1.5", "add:1")).to(be_ok().value("2.5".to_string()));
+    expect!(apply_header_transform("abc", "prefix:req-")).to(be_ok().value("req-abc".to_string()));
+    expect!(apply_header_transform("abc", "suffix:-1")).to(be_ok().value("abc-1".to_string()));
+    expect!(apply_header_transform("abc", "uppercase")).to(be_ok().value("ABC".to_string()));
+    expect!(apply_header_transform("abc", "add:1")).to(be_err());
+    expect!(apply_header_transform("5", "add:x")).to(be_err());
+    expect!(apply_header_transform("5", "divide:2")).to(be_err());
+  }
+
+  #[test]
+  fn match_header_values_ignoring_order() {
+    let context = HeaderMatchingContext::new(&CoreMatchingContext::new(
+      DiffConfig::NoUnexpectedKeys,
+      &matchingrules! {
+        "header" => {
+          "$.Vary" => [ MatchingRule::UnorderedValues ]
+        }
+      }.rules_for_category("header").unwrap_or_default(), &hashmap!{}
+    ));
+    let expected = hashmap! {
+      "Vary".to_string() => vec!["Accept".to_string(), "Accept-Encoding".to_string(), "Origin".to_string()]
+    };
+
+    let actual = hashmap! {
+      "vary".to_string() => vec!["Origin".to_string(), "Accept".to_string(), "Accept-Encoding".to_string()]
+    };
+    let result = match_headers(Some(expected.clone()), Some(actual.clone()), &context);
+    expect!(result.values().flatten()).to(be_empty());
+
+    let ordered_context = HeaderMatchingContext::new(&CoreMatchingContext::new(
+      DiffConfig::NoUnexpectedKeys, &MatchingRuleCategory::empty("header"), &hashmap!{}
+    ));
+    let result = match_headers(Some(expected.clone()), Some(actual), &ordered_context);
+    expect!(result.values().flatten()).to_not(be_empty());
+
+    let actual = hashmap! {
+      "vary".to_string() => vec!["Origin".to_string(), "Accept".to_string(), "Cookie".to_string()]
+    };
+    let result = match_headers(Some(expected), Some(actual), &context);
+    let mismatches = result.values().flatten().map(|m| m.description()).sorted().collect::<Vec<_>>();
+    expect!(mismatches).to(be_equal_to(vec![
+      "Mismatch with header 'Vary': Expected value 'Accept-Encoding' but it was not found in the actual values 'Origin, Accept, Cookie' (ignoring order)".to_string(),
+      "Mismatch with header 'Vary': Unexpected value 'Cookie' received (ignoring order)".to_string()
+    ]));
+  }
+
+  #[test]
+  fn match_header_values_ignoring_order_applies_other_matchers_to_each_value() {
+    let context = HeaderMatchingContext::new(&CoreMatchingContext::new(
+      DiffConfig::NoUnexpectedKeys,
+      &matchingrules! {
+        "header" => {
+          "$.X-Ids" => [ MatchingRule::UnorderedValues, MatchingRule::Regex("^\\d+$".to_string()) ]
+        }
+      }.rules_for_category("header").unwrap_or_default(), &hashmap!{}
+    ));
+    let expected = hashmap! {
+      "X-Ids".to_string() => vec!["1".to_string(), "2".to_string()]
+    };
+
+    let actual = hashmap! {
+      "x-ids".to_string() => vec!["200".to_string(), "100".to_string()]
+    };
+    let result = match_headers(Some(expected.clone()), Some(actual), &context);
+    expect!(result.values().flatten()).to(be_empty());
+
+    let actual = hashmap! {
+      "x-ids".to_string() => vec!["200".to_string(), "abc".to_string()]
+    };
+    let result = match_headers(Some(expected), Some(actual), &context);
+    expect!(result.values().flatten().count()).to(be_equal_to(2));
+  }
 }
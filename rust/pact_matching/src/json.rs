@@ -1,5 +1,6 @@
 //! The `json` module provides functions to compare and display the differences between JSON bodies
 
+use std::collections::HashSet;
 use std::str::FromStr;
 
 use ansi_term::Colour::*;
@@ -11,16 +12,16 @@ use semver::Version;
 use serde_json::{json, Value};
 
 use pact_models::http_parts::HttpPart;
-use pact_models::json_utils::json_to_string;
+use pact_models::json_utils::{json_to_string, sort_json_keys};
 use pact_models::matchingrules::MatchingRule;
-use pact_models::path_exp::DocPath;
+use pact_models::path_exp::{DocPath, PathToken};
 #[cfg(feature = "datetime")] use pact_models::time_utils::validate_datetime;
 use tracing::debug;
 
 use crate::{DiffConfig, MatchingContext, Mismatch, CommonMismatch, merge_result};
 use crate::binary_utils::{convert_data, match_content_type};
 use crate::matchers::*;
-use crate::matchingrules::{compare_lists_with_matchingrules, compare_maps_with_matchingrule};
+use crate::matchingrules::{actual_value_for_key, compare_lists_with_matchingrules, compare_maps_with_matchingrule, match_allowed_keys};
 
 lazy_static! {
   static ref DEC_REGEX: Regex = Regex::new(r"\d+\.\d+").unwrap();
@@ -164,6 +165,11 @@ impl Matches<&Value> for Value {
             value_of(actual), type_of(actual), value_of(self), type_of(self)))
         }
       },
+      MatchingRule::Nullable(rule) => if actual.is_null() {
+        Ok(())
+      } else {
+        self.matches_with(actual, rule.as_ref(), cascaded)
+      },
       MatchingRule::Null => match actual {
         Value::Null => Ok(()),
         _ => Err(anyhow!("Expected {} ({}) to be a null value", value_of(actual), type_of(actual)))
@@ -173,6 +179,61 @@ impl Matches<&Value> for Value {
       } else {
         Err(anyhow!("Expected {} ({}) to be an integer", value_of(actual), type_of(actual)))
       },
+      MatchingRule::IntegerRange { min, max } => match actual.as_i64() {
+        Some(value) => match_integer_range(value, *min, *max),
+        None => Err(anyhow!("Expected {} to be an integer between {} and {}", value_of(actual), min, max))
+      },
+      MatchingRule::DateTimeAfter { format, reference } =>
+        match_datetime_order(&json_to_string(actual), format, reference, true),
+      MatchingRule::DateTimeBefore { format, reference } =>
+        match_datetime_order(&json_to_string(actual), format, reference, false),
+      MatchingRule::OneOf(values) => match actual {
+        Value::String(s) => match_one_of(s, values),
+        _ => Err(anyhow!("Expected {} ({}) to be one of [{}]", value_of(actual), type_of(actual), values.join(", ")))
+      },
+      MatchingRule::IpAddress { version } => match actual {
+        Value::String(s) => match_ip_address(s, *version),
+        _ => Err(anyhow!("Expected {} ({}) to be an IP address", value_of(actual), type_of(actual)))
+      },
+      MatchingRule::Format(format) => match actual {
+        Value::String(s) => match_format(s, format),
+        _ => Err(anyhow!("Expected {} ({}) to be a string in the '{}' format", value_of(actual), type_of(actual), format))
+      },
+      MatchingRule::Any => Ok(()),
+      MatchingRule::JsonRegex(regex) => match_json_regex(actual, regex),
+      MatchingRule::NoCascade => Ok(()),
+      // Cookie headers are parsed and matched as structured values when matching the headers
+      MatchingRule::Cookie => Ok(()),
+      // The raw request target is handled when matching the request path
+      MatchingRule::RawTarget => Ok(()),
+      // Header values are compared ignoring their order when matching the headers
+      MatchingRule::UnorderedValues => Ok(()),
+      // Sensitive values are redacted from the mismatches once the matching is done
+      MatchingRule::Sensitive => Ok(()),
+      // Header values are parsed and matched as JSON when matching the headers
+      MatchingRule::JsonValue => Ok(()),
+      MatchingRule::MultipleOf(base) => match actual.as_f64() {
+        Some(value) => match_multiple_of(value, *base),
+        None => Err(anyhow!("Expected {} ({}) to be a multiple of {}", value_of(actual), type_of(actual), base))
+      },
+      MatchingRule::NumericString { tolerance } => match (self, actual) {
+        (Value::String(expected), Value::String(actual)) => match_numeric_string(expected, actual, *tolerance),
+        _ => Err(anyhow!("Expected {} ({}) to be a numeric string", value_of(actual), type_of(actual)))
+      },
+      MatchingRule::Hex { length } => match actual {
+        Value::String(s) => match_hex(s, *length),
+        _ => Err(anyhow!("Expected {} ({}) to be a hexadecimal string", value_of(actual), type_of(actual)))
+      },
+      MatchingRule::JsonSchema(schema) => {
+        let errors = validate_json_schema(&DocPath::root(), schema, actual)?;
+        if errors.is_empty() {
+          Ok(())
+        } else {
+          Err(anyhow!("Expected {} to be valid against the JSON Schema: {}", value_of(actual),
+            errors.iter().map(|error| format!("{} at {}", error.description, error.path))
+              .collect::<Vec<_>>().join(", ")))
+        }
+      }
       MatchingRule::Decimal => if actual.is_f64() {
         Ok(())
       } else {
@@ -269,6 +330,26 @@ impl Matches<&Value> for Value {
         }
         _ => Ok(())
       }
+      MatchingRule::Empty => match actual {
+        Value::String(s) => if s.is_empty() {
+          Ok(())
+        } else {
+          Err(anyhow!("Expected '{}' (String) to be empty", s))
+        }
+        Value::Array(a) => if a.is_empty() {
+          Ok(())
+        } else {
+          Err(anyhow!("Expected an empty Array but found {} item(s): {}", a.len(),
+            a.iter().map(json_to_string).collect::<Vec<_>>().join(", ")))
+        }
+        Value::Object(o) => if o.is_empty() {
+          Ok(())
+        } else {
+          Err(anyhow!("Expected an empty Object but found {} key(s): {}", o.len(),
+            o.keys().map(|k| format!("'{}'", k)).collect::<Vec<_>>().join(", ")))
+        }
+        _ => Err(anyhow!("Expected {} ({}) to be an empty Object, Array or String", value_of(actual), type_of(actual)))
+      }
       MatchingRule::Semver => match actual {
         Value::String(s) => match Version::parse(s) {
           Ok(_) => Ok(()),
@@ -276,6 +357,38 @@ impl Matches<&Value> for Value {
         }
         _ => Err(anyhow!("Expected something that matches a semantic version, but got '{}'", actual))
       }
+      MatchingRule::PathExpression => match actual {
+        Value::String(s) => match_path_expression(s),
+        _ => Err(anyhow!("Expected a path expression, but got '{}'", actual))
+      }
+      MatchingRule::Base64(min, max) => match actual {
+        Value::String(s) => match_base64(s, *min, *max),
+        _ => Err(anyhow!("Expected a Base64 encoded string, but got '{}'", actual))
+      }
+      MatchingRule::Duration => match actual {
+        Value::String(s) => match_duration(s),
+        _ => Err(anyhow!("Expected '{}' to be a valid ISO-8601 duration", actual))
+      }
+      MatchingRule::StringLength { min, max } => match actual {
+        Value::String(s) => match_string_length(s, *min, *max),
+        _ => Err(anyhow!("Expected {} ({}) to be a string", value_of(actual), type_of(actual)))
+      }
+      MatchingRule::AllowedKeys(keys) => match actual {
+        Value::Object(map) => match_allowed_keys(map.keys(), keys),
+        _ => if cascaded {
+          Ok(())
+        } else {
+          Err(anyhow!("Expected {} ({}) to be an Object", value_of(actual), type_of(actual)))
+        }
+      }
+      MatchingRule::Keys(_) => match actual {
+        Value::Object(_) => Ok(()),
+        _ => if cascaded {
+          Ok(())
+        } else {
+          Err(anyhow!("Expected {} ({}) to be an Object", value_of(actual), type_of(actual)))
+        }
+      }
       _ => Ok(())
     };
     debug!("JSON -> JSON: Comparing '{}' ({}) to '{}' ({}) using {:?} -> {:?}", self,
@@ -332,6 +445,35 @@ fn walk_json(json: &Value, path: &mut dyn Iterator<Item=&str>) -> Option<Value>
   }
 }
 
+/// Serialises the JSON value to the canonical text form used by the `JsonRegex` matcher. The
+/// rules are:
+/// * There is no whitespace between tokens.
+/// * Strings are written in double quotes, with `"`, `\` and control characters escaped.
+/// * Integers are written as digits only, with a leading `-` if negative.
+/// * Floating point numbers are written in the shortest form that round-trips, which always has
+///   a decimal point or exponent (i.e. `1.0`, `12.34`, `1e20`). As the value is re-serialised,
+///   the text from the original document is not kept (`1.50` is written as `1.5`).
+/// * `true`, `false` and `null` are written as is.
+/// * Object keys are written in sorted order.
+pub fn canonical_json_string(value: &Value) -> String {
+  sort_json_keys(value).to_string()
+}
+
+/// Matches the canonical JSON text of the value (see `canonical_json_string`) against the regex
+pub(crate) fn match_json_regex(value: &Value, regex: &str) -> anyhow::Result<()> {
+  match Regex::new(regex) {
+    Ok(re) => {
+      let text = canonical_json_string(value);
+      if re.is_match(&text) {
+        Ok(())
+      } else {
+        Err(anyhow!("Expected the JSON text {} to match '{}'", text, regex))
+      }
+    },
+    Err(err) => Err(anyhow!("'{}' is not a valid regular expression - {}", regex, err))
+  }
+}
+
 /// Returns a diff of the expected versus the actual JSON bodies, focusing on a particular path
 pub fn display_diff(expected: &str, actual: &str, path: &str, indent: &str) -> String {
   let expected_body = if expected.is_empty() {
@@ -382,6 +524,38 @@ pub fn compare_json(
   context: &(dyn MatchingContext + Send + Sync)
 ) -> Result<(), Vec<CommonMismatch>> {
   debug!("compare: Comparing path {}", path);
+  if context.direct_matcher_defined(path, &HashSet::from(["json-schema"])) {
+    if let Some(schema) = context.select_best_matcher(path).rules.iter()
+      .find_map(|rule| if let MatchingRule::JsonSchema(schema) = rule { Some(schema.clone()) } else { None }) {
+      debug!("compare: Validating path {} against the JSON Schema", path);
+      return match validate_json_schema(path, &schema, actual) {
+        Ok(mismatches) if mismatches.is_empty() => Ok(()),
+        Ok(mismatches) => Err(mismatches),
+        Err(err) => Err(vec![ CommonMismatch {
+          path: path.to_string(),
+          expected: schema.to_string(),
+          actual: json_to_string(actual),
+          description: err.to_string()
+        } ])
+      };
+    }
+  }
+  if path.len().saturating_sub(1) > context.max_depth() {
+    return Err(vec![ CommonMismatch {
+      path: path.to_string(),
+      expected: json_to_string(expected),
+      actual: json_to_string(actual),
+      description: format!("Values are nested deeper than the maximum depth of {}", context.max_depth())
+    } ]);
+  }
+  if actual.is_null() && context.direct_matcher_defined(path, &HashSet::from(["nullable"])) {
+    debug!("compare: Path {} is nullable and the actual value is null", path);
+    return Ok(());
+  }
+  if context.direct_matcher_defined(path, &HashSet::from(["any"])) {
+    debug!("compare: Path {} has an 'any' matcher, not comparing the values", path);
+    return Ok(());
+  }
   match (expected, actual) {
     (&Value::Object(ref emap), &Value::Object(ref amap)) => compare_maps(path, emap, amap, context),
     (&Value::Object(_), _) => {
@@ -393,6 +567,15 @@ pub fn compare_json(
           value_of(actual), type_of(actual), value_of(expected), type_of(expected))
       } ])
     }
+    (&Value::Array(_), &Value::Array(ref alist)) if alist.len() > context.max_array_length() => {
+      Err(vec![ CommonMismatch {
+        path: path.to_string(),
+        expected: json_to_string(expected),
+        actual: format!("<list with {} items>", alist.len()),
+        description: format!("Expected a List with at most {} items but received {} items",
+          context.max_array_length(), alist.len())
+      } ])
+    }
     (&Value::Array(ref elist), &Value::Array(ref alist)) => compare_lists(path, elist, alist, context),
     (&Value::Array(_), _) => {
       Err(vec![ CommonMismatch {
@@ -443,8 +626,8 @@ fn compare_maps(
       result = merge_result(result, context.match_keys(path, &expected_keys, &actual_keys));
       for (key, value) in expected.iter() {
         let p = path.join(key);
-        if actual.contains_key(key) {
-          result = merge_result(result, compare_json(&p, value, &actual[key], context));
+        if let Some(actual_value) = actual_value_for_key(key, &actual, context) {
+          result = merge_result(result, compare_json(&p, value, actual_value, context));
         }
       }
     };
@@ -461,9 +644,18 @@ fn compare_lists(
   let spath = path.to_string();
   if context.matcher_is_defined(path) {
     debug!("compare_lists: matcher defined for path '{}'", path);
-    compare_lists_with_matchingrules(path, &context.select_best_matcher(path), expected, actual, context, &mut |p, expected, actual, context| {
+    let rules = context.select_best_matcher(path);
+    let mut result = compare_lists_with_matchingrules(path, &rules, expected, actual, context, &mut |p, expected, actual, context| {
         compare_json(p, expected, actual, context)
-    })
+    });
+    if !rules.cascaded {
+      for rule in &rules.rules {
+        if let MatchingRule::ArrayDistinct(by) = rule {
+          result = merge_result(result, match_distinct(path, by.as_deref(), expected, actual));
+        }
+      }
+    }
+    result
   } else if expected.is_empty() && !actual.is_empty() {
     Err(vec![ CommonMismatch {
       path: spath,
@@ -487,6 +679,68 @@ fn compare_lists(
   }
 }
 
+/// Checks that the items in the actual list are unique. If a path is given, the values at that
+/// path relative to each item are compared instead (items without a value at the path are ignored).
+fn match_distinct(
+  path: &DocPath,
+  by: Option<&str>,
+  expected: &[Value],
+  actual: &[Value]
+) -> Result<(), Vec<CommonMismatch>> {
+  let by_path = match by.map(DocPath::new).transpose() {
+    Ok(by_path) => by_path,
+    Err(err) => return Err(vec![ CommonMismatch {
+      path: path.to_string(),
+      expected: json_to_string(&json!(expected)),
+      actual: json_to_string(&json!(actual)),
+      description: format!("ArrayDistinct: '{}' is not a valid path expression - {}", by.unwrap_or_default(), err)
+    } ])
+  };
+
+  let mut seen = vec![];
+  let mut duplicates = vec![];
+  for item in actual {
+    let value = match &by_path {
+      Some(by_path) => value_at_path(item, by_path),
+      None => Some(item)
+    };
+    if let Some(value) = value {
+      if !seen.contains(&value) {
+        seen.push(value);
+      } else if !duplicates.contains(&value) {
+        duplicates.push(value);
+      }
+    }
+  }
+
+  if duplicates.is_empty() {
+    Ok(())
+  } else {
+    let values = duplicates.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", ");
+    let verb = if duplicates.len() == 1 { "was" } else { "were" };
+    Err(vec![ CommonMismatch {
+      path: path.to_string(),
+      expected: json_to_string(&json!(expected)),
+      actual: json_to_string(&json!(actual)),
+      description: match by {
+        Some(by) => format!("Expected the items to be unique by '{}', but {} {} duplicated", by, values, verb),
+        None => format!("Expected the items to be unique, but {} {} duplicated", values, verb)
+      }
+    } ])
+  }
+}
+
+/// Returns the value at the path relative to the given value. Only field and index path tokens
+/// are supported.
+fn value_at_path<'a>(value: &'a Value, path: &DocPath) -> Option<&'a Value> {
+  path.tokens().iter().try_fold(value, |value, token| match token {
+    PathToken::Root => Some(value),
+    PathToken::Field(name) => value.as_object().and_then(|map| map.get(name)),
+    PathToken::Index(index) => value.as_array().and_then(|list| list.get(*index)),
+    PathToken::Star | PathToken::StarIndex => None
+  })
+}
+
 fn compare_list_content(
   path: &DocPath,
   expected: &[Value],
@@ -519,7 +773,13 @@ fn compare_values(
 ) -> Result<(), Vec<CommonMismatch>> {
   let matcher_result = if context.matcher_is_defined(path) {
     debug!("compare_values: Calling match_values for path {}", path);
-    match_values(path, &context.select_best_matcher(&path), expected, actual)
+    let rules = context.select_best_matcher(&path);
+    if context.matching_config().strict_number_types && rules.type_matcher_defined() && number_types_differ(expected, actual) {
+      Err(vec![format!("Expected {} ({}) to be the same type as {} ({})",
+        value_of(actual), type_of(actual), value_of(expected), type_of(expected))])
+    } else {
+      match_values(path, &rules, expected, actual)
+    }
   } else {
     expected.matches_with(actual, &MatchingRule::Equality, false).map_err(|err| vec![err.to_string()])
   };
@@ -536,20 +796,114 @@ fn compare_values(
   })
 }
 
+/// If both values are numbers, but one is an integer and the other a decimal
+fn number_types_differ(expected: &Value, actual: &Value) -> bool {
+  match (expected, actual) {
+    (Value::Number(_), Value::Number(_)) => type_of(expected) != type_of(actual),
+    _ => false
+  }
+}
+
+/// Validates the actual JSON against the JSON Schema. Each validation error is returned as a
+/// mismatch, with the path to the invalid value (relative to the given path).
+#[cfg(feature = "json_schema")]
+pub fn validate_json_schema(
+  path: &DocPath,
+  schema: &Value,
+  actual: &Value
+) -> anyhow::Result<Vec<CommonMismatch>> {
+  let validator = jsonschema::validator_for(schema)
+    .map_err(|err| anyhow!("JSON Schema is not valid - {}", err))?;
+  Ok(validator.iter_errors(actual).map(|error| {
+    let instance_path = error.instance_path.to_string();
+    let error_path = instance_path.split('/')
+      .skip(1)
+      .fold(path.clone(), |p, segment| p.join(segment.replace("~1", "/").replace("~0", "~")));
+    CommonMismatch {
+      path: error_path.to_string(),
+      expected: schema.to_string(),
+      actual: error.instance.to_string(),
+      description: error.to_string()
+    }
+  }).collect())
+}
+
+/// Validates the actual JSON against the JSON Schema. Each validation error is returned as a
+/// mismatch, with the path to the invalid value (relative to the given path).
+#[cfg(not(feature = "json_schema"))]
+pub fn validate_json_schema(
+  _path: &DocPath,
+  _schema: &Value,
+  _actual: &Value
+) -> anyhow::Result<Vec<CommonMismatch>> {
+  Err(anyhow!("JSON Schema matchers require the json_schema feature to be enabled"))
+}
+
+/// Compares a new version of a JSON document against an old version, returning the changes that
+/// would break a consumer of the old version. Adding fields, changing values, replacing a null
+/// value and widening an integer to a decimal are allowed. Removing fields and any other change
+/// of type are returned as mismatches.
+pub fn compare_json_compatibility(path: &DocPath, old: &Value, new: &Value) -> Vec<CommonMismatch> {
+  match (old, new) {
+    (Value::Object(old_map), Value::Object(new_map)) => {
+      old_map.iter().flat_map(|(key, old_value)| {
+        let p = path.join(key);
+        match new_map.get(key) {
+          Some(new_value) => compare_json_compatibility(&p, old_value, new_value),
+          None => vec![ CommonMismatch {
+            path: p.to_string(),
+            expected: json_to_string(old_value),
+            actual: "".to_string(),
+            description: format!("Field '{}' has been removed", key)
+          } ]
+        }
+      }).collect()
+    }
+    (Value::Array(old_list), Value::Array(new_list)) => match old_list.first() {
+      Some(template) => new_list.iter().enumerate()
+        .flat_map(|(index, new_value)| compare_json_compatibility(&path.join_index(index), template, new_value))
+        .collect(),
+      None => vec![]
+    }
+    (Value::Null, _) => vec![],
+    (Value::Number(_), Value::Number(_)) => if type_of(old) == "Decimal" && type_of(new) == "Integer" {
+      vec![ CommonMismatch {
+        path: path.to_string(),
+        expected: json_to_string(old),
+        actual: json_to_string(new),
+        description: format!("Type has been narrowed from {} to {}", type_of(old), type_of(new))
+      } ]
+    } else {
+      vec![]
+    }
+    (_, _) => if type_of(old) == type_of(new) {
+      vec![]
+    } else {
+      vec![ CommonMismatch {
+        path: path.to_string(),
+        expected: json_to_string(old),
+        actual: json_to_string(new),
+        description: format!("Type has been changed from {} to {}", type_of(old), type_of(new))
+      } ]
+    }
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use std::collections::HashMap;
 
   use expectest::expect;
   use expectest::prelude::*;
+  use itertools::{Either, Itertools};
   use maplit::hashmap;
   use pact_models::{matchingrules, matchingrules_list};
   use pact_models::bodies::OptionalBody;
-  use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory};
-  use pact_models::matchingrules::expressions::{MatchingRuleDefinition, ValueType};
+  use pact_models::matchingrules::{MatchingRule, MatchingRuleCategory, RuleList};
+  use pact_models::matchingrules::expressions::{MatchingReference, MatchingRuleDefinition, ValueType};
   use pact_models::request::Request;
 
-  use crate::{CoreMatchingContext, DiffConfig};
+  use crate::{CoreMatchingContext, DEFAULT_MAX_DEPTH, DiffConfig, MatchingConfig};
   use crate::Mismatch;
   use crate::Mismatch::BodyMismatch;
 
@@ -915,6 +1269,91 @@ mod tests {
     expect!(Value::String("100".into()).matches_with(&json!(100.02), &matcher, false)).to(be_err());
   }
 
+  #[test]
+  fn integer_range_matcher_test() {
+    let matcher = MatchingRule::IntegerRange { min: 0, max: 100 };
+    expect!(json!(50).matches_with(&json!(0), &matcher, false)).to(be_ok());
+    expect!(json!(50).matches_with(&json!(100), &matcher, false)).to(be_ok());
+    expect!(json!(50).matches_with(&json!(-5), &matcher, false)).to(be_err());
+    expect!(json!(50).matches_with(&json!(150), &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected 150 to be an integer between 0 and 100"));
+    expect!(json!(50).matches_with(&json!(50.5), &matcher, false)).to(be_err());
+    expect!(json!(50).matches_with(&json!("50"), &matcher, false)).to(be_err());
+  }
+
+  #[test]
+  fn multiple_of_matcher_test() {
+    let matcher = MatchingRule::MultipleOf(5.0);
+    expect!(json!(10).matches_with(&json!(25), &matcher, false)).to(be_ok());
+    expect!(json!(10).matches_with(&json!(25.0), &matcher, false)).to(be_ok());
+    expect!(json!(10).matches_with(&json!(7), &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected 7 to be a multiple of 5"));
+    expect!(json!(10).matches_with(&json!("10"), &matcher, false)).to(be_err());
+    expect!(json!(10).matches_with(&json!(10), &MatchingRule::MultipleOf(0.0), false)).to(be_err());
+  }
+
+  #[test]
+  fn hex_matcher_test() {
+    let matcher = MatchingRule::Hex { length: Some(4) };
+    expect!(json!("").matches_with(&json!("00ff"), &matcher, false)).to(be_ok());
+    expect!(json!("").matches_with(&json!("00fg"), &matcher, false)).to(be_err());
+    expect!(json!("").matches_with(&json!(1234), &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected 1234 (Integer) to be a hexadecimal string"));
+  }
+
+  #[test]
+  fn numeric_string_matcher_test() {
+    let matcher = MatchingRule::NumericString { tolerance: None };
+    expect!(json!("100.50").matches_with(&json!("100.5"), &matcher, false)).to(be_ok());
+    expect!(json!("100.50").matches_with(&json!("100.6"), &matcher, false)).to(be_err());
+    expect!(json!("100.50").matches_with(&json!(100.5), &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected 100.5 (Decimal) to be a numeric string"));
+    expect!(json!("100.50").matches_with(&json!("100.6"), &MatchingRule::NumericString { tolerance: Some(0.1) }, false))
+      .to(be_ok());
+  }
+
+  #[test]
+  #[cfg(feature = "json_schema")]
+  fn json_schema_matcher_test() {
+    let matcher = MatchingRule::JsonSchema(json!({
+      "type": "object",
+      "properties": { "id": { "type": "integer" } }
+    }));
+    expect!(json!({}).matches_with(&json!({ "id": 1 }), &matcher, false)).to(be_ok());
+    expect!(json!({}).matches_with(&json!({ "id": "1" }), &matcher, false)).to(be_err());
+    expect!(json!({}).matches_with(&json!([1]), &matcher, false)).to(be_err());
+
+    let invalid_schema = MatchingRule::JsonSchema(json!({ "type": 100 }));
+    expect!(json!({}).matches_with(&json!({}), &invalid_schema, false)).to(be_err());
+  }
+
+  #[test]
+  #[cfg(feature = "json_schema")]
+  fn match_json_with_a_json_schema_matcher_on_a_nested_value() {
+    let expected = request!(r#"{"id": 1, "payload": {"name": "Fred", "tags": ["a"]}}"#);
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys, &matchingrules!{
+      "body" => {
+        "$.payload" => [ MatchingRule::JsonSchema(json!({
+          "type": "object",
+          "required": ["name"],
+          "properties": { "tags": { "type": "array", "items": { "type": "string" } } }
+        })) ]
+      }
+    }.rules_for_category("body").unwrap(), &hashmap!{});
+
+    let actual = request!(r#"{"id": 1, "payload": {"name": "Mary", "tags": []}}"#);
+    expect!(match_json(&expected, &actual, &context)).to(be_ok());
+
+    let actual = request!(r#"{"id": 1, "payload": {"tags": ["b", 1]}}"#);
+    let mismatches = match_json(&expected, &actual, &context).unwrap_err();
+    let mut paths = mismatches.iter().map(|m| match m {
+      BodyMismatch { path, .. } => path.clone(),
+      _ => String::default()
+    }).collect::<Vec<_>>();
+    paths.sort();
+    expect!(paths).to(be_equal_to(vec!["$.payload".to_string(), "$.payload.tags[1]".to_string()]));
+  }
+
   #[test]
   fn decimal_matcher_test() {
     let matcher = MatchingRule::Decimal;
@@ -974,6 +1413,83 @@ mod tests {
     }
   }
 
+  #[test]
+  fn match_json_with_case_insensitive_keys() {
+    let expected = request!(r#"{"id": 1, "name": "Fred"}"#);
+    let actual = request!(r#"{"Id": 1, "NAME": "Fred"}"#);
+    let context = CoreMatchingContext::with_config(DiffConfig::NoUnexpectedKeys);
+    expect!(match_json(&expected, &actual, &context)).to(be_err());
+
+    let context = context.with_matching_config(MatchingConfig { case_insensitive_keys: true, .. MatchingConfig::default() });
+    expect!(match_json(&expected, &actual, &context)).to(be_ok());
+
+    let actual = request!(r#"{"Id": 2, "name": "Fred"}"#);
+    let mismatches = match_json(&expected, &actual, &context).unwrap_err();
+    expect!(mismatches.iter().map(|m| m.description()).collect::<Vec<_>>()).to(be_equal_to(vec![
+      "$.id -> Expected 2 (Integer) to be equal to 1 (Integer)".to_string()
+    ]));
+
+    let actual = request!(r#"{"ID": 2, "id": 1, "name": "Fred"}"#);
+    let mismatches = match_json(&expected, &actual, &context).unwrap_err();
+    expect!(mismatches.iter().map(|m| m.description()).collect::<Vec<_>>()).to(be_equal_to(vec![
+      "$ -> Expected a Map with keys [id, name] but received one with keys [ID, id, name]".to_string()
+    ]));
+    let context = CoreMatchingContext::with_config(DiffConfig::AllowUnexpectedKeys)
+      .with_matching_config(MatchingConfig { case_insensitive_keys: true, .. MatchingConfig::default() });
+    expect!(match_json(&expected, &actual, &context)).to(be_ok());
+  }
+
+  #[test]
+  fn match_json_with_strict_number_types() {
+    let expected = request!(r#"{"id": 100, "price": 10.5}"#);
+    let actual = request!(r#"{"id": 100.0, "price": 10}"#);
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys, &matchingrules!{
+      "body" => { "$" => [ MatchingRule::Type ] }
+    }.rules_for_category("body").unwrap(), &hashmap!{});
+    expect!(match_json(&expected, &actual, &context)).to(be_ok());
+
+    let context = context.with_matching_config(MatchingConfig { strict_number_types: true, .. MatchingConfig::default() });
+    let mismatches = match_json(&expected, &actual, &context).unwrap_err();
+    expect!(mismatches.iter().map(|m| m.description()).collect::<Vec<_>>()).to(be_equal_to(vec![
+      "$.id -> Expected 100.0 (Decimal) to be the same type as 100 (Integer)".to_string(),
+      "$.price -> Expected 10 (Integer) to be the same type as 10.5 (Decimal)".to_string()
+    ]));
+
+    let actual = request!(r#"{"id": 200, "price": 20.25}"#);
+    expect!(match_json(&expected, &actual, &context)).to(be_ok());
+  }
+
+  #[test]
+  fn match_json_with_size_limits() {
+    let expected = request!(r#"{"a": {"b": {"c": [1, 2, 3]}}}"#);
+    let context = CoreMatchingContext::with_config(DiffConfig::AllowUnexpectedKeys);
+    expect!(match_json(&expected, &expected, &context)).to(be_ok());
+
+    let context = CoreMatchingContext::with_config(DiffConfig::AllowUnexpectedKeys)
+      .with_max_depth(4);
+    expect!(match_json(&expected, &expected, &context)).to(be_ok());
+    let context = context.with_max_depth(2);
+    let mismatches = match_json(&expected, &expected, &context).unwrap_err();
+    expect!(mismatches.iter().map(|m| m.description()).collect::<Vec<_>>()).to(be_equal_to(vec![
+      "$.a.b.c -> Values are nested deeper than the maximum depth of 2".to_string()
+    ]));
+
+    let nested = |depth: usize| request!(format!("{}1{}", "[".repeat(depth), "]".repeat(depth)));
+    let context = CoreMatchingContext::with_config(DiffConfig::AllowUnexpectedKeys);
+    expect!(match_json(&nested(DEFAULT_MAX_DEPTH), &nested(DEFAULT_MAX_DEPTH), &context)).to(be_ok());
+    let mismatches = match_json(&nested(DEFAULT_MAX_DEPTH + 1), &nested(DEFAULT_MAX_DEPTH + 1), &context).unwrap_err();
+    expect!(mismatches.len()).to(be_equal_to(1));
+    expect!(mismatches[0].description().ends_with(
+      format!("Values are nested deeper than the maximum depth of {}", DEFAULT_MAX_DEPTH).as_str())).to(be_true());
+
+    let context = CoreMatchingContext::with_config(DiffConfig::AllowUnexpectedKeys)
+      .with_max_array_length(2);
+    let mismatches = match_json(&expected, &expected, &context).unwrap_err();
+    expect!(mismatches.iter().map(|m| m.description()).collect::<Vec<_>>()).to(be_equal_to(vec![
+      "$.a.b.c -> Expected a List with at most 2 items but received 3 items".to_string()
+    ]));
+  }
+
   #[test_log::test]
   fn compare_maps_handles_wildcard_matchers() {
     let val1 = request!(r#"
@@ -1086,6 +1602,149 @@ mod tests {
     ]));
   }
 
+  #[test]
+  fn compare_lists_with_array_contains_in_order_matcher() {
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys, &matchingrules!{
+      "body" => {
+        "$" => [ MatchingRule::ArrayContainsInOrder ]
+      }
+    }.rules_for_category("body").unwrap(), &hashmap!{});
+
+    for (expected, actual) in [
+      ("[1, 2, 3]", "[1, 2, 3]"),
+      ("[1, 2, 3]", "[10, 1, 22, 2, 6, 3, 5]"),
+      ("[1, 1, 2]", "[1, 3, 1, 2]"),
+      ("[2, 2]", "[2, 1, 2, 2]"),
+      ("[\"start\", \"end\"]", "[\"start\", \"start\", \"middle\", \"end\", \"end\"]")
+    ] {
+      expect!(match_json(&request!(expected), &request!(actual), &context)).to(be_ok());
+    }
+  }
+
+  #[test]
+  fn compare_lists_with_array_contains_in_order_matcher_fails_if_items_are_out_of_order() {
+    let val1 = request!(r#"
+    [1, 2, 3]
+    "#);
+    let val2 = request!(r#"
+    [10, 1, 3, 2, 5]
+    "#);
+
+    let result = match_json(&val1, &val2, &CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys, &matchingrules!{
+      "body" => {
+        "$" => [ MatchingRule::ArrayContainsInOrder ]
+      }
+    }.rules_for_category("body").unwrap(), &hashmap!{}));
+    expect!(result).to(be_err().value(vec![
+      BodyMismatch {
+        path: "$".to_string(),
+        expected: Some("3".into()),
+        actual: Some("[\"10\",\"1\",\"3\",\"2\",\"5\"]".into()),
+        mismatch: "Expected item at index 2 (3) was not found in the actual list in order".to_string()
+      }
+    ]));
+  }
+
+  #[test]
+  fn compare_lists_with_array_contains_in_order_matcher_with_duplicate_values() {
+    let val1 = request!(r#"
+    [1, 2, 1]
+    "#);
+    let val2 = request!(r#"
+    [1, 1, 2]
+    "#);
+
+    let result = match_json(&val1, &val2, &CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys, &matchingrules!{
+      "body" => {
+        "$" => [ MatchingRule::ArrayContainsInOrder ]
+      }
+    }.rules_for_category("body").unwrap(), &hashmap!{}));
+    expect!(result).to(be_err().value(vec![
+      BodyMismatch {
+        path: "$".to_string(),
+        expected: Some("1".into()),
+        actual: Some("[\"1\",\"1\",\"2\"]".into()),
+        mismatch: "Expected item at index 2 (1) was not found in the actual list in order".to_string()
+      }
+    ]));
+  }
+
+  #[test]
+  fn compare_lists_with_array_distinct_matcher() {
+    let expected = request!(r#"
+    ["a", "b"]
+    "#);
+    let actual = request!(r#"
+    ["a", "b", "c"]
+    "#);
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys, &matchingrules!{
+      "body" => {
+        "$" => [ MatchingRule::ArrayDistinct(None) ]
+      }
+    }.rules_for_category("body").unwrap(), &hashmap!{});
+
+    expect!(match_json(&expected, &actual, &context)).to(be_ok());
+
+    let actual = request!(r#"
+    ["a", "b", "a", "c", "b", "a"]
+    "#);
+    let result = match_json(&expected, &actual, &context);
+    expect!(result.unwrap_err().iter().map(|m| m.description()).collect::<Vec<_>>()).to(be_equal_to(vec![
+      "$ -> Expected the items to be unique, but \"a\", \"b\" were duplicated".to_string()
+    ]));
+  }
+
+  #[test]
+  fn compare_lists_with_array_distinct_matcher_by_path() {
+    let expected = request!(r#"
+    [{"name": "a"}]
+    "#);
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys, &matchingrules!{
+      "body" => {
+        "$" => [ MatchingRule::ArrayDistinct(Some("$.id".to_string())) ],
+        "$[*]" => [ MatchingRule::Type ]
+      }
+    }.rules_for_category("body").unwrap(), &hashmap!{});
+
+    let actual = request!(r#"
+    [{"id": 1, "name": "a"}, {"id": 2, "name": "a"}, {"name": "c"}, {"name": "d"}]
+    "#);
+    expect!(match_json(&expected, &actual, &context)).to(be_ok());
+
+    let actual = request!(r#"
+    [{"id": 1, "name": "a"}, {"id": 2, "name": "b"}, {"id": 1, "name": "c"}]
+    "#);
+    let result = match_json(&expected, &actual, &context);
+    expect!(result.unwrap_err().iter().map(|m| m.description()).collect::<Vec<_>>()).to(be_equal_to(vec![
+      "$ -> Expected the items to be unique by '$.id', but 1 was duplicated".to_string()
+    ]));
+  }
+
+  #[test]
+  fn compare_lists_with_array_distinct_and_each_value_matchers() {
+    let expected = request!(r#"
+    [1]
+    "#);
+    let rules = matchingrules! {
+      "body" => { "$" => [
+        MatchingRule::EachValue(MatchingRuleDefinition::new("100".to_string(), ValueType::String,
+          MatchingRule::Integer, None)),
+        MatchingRule::ArrayDistinct(None)
+      ] }
+    };
+    let context = CoreMatchingContext::new(
+      DiffConfig::AllowUnexpectedKeys,
+      &rules.rules_for_category("body").unwrap_or_default(),
+      &hashmap!{}
+    );
+
+    expect!(match_json(&expected, &request!("[3, 4, 567]"), &context)).to(be_ok());
+
+    let result = match_json(&expected, &request!("[3, 4, 3, \"x\"]"), &context);
+    let descriptions = result.unwrap_err().iter().map(|m| m.description()).collect::<Vec<_>>();
+    expect!(descriptions.contains(&"$ -> Expected the items to be unique, but 3 was duplicated".to_string())).to(be_true());
+  }
+
   #[test]
   fn compare_lists_with_each_value_matcher() {
     let expected = request!(r#"
@@ -1145,6 +1804,181 @@ mod tests {
     ]));
   }
 
+  #[test]
+  fn compare_lists_with_each_value_hex_matcher() {
+    let expected = request!(r#"
+    ["00ff00", "ff0000"]
+    "#);
+    let actual = request!(r#"
+    ["0000ff", "abc", "zzzzzz"]
+    "#);
+
+    let rules = matchingrules! {
+      "body" => { "$" => [ MatchingRule::EachValue(MatchingRuleDefinition::new("\"00ff00\"".to_string(), ValueType::String,
+        MatchingRule::Hex { length: Some(6) }, None)) ] }
+    };
+    let context = CoreMatchingContext::new(
+      DiffConfig::AllowUnexpectedKeys,
+      &rules.rules_for_category("body").unwrap_or_default(),
+      &hashmap!{}
+    );
+
+    let result = match_json(&expected, &actual, &context);
+    let mismatches = result.unwrap_err().iter().map(|m| m.description()).collect::<Vec<_>>();
+    expect!(mismatches).to(be_equal_to(vec![
+      "$[1] -> Expected 'abc' to be a hexadecimal string with 6 digits but it has 3".to_string(),
+      "$[2] -> Expected 'zzzzzz' to be a hexadecimal string".to_string()
+    ]));
+  }
+
+  #[test]
+  fn each_key_and_each_value_matchers_with_references_to_matcher_definitions() {
+    let expected = request!(r#"{ "a": { "x": "1" }, "b": { "1": "value" } }"#);
+    let reference = MatchingRuleDefinition {
+      value: "".to_string(),
+      value_type: ValueType::Unknown,
+      rules: vec![ Either::Right(MatchingReference { name: "id".to_string() }) ],
+      generator: None
+    };
+    let mut rules = matchingrules_list! {
+      "body";
+      "$.a" => [ MatchingRule::EachValue(reference.clone()) ],
+      "$.b" => [ MatchingRule::EachKey(reference) ]
+    };
+    rules.add_definition("id", RuleList::new(MatchingRule::Regex("^\\d+$".to_string())));
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys, &rules, &hashmap!{});
+
+    let actual = request!(r#"{ "a": { "y": "22", "z": "333" }, "b": { "42": "value" } }"#);
+    expect!(match_json(&expected, &actual, &context)).to(be_ok());
+
+    let actual = request!(r#"{ "a": { "y": "abc" }, "b": { "key": "value" } }"#);
+    let mismatches = match_json(&expected, &actual, &context).unwrap_err();
+    expect!(mismatches.iter().map(|m| m.description().split(" -> ").next().unwrap_or_default().to_string()).sorted().collect::<Vec<_>>()).to(be_equal_to(vec![
+      "$.a.y".to_string(),
+      "$.b.key".to_string()
+    ]));
+  }
+
+  #[test]
+  fn nullable_matcher_accepts_null_or_the_wrapped_matcher() {
+    let expected = request!(r#"
+    { "name": "Fred", "address": { "street": "Main St" } }
+    "#);
+
+    let rules = matchingrules! {
+      "body" => {
+        "$.name" => [ MatchingRule::Nullable(Box::new(MatchingRule::Type)) ],
+        "$.address" => [ MatchingRule::Nullable(Box::new(MatchingRule::Type)) ]
+      }
+    };
+    let context = CoreMatchingContext::new(
+      DiffConfig::AllowUnexpectedKeys,
+      &rules.rules_for_category("body").unwrap_or_default(),
+      &hashmap!{}
+    );
+
+    expect!(match_json(&expected, &request!(r#"{ "name": null, "address": null }"#), &context)).to(be_ok());
+    expect!(match_json(&expected, &request!(r#"{ "name": "Mary", "address": { "street": "High St" } }"#), &context)).to(be_ok());
+    expect!(match_json(&expected, &request!(r#"{ "name": 100, "address": null }"#), &context)).to(be_err().value(vec![
+      BodyMismatch {
+        path: "$.name".to_string(),
+        expected: Some("\"Fred\"".into()),
+        actual: Some("100".into()),
+        mismatch: "Expected 100 (Integer) to be the same type as 'Fred' (String)".to_string(),
+      }
+    ]));
+  }
+
+  #[test]
+  fn compare_lists_with_each_value_one_of_matcher() {
+    let expected = request!(r#"
+    ["RED"]
+    "#);
+
+    let rules = matchingrules! {
+      "body" => { "$" => [ MatchingRule::EachValue(MatchingRuleDefinition::new("\"RED\"".to_string(), ValueType::String,
+        MatchingRule::OneOf(vec!["RED".to_string(), "GREEN".to_string()]), None)) ] }
+    };
+    let context = CoreMatchingContext::new(
+      DiffConfig::AllowUnexpectedKeys,
+      &rules.rules_for_category("body").unwrap_or_default(),
+      &hashmap!{}
+    );
+
+    expect!(match_json(&expected, &request!(r#"["GREEN", "RED", "GREEN"]"#), &context)).to(be_ok());
+    expect!(match_json(&expected, &request!(r#"["GREEN", "BLUE"]"#), &context)).to(be_err().value(vec![
+      BodyMismatch {
+        path: "$[1]".to_string(),
+        expected: Some("\"RED\"".into()),
+        actual: Some("\"BLUE\"".into()),
+        mismatch: "Expected 'BLUE' to be one of [RED, GREEN]".to_string(),
+      }
+    ]));
+  }
+
+  #[test]
+  fn canonical_json_string_test() {
+    expect!(canonical_json_string(&json!("a \"quoted\" string"))).to(be_equal_to(r#""a \"quoted\" string""#.to_string()));
+    expect!(canonical_json_string(&json!(100))).to(be_equal_to("100".to_string()));
+    expect!(canonical_json_string(&json!(-100))).to(be_equal_to("-100".to_string()));
+    expect!(canonical_json_string(&json!(1.0))).to(be_equal_to("1.0".to_string()));
+    expect!(canonical_json_string(&serde_json::from_str::<Value>("1.50").unwrap())).to(be_equal_to("1.5".to_string()));
+    expect!(canonical_json_string(&json!(true))).to(be_equal_to("true".to_string()));
+    expect!(canonical_json_string(&Value::Null)).to(be_equal_to("null".to_string()));
+    expect!(canonical_json_string(&json!({ "b": [1, 2], "a": { "d": null, "c": "x" } })))
+      .to(be_equal_to(r#"{"a":{"c":"x","d":null},"b":[1,2]}"#.to_string()));
+  }
+
+  #[test]
+  fn json_regex_matcher_test() {
+    let matcher = MatchingRule::JsonRegex("^-?\\d+(\\.\\d{1,2})?$".to_string());
+    expect!(json!(1.5).matches_with(&json!(12.25), &matcher, false)).to(be_ok());
+    expect!(json!(1.5).matches_with(&json!(12), &matcher, false)).to(be_ok());
+    expect!(json!(1.5).matches_with(&json!(12.255), &matcher, false)).to(be_err());
+    expect!(json!(1.5).matches_with(&json!("12.25"), &matcher, false)).to(be_err());
+
+    let matcher = MatchingRule::JsonRegex("^\"[A-Z]+\"$".to_string());
+    expect!(json!("ABC").matches_with(&json!("XYZ"), &matcher, false)).to(be_ok());
+    expect!(json!("ABC").matches_with(&json!(100), &matcher, false)).to(be_err());
+    expect!("ABC".matches_with("XYZ", &matcher, false)).to(be_ok());
+
+    let matcher = MatchingRule::JsonRegex("^\\{\"id\":\\d+,\"name\":.*$".to_string());
+    expect!(json!({}).matches_with(&json!({ "name": "x", "id": 1 }), &matcher, false)).to(be_ok());
+    expect!(json!(1.5).matches_with(&json!(12.255), &MatchingRule::JsonRegex("[".to_string()), false)).to(be_err());
+  }
+
+  #[test]
+  fn compare_lists_with_ip_address_matcher() {
+    let expected = request!(r#"
+    ["10.0.0.1"]
+    "#);
+
+    let rules = matchingrules! {
+      "body" => { "$" => [ MatchingRule::IpAddress { version: Some(4) } ] }
+    };
+    let context = CoreMatchingContext::new(
+      DiffConfig::AllowUnexpectedKeys,
+      &rules.rules_for_category("body").unwrap_or_default(),
+      &hashmap!{}
+    );
+
+    expect!(match_json(&expected, &request!(r#"["192.168.0.1", "127.0.0.1"]"#), &context)).to(be_ok());
+    expect!(match_json(&expected, &request!(r#"["192.168.0.1", "::1", "10.0.0.300"]"#), &context)).to(be_err().value(vec![
+      BodyMismatch {
+        path: "$[1]".to_string(),
+        expected: Some("\"10.0.0.1\"".into()),
+        actual: Some("\"::1\"".into()),
+        mismatch: "Expected '::1' to be an IPv4 address, but it is an IPv6 address".to_string(),
+      },
+      BodyMismatch {
+        path: "$[2]".to_string(),
+        expected: Some("\"10.0.0.1\"".into()),
+        actual: Some("\"10.0.0.300\"".into()),
+        mismatch: "Expected '10.0.0.300' to be a valid IPv4 address".to_string(),
+      }
+    ]));
+  }
+
   #[test]
   fn compare_lists_with_array_contains_matcher_with_more_complex_object() {
     let expected = request!(r#"
@@ -1282,6 +2116,35 @@ mod tests {
     expect!(result).to(be_err());
   }
 
+  #[test]
+  fn compare_json_with_empty_matcher() {
+    let expected = json!({ "items": [], "meta": {}, "name": "" });
+    let rules = matchingrules_list! {
+      "body";
+      "$.items" => [ MatchingRule::Empty ],
+      "$.meta" => [ MatchingRule::Empty ],
+      "$.name" => [ MatchingRule::Empty ]
+    };
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys, &rules, &hashmap!{});
+
+    expect!(compare_json(&DocPath::root(), &expected, &expected, &context)).to(be_ok());
+
+    let actual = json!({ "items": [1, 2], "meta": { "page": 1 }, "name": "test" });
+    let mismatches = compare_json(&DocPath::root(), &expected, &actual, &context).unwrap_err();
+    let descriptions = mismatches.iter().map(|m| m.description.clone()).collect::<Vec<_>>();
+    expect!(descriptions.iter().any(|d| d == "Expected an empty Array but found 2 item(s): [1, 2]")).to(be_true());
+    expect!(descriptions.iter().any(|d| d == "Expected an empty Object but found 1 key(s): 'page'")).to(be_true());
+    expect!(descriptions.iter().any(|d| d == "Expected 'test' (String) to be empty")).to(be_true());
+
+    let actual = json!({ "items": null, "meta": {}, "name": "" });
+    expect!(compare_json(&DocPath::root(), &expected, &actual, &context)).to(be_err());
+
+    let actual = json!({ "meta": {}, "name": "" });
+    let mismatches = compare_json(&DocPath::root(), &expected, &actual, &context).unwrap_err();
+    expect!(mismatches.iter().any(|m| m.description.contains("to be empty") || m.description.contains("Expected an empty")))
+      .to(be_false());
+  }
+
   #[test_log::test]
   fn compare_maps_with_each_value_matcher() {
     let expected_json = json!({
@@ -1314,6 +2177,80 @@ mod tests {
     let result = compare_maps(&DocPath::root(), expected, invalid, &context);
     expect!(result).to(be_err());
   }
+
+  #[test]
+  fn compare_maps_with_allowed_keys_matcher() {
+    let expected_json = json!({
+      "id": 100
+    });
+    let expected = expected_json.as_object().unwrap();
+    let matchingrules = matchingrules_list! {
+      "body"; "$" => [ MatchingRule::AllowedKeys(vec!["id".to_string(), "name".to_string()]) ]
+    };
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
+      &matchingrules, &hashmap!{});
+
+    let actual_json = json!({
+      "id": 100,
+      "name": "Fred"
+    });
+    let actual = actual_json.as_object().unwrap();
+    let result = compare_maps(&DocPath::root(), expected, actual, &context);
+    expect!(result).to(be_ok());
+
+    let invalid_json = json!({
+      "id": 100,
+      "name": "Fred",
+      "password": "secret"
+    });
+    let invalid = invalid_json.as_object().unwrap();
+    let result = compare_maps(&DocPath::root(), expected, invalid, &context);
+    let messages = result.unwrap_err().iter().map(|m| m.to_string()).collect::<Vec<String>>();
+    expect!(messages).to(be_equal_to(vec![
+      "Expected the keys to be one of [\"id\",\"name\"] but found key(s) that are not allowed [\"password\"]".to_string()
+    ]));
+  }
+
+  #[test]
+  fn compare_maps_with_keys_matcher_ignores_the_values() {
+    let expected_json = json!({
+      "id": 100,
+      "name": "Fred"
+    });
+    let expected = expected_json.as_object().unwrap();
+    let matchingrules = matchingrules_list! {
+      "body"; "$" => [ MatchingRule::Keys(vec!["id".to_string(), "name".to_string()]) ]
+    };
+    let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
+      &matchingrules, &hashmap!{});
+
+    let actual_json = json!({
+      "id": "not a number",
+      "name": [ "Mary" ]
+    });
+    let actual = actual_json.as_object().unwrap();
+    let result = compare_maps(&DocPath::root(), expected, actual, &context);
+    expect!(result).to(be_ok());
+
+    let missing_json = json!({
+      "id": 100
+    });
+    let missing = missing_json.as_object().unwrap();
+    let result = compare_maps(&DocPath::root(), expected, missing, &context);
+    let messages = result.unwrap_err().iter().map(|m| m.description.clone()).collect::<Vec<String>>();
+    expect!(messages).to(be_equal_to(vec![
+      "Expected a Map with exactly the keys [id, name] but received one with keys [id]".to_string()
+    ]));
+
+    let extra_json = json!({
+      "id": 100,
+      "name": "Fred",
+      "password": "secret"
+    });
+    let extra = extra_json.as_object().unwrap();
+    let result = compare_maps(&DocPath::root(), expected, extra, &context);
+    expect!(result).to(be_err());
+  }
 }
 
 #[cfg(test)]
@@ -1402,4 +2339,47 @@ mod tests2 {
       expect!(result).to(be_err());
     }
   }
+
+  #[test]
+  fn compare_json_compatibility_allows_additive_changes() {
+    let old = json!({
+      "id": 100,
+      "price": 10,
+      "description": null,
+      "tags": [ "a" ]
+    });
+    let new = json!({
+      "id": 200,
+      "price": 10.5,
+      "description": "Some item",
+      "tags": [ "b", "c" ],
+      "created": "2024-01-01"
+    });
+    expect!(compare_json_compatibility(&DocPath::root(), &old, &new)).to(be_empty());
+  }
+
+  #[test]
+  fn compare_json_compatibility_returns_the_breaking_changes() {
+    let old = json!({
+      "id": 100,
+      "name": "Fred",
+      "price": 10.5,
+      "items": [ { "sku": "a" } ]
+    });
+    let new = json!({
+      "id": "100",
+      "price": 10,
+      "items": [ { "sku": "a" }, { "code": "b" } ]
+    });
+    let mut descriptions = compare_json_compatibility(&DocPath::root(), &old, &new).iter()
+      .map(|m| format!("{} -> {}", m.path, m.description))
+      .collect::<Vec<_>>();
+    descriptions.sort();
+    expect!(descriptions).to(be_equal_to(vec![
+      "$.id -> Type has been changed from Integer to String".to_string(),
+      "$.items[1].sku -> Field 'sku' has been removed".to_string(),
+      "$.name -> Field 'name' has been removed".to_string(),
+      "$.price -> Type has been narrowed from Decimal to Integer".to_string()
+    ]));
+  }
 }
@@ -15,12 +15,14 @@
 //! any response.
 //!
 //! ## Crate features
-//! All features are enabled by default.
+//! All features are enabled by default, except for `image` and `json_schema`.
 //!
 //! * `datetime`: Enables support of date and time expressions and generators. This will add the `chronos` crate as a dependency.
 //! * `xml`: Enables support for parsing XML documents. This feature will add the `sxd-document` crate as a dependency.
 //! * `plugins`: Enables support for using plugins. This feature will add the `pact-plugin-driver` crate as a dependency.
 //! * `multipart`: Enables support for MIME multipart bodies. This feature will add the `multer` crate as a dependency.
+//! * `image`: Enables the image similarity matcher. This feature will add the `image` crate as a dependency.
+//! * `json_schema`: Enables the JSON Schema matcher. This feature will add the `jsonschema` crate as a dependency.
 //!
 //! ## Reading and writing Pact files
 //!
@@ -40,6 +42,12 @@
 //! same elements that match in the same sequence, with cases where the additional elements
 //! in an actual Map are ignored.
 //!
+//! To make all the matching strict, pass `MatchingConfig::strict()` to `match_request_with_config`
+//! or `match_response_with_config`. Unexpected keys in response bodies will then be mismatches, and
+//! header values, content types and number types must match exactly. The same configuration can
+//! be passed to `match_message_with_config` and `match_sync_message_response_with_config`, where
+//! the unexpected keys and number types settings apply to the message contents.
+//!
 //! Matching rules can be defined for both request and response elements based on a pseudo JSON-Path
 //! syntax.
 //!
@@ -81,6 +89,12 @@
 //!
 //! Postel's law governs if we allow unexpected keys or not.
 //!
+//! If the Pact has been configured with case-insensitive keys (`caseInsensitiveKeys` in the
+//! `pactRust` metadata section), the keys are compared ignoring case. Note that this is ambiguous
+//! if more than one actual key is equal to the expected key ignoring case (i.e. `Id` and `ID`).
+//! In that case, a key that is exactly equal is used, otherwise the first key in sorted order.
+//! The other keys are treated as unexpected keys.
+//!
 //! ##### For comparing lists
 //!
 //! 1. If there is a body matcher defined that matches the path to the list, default
@@ -103,6 +117,10 @@
 //! matcher
 //! 2. Otherwise compare the values using equality.
 //!
+//! If the Pact has been configured with strict number types (`strictNumberTypes` in the `pactRust`
+//! metadata section), integer and decimal numbers are treated as different types when matching by
+//! type (i.e. `100` will not match `100.0`).
+//!
 //! #### XML body matching rules
 //!
 //! Bodies consist of a root element, Elements (Lists with children), Attributes (Maps) and values (Strings).
@@ -183,6 +201,26 @@
 //! 1. If there is a matcher defined for `path`, default to that matcher.
 //! 2. Otherwise paths are compared as Strings
 //!
+//! #### Matching Path Segments
+//!
+//! Matchers can also be defined for the individual segments of the path, keyed by the index of the
+//! segment (i.e. `$[1]` is the `100` segment of `/users/100`). If there are any segment matchers,
+//! the paths must have the same number of segments, and each segment is compared using the
+//! matchers defined for it, or as Strings if there are none. Any matcher defined for the whole
+//! path is applied as well.
+//!
+//! Segment matchers are only supported by V4 pacts, where the `path` category is written keyed by
+//! the segment path (with `$` for the whole path), i.e. `"path": { "$": {...}, "$[1]": {...} }`.
+//! When writing V2 or V3 pacts, the segment matchers are dropped and a warning is logged.
+//!
+//! #### Matching the Request Target
+//!
+//! If the matchers defined for `path` include a `rawTarget` marker, the path and query string are
+//! matched together as a single string (i.e. `/path?a=1&b=2`) using the other matchers defined for
+//! `path`, and the query parameters are not compared separately. The target of the actual request
+//! as it was received is used if it is passed in (see `match_request_with_raw_target`), otherwise
+//! it is built from the path and the parsed query parameters, with the parameters sorted by name.
+//!
 //! ### Matching Queries
 //!
 //! 1. If the actual and expected query strings are empty, they match.
@@ -206,7 +244,13 @@
 //! For matching header values:
 //!
 //! 1. If there is a matcher defined for `header.<HEADER_KEY>`, default to that matcher
-//! 2. Otherwise strip all whitespace after commas and compare the resulting strings.
+//! 2. Otherwise strip all whitespace after commas and compare the resulting strings. If the Pact
+//!    has been configured with strict header whitespace (`strictHeaderWhitespace` in the `pactRust`
+//!    metadata section), the whitespace is not stripped and the exact values are compared.
+//!
+//! If the header has an `unorderedValues` matching rule defined for it, the header values are
+//! compared ignoring their order. Each expected value must match a different actual value (using
+//! any other matching rules defined for the header), and no additional values are allowed.
 //!
 //! #### Matching Request Headers
 //!
@@ -216,6 +260,29 @@
 //!
 //! If the list of expected cookies contains all the actual cookies, the cookies match.
 //!
+//! #### Matching Cookies
+//!
+//! If a `Cookie` or `Set-Cookie` header has a `cookie` matching rule defined for it, the header
+//! values are parsed into structured cookies and compared the same way as a JSON body, with any
+//! other matching rules for paths under the header name applied to the parsed values. Additional
+//! cookies and attributes in the actual header are allowed.
+//!
+//! * `Cookie` request headers are parsed into an object of the cookie names mapped to their
+//!   values, i.e. `$.cookie.session` matches the value of the `session` cookie.
+//! * `Set-Cookie` response headers are parsed into an object of the cookie names mapped to the
+//!   cookie attributes: `value`, `domain`, `path`, `expires`, `maxAge`, `sameSite`, `secure` and
+//!   `httpOnly`, i.e. `$['set-cookie'].session.value`. The `secure` and `httpOnly` flags are
+//!   only present (with a value of `true`) if they are set, so they are only checked if they are
+//!   set on the expected cookie.
+//!
+//! #### Matching JSON Header Values
+//!
+//! If a header has a `jsonValue` matching rule defined for it, the header values are joined back
+//! together with commas and parsed as JSON, and then compared the same way as a JSON body. Any
+//! other matching rules for paths under the header name are applied to the parsed value, i.e.
+//! `$['x-data'].id` applies to the `id` attribute of the JSON value of the `X-Data` header. An
+//! actual value that is not valid JSON is a mismatch.
+//!
 //! ### Matching Status Codes
 //!
 //! Status codes are compared as integer values.
@@ -224,6 +291,19 @@
 //!
 //! The actual and expected methods are compared as case-insensitive strings.
 //!
+//! ### Redacting sensitive values
+//!
+//! By default, mismatches include the expected and actual values. Values such as tokens or personal
+//! data can be marked as sensitive by adding a `sensitive` matching rule at their path (i.e. `$.token`
+//! in the body, or `$.Authorization` in the headers). Any mismatches at those paths (or at a parent
+//! path, as the value there contains the sensitive value) will have the expected and actual values
+//! replaced with `***`, and their descriptions will not include the values. If any part of a body is
+//! sensitive, the bodies in a body type mismatch are also redacted.
+//!
+//! The marker does not change how the values are matched. It is ignored when selecting the matchers
+//! for a path, so the value is matched with any other rules at the path, the rules of a parent path,
+//! or the default comparison.
+//!
 //! ## Matching Rules
 //!
 //! Pact supports extending the matching rules on each type of object (Request or Response) with a `matchingRules` element in the pact file.
@@ -333,18 +413,41 @@
 //! | MinMaxType | V2 | `{ "match": "type", "max": 10, "min": 2 }` | This executes a type based match against the values, that is, they are equal if they are the same type. In addition, if the values represent a collection, the length of the actual value is compared against the minimum and maximum. |
 //! | Include | V3 | `{ "match": "include", "value": "substr" }` | This checks if the string representation of a value contains the substring. |
 //! | Integer | V3 | `{ "match": "integer" }` | This checks if the type of the value is an integer. |
+//! | IntegerRange | V4 | `{ "match": "integerRange", "min": 0, "max": 100 }` | This checks if the value is an integer between the minimum and maximum (inclusive). |
 //! | Decimal | V3 | `{ "match": "decimal" }` | This checks if the type of the value is a number with decimal places. |
 //! | Number | V3 | `{ "match": "number" }` | This checks if the type of the value is a number. |
 //! | Timestamp | V3 | `{ "match": "datetime", "format": "yyyy-MM-dd HH:ss:mm" }` | Matches the string representation of a value against the datetime format |
 //! | Time  | V3 | `{ "match": "time", "format": "HH:ss:mm" }` | Matches the string representation of a value against the time format |
+//! | DateTimeAfter | V4 | `{ "match": "datetimeAfter", "format": "yyyy-MM-dd'T'HH:mm:ssXXX", "reference": "now" }` | Matches the string representation of a value against the datetime format, and checks that it is after the reference (either `now` or a literal datetime in the same format, references to context values are not supported) |
+//! | DateTimeBefore | V4 | `{ "match": "datetimeBefore", "format": "yyyy-MM-dd", "reference": "2030-01-01" }` | Matches the string representation of a value against the datetime format, and checks that it is before the reference (either `now` or a literal datetime in the same format, references to context values are not supported) |
 //! | Date  | V3 | `{ "match": "date", "format": "yyyy-MM-dd" }` | Matches the string representation of a value against the date format |
 //! | Null  | V3 | `{ "match": "null" }` | Match if the value is a null value (this is content specific, for JSON will match a JSON null) |
 //! | Boolean  | V3 | `{ "match": "boolean" }` | Match if the value is a boolean value (booleans and the string values `true` and `false`) |
 //! | ContentType  | V3 | `{ "match": "contentType", "value": "image/jpeg" }` | Match binary data by its content type (magic file check) |
 //! | Values  | V3 | `{ "match": "values" }` | Match the values in a map, ignoring the keys |
+//! | Keys | V4 | `{ "match": "keys", "value": ["a", "b"] }` | Match the keys in a map, which must be exactly the given keys, ignoring the values |
+//! | OneOf | V4 | `{ "match": "oneOf", "values": ["RED", "GREEN", "BLUE"] }` | Match if the string value is one of the given values |
+//! | Format | V4 | `{ "match": "format", "format": "email" }` | Match if the string value is valid for the named format, using the validator registered for the format (see `register_format_validator`). `email` and `phone-e164` are built in. |
+//! | IpAddress | V4 | `{ "match": "ipAddress", "version": 4 }` | Match if the string value is a valid IP address. The version (4 or 6) is optional. |
+//! | JsonRegex | V4 | `{ "match": "jsonRegex", "regex": "^\\d+(\\.\\d{1,2})?$" }` | Match the value serialised as canonical JSON text against the regex. See `json::canonical_json_string` for the serialisation rules |
+//! | MultipleOf | V4 | `{ "match": "multipleOf", "base": 5 }` | Match if the value is a number that is a multiple of the base. The base must be a positive number. Values are compared with a tolerance of `1e-9` (`MULTIPLE_OF_TOLERANCE`), so decimal bases like `0.01` work |
+//! | Cookie | V4 | `{ "match": "cookie" }` | Marker for a `Cookie` or `Set-Cookie` header that parses the header values into structured cookies, so matching rules can be applied to each cookie. See [Matching Cookies](#matching-cookies) |
+//! | RawTarget | V4 | `{ "match": "rawTarget" }` | Marker for the request path that causes the path and query string to be matched together as a single string with the other path matchers. See [Matching the Request Target](#matching-the-request-target) |
+//! | NoCascade | V4 | `{ "match": "noCascade" }` | Marker that stops rules from cascading past the path. The other rules at the path and the rules of any parent paths are not applied to the descendants of the path, only rules defined below it are. With no other rules at the path, the value is compared for equality |
+//! | Any | V4 | `{ "match": "any" }` | Match any value. At the root of a body, the contents of the body are ignored and only the presence of the body and its content type are checked |
+//! | Nullable | V4 | `{ "match": "nullable", "rule": { "match": "type" } }` | Match if the value is null, or matches the given matching rule |
 //! | ArrayContains | V4 | `{ "match": "arrayContains", "variants": [...] }` | Checks if all the variants are present in an array. |
+//! | ArrayContainsInOrder | V4 | `{ "match": "arrayContainsInOrder" }` | Checks if all the expected items are present in an array in the same relative order, allowing other items between them. |
+//! | ArrayDistinct | V4 | `{ "match": "arrayDistinct", "by": "$.id" }` | Checks that all the items in an array are unique, optionally comparing the value at the `by` path of each item. |
+//! | JsonSchema | V4 | `{ "match": "jsonSchema", "schema": { "type": "object" } }` | Validates the JSON value against the JSON Schema. At the root of the body, each validation error is reported at the path of the invalid value (requires the `json_schema` feature). |
 //! | StatusCode | V4 | `{ "match": "statusCode", "status": "success" }` | Matches the response status code. |
 //! | NotEmpty | V4 | `{ "match": "notEmpty" }` | Value must be present and not empty (not null or the empty string) |
+//! | Empty | V4 | `{ "match": "empty" }` | Value must be present and empty (an empty object, array or string). A missing or null value does not match |
+//! | NumericString | V4 | `{ "match": "numericString", "tolerance": 0.01 }` | Match if the value is a string containing a number that is numerically equal to the expected one (i.e. `"100.50"` matches `"100.5"`). The tolerance is optional, and allows the numbers to differ by up to that amount |
+//! | Hex | V4 | `{ "match": "hex", "length": 64 }` | Match if the value is a string of hexadecimal digits. The length (number of digits) is optional |
+//! | UnorderedValues | V4 | `{ "match": "unorderedValues" }` | Marks a multi-valued header so that the values are compared ignoring their order |
+//! | JsonValue | V4 | `{ "match": "jsonValue" }` | Marker for a header with a JSON value that parses the header value as JSON, so matching rules can be applied to the JSON value. See [Matching JSON Header Values](#matching-json-header-values) |
+//! | Sensitive | V4 | `{ "match": "sensitive" }` | Marks the value as sensitive, so the expected and actual values are redacted from any mismatches. The marker is ignored when selecting the matchers for the value, so the value is matched with any other rules at the path, the rules of a parent path, or the default comparison |
 //! | Semver | V4 | `{ "match": "semver" }` | Value must be valid based on the semver specification |
 //! | Semver | V4 | `{ "match": "semver" }` | Value must be valid based on the semver specification |
 //! | EachKey | V4 | `{ "match": "eachKey", "rules": [{"match": "regex", "regex": "\\$(\\.\\w+)+"}], "value": "$.test.one" }` | Allows defining matching rules to apply to the keys in a map |
@@ -359,6 +462,7 @@ use std::hash::Hash;
 use std::panic::RefUnwindSafe;
 use std::str;
 use std::str::from_utf8;
+use std::sync::{Arc, RwLock};
 
 use ansi_term::*;
 use ansi_term::Colour::*;
@@ -378,17 +482,18 @@ use pact_models::generators::{apply_generators, GenerateValue, GeneratorCategory
 use pact_models::http_parts::HttpPart;
 use pact_models::interaction::Interaction;
 use pact_models::json_utils::json_to_string;
-use pact_models::matchingrules::{Category, MatchingRule, MatchingRuleCategory, RuleList};
+use pact_models::matchingrules::{Category, MatchingRule, MatchingRuleCategory, MatchingRules, RuleList};
 use pact_models::pact::Pact;
 use pact_models::PactSpecification;
-use pact_models::path_exp::DocPath;
+use pact_models::query_strings::build_query_string;
+use pact_models::path_exp::{DocPath, PathToken};
 use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
 use pact_models::v4::message_parts::MessageContents;
 use pact_models::v4::sync_message::SynchronousMessage;
 
 use crate::generators::bodies::generators_process_body;
 use crate::generators::DefaultVariantMatcher;
-use crate::headers::{match_header_value, match_headers};
+use crate::headers::{match_body_hash_headers, match_header_correlations, match_header_value, match_headers};
 #[cfg(feature = "plugins")] use crate::json::match_json;
 use crate::matchers::*;
 use crate::matchingrules::DisplayForMismatch;
@@ -454,8 +559,32 @@ pub trait MatchingContext: Debug {
 
   /// Clones the current context with the provided matching rules
   fn clone_with(&self, matchers: &MatchingRuleCategory) -> Box<dyn MatchingContext + Send + Sync>;
+
+  /// Settings that change how values are compared (i.e. strict header whitespace or
+  /// case-insensitive keys)
+  fn matching_config(&self) -> MatchingConfig {
+    MatchingConfig::default()
+  }
+
+  /// Maximum depth of nested values that will be compared before a mismatch is returned
+  fn max_depth(&self) -> usize {
+    DEFAULT_MAX_DEPTH
+  }
+
+  /// Maximum number of items in an actual list that will be compared before a mismatch is returned
+  fn max_array_length(&self) -> usize {
+    DEFAULT_MAX_ARRAY_LENGTH
+  }
 }
 
+/// Default maximum depth of nested values that will be compared. This is below the recursion limit
+/// of 128 that `serde_json` applies when parsing, so that the limit can be reached by bodies that
+/// can be parsed.
+pub const DEFAULT_MAX_DEPTH: usize = 100;
+
+/// Default maximum number of items in a list that will be compared
+pub const DEFAULT_MAX_ARRAY_LENGTH: usize = 100_000;
+
 #[derive(Debug, Clone)]
 /// Core implementation of a matching context
 pub struct CoreMatchingContext {
@@ -466,7 +595,13 @@ pub struct CoreMatchingContext {
   /// Specification version to apply when matching with the context
   pub matching_spec: PactSpecification,
   /// Any plugin configuration available for the interaction
-  pub plugin_configuration: HashMap<String, PluginInteractionConfig>
+  pub plugin_configuration: HashMap<String, PluginInteractionConfig>,
+  /// Settings that change how values are compared
+  pub matching_config: MatchingConfig,
+  /// Maximum depth of nested values that will be compared
+  pub max_depth: usize,
+  /// Maximum number of items in an actual list that will be compared
+  pub max_array_length: usize
 }
 
 impl CoreMatchingContext {
@@ -492,6 +627,30 @@ impl CoreMatchingContext {
     }
   }
 
+  /// Sets the settings that change how values are compared. See `MatchingConfig` for the
+  /// settings. By default, none of them are enabled.
+  pub fn with_matching_config(mut self, matching_config: MatchingConfig) -> Self {
+    self.matching_config = matching_config;
+    self
+  }
+
+  /// Sets the maximum depth of nested values that will be compared. Any value nested deeper than
+  /// this will result in a mismatch instead of being compared. Defaults to `DEFAULT_MAX_DEPTH`.
+  /// Note that JSON bodies nested deeper than 128 levels will fail to be parsed, so larger values
+  /// will have no effect on them.
+  pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+    self.max_depth = max_depth;
+    self
+  }
+
+  /// Sets the maximum number of items in an actual list that will be compared. Any list longer
+  /// than this will result in a mismatch instead of being compared. Defaults to
+  /// `DEFAULT_MAX_ARRAY_LENGTH`.
+  pub fn with_max_array_length(mut self, max_array_length: usize) -> Self {
+    self.max_array_length = max_array_length;
+    self
+  }
+
   fn matchers_for_exact_path(&self, path: &DocPath) -> MatchingRuleCategory {
     match self.matchers.name {
       Category::HEADER | Category::QUERY => self.matchers.filter(|&(val, _)| {
@@ -512,6 +671,9 @@ impl CoreMatchingContext {
       matchers: context.matchers().clone(),
       config: context.config().clone(),
       plugin_configuration: context.plugin_configuration().clone(),
+      matching_config: context.matching_config(),
+      max_depth: context.max_depth(),
+      max_array_length: context.max_array_length(),
       .. CoreMatchingContext::default()
     }
   }
@@ -523,7 +685,10 @@ impl Default for CoreMatchingContext {
       matchers: Default::default(),
       config: DiffConfig::AllowUnexpectedKeys,
       matching_spec: PactSpecification::V3,
-      plugin_configuration: Default::default()
+      plugin_configuration: Default::default(),
+      matching_config: MatchingConfig::default(),
+      max_depth: DEFAULT_MAX_DEPTH,
+      max_array_length: DEFAULT_MAX_ARRAY_LENGTH
     }
   }
 }
@@ -566,10 +731,22 @@ impl MatchingContext for CoreMatchingContext {
     expected_keys.sort();
     let mut actual_keys = actual.iter().cloned().collect::<Vec<String>>();
     actual_keys.sort();
-    let missing_keys: Vec<String> = expected.iter().filter(|key| !actual.contains(*key)).cloned().collect();
+    let missing_keys: Vec<String> = expected.iter().filter(|key| {
+      if self.matching_config.case_insensitive_keys {
+        !actual.iter().any(|actual_key| actual_key.to_lowercase() == key.to_lowercase())
+      } else {
+        !actual.contains(*key)
+      }
+    }).cloned().collect();
+    let keys_equal = if self.matching_config.case_insensitive_keys {
+      expected_keys.iter().map(|key| key.to_lowercase()).sorted().collect_vec() ==
+        actual_keys.iter().map(|key| key.to_lowercase()).sorted().collect_vec()
+    } else {
+      expected_keys == actual_keys
+    };
     let mut result = vec![];
 
-    if !self.direct_matcher_defined(path, &hashset! { "values", "each-value", "each-key" }) {
+    if !self.direct_matcher_defined(path, &hashset! { "values", "each-value", "each-key", "keys" }) {
       match self.config {
         DiffConfig::AllowUnexpectedKeys if !missing_keys.is_empty() => {
           result.push(CommonMismatch {
@@ -579,7 +756,7 @@ impl MatchingContext for CoreMatchingContext {
             description: format!("Actual map is missing the following keys: {}", missing_keys.join(", ")),
           });
         }
-        DiffConfig::NoUnexpectedKeys if expected_keys != actual_keys => {
+        DiffConfig::NoUnexpectedKeys if !keys_equal => {
           result.push(CommonMismatch {
             path: path.to_string(),
             expected: expected.for_mismatch(),
@@ -624,6 +801,24 @@ impl MatchingContext for CoreMatchingContext {
               }
             }
           }
+          MatchingRule::Keys(keys) => {
+            let normalise = |key: &String| if self.matching_config.case_insensitive_keys {
+              key.to_lowercase()
+            } else {
+              key.clone()
+            };
+            let expected_keys = keys.iter().map(normalise).sorted().dedup().collect_vec();
+            let received_keys = actual_keys.iter().map(normalise).sorted().collect_vec();
+            if expected_keys != received_keys {
+              result.push(CommonMismatch {
+                path: path.to_string(),
+                expected: keys.for_mismatch(),
+                actual: actual.for_mismatch(),
+                description: format!("Expected a Map with exactly the keys [{}] but received one with keys [{}]",
+                  keys.join(", "), actual_keys.join(", ")),
+              });
+            }
+          }
           _ => {}
         }
       }
@@ -653,9 +848,39 @@ impl MatchingContext for CoreMatchingContext {
       matchers: matchers.clone(),
       config: self.config.clone(),
       matching_spec: self.matching_spec,
-      plugin_configuration: self.plugin_configuration.clone()
+      plugin_configuration: self.plugin_configuration.clone(),
+      matching_config: self.matching_config,
+      max_depth: self.max_depth,
+      max_array_length: self.max_array_length
     })
   }
+
+  fn matching_config(&self) -> MatchingConfig {
+    self.matching_config
+  }
+
+  fn max_depth(&self) -> usize {
+    self.max_depth
+  }
+
+  fn max_array_length(&self) -> usize {
+    self.max_array_length
+  }
+}
+
+/// Downcases the first field of the path (the header name)
+fn lower_case_header_name(path: &DocPath) -> DocPath {
+  let mut first_field = true;
+  path.tokens().iter().fold(DocPath::empty(), |mut lower_case_path, token| {
+    match token {
+      PathToken::Field(name) if first_field => {
+        first_field = false;
+        lower_case_path.push(PathToken::Field(name.to_lowercase()))
+      }
+      _ => lower_case_path.push(token.clone())
+    };
+    lower_case_path
+  })
 }
 
 #[derive(Debug, Clone, Default)]
@@ -665,24 +890,32 @@ pub struct HeaderMatchingContext {
 }
 
 impl HeaderMatchingContext {
-  /// Wraps a MatchingContext, downcasing all the matching path keys
+  /// Wraps a MatchingContext, downcasing the header names in all the matching paths. Any path
+  /// tokens after the header name (i.e. for headers with JSON values) are left as is. The matching
+  /// configuration of the wrapped context is kept.
   pub fn new(context: &(dyn MatchingContext + Send + Sync)) -> Self {
     let matchers = context.matchers();
+    let mut header_matchers = matchers.clone();
+    header_matchers.rules = matchers.rules.iter()
+      .map(|(path, rules)| {
+        (lower_case_header_name(path), rules.clone())
+      })
+      .collect();
     HeaderMatchingContext {
       inner_context: CoreMatchingContext::new(
         context.config(),
-        &MatchingRuleCategory {
-          name: matchers.name.clone(),
-          rules: matchers.rules.iter()
-            .map(|(path, rules)| {
-              (path.to_lower_case(), rules.clone())
-            })
-            .collect()
-        },
+        &header_matchers,
         &context.plugin_configuration()
-      )
+      ).with_matching_config(context.matching_config())
     }
   }
+
+  /// Sets the settings that change how values are compared. The strict header whitespace and
+  /// strict content type settings apply to the headers. See `MatchingConfig` for the settings.
+  pub fn with_matching_config(mut self, matching_config: MatchingConfig) -> Self {
+    self.inner_context.matching_config = matching_config;
+    self
+  }
 }
 
 impl MatchingContext for HeaderMatchingContext {
@@ -728,10 +961,145 @@ impl MatchingContext for HeaderMatchingContext {
         matchers: matchers.clone(),
         config: self.inner_context.config.clone(),
         matching_spec: self.inner_context.matching_spec,
-        plugin_configuration: self.inner_context.plugin_configuration.clone()
+        plugin_configuration: self.inner_context.plugin_configuration.clone(),
+        matching_config: self.inner_context.matching_config,
+        max_depth: self.inner_context.max_depth,
+        max_array_length: self.inner_context.max_array_length
       }
     ))
   }
+
+  fn matching_config(&self) -> MatchingConfig {
+    self.inner_context.matching_config
+  }
+}
+
+/// Key in the Pact-Rust metadata section of a Pact used to disallow unexpected keys in the bodies
+/// of responses
+pub const NO_UNEXPECTED_KEYS: &str = "noUnexpectedKeys";
+
+/// Key in the Pact-Rust metadata section of a Pact used to enable strict whitespace matching of
+/// header values
+pub const STRICT_HEADER_WHITESPACE: &str = "strictHeaderWhitespace";
+
+/// Key in the Pact-Rust metadata section of a Pact used to require the content types of bodies
+/// to match exactly
+pub const STRICT_CONTENT_TYPE: &str = "strictContentType";
+
+/// Key in the Pact-Rust metadata section of a Pact used to enable bracket notation for form post
+/// parameters
+pub const FORM_BRACKET_NOTATION: &str = "formBracketNotation";
+
+/// Key in the Pact-Rust metadata section of a Pact used to enable case-insensitive matching of
+/// the keys of JSON objects
+pub const CASE_INSENSITIVE_KEYS: &str = "caseInsensitiveKeys";
+
+/// Key in the Pact-Rust metadata section of a Pact used to treat integer and decimal numbers as
+/// different types when matching by type
+pub const STRICT_NUMBER_TYPES: &str = "strictNumberTypes";
+
+/// If the flag with the given key has been set to `true` in the Pact-Rust metadata section of
+/// the Pact
+pub fn pact_rust_flag(pact: &(dyn Pact + Send + Sync + RefUnwindSafe), key: &str) -> bool {
+  pact.metadata().get("pactRust")
+    .and_then(|md| md.get(key))
+    .map(|value| value == "true")
+    .unwrap_or(false)
+}
+
+/// Settings that change how requests and responses are matched. These can be stored in the
+/// Pact-Rust metadata section of a Pact (see `MatchingConfig::from_pact`), or passed to the
+/// matching functions to apply to all the interactions (i.e. `match_request_with_config`). The
+/// settings passed in are combined with the ones from the Pact, so a setting is enabled if it is
+/// set in either. The default configuration does not change the matching behaviour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MatchingConfig {
+  /// If unexpected keys in the actual bodies of responses are mismatches. Requests never allow
+  /// unexpected keys.
+  pub no_unexpected_keys: bool,
+  /// If header values must match exactly. By default, whitespace around the values (and around
+  /// any parameters) is removed before they are compared.
+  pub strict_header_whitespace: bool,
+  /// If the content types of bodies must have the same sub-type. By default, a body with a content
+  /// type that has the same base type as the expected one (i.e. `application/hal+json` and
+  /// `application/json`) will be matched.
+  pub strict_content_type: bool,
+  /// If integer and decimal numbers should be treated as different types when matching by type
+  /// (i.e. `100` will not match `100.0` with a type matcher)
+  pub strict_number_types: bool,
+  /// If form post parameters using bracket notation (i.e. `a[]=1&a[]=2` or `a[0]=1&a[1]=2`)
+  /// should be treated as multiple values of the same parameter (i.e. `a=1&a=2`)
+  pub form_bracket_notation: bool,
+  /// If the keys of JSON objects should be compared ignoring case (i.e. `{"Id": 1}` will match
+  /// `{"id": 1}`). If more than one actual key is equal to an expected key ignoring case, a key
+  /// that matches exactly is used, otherwise the first one in sorted order. The other keys are
+  /// then treated as unexpected keys.
+  pub case_insensitive_keys: bool
+}
+
+impl MatchingConfig {
+  /// Configuration that makes all the matching strict (i.e. does not follow Postel's law): no
+  /// unexpected keys in any body, header values must match exactly, content types must match
+  /// exactly and integer and decimal numbers are different types.
+  pub fn strict() -> MatchingConfig {
+    MatchingConfig {
+      no_unexpected_keys: true,
+      strict_header_whitespace: true,
+      strict_content_type: true,
+      strict_number_types: true,
+      .. MatchingConfig::default()
+    }
+  }
+
+  /// Reads the settings from the Pact-Rust metadata section of the Pact
+  pub fn from_pact(pact: &(dyn Pact + Send + Sync + RefUnwindSafe)) -> MatchingConfig {
+    MatchingConfig {
+      no_unexpected_keys: pact_rust_flag(pact, NO_UNEXPECTED_KEYS),
+      strict_header_whitespace: pact_rust_flag(pact, STRICT_HEADER_WHITESPACE),
+      strict_content_type: pact_rust_flag(pact, STRICT_CONTENT_TYPE),
+      strict_number_types: pact_rust_flag(pact, STRICT_NUMBER_TYPES),
+      form_bracket_notation: pact_rust_flag(pact, FORM_BRACKET_NOTATION),
+      case_insensitive_keys: pact_rust_flag(pact, CASE_INSENSITIVE_KEYS)
+    }
+  }
+
+  /// Returns the keys for the Pact-Rust metadata section of a Pact for the settings that are
+  /// enabled
+  pub fn metadata_keys(&self) -> Vec<&'static str> {
+    [
+      (self.no_unexpected_keys, NO_UNEXPECTED_KEYS),
+      (self.strict_header_whitespace, STRICT_HEADER_WHITESPACE),
+      (self.strict_content_type, STRICT_CONTENT_TYPE),
+      (self.strict_number_types, STRICT_NUMBER_TYPES),
+      (self.form_bracket_notation, FORM_BRACKET_NOTATION),
+      (self.case_insensitive_keys, CASE_INSENSITIVE_KEYS)
+    ].iter()
+      .filter(|(enabled, _)| *enabled)
+      .map(|(_, key)| *key)
+      .collect()
+  }
+
+  /// Combines the settings with the other ones, so that a setting is enabled if it is enabled
+  /// in either
+  pub fn merge(&self, other: &MatchingConfig) -> MatchingConfig {
+    MatchingConfig {
+      no_unexpected_keys: self.no_unexpected_keys || other.no_unexpected_keys,
+      strict_header_whitespace: self.strict_header_whitespace || other.strict_header_whitespace,
+      strict_content_type: self.strict_content_type || other.strict_content_type,
+      strict_number_types: self.strict_number_types || other.strict_number_types,
+      form_bracket_notation: self.form_bracket_notation || other.form_bracket_notation,
+      case_insensitive_keys: self.case_insensitive_keys || other.case_insensitive_keys
+    }
+  }
+
+  /// Diff configuration to use for the bodies of responses
+  fn response_diff_config(&self) -> DiffConfig {
+    if self.no_unexpected_keys {
+      DiffConfig::NoUnexpectedKeys
+    } else {
+      DiffConfig::AllowUnexpectedKeys
+    }
+  }
 }
 
 lazy_static! {
@@ -747,6 +1115,65 @@ lazy_static! {
   ];
 }
 
+/// Function used to match the bodies of a particular content type. It is passed the content type,
+/// and the expected and actual bodies, and returns any mismatches.
+pub type BodyMatcherFn = dyn Fn(&ContentType, &Bytes, &Bytes) -> Vec<Mismatch> + Send + Sync;
+
+lazy_static! {
+  static ref CUSTOM_BODY_MATCHERS: RwLock<HashMap<String, Arc<BodyMatcherFn>>> = RwLock::new(hashmap!{});
+}
+
+/// Registers a custom matcher for bodies with the given content type. Custom matchers are used
+/// in preference to any plugin or core matchers, and are keyed by the base content type (i.e.
+/// without any parameters). Any existing matcher for the content type is replaced.
+pub fn register_body_matcher(content_type: &ContentType, matcher: Arc<BodyMatcherFn>) {
+  let mut matchers = CUSTOM_BODY_MATCHERS.write().unwrap();
+  matchers.insert(content_type.base_type().to_string(), matcher);
+}
+
+/// Removes the custom matcher for bodies with the given content type. Returns true if there was
+/// a matcher registered for the content type.
+pub fn unregister_body_matcher(content_type: &ContentType) -> bool {
+  let mut matchers = CUSTOM_BODY_MATCHERS.write().unwrap();
+  matchers.remove(&content_type.base_type().to_string()).is_some()
+}
+
+fn find_custom_body_matcher(content_type: &ContentType) -> Option<Arc<BodyMatcherFn>> {
+  let matchers = CUSTOM_BODY_MATCHERS.read().unwrap();
+  matchers.get(&content_type.base_type().to_string()).cloned()
+}
+
+/// Function used to validate a string value for a named format, used by `Format` matching
+/// rules. It returns an error describing why the value is not valid.
+pub type FormatValidatorFn = dyn Fn(&str) -> anyhow::Result<()> + Send + Sync;
+
+lazy_static! {
+  static ref FORMAT_VALIDATORS: RwLock<HashMap<String, Arc<FormatValidatorFn>>> = RwLock::new(hashmap!{
+    "email".to_string() => Arc::new(validate_email) as Arc<FormatValidatorFn>,
+    "phone-e164".to_string() => Arc::new(validate_e164_phone_number) as Arc<FormatValidatorFn>
+  });
+}
+
+/// Registers a validator for the named format, which will be used by any `Format` matching rules
+/// for the format. The built-in formats are `email` and `phone-e164`. Any existing validator for
+/// the format (including the built-in ones) is replaced.
+pub fn register_format_validator(format: &str, validator: Arc<FormatValidatorFn>) {
+  let mut validators = FORMAT_VALIDATORS.write().unwrap();
+  validators.insert(format.to_string(), validator);
+}
+
+/// Removes the validator for the named format. Returns true if there was a validator registered
+/// for the format.
+pub fn unregister_format_validator(format: &str) -> bool {
+  let mut validators = FORMAT_VALIDATORS.write().unwrap();
+  validators.remove(format).is_some()
+}
+
+pub(crate) fn find_format_validator(format: &str) -> Option<Arc<FormatValidatorFn>> {
+  let validators = FORMAT_VALIDATORS.read().unwrap();
+  validators.get(format).cloned()
+}
+
 fn match_xml(
   expected: &(dyn HttpPart + Send + Sync),
   actual: &(dyn HttpPart + Send + Sync),
@@ -1166,6 +1593,156 @@ impl Display for Mismatch {
   }
 }
 
+const REDACTED: &str = "***";
+
+/// Redacts the expected and actual values from the mismatch if the matching rules mark the
+/// location of the mismatch (or a location inside the value at it) as sensitive with a
+/// `Sensitive` matching rule. The values are replaced with `***`, and the description is replaced
+/// with one that does not include them. Body type mismatches have the bodies redacted if any part
+/// of the body is sensitive. Mismatches at locations that are not marked as sensitive are
+/// returned unchanged.
+pub fn redact_sensitive_values(mismatch: Mismatch, matching_rules: &MatchingRules) -> Mismatch {
+  match mismatch {
+    Mismatch::PathMismatch { .. } if category_is_sensitive(matching_rules, "path") => Mismatch::PathMismatch {
+      expected: REDACTED.to_string(),
+      actual: REDACTED.to_string(),
+      mismatch: "Path did not match (the values are sensitive and have been redacted)".to_string()
+    },
+    Mismatch::QueryMismatch { parameter, .. }
+      if path_is_sensitive(matching_rules, "query", &["$", parameter.as_str()], false) => Mismatch::QueryMismatch {
+      expected: REDACTED.to_string(),
+      actual: REDACTED.to_string(),
+      mismatch: format!("Mismatch with query parameter '{}' (the values are sensitive and have been redacted)", parameter),
+      parameter
+    },
+    Mismatch::HeaderMismatch { key, .. }
+      if path_is_sensitive(matching_rules, "header", &["$", key.to_lowercase().as_str()], true) => Mismatch::HeaderMismatch {
+      expected: REDACTED.to_string(),
+      actual: REDACTED.to_string(),
+      mismatch: format!("Mismatch with header '{}' (the values are sensitive and have been redacted)", key),
+      key
+    },
+    Mismatch::BodyMismatch { path, .. } if body_path_is_sensitive(matching_rules, &path) => Mismatch::BodyMismatch {
+      path,
+      expected: Some(REDACTED.into()),
+      actual: Some(REDACTED.into()),
+      mismatch: "Value did not match (the values are sensitive and have been redacted)".to_string()
+    },
+    Mismatch::MetadataMismatch { key, .. }
+      if path_is_sensitive(matching_rules, "metadata", &["$", key.as_str()], false) => Mismatch::MetadataMismatch {
+      expected: REDACTED.to_string(),
+      actual: REDACTED.to_string(),
+      mismatch: format!("Mismatch with metadata key '{}' (the values are sensitive and have been redacted)", key),
+      key
+    },
+    Mismatch::BodyTypeMismatch { expected, actual, mismatch, expected_body, actual_body }
+      if body_is_sensitive(matching_rules) => Mismatch::BodyTypeMismatch {
+      expected,
+      actual,
+      mismatch,
+      expected_body: expected_body.map(|_| REDACTED.into()),
+      actual_body: actual_body.map(|_| REDACTED.into())
+    },
+    _ => mismatch
+  }
+}
+
+fn redact_mismatches(mismatches: Vec<Mismatch>, matching_rules: &MatchingRules) -> Vec<Mismatch> {
+  mismatches.into_iter()
+    .map(|mismatch| redact_sensitive_values(mismatch, matching_rules))
+    .collect()
+}
+
+fn redact_request_result(result: RequestMatchResult, matching_rules: &MatchingRules) -> RequestMatchResult {
+  RequestMatchResult {
+    method: result.method,
+    path: result.path.map(|mismatches| redact_mismatches(mismatches, matching_rules)),
+    body: match result.body {
+      BodyMatchResult::BodyMismatches(results) => BodyMatchResult::BodyMismatches(results.into_iter()
+        .map(|(path, mismatches)| (path, redact_mismatches(mismatches, matching_rules)))
+        .collect()),
+      BodyMatchResult::BodyTypeMismatch { expected_type, actual_type, message, expected, actual }
+        if body_is_sensitive(matching_rules) => BodyMatchResult::BodyTypeMismatch {
+        expected_type,
+        actual_type,
+        message,
+        expected: expected.map(|_| REDACTED.into()),
+        actual: actual.map(|_| REDACTED.into())
+      },
+      body => body
+    },
+    query: result.query.into_iter()
+      .map(|(parameter, mismatches)| (parameter, redact_mismatches(mismatches, matching_rules)))
+      .collect(),
+    headers: result.headers.into_iter()
+      .map(|(key, mismatches)| (key, redact_mismatches(mismatches, matching_rules)))
+      .collect()
+  }
+}
+
+fn category_is_sensitive(matching_rules: &MatchingRules, category: &str) -> bool {
+  matching_rules.rules_for_category(category)
+    .map(|rules| rules.rules.values().any(|rule_list| rule_list.is_sensitive()))
+    .unwrap_or(false)
+}
+
+fn path_is_sensitive(matching_rules: &MatchingRules, category: &str, path: &[&str], headers: bool) -> bool {
+  matching_rules.rules_for_category(category)
+    .map(|rules| rules.rules.iter().any(|(rule_path, rule_list)| {
+      let rule_path = if headers { lower_case_header_name(rule_path) } else { rule_path.clone() };
+      rule_list.is_sensitive() && (rule_path.matches_path(path) || is_parent_path(path, &rule_path))
+    }))
+    .unwrap_or(false)
+}
+
+// If the path is a parent of the rule path, the value at the path contains the value at the rule path
+fn is_parent_path(path: &[&str], rule_path: &DocPath) -> bool {
+  path.len() < rule_path.len() && rule_path.tokens().iter().zip(path.iter())
+    .all(|(token, fragment)| match token {
+      PathToken::Root => *fragment == "$",
+      PathToken::Field(name) => name == fragment,
+      PathToken::Index(index) => fragment.parse::<usize>().map(|i| i == *index).unwrap_or(false),
+      PathToken::Star => true,
+      PathToken::StarIndex => fragment.parse::<usize>().is_ok()
+    })
+}
+
+// V4 messages use the content category for the body rules
+fn body_is_sensitive(matching_rules: &MatchingRules) -> bool {
+  category_is_sensitive(matching_rules, "body") || category_is_sensitive(matching_rules, "content")
+}
+
+fn body_path_is_sensitive(matching_rules: &MatchingRules, path: &str) -> bool {
+  match DocPath::new(path) {
+    Ok(path) => {
+      let tokens = path.to_vec();
+      let tokens = tokens.iter().map(|token| token.as_str()).collect_vec();
+      path_is_sensitive(matching_rules, "body", &tokens, false) ||
+        path_is_sensitive(matching_rules, "content", &tokens, false)
+    }
+    // If the path can not be parsed, it can not be checked, so it is treated as sensitive
+    Err(_) => body_is_sensitive(matching_rules)
+  }
+}
+
+/// Compares two collections of mismatches for equivalence, ignoring the order of the mismatches.
+/// The collections are compared as multisets, so each mismatch must occur the same number of
+/// times in both. Mismatches are compared with the `PartialEq` implementation for `Mismatch`,
+/// so the mismatch descriptions are not compared.
+pub fn mismatches_equivalent(a: &[Mismatch], b: &[Mismatch]) -> bool {
+  if a.len() != b.len() {
+    return false;
+  }
+
+  // The derived ordering compares the fields used for equality before the descriptions, so
+  // sorting brings equivalent mismatches into the same positions
+  let mut a = a.to_vec();
+  a.sort();
+  let mut b = b.to_vec();
+  b.sort();
+  a == b
+}
+
 fn merge_result<T: Clone>(res1: Result<(), Vec<T>>, res2: Result<(), Vec<T>>) -> Result<(), Vec<T>> {
   match (&res1, &res2) {
     (Ok(_), Ok(_)) => res1.clone(),
@@ -1336,6 +1913,16 @@ pub enum DiffConfig {
     NoUnexpectedKeys
 }
 
+/// Controls how much work is done when matching a request or interaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchMode {
+  /// Compare every part and collect all the mismatches. This is what verification needs.
+  AllMismatches,
+  /// Return as soon as any part mismatches. Parts after the first mismatching one (including
+  /// the body) are not compared, so the result can only be used to tell if there was a match.
+  FirstMismatch
+}
+
 /// Matches the actual text body to the expected one.
 pub fn match_text(expected: &Option<Bytes>, actual: &Option<Bytes>, context: &dyn MatchingContext) -> Result<(), Vec<Mismatch>> {
   let path = DocPath::root();
@@ -1411,6 +1998,33 @@ pub fn match_method(expected: &str, actual: &str) -> Result<(), Mismatch> {
 
 /// Matches the actual request path to the expected one.
 pub fn match_path(expected: &str, actual: &str, context: &(dyn MatchingContext + Send + Sync)) -> Result<(), Vec<Mismatch>> {
+  let (path_rules, segment_rules) = context.matchers().rules.iter()
+    .map(|(path, rules)| (path.clone(), rules.clone()))
+    .partition::<HashMap<DocPath, RuleList>, _>(|(path, _)| path.len() <= 1);
+  if segment_rules.is_empty() {
+    match_whole_path(expected, actual, context)
+  } else {
+    let mut path_matchers = context.matchers().clone();
+    path_matchers.rules = path_rules;
+    let path_context = context.clone_with(&path_matchers);
+    let mut mismatches = vec![];
+    if path_context.matcher_is_defined(&DocPath::empty()) {
+      if let Err(err) = match_whole_path(expected, actual, path_context.as_ref()) {
+        mismatches.extend(err);
+      }
+    }
+    if let Err(err) = match_path_segments(expected, actual, &segment_rules) {
+      mismatches.extend(err);
+    }
+    if mismatches.is_empty() {
+      Ok(())
+    } else {
+      Err(mismatches)
+    }
+  }
+}
+
+fn match_whole_path(expected: &str, actual: &str, context: &(dyn MatchingContext + Send + Sync)) -> Result<(), Vec<Mismatch>> {
   let path = DocPath::empty();
   let matcher_result = if context.matcher_is_defined(&path) {
     match_values(&path, &context.select_best_matcher(&path), expected.to_string(), actual.to_string())
@@ -1426,6 +2040,105 @@ pub fn match_path(expected: &str, actual: &str, context: &(dyn MatchingContext +
   }).collect())
 }
 
+/// Matches the path segment by segment, using the matching rules defined for the individual
+/// segments (i.e. `$[1]` for the second segment of `/users/100`), or equality for segments
+/// without any rules.
+fn match_path_segments(
+  expected: &str,
+  actual: &str,
+  segment_rules: &HashMap<DocPath, RuleList>
+) -> Result<(), Vec<Mismatch>> {
+  let expected_segments = path_segments(expected);
+  let actual_segments = path_segments(actual);
+  if expected_segments.len() != actual_segments.len() {
+    return Err(vec![Mismatch::PathMismatch {
+      expected: expected.to_string(),
+      actual: actual.to_string(),
+      mismatch: format!("Expected a path with {} segment(s) but '{}' has {} segment(s)",
+        expected_segments.len(), actual, actual_segments.len())
+    }]);
+  }
+
+  let mismatches = expected_segments.iter()
+    .zip(actual_segments.iter())
+    .enumerate()
+    .flat_map(|(index, (expected_segment, actual_segment))| {
+      let path = DocPath::root().join_index(index);
+      let result = match segment_rules.get(&path) {
+        Some(rules) => match_values(&path, rules, expected_segment.to_string(), actual_segment.to_string()),
+        None => expected_segment.matches_with(*actual_segment, &MatchingRule::Equality, false)
+          .map_err(|err| vec![err.to_string()])
+      };
+      result.err().unwrap_or_default().into_iter()
+        .map(move |message| Mismatch::PathMismatch {
+          expected: expected.to_string(),
+          actual: actual.to_string(),
+          mismatch: format!("{} for path segment {}", message, index)
+        })
+    })
+    .collect_vec();
+  if mismatches.is_empty() {
+    Ok(())
+  } else {
+    Err(mismatches)
+  }
+}
+
+fn path_segments(path: &str) -> Vec<&str> {
+  path.strip_prefix('/').unwrap_or(path).split('/').collect()
+}
+
+/// If the path matching rules contain a `RawTarget` marker, the request path and query string
+/// are to be matched as a single string, instead of matching them separately.
+pub fn raw_target_matching_enabled(context: &(dyn MatchingContext + Send + Sync)) -> bool {
+  context.select_best_matcher(&DocPath::empty()).rules.contains(&MatchingRule::RawTarget)
+}
+
+/// Returns the request target (the path and query string, i.e. `/path?a=1&b=2`) of the request.
+/// The query string is built from the parsed query parameters, so the parameters will be sorted
+/// by name.
+pub fn raw_request_target(request: &HttpRequest) -> String {
+  match &request.query {
+    Some(query) if !query.is_empty() => format!("{}?{}", request.path, build_query_string(query.clone())),
+    _ => request.path.clone()
+  }
+}
+
+/// Matches the actual request target (path and query string) to the expected one as a single
+/// string, using the path matching rules. The `RawTarget` marker is not applied, and if there are
+/// no other path matching rules, the targets must be equal. If the target of the actual request
+/// as it was received is given, it is used instead of building one from the actual request.
+pub fn match_raw_target(
+  expected: &HttpRequest,
+  actual: &HttpRequest,
+  actual_raw_target: Option<&str>,
+  context: &(dyn MatchingContext + Send + Sync)
+) -> Result<(), Vec<Mismatch>> {
+  let path = DocPath::empty();
+  let expected_target = raw_request_target(expected);
+  let actual_target = actual_raw_target.map(|target| target.to_string())
+    .unwrap_or_else(|| raw_request_target(actual));
+  let rules = context.select_best_matcher(&path);
+  let target_rules = rules.rules.iter()
+    .filter(|rule| **rule != MatchingRule::RawTarget)
+    .cloned()
+    .collect_vec();
+  let matcher_result = if target_rules.is_empty() {
+    expected_target.as_str().matches_with(actual_target.as_str(), &MatchingRule::Equality, false)
+      .map_err(|err| vec![err.to_string()])
+  } else {
+    let rule_list = RuleList { rules: target_rules, .. rules };
+    match_values(&path, &rule_list, expected_target.clone(), actual_target.clone())
+  };
+  matcher_result.map_err(|messages| messages.iter().map(|message| {
+    Mismatch::PathMismatch {
+      expected: expected_target.clone(),
+      actual: actual_target.clone(),
+      mismatch: message.clone()
+    }
+  }).collect())
+}
+
 /// Matches the actual query parameters to the expected ones.
 pub fn match_query(
   expected: Option<HashMap<String, Vec<Option<String>>>>,
@@ -1478,6 +2191,14 @@ pub(crate) async fn compare_bodies(
 
   trace!(?content_type, "Comparing bodies");
 
+  if let Some(matcher) = find_custom_body_matcher(content_type) {
+    debug!("Using custom body matcher for content type '{}'", content_type);
+    let expected_body = expected.body().value().unwrap_or_default();
+    let actual_body = actual.body().value().unwrap_or_default();
+    mismatches.extend(matcher(content_type, &expected_body, &actual_body));
+    return body_match_result(mismatches);
+  }
+
   #[cfg(feature = "plugins")]
   {
     match find_content_matcher(content_type) {
@@ -1540,6 +2261,10 @@ pub(crate) async fn compare_bodies(
     mismatches.extend(compare_bodies_core(content_type, expected, actual, context));
   }
 
+  body_match_result(mismatches)
+}
+
+fn body_match_result(mismatches: Vec<Mismatch>) -> BodyMatchResult {
   if mismatches.is_empty() {
     BodyMatchResult::Ok
   } else {
@@ -1556,6 +2281,15 @@ fn compare_bodies_core(
   actual: &(dyn HttpPart + Send + Sync),
   context: &(dyn MatchingContext + Send + Sync)
 ) -> Vec<Mismatch> {
+  let root = DocPath::root();
+  if context.matcher_is_defined(&root) {
+    if let Some(schema) = context.select_best_matcher(&root).rules.iter()
+      .find_map(|rule| if let MatchingRule::JsonSchema(schema) = rule { Some(schema) } else { None }) {
+      debug!("Found a JSON Schema matcher, validating the body against the schema");
+      return match_json_schema_body(schema, actual);
+    }
+  }
+
   let mut mismatches = vec![];
   match BODY_MATCHERS.iter().find(|mt| mt.0(content_type)) {
     Some(match_fn) => {
@@ -1584,6 +2318,22 @@ fn compare_bodies_core(
   mismatches
 }
 
+fn match_json_schema_body(schema: &Value, actual: &(dyn HttpPart + Send + Sync)) -> Vec<Mismatch> {
+  let actual_body = actual.body().value().unwrap_or_default();
+  let result = serde_json::from_slice::<Value>(&actual_body)
+    .map_err(|err| anyhow!("Failed to parse the actual body as JSON: {}", err))
+    .and_then(|actual_json| json::validate_json_schema(&DocPath::root(), schema, &actual_json));
+  match result {
+    Ok(mismatches) => mismatches.iter().map(|mismatch| mismatch.to_body_mismatch()).collect(),
+    Err(err) => vec![Mismatch::BodyMismatch {
+      path: "$".to_string(),
+      expected: Some(Bytes::from(schema.to_string())),
+      actual: Some(actual_body),
+      mismatch: err.to_string()
+    }]
+  }
+}
+
 #[instrument(level = "trace", ret, skip_all, fields(%content_type, ?context))]
 async fn match_body_content(
   content_type: &ContentType,
@@ -1621,6 +2371,10 @@ async fn match_body_content(
         mismatch: format!("Expected body {} but was empty", e),
         path: s!("/")}]})
     },
+    (_, &OptionalBody::Present(_, _, _)) if context.direct_matcher_defined(&DocPath::root(), &hashset!{ "any" }) => {
+      debug!("Body has an 'any' matcher at the root, not comparing the contents");
+      BodyMatchResult::Ok
+    },
     (_, _) => compare_bodies(content_type, expected, actual, context).await
   }
 }
@@ -1638,9 +2392,16 @@ pub async fn match_body(
          actual_content_type);
   let content_type_matcher = header_context.select_best_matcher(&DocPath::root().join("Content-Type"));
   debug!("content type header matcher = '{:?}'", content_type_matcher);
-  if expected_content_type.is_unknown() || actual_content_type.is_unknown() ||
+  let content_types_match = if header_context.matching_config().strict_content_type {
+    expected_content_type.main_type == actual_content_type.main_type &&
+      expected_content_type.sub_type == actual_content_type.sub_type &&
+      expected_content_type.suffix == actual_content_type.suffix
+  } else {
     expected_content_type.is_equivalent_to(&actual_content_type) ||
-    expected_content_type.is_equivalent_to(&actual_content_type.base_type()) ||
+      expected_content_type.is_equivalent_to(&actual_content_type.base_type())
+  };
+  if expected_content_type.is_unknown() || actual_content_type.is_unknown() ||
+    content_types_match ||
     (!content_type_matcher.is_empty() &&
       match_header_value("Content-Type", 0, expected_content_type.to_string().as_str(),
                          actual_content_type.to_string().as_str(), header_context, true
@@ -1661,12 +2422,84 @@ pub async fn match_body(
 }
 
 /// Matches the expected and actual requests
-#[allow(unused_variables)]
 pub async fn match_request<'a>(
   expected: HttpRequest,
   actual: HttpRequest,
   pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
   interaction: &Box<dyn Interaction + Send + Sync + RefUnwindSafe>
+) -> RequestMatchResult {
+  match_request_with_mode(expected, actual, pact, interaction, MatchMode::AllMismatches).await
+}
+
+/// Returns true if the actual request matches the expected one. This stops at the first
+/// mismatch, so is cheaper than `match_request` when the mismatches are not needed.
+pub async fn request_matches<'a>(
+  expected: HttpRequest,
+  actual: HttpRequest,
+  pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
+  interaction: &Box<dyn Interaction + Send + Sync + RefUnwindSafe>
+) -> bool {
+  match_request_with_mode(expected, actual, pact, interaction, MatchMode::FirstMismatch).await
+    .all_matched()
+}
+
+/// Matches the expected and actual requests using the given match mode. The method, path,
+/// query parameters and headers are compared before the body. With `MatchMode::FirstMismatch`,
+/// the parts after the first one that mismatches are not compared and will be reported as
+/// matching in the result.
+pub async fn match_request_with_mode<'a>(
+  expected: HttpRequest,
+  actual: HttpRequest,
+  pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
+  interaction: &Box<dyn Interaction + Send + Sync + RefUnwindSafe>,
+  mode: MatchMode
+) -> RequestMatchResult {
+  match_request_with_config(expected, actual, pact, interaction, mode, &MatchingConfig::default()).await
+}
+
+/// Matches the expected and actual requests using the given match mode and matching
+/// configuration. See `match_request_with_mode`. Any values marked as sensitive by the
+/// expected matching rules are redacted from the mismatches.
+pub async fn match_request_with_config<'a>(
+  expected: HttpRequest,
+  actual: HttpRequest,
+  pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
+  interaction: &Box<dyn Interaction + Send + Sync + RefUnwindSafe>,
+  mode: MatchMode,
+  config: &MatchingConfig
+) -> RequestMatchResult {
+  match_request_with_raw_target(expected, actual, None, pact, interaction, mode, config).await
+}
+
+/// Matches the expected and actual requests, using the target of the actual request as it was
+/// received (the path and query string, i.e. `/orders?page=1`) when matching the request target
+/// with a `rawTarget` marker. See `match_request_with_config`. The raw target is not used if the
+/// expected request does not have the `rawTarget` marker.
+pub async fn match_request_with_raw_target<'a>(
+  expected: HttpRequest,
+  actual: HttpRequest,
+  raw_target: Option<&str>,
+  pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
+  interaction: &Box<dyn Interaction + Send + Sync + RefUnwindSafe>,
+  mode: MatchMode,
+  config: &MatchingConfig
+) -> RequestMatchResult {
+  let matching_rules = expected.matching_rules.clone();
+  let result = redact_request_result(
+    compare_requests(expected, actual, raw_target, pact, interaction, mode, config).await, &matching_rules);
+  debug!("--> Mismatches: {:?}", result.mismatches());
+  result
+}
+
+#[allow(unused_variables)]
+async fn compare_requests<'a>(
+  expected: HttpRequest,
+  actual: HttpRequest,
+  raw_target: Option<&str>,
+  pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
+  interaction: &Box<dyn Interaction + Send + Sync + RefUnwindSafe>,
+  mode: MatchMode,
+  config: &MatchingConfig
 ) -> RequestMatchResult {
   debug!("comparing to expected {}", expected);
   debug!("     body: '{}'", expected.body.display_string());
@@ -1680,12 +2513,14 @@ pub async fn match_request<'a>(
   };
   trace!("plugin_data = {:?}", plugin_data);
 
+  let config = config.merge(&MatchingConfig::from_pact(pact.as_ref()));
   let path_context = CoreMatchingContext::new(DiffConfig::NoUnexpectedKeys,
     &expected.matching_rules.rules_for_category("path").unwrap_or_default(),
     &plugin_data);
   let body_context = CoreMatchingContext::new(DiffConfig::NoUnexpectedKeys,
     &expected.matching_rules.rules_for_category("body").unwrap_or_default(),
-    &plugin_data);
+    &plugin_data)
+    .with_matching_config(config);
   let query_context = CoreMatchingContext::new(DiffConfig::NoUnexpectedKeys,
     &expected.matching_rules.rules_for_category("query").unwrap_or_default(),
     &plugin_data);
@@ -1694,16 +2529,45 @@ pub async fn match_request<'a>(
      &expected.matching_rules.rules_for_category("header").unwrap_or_default(),
      &plugin_data
     )
-  );
-  let result = RequestMatchResult {
-    method: match_method(&expected.method, &actual.method).err(),
-    path: match_path(&expected.path, &actual.path, &path_context).err(),
-    body: match_body(&expected, &actual, &body_context, &header_context).await,
-    query: match_query(expected.query, actual.query, &query_context),
-    headers: match_headers(expected.headers, actual.headers, &header_context)
+  ).with_matching_config(config);
+  let mut result = RequestMatchResult {
+    method: None,
+    path: None,
+    body: BodyMatchResult::Ok,
+    query: hashmap!{},
+    headers: hashmap!{}
   };
-
-  debug!("--> Mismatches: {:?}", result.mismatches());
+  let stop_at_first = mode == MatchMode::FirstMismatch;
+
+  result.method = match_method(&expected.method, &actual.method).err();
+  if stop_at_first && result.method.is_some() {
+    debug!("--> Method did not match, not comparing the rest of the request");
+    return result;
+  }
+  if raw_target_matching_enabled(&path_context) {
+    result.path = match_raw_target(&expected, &actual, raw_target, &path_context).err();
+    if stop_at_first && result.path.is_some() {
+      debug!("--> Request target did not match, not comparing the rest of the request");
+      return result;
+    }
+  } else {
+    result.path = match_path(&expected.path, &actual.path, &path_context).err();
+    if stop_at_first && result.path.is_some() {
+      debug!("--> Path did not match, not comparing the rest of the request");
+      return result;
+    }
+    result.query = match_query(expected.query.clone(), actual.query.clone(), &query_context);
+    if stop_at_first && !result.query.values().all(|m| m.is_empty()) {
+      debug!("--> Query parameters did not match, not comparing the rest of the request");
+      return result;
+    }
+  }
+  result.headers = match_headers(expected.headers.clone(), actual.headers.clone(), &header_context);
+  if stop_at_first && !result.headers.values().all(|m| m.is_empty()) {
+    debug!("--> Headers did not match, not comparing the rest of the request");
+    return result;
+  }
+  result.body = match_body(&expected, &actual, &body_context, &header_context).await;
   result
 }
 
@@ -1734,14 +2598,49 @@ pub fn match_status(expected: u16, actual: u16, context: &dyn MatchingContext) -
 }
 
 /// Matches the actual and expected responses.
-#[allow(unused_variables)]
 pub async fn match_response<'a>(
   expected: HttpResponse,
   actual: HttpResponse,
   pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
   interaction: &Box<dyn Interaction + Send + Sync + RefUnwindSafe>
+) -> Vec<Mismatch> {
+  match_response_with_config(expected, actual, pact, interaction, &MatchingConfig::default()).await
+}
+
+/// Matches the actual and expected responses using the given matching configuration. Any values
+/// marked as sensitive by the expected matching rules are redacted from the mismatches.
+///
+/// Correlation matching rules on the response headers are checked against the request of the
+/// expected interaction. Use `match_response_to_request` to check them against the request that
+/// was actually made.
+pub async fn match_response_with_config<'a>(
+  expected: HttpResponse,
+  actual: HttpResponse,
+  pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
+  interaction: &Box<dyn Interaction + Send + Sync + RefUnwindSafe>,
+  config: &MatchingConfig
+) -> Vec<Mismatch> {
+  let request = interaction.as_v4_http()
+    .map(|interaction| interaction.request)
+    .unwrap_or_default();
+  match_response_to_request(expected, actual, &request, pact, interaction, config).await
+}
+
+/// Matches the actual and expected responses using the given matching configuration, checking any
+/// correlation matching rules on the response headers against the headers of the given request
+/// (i.e. the request that was made to get the actual response). Any values marked as sensitive by
+/// the expected matching rules are redacted from the mismatches.
+#[allow(unused_variables)]
+pub async fn match_response_to_request<'a>(
+  expected: HttpResponse,
+  actual: HttpResponse,
+  request: &HttpRequest,
+  pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
+  interaction: &Box<dyn Interaction + Send + Sync + RefUnwindSafe>,
+  config: &MatchingConfig
 ) -> Vec<Mismatch> {
   let mut mismatches = vec![];
+  let matching_rules = expected.matching_rules.clone();
 
   debug!("comparing to expected response: {}", expected);
   #[allow(unused_mut, unused_assignments)] let mut plugin_data = hashmap!{};
@@ -1751,31 +2650,111 @@ pub async fn match_response<'a>(
   };
   trace!("plugin_data = {:?}", plugin_data);
 
+  let config = config.merge(&MatchingConfig::from_pact(pact.as_ref()));
   let status_context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
     &expected.matching_rules.rules_for_category("status").unwrap_or_default(),
     &plugin_data);
-  let body_context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
+  let body_context = CoreMatchingContext::new(config.response_diff_config(),
     &expected.matching_rules.rules_for_category("body").unwrap_or_default(),
-    &plugin_data);
+    &plugin_data).with_matching_config(config);
   let header_context = HeaderMatchingContext::new(
     &CoreMatchingContext::new(DiffConfig::NoUnexpectedKeys,
       &expected.matching_rules.rules_for_category("header").unwrap_or_default(),
       &plugin_data
     )
-  );
+  ).with_matching_config(config);
 
   mismatches.extend_from_slice(match_body(&expected, &actual, &body_context, &header_context).await
     .mismatches().as_slice());
   if let Err(m) = match_status(expected.status, actual.status, &status_context) {
     mismatches.extend_from_slice(&m);
   }
+  let body_hash_mismatches = match_body_hash_headers(
+    &expected.matching_rules.rules_for_category("header").unwrap_or_default(),
+    &actual.headers, &actual.body);
+  let correlation_mismatches = match_header_correlations(
+    &expected.matching_rules.rules_for_category("header").unwrap_or_default(),
+    &request.headers, &actual.headers);
   let result = match_headers(expected.headers, actual.headers,
                              &header_context);
   for values in result.values() {
     mismatches.extend_from_slice(values.as_slice());
   }
+  mismatches.extend(body_hash_mismatches);
+  mismatches.extend(correlation_mismatches);
+  let mismatches = redact_mismatches(mismatches, &matching_rules);
+
+  trace!(?mismatches, "match response");
+
+  mismatches
+}
+
+/// Checks if a new version of a response is backward compatible with an old version, returning
+/// the breaking changes. Unlike `match_response`, this is a looser comparison intended for
+/// checking the evolution of a provider API: the new response can add headers and body fields,
+/// change values and widen types (i.e. integer to decimal, or replace a null value). Changing the
+/// status or content type, removing headers or body fields and changing the type of a value are
+/// returned as mismatches. Only JSON bodies are compared structurally.
+pub fn is_backward_compatible(old_response: &HttpResponse, new_response: &HttpResponse) -> Vec<Mismatch> {
+  let mut mismatches = vec![];
+
+  if old_response.status != new_response.status {
+    mismatches.push(Mismatch::StatusMismatch {
+      expected: old_response.status,
+      actual: new_response.status,
+      mismatch: format!("Status has been changed from {} to {}", old_response.status, new_response.status)
+    });
+  }
 
-    trace!(?mismatches, "match response");
+  if let Some(headers) = &old_response.headers {
+    for key in headers.keys().sorted() {
+      if !new_response.has_header(key) {
+        mismatches.push(Mismatch::HeaderMismatch {
+          key: key.clone(),
+          expected: headers[key].join(", "),
+          actual: "".to_string(),
+          mismatch: format!("Header '{}' has been removed", key)
+        });
+      }
+    }
+  }
+
+  if let Some(old_body) = old_response.body.value() {
+    match new_response.body.value() {
+      Some(new_body) => {
+        let old_content_type = old_response.content_type().unwrap_or_default();
+        let new_content_type = new_response.content_type().unwrap_or_default();
+        if !old_content_type.is_equivalent_to(&new_content_type) {
+          mismatches.push(Mismatch::BodyTypeMismatch {
+            expected: old_content_type.to_string(),
+            actual: new_content_type.to_string(),
+            mismatch: format!("Content type has been changed from '{}' to '{}'", old_content_type, new_content_type),
+            expected_body: Some(old_body),
+            actual_body: Some(new_body)
+          });
+        } else if old_content_type.is_json() {
+          match (serde_json::from_slice::<Value>(&old_body), serde_json::from_slice::<Value>(&new_body)) {
+            (Ok(old_json), Ok(new_json)) => {
+              mismatches.extend(json::compare_json_compatibility(&DocPath::root(), &old_json, &new_json)
+                .iter().map(|mismatch| mismatch.to_body_mismatch()));
+            }
+            (Err(err), _) | (_, Err(err)) => mismatches.push(Mismatch::BodyMismatch {
+              path: "$".to_string(),
+              expected: Some(old_body),
+              actual: Some(new_body),
+              mismatch: format!("Failed to parse the JSON body - {}", err)
+            })
+          }
+        }
+      }
+      None => mismatches.push(Mismatch::BodyMismatch {
+        path: "$".to_string(),
+        expected: Some(old_body),
+        actual: None,
+        mismatch: "Body has been removed".to_string()
+      })
+    }
+  }
 
   mismatches
 }
@@ -1885,11 +2864,23 @@ fn match_metadata_value(
 }
 
 /// Matches the actual and expected messages.
-#[allow(unused_variables)]
 pub async fn match_message<'a>(
   expected: &Box<dyn Interaction + Send + Sync + RefUnwindSafe>,
   actual: &Box<dyn Interaction + Send + Sync + RefUnwindSafe>,
   pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>) -> Vec<Mismatch> {
+  match_message_with_config(expected, actual, pact, &MatchingConfig::default()).await
+}
+
+/// Matches the actual and expected messages using the given matching configuration. Messages
+/// have no headers, so only the settings for unexpected keys and number types are applied to
+/// the message contents.
+#[allow(unused_variables)]
+pub async fn match_message_with_config<'a>(
+  expected: &Box<dyn Interaction + Send + Sync + RefUnwindSafe>,
+  actual: &Box<dyn Interaction + Send + Sync + RefUnwindSafe>,
+  pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
+  config: &MatchingConfig
+) -> Vec<Mismatch> {
   let mut mismatches = vec![];
 
   if expected.is_message() && actual.is_message() {
@@ -1904,17 +2895,20 @@ pub async fn match_message<'a>(
       plugin_data = setup_plugin_config(pact, expected, InteractionPart::None);
     };
 
+    let config = config.merge(&MatchingConfig::from_pact(pact.as_ref()));
     let body_context = if expected.is_v4() {
       CoreMatchingContext {
         matchers: matching_rules.rules_for_category("content").unwrap_or_default(),
-        config: DiffConfig::AllowUnexpectedKeys,
+        config: config.response_diff_config(),
         matching_spec: PactSpecification::V4,
-        plugin_configuration: plugin_data.clone()
+        plugin_configuration: plugin_data.clone(),
+        matching_config: config,
+        .. CoreMatchingContext::default()
       }
     } else {
-      CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
+      CoreMatchingContext::new(config.response_diff_config(),
                            &matching_rules.rules_for_category("body").unwrap_or_default(),
-                           &plugin_data)
+                           &plugin_data).with_matching_config(config)
     };
 
     let metadata_context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
@@ -1926,6 +2920,7 @@ pub async fn match_message<'a>(
     for values in match_message_metadata(&expected_message.as_message_content(), &actual_message.as_message_content(), &metadata_context).values() {
       mismatches.extend_from_slice(values.as_slice());
     }
+    mismatches = redact_mismatches(mismatches, matching_rules);
   } else {
     mismatches.push(Mismatch::BodyTypeMismatch {
       expected: "message".into(),
@@ -1967,7 +2962,9 @@ pub async fn match_sync_message_request<'a>(
     matchers: matching_rules.rules_for_category("content").unwrap_or_default(),
     config: DiffConfig::AllowUnexpectedKeys,
     matching_spec: PactSpecification::V4,
-    plugin_configuration: plugin_data.clone()
+    plugin_configuration: plugin_data.clone(),
+    matching_config: MatchingConfig::from_pact(pact.as_ref()),
+    .. CoreMatchingContext::default()
   };
 
   let metadata_context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
@@ -1980,16 +2977,29 @@ pub async fn match_sync_message_request<'a>(
   for values in match_message_metadata(&expected.request, &actual.request, &metadata_context).values() {
     mismatches.extend_from_slice(values.as_slice());
   }
-  mismatches
+  redact_mismatches(mismatches, matching_rules)
 }
 
 /// Match the response part of a synchronous request/response message
-#[allow(unused_variables)]
 pub async fn match_sync_message_response<'a>(
   expected: &SynchronousMessage,
   expected_responses: &[MessageContents],
   actual_responses: &[MessageContents],
   pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>
+) -> Vec<Mismatch> {
+  match_sync_message_response_with_config(expected, expected_responses, actual_responses, pact,
+    &MatchingConfig::default()).await
+}
+
+/// Match the response part of a synchronous request/response message using the given matching
+/// configuration. See `match_message_with_config` for the settings that are applied.
+#[allow(unused_variables)]
+pub async fn match_sync_message_response_with_config<'a>(
+  expected: &SynchronousMessage,
+  expected_responses: &[MessageContents],
+  actual_responses: &[MessageContents],
+  pact: &Box<dyn Pact + Send + Sync + RefUnwindSafe + 'a>,
+  config: &MatchingConfig
 ) -> Vec<Mismatch> {
   debug!("comparing to expected message responses: {:?}", expected_responses);
 
@@ -2020,13 +3030,16 @@ pub async fn match_sync_message_response<'a>(
     {
       plugin_data = setup_plugin_config(pact, &expected.boxed(), InteractionPart::None);
     };
+    let config = config.merge(&MatchingConfig::from_pact(pact.as_ref()));
     for (expected_response, actual_response) in expected_responses.iter().zip(actual_responses) {
       let matching_rules = &expected_response.matching_rules;
       let body_context = CoreMatchingContext {
         matchers: matching_rules.rules_for_category("content").unwrap_or_default(),
-        config: DiffConfig::AllowUnexpectedKeys,
+        config: config.response_diff_config(),
         matching_spec: PactSpecification::V4,
-        plugin_configuration: plugin_data.clone()
+        plugin_configuration: plugin_data.clone(),
+        matching_config: config,
+        .. CoreMatchingContext::default()
       };
 
       let metadata_context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
@@ -2034,10 +3047,11 @@ pub async fn match_sync_message_response<'a>(
                                                   &plugin_data);
       let contents = match_message_contents(expected_response, actual_response, &body_context).await;
 
-      mismatches.extend_from_slice(contents.err().unwrap_or_default().as_slice());
+      let mut response_mismatches = contents.err().unwrap_or_default();
       for values in match_message_metadata(expected_response, actual_response, &metadata_context).values() {
-        mismatches.extend_from_slice(values.as_slice());
+        response_mismatches.extend_from_slice(values.as_slice());
       }
+      mismatches.extend(redact_mismatches(response_mismatches, matching_rules));
     }
   }
   mismatches
@@ -2204,20 +3218,52 @@ pub async fn match_interaction_response(
   if let Some(expected) = expected.as_v4_http() {
     let expected_response = expected.response.clone();
     let expected = expected.boxed();
-    let response = actual.as_v4_http()
-      .ok_or_else(|| anyhow!("Could not unpack actual response as a V4 Http Response"))?.response;
-    Ok(match_response(expected_response, response, &pact, &expected).await)
+    let actual = actual.as_v4_http()
+      .ok_or_else(|| anyhow!("Could not unpack actual response as a V4 Http Response"))?;
+    Ok(match_response_to_request(expected_response, actual.response, &actual.request, &pact, &expected,
+      &MatchingConfig::default()).await)
   } else {
     Err(anyhow!("match_interaction_response must be called with HTTP request/response interactions, got {}", expected.type_of()))
   }
 }
 
+/// Returns true if the request part of the actual interaction matches the expected one. This
+/// stops at the first mismatch, so is cheaper than `match_interaction_request` when the mismatches
+/// are not needed (for instance, when selecting the interaction that matches a request).
+pub async fn interaction_request_matches(
+  expected: Box<dyn Interaction + Send + Sync + RefUnwindSafe>,
+  actual: Box<dyn Interaction + Send + Sync + RefUnwindSafe>,
+  pact: Box<dyn Pact + Send + Sync + RefUnwindSafe>,
+  _spec_version: &PactSpecification
+) -> anyhow::Result<bool> {
+  if let Some(http_interaction) = expected.as_v4_http() {
+    let request = actual.as_v4_http()
+      .ok_or_else(|| anyhow!("Could not unpack actual request as a V4 Http Request"))?.request;
+    Ok(request_matches(http_interaction.request, request, &pact, &expected).await)
+  } else {
+    Err(anyhow!("interaction_request_matches must be called with HTTP request/response interactions, got {}", expected.type_of()))
+  }
+}
+
 /// Matches an interaction
 pub async fn match_interaction(
   expected: Box<dyn Interaction + Send + Sync + RefUnwindSafe>,
   actual: Box<dyn Interaction + Send + Sync + RefUnwindSafe>,
   pact: Box<dyn Pact + Send + Sync + RefUnwindSafe>,
-  _spec_version: &PactSpecification
+  spec_version: &PactSpecification
+) -> anyhow::Result<Vec<Mismatch>> {
+  match_interaction_with_mode(expected, actual, pact, spec_version, MatchMode::AllMismatches).await
+}
+
+/// Matches an interaction using the given match mode. With `MatchMode::FirstMismatch`, the
+/// response of an HTTP interaction is not compared if the request did not match, and only the
+/// mismatches from the first part of the request that did not match are returned.
+pub async fn match_interaction_with_mode(
+  expected: Box<dyn Interaction + Send + Sync + RefUnwindSafe>,
+  actual: Box<dyn Interaction + Send + Sync + RefUnwindSafe>,
+  pact: Box<dyn Pact + Send + Sync + RefUnwindSafe>,
+  _spec_version: &PactSpecification,
+  mode: MatchMode
 ) -> anyhow::Result<Vec<Mismatch>> {
   if let Some(expected) = expected.as_v4_http() {
     let expected_request = expected.request.clone();
@@ -2225,10 +3271,14 @@ pub async fn match_interaction(
     let expected = expected.boxed();
     let request = actual.as_v4_http()
       .ok_or_else(|| anyhow!("Could not unpack actual request as a V4 Http Request"))?.request;
-    let request_result = match_request(expected_request, request, &pact, &expected).await;
+    let request_result = match_request_with_mode(expected_request, request.clone(), &pact, &expected, mode).await;
+    if mode == MatchMode::FirstMismatch && !request_result.all_matched() {
+      return Ok(request_result.mismatches());
+    }
     let response = actual.as_v4_http()
       .ok_or_else(|| anyhow!("Could not unpack actual response as a V4 Http Response"))?.response;
-    let response_result = match_response(expected_response, response, &pact, &expected).await;
+    let response_result = match_response_to_request(expected_response, response, &request, &pact,
+      &expected, &MatchingConfig::default()).await;
     let mut mismatches = request_result.mismatches();
     mismatches.extend_from_slice(&*response_result);
     Ok(mismatches)
@@ -2239,6 +3289,87 @@ pub async fn match_interaction(
   }
 }
 
+/// Result of comparing an actual interaction to the expected one with `compare_interaction`.
+/// Only the parts for the type of the expected interaction will be set.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct InteractionComparisonResult {
+  /// Result of matching the request of an HTTP interaction
+  pub request: Option<RequestMatchResult>,
+  /// Mismatches with the response of an HTTP interaction, including any mismatches between the
+  /// request and response headers (header correlations)
+  pub response: Option<Vec<Mismatch>>,
+  /// Mismatches with the contents and metadata of a message, or with the request and responses
+  /// of a synchronous message
+  pub message: Option<Vec<Mismatch>>
+}
+
+impl InteractionComparisonResult {
+  /// Returns all the mismatches
+  pub fn mismatches(&self) -> Vec<Mismatch> {
+    let mut mismatches = vec![];
+
+    if let Some(request) = &self.request {
+      mismatches.extend(request.mismatches());
+    }
+    if let Some(response) = &self.response {
+      mismatches.extend_from_slice(response.as_slice());
+    }
+    if let Some(message) = &self.message {
+      mismatches.extend_from_slice(message.as_slice());
+    }
+
+    mismatches
+  }
+
+  /// If all the parts of the interaction matched OK
+  pub fn all_matched(&self) -> bool {
+    self.request.as_ref().map(|request| request.all_matched()).unwrap_or(true) &&
+      self.response.as_ref().map(|response| response.is_empty()).unwrap_or(true) &&
+      self.message.as_ref().map(|message| message.is_empty()).unwrap_or(true)
+  }
+}
+
+/// Compares the actual interaction to the expected one, returning the results for each part of
+/// the interaction. HTTP interactions will have their requests and responses compared, and
+/// messages (asynchronous or synchronous) will have their contents and metadata compared. An
+/// error is returned if the actual interaction is a different type to the expected one.
+pub async fn compare_interaction(
+  expected: &dyn Interaction,
+  actual: &dyn Interaction,
+  pact: &dyn Pact
+) -> anyhow::Result<InteractionComparisonResult> {
+  let pact = pact.boxed();
+  let expected = expected.boxed();
+  let actual = actual.boxed();
+
+  if let Some(expected_http) = expected.as_v4_http() {
+    let actual_http = actual.as_v4_http()
+      .ok_or_else(|| anyhow!("Can not compare a {} with a {}", expected.type_of(), actual.type_of()))?;
+    let request = match_request(expected_http.request.clone(), actual_http.request.clone(), &pact, &expected).await;
+    let response = match_response_to_request(expected_http.response.clone(), actual_http.response,
+      &actual_http.request, &pact, &expected, &MatchingConfig::default()).await;
+    Ok(InteractionComparisonResult {
+      request: Some(request),
+      response: Some(response),
+      .. InteractionComparisonResult::default()
+    })
+  } else if let Some(expected_message) = expected.as_v4_sync_message() {
+    let actual_message = actual.as_v4_sync_message()
+      .ok_or_else(|| anyhow!("Can not compare a {} with a {}", expected.type_of(), actual.type_of()))?;
+    Ok(InteractionComparisonResult {
+      message: Some(match_sync_message(expected_message, actual_message, &pact).await),
+      .. InteractionComparisonResult::default()
+    })
+  } else if expected.is_message() && actual.is_message() {
+    Ok(InteractionComparisonResult {
+      message: Some(match_message(&expected, &actual, &pact).await),
+      .. InteractionComparisonResult::default()
+    })
+  } else {
+    Err(anyhow!("Can not compare a {} with a {}", expected.type_of(), actual.type_of()))
+  }
+}
+
 #[cfg(test)]
 mod tests;
 #[cfg(test)]
@@ -1,16 +1,19 @@
 //! Matching rule implementations
 
+use std::net::IpAddr;
 use std::str::from_utf8;
 
 use anyhow::anyhow;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
 use bytes::Bytes;
 #[cfg(feature = "plugins")] use lazy_static::lazy_static;
 #[cfg(feature = "plugins")] use maplit::hashmap;
 use onig::Regex;
 use pact_models::HttpStatus;
-use pact_models::matchingrules::{MatchingRule, RuleList, RuleLogic};
+use pact_models::matchingrules::{MatchingRule, MULTIPLE_OF_TOLERANCE, RuleList, RuleLogic};
 use pact_models::path_exp::DocPath;
-#[cfg(feature = "datetime")] use pact_models::time_utils::validate_datetime;
+#[cfg(feature = "datetime")] use pact_models::time_utils::{parse_pattern, to_chrono_pattern, validate_datetime, validate_duration};
 #[cfg(feature = "plugins")]  use pact_plugin_driver::catalogue_manager::{
   CatalogueEntry,
   CatalogueEntryProviderType,
@@ -20,8 +23,9 @@ use pact_models::path_exp::DocPath;
 use semver::Version;
 use tracing::{debug, instrument, trace};
 
-use crate::binary_utils::match_content_type;
-use crate::{MatchingContext, CommonMismatch};
+use crate::binary_utils::{match_content_type, match_image_similarity};
+use crate::headers::match_retry_after;
+use crate::{MatchingContext, CommonMismatch, find_format_validator};
 
 #[cfg(feature = "plugins")]
 lazy_static! {
@@ -283,6 +287,13 @@ impl Matches<&str> for &str {
           Ok(())
         }
       }
+      MatchingRule::Empty => {
+        if actual.is_empty() {
+          Ok(())
+        } else {
+          Err(anyhow!("Expected '{}' (String) to be empty", actual))
+        }
+      }
       MatchingRule::Semver => {
         match Version::parse(actual) {
           Ok(_) => Ok(()),
@@ -290,6 +301,44 @@ impl Matches<&str> for &str {
         }
       }
       MatchingRule::ContentType(content_type) => match_content_type(actual.as_bytes(), content_type),
+      // The body hash is checked against the body once the headers have been matched
+      MatchingRule::BodyHash(_) => Ok(()),
+      // Correlations are checked against the request headers once the response headers have been matched
+      MatchingRule::Correlation(_, _) => Ok(()),
+      MatchingRule::PathExpression => match_path_expression(actual),
+      MatchingRule::Base64(min, max) => match_base64(actual, *min, *max),
+      MatchingRule::RetryAfter(min, max) => match_retry_after(actual, *min, *max),
+      MatchingRule::Duration => match_duration(actual),
+      MatchingRule::StringLength { min, max } => match_string_length(actual, *min, *max),
+      MatchingRule::IntegerRange { min, max } => match actual.parse::<i64>() {
+        Ok(value) => match_integer_range(value, *min, *max),
+        Err(_) => Err(anyhow!("Expected '{}' to be an integer between {} and {}", actual, min, max))
+      },
+      MatchingRule::DateTimeAfter { format, reference } => match_datetime_order(actual, format, reference, true),
+      MatchingRule::DateTimeBefore { format, reference } => match_datetime_order(actual, format, reference, false),
+      MatchingRule::OneOf(values) => match_one_of(actual, values),
+      MatchingRule::IpAddress { version } => match_ip_address(actual, *version),
+      MatchingRule::Format(format) => match_format(actual, format),
+      MatchingRule::Any => Ok(()),
+      MatchingRule::JsonRegex(regex) => crate::json::match_json_regex(&serde_json::Value::String(actual.to_string()), regex),
+      MatchingRule::NoCascade => Ok(()),
+      // Cookie headers are parsed and matched as structured values when matching the headers
+      MatchingRule::Cookie => Ok(()),
+      // The raw request target is handled when matching the request path
+      MatchingRule::RawTarget => Ok(()),
+      // Header values are compared ignoring their order when matching the headers
+      MatchingRule::UnorderedValues => Ok(()),
+      // Sensitive values are redacted from the mismatches once the matching is done
+      MatchingRule::Sensitive => Ok(()),
+      // Header values are parsed and matched as JSON when matching the headers
+      MatchingRule::JsonValue => Ok(()),
+      MatchingRule::MultipleOf(base) => match actual.parse::<f64>() {
+        Ok(value) => match_multiple_of(value, *base),
+        Err(_) => Err(anyhow!("Expected '{}' to be a multiple of {}", actual, base))
+      },
+      MatchingRule::NumericString { tolerance } => match_numeric_string(self, actual, *tolerance),
+      MatchingRule::Hex { length } => match_hex(actual, *length),
+      MatchingRule::Nullable(rule) => self.matches_with(actual, rule.as_ref(), cascaded),
       _ => if !cascaded || matcher.can_cascade() {
         Err(anyhow!("Unable to match '{}' using {:?}", self, matcher))
       } else {
@@ -753,6 +802,7 @@ impl Matches<&Bytes> for Bytes {
         }
       },
       MatchingRule::ContentType(content_type) => match_content_type(actual, content_type),
+      MatchingRule::ImageSimilarity { threshold } => match_image_similarity(self, actual, *threshold),
       MatchingRule::NotEmpty => {
         if actual.is_empty() {
           Err(anyhow!("Expected [] (0 bytes) to not be empty"))
@@ -760,6 +810,13 @@ impl Matches<&Bytes> for Bytes {
           Ok(())
         }
       }
+      MatchingRule::Empty => {
+        if actual.is_empty() {
+          Ok(())
+        } else {
+          Err(anyhow!("Expected {} bytes to be empty", actual.len()))
+        }
+      }
       _ => if !cascaded || matcher.can_cascade() {
         Err(anyhow!("Unable to match '{:?}...' ({} bytes) using {:?}", actual.split_at(10).0, actual.len(), matcher))
       } else {
@@ -816,6 +873,8 @@ fn match_status_code(status_code: u16, status: &HttpStatus) -> anyhow::Result<()
   };
   let result = if matches {
     Ok(())
+  } else if let HttpStatus::StatusCodes(_) = status {
+    Err(anyhow!("Expected status code {} to be one of {}", status_code, status))
   } else {
     Err(anyhow!("Expected status code {} to be a {}", status_code, status))
   };
@@ -823,6 +882,222 @@ fn match_status_code(status_code: u16, status: &HttpStatus) -> anyhow::Result<()
   result
 }
 
+/// Matches that the value is a valid path expression (i.e. `$.a.b[0]`)
+pub(crate) fn match_path_expression(value: &str) -> anyhow::Result<()> {
+  if value.is_empty() {
+    Err(anyhow!("Expected '' to be a valid path expression"))
+  } else {
+    DocPath::new(value)
+      .map(|_| ())
+      .map_err(|err| anyhow!("'{}' is not a valid path expression - {}", value, err))
+  }
+}
+
+/// Matches that the value is a valid Base64 encoded string, and that the length of the decoded
+/// data is within the optional bounds
+pub(crate) fn match_base64(value: &str, min: Option<usize>, max: Option<usize>) -> anyhow::Result<()> {
+  let decoded = BASE64.decode(value)
+    .map_err(|err| anyhow!("Expected '{}' to be a valid Base64 encoded string - {}", value, err))?;
+  if let Some(min) = min {
+    if decoded.len() < min {
+      return Err(anyhow!("Expected '{}' to decode to at least {} byte(s), but got {}", value, min, decoded.len()));
+    }
+  }
+  if let Some(max) = max {
+    if decoded.len() > max {
+      return Err(anyhow!("Expected '{}' to decode to at most {} byte(s), but got {}", value, max, decoded.len()));
+    }
+  }
+  Ok(())
+}
+
+/// Matches that the value is an ISO-8601 duration (i.e. `PT1H30M`)
+#[allow(unused_variables)]
+pub(crate) fn match_duration(value: &str) -> anyhow::Result<()> {
+  #[cfg(feature = "datetime")]
+  {
+    validate_duration(value).map_err(|err| anyhow!(err))
+  }
+  #[cfg(not(feature = "datetime"))]
+  {
+    Err(anyhow!("Duration matchers require the datetime feature to be enabled"))
+  }
+}
+
+/// Matches that the length of the string (in characters) is within the optional bounds
+pub(crate) fn match_string_length(value: &str, min: Option<usize>, max: Option<usize>) -> anyhow::Result<()> {
+  let length = value.chars().count();
+  match (min, max) {
+    (Some(min), Some(max)) if min == max && length != min =>
+      Err(anyhow!("Expected string length {} but was {}", min, length)),
+    (Some(min), _) if length < min =>
+      Err(anyhow!("Expected string length of at least {} but was {}", min, length)),
+    (_, Some(max)) if length > max =>
+      Err(anyhow!("Expected string length of at most {} but was {}", max, length)),
+    _ => Ok(())
+  }
+}
+
+pub(crate) fn match_integer_range(value: i64, min: i64, max: i64) -> anyhow::Result<()> {
+  if value >= min && value <= max {
+    Ok(())
+  } else {
+    Err(anyhow!("Expected {} to be an integer between {} and {}", value, min, max))
+  }
+}
+
+/// Matches that the value is a multiple of the base, within `MULTIPLE_OF_TOLERANCE` to allow for
+/// floating point errors with decimal bases
+pub(crate) fn match_multiple_of(value: f64, base: f64) -> anyhow::Result<()> {
+  if !(base > 0.0 && base.is_finite()) {
+    return Err(anyhow!("MultipleOf matcher requires a positive base, but was {}", base));
+  }
+  let quotient = value / base;
+  if quotient.is_finite() && (quotient - quotient.round()).abs() < MULTIPLE_OF_TOLERANCE {
+    Ok(())
+  } else {
+    Err(anyhow!("Expected {} to be a multiple of {}", value, base))
+  }
+}
+
+/// Parses both strings as numbers and compares them numerically, optionally within a tolerance
+pub(crate) fn match_numeric_string(expected: &str, actual: &str, tolerance: Option<f64>) -> anyhow::Result<()> {
+  let expected_value = expected.trim().parse::<f64>()
+    .map_err(|_| anyhow!("Expected value '{}' for the numeric string matcher is not a number", expected))?;
+  let actual_value = match actual.trim().parse::<f64>() {
+    Ok(value) if value.is_finite() => value,
+    _ => return Err(anyhow!("Expected '{}' to be a number", actual))
+  };
+  let difference = (expected_value - actual_value).abs();
+  match tolerance {
+    Some(tolerance) => if difference <= tolerance {
+      Ok(())
+    } else {
+      Err(anyhow!("Expected '{}' to be numerically equal to '{}' within a tolerance of {}", actual, expected, tolerance))
+    }
+    None => if difference == 0.0 {
+      Ok(())
+    } else {
+      Err(anyhow!("Expected '{}' to be numerically equal to '{}'", actual, expected))
+    }
+  }
+}
+
+pub(crate) fn match_hex(value: &str, length: Option<usize>) -> anyhow::Result<()> {
+  if value.is_empty() || !value.chars().all(|ch| ch.is_ascii_hexdigit()) {
+    return Err(anyhow!("Expected '{}' to be a hexadecimal string", value));
+  }
+  match length {
+    Some(length) if value.len() != length =>
+      Err(anyhow!("Expected '{}' to be a hexadecimal string with {} digits but it has {}", value, length, value.len())),
+    _ => Ok(())
+  }
+}
+
+pub(crate) fn match_one_of(value: &str, values: &[String]) -> anyhow::Result<()> {
+  if values.iter().any(|v| v == value) {
+    Ok(())
+  } else {
+    Err(anyhow!("Expected '{}' to be one of [{}]", value, values.join(", ")))
+  }
+}
+
+/// Matches the value with the validator registered for the named format
+pub(crate) fn match_format(value: &str, format: &str) -> anyhow::Result<()> {
+  match find_format_validator(format) {
+    Some(validator) => validator(value)
+      .map_err(|err| anyhow!("Expected '{}' to be in the '{}' format - {}", value, format, err)),
+    None => Err(anyhow!("Unknown format '{}', no validator has been registered for it", format))
+  }
+}
+
+/// Validates that the value is an email address (i.e. `name@example.com`)
+pub(crate) fn validate_email(value: &str) -> anyhow::Result<()> {
+  let (local, domain) = value.rsplit_once('@')
+    .ok_or_else(|| anyhow!("it does not contain an '@'"))?;
+  if local.is_empty() || local.chars().any(|ch| ch.is_whitespace() || ch.is_control()) {
+    return Err(anyhow!("'{}' is not a valid local part", local));
+  }
+  let labels = domain.split('.').collect::<Vec<_>>();
+  if labels.len() < 2 || labels.iter().any(|label| label.is_empty() || label.starts_with('-') ||
+    label.ends_with('-') || !label.chars().all(|ch| ch.is_alphanumeric() || ch == '-')) {
+    return Err(anyhow!("'{}' is not a valid domain", domain));
+  }
+  Ok(())
+}
+
+/// Validates that the value is a phone number in E.164 format (i.e. `+14155552671`)
+pub(crate) fn validate_e164_phone_number(value: &str) -> anyhow::Result<()> {
+  match value.strip_prefix('+') {
+    Some(digits) if (2..=15).contains(&digits.len()) && !digits.starts_with('0') &&
+      digits.chars().all(|ch| ch.is_ascii_digit()) => Ok(()),
+    _ => Err(anyhow!("it must be a '+' followed by up to 15 digits, without a leading zero"))
+  }
+}
+
+/// Matches that the value is a valid IP address, and if a version is given, that it is an
+/// address of that version (4 or 6)
+pub(crate) fn match_ip_address(value: &str, version: Option<u8>) -> anyhow::Result<()> {
+  match value.parse::<IpAddr>() {
+    Ok(address) => match (version, address) {
+      (Some(4), IpAddr::V6(_)) => Err(anyhow!("Expected '{}' to be an IPv4 address, but it is an IPv6 address", value)),
+      (Some(6), IpAddr::V4(_)) => Err(anyhow!("Expected '{}' to be an IPv6 address, but it is an IPv4 address", value)),
+      _ => Ok(())
+    },
+    Err(_) => match version {
+      Some(version) => Err(anyhow!("Expected '{}' to be a valid IPv{} address", value, version)),
+      None => Err(anyhow!("Expected '{}' to be a valid IP address", value))
+    }
+  }
+}
+
+/// Matches that the value is a date/time in the format that is after (or before) the reference
+/// date/time. The reference is either `now` (the current time) or a literal date/time in the same
+/// format. References to values from the matching context are not supported, as matchers do not
+/// have access to them, and will result in an error.
+#[allow(unused_variables)]
+pub(crate) fn match_datetime_order(value: &str, format: &str, reference: &str, after: bool) -> anyhow::Result<()> {
+  #[cfg(feature = "datetime")]
+  {
+    let format = if format.is_empty() {
+      "yyyy-MM-dd'T'HH:mm:ssXXX"
+    } else {
+      format
+    };
+    let actual = parse_datetime(value, format)?;
+    let reference_datetime = if reference == "now" {
+      chrono::Utc::now().fixed_offset()
+    } else {
+      parse_datetime(reference, format)
+        .map_err(|err| anyhow!("Reference '{}' must be 'now' or a literal date/time in the format '{}' - {}",
+          reference, format, err))?
+    };
+    match (after, actual.cmp(&reference_datetime)) {
+      (true, std::cmp::Ordering::Greater) | (false, std::cmp::Ordering::Less) => Ok(()),
+      _ => Err(anyhow!("Expected '{}' to be {} '{}'", value, if after { "after" } else { "before" }, reference))
+    }
+  }
+  #[cfg(not(feature = "datetime"))]
+  {
+    Err(anyhow!("DateTime matchers require the datetime feature to be enabled"))
+  }
+}
+
+/// Parses the date/time using the format. Values without a timezone are treated as UTC, and
+/// values without a time are treated as the start of the day.
+#[cfg(feature = "datetime")]
+fn parse_datetime(value: &str, format: &str) -> anyhow::Result<chrono::DateTime<chrono::FixedOffset>> {
+  let tokens = parse_pattern(format)
+    .map_err(|err| anyhow!("'{}' is not a valid date/time format - {}", format, err))?;
+  let pattern = to_chrono_pattern(&tokens);
+  chrono::DateTime::parse_from_str(value, &pattern)
+    .or_else(|_| chrono::NaiveDateTime::parse_from_str(value, &pattern)
+      .map(|datetime| datetime.and_utc().fixed_offset()))
+    .or_else(|_| chrono::NaiveDate::parse_from_str(value, &pattern)
+      .map(|date| date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc().fixed_offset()))
+    .map_err(|err| anyhow!("'{}' does not match the date/time format '{}' - {}", value, format, err))
+}
+
 /// Basic matching implementation for string slices
 pub fn match_strings(
   path: &DocPath,
@@ -853,11 +1128,15 @@ pub fn match_strings(
 
 #[cfg(test)]
 mod tests {
+  use std::sync::Arc;
+
   use expectest::expect;
   use expectest::prelude::*;
   use pact_models::{matchingrules, matchingrules::RuleList, matchingrules_list};
   use serde_json::json;
 
+  use crate::{register_format_validator, unregister_format_validator};
+
   use super::*;
 
   #[test]
@@ -1200,7 +1479,8 @@ mod tests {
     expect!(match_status_code(503, &HttpStatus::ServerError)).to(be_ok());
     expect!(match_status_code(499, &HttpStatus::ServerError)).to(be_err());
     expect!(match_status_code(200, &HttpStatus::StatusCodes(vec![200, 201, 204]))).to(be_ok());
-    expect!(match_status_code(202, &HttpStatus::StatusCodes(vec![200, 201, 204]))).to(be_err());
+    expect!(match_status_code(202, &HttpStatus::StatusCodes(vec![200, 201, 204])).unwrap_err().to_string())
+      .to(be_equal_to("Expected status code 202 to be one of 200, 201, 204"));
     expect!(match_status_code(333, &HttpStatus::NonError)).to(be_ok());
     expect!(match_status_code(599, &HttpStatus::NonError)).to(be_err());
     expect!(match_status_code(555, &HttpStatus::Error)).to(be_ok());
@@ -1232,6 +1512,247 @@ mod tests {
     expect!(json!("1.0.0").matches_with(&json!("1"), &matcher, false)).to(be_err());
   }
 
+  #[test]
+  fn path_expression_matcher_test() {
+    let matcher = MatchingRule::PathExpression;
+    expect!("$.a".matches_with("$.a.b[0]", &matcher, false)).to(be_ok());
+    expect!("$.a".matches_with("$.test.one", &matcher, false)).to(be_ok());
+    expect!("$.a".matches_with("$.a[", &matcher, false)).to(be_err());
+    expect!("$.a".matches_with("#.a", &matcher, false)).to(be_err());
+    expect!("$.a".matches_with("", &matcher, false)).to(be_err());
+    expect!(json!("$.a").matches_with(&json!("$.a.b[0]"), &matcher, false)).to(be_ok());
+    expect!(json!("$.a").matches_with(&json!("$.a[*"), &matcher, false)).to(be_err());
+    expect!(json!("$.a").matches_with(&json!(100), &matcher, false)).to(be_err());
+  }
+
+  #[test]
+  fn base64_matcher_test() {
+    let matcher = MatchingRule::Base64(None, None);
+    expect!("".matches_with("SGVsbG8gV29ybGQ=", &matcher, false)).to(be_ok());
+    expect!("".matches_with("", &matcher, false)).to(be_ok());
+    expect!("".matches_with("SGVsbG8gV29ybGQ", &matcher, false)).to(be_err());
+    expect!("".matches_with("not base64!", &matcher, false)).to(be_err());
+    expect!(json!("").matches_with(&json!("SGVsbG8gV29ybGQ="), &matcher, false)).to(be_ok());
+    expect!(json!("").matches_with(&json!("&&&"), &matcher, false)).to(be_err());
+    expect!(json!("").matches_with(&json!(100), &matcher, false)).to(be_err());
+
+    let matcher = MatchingRule::Base64(Some(5), Some(11));
+    expect!("".matches_with("SGVsbG8=", &matcher, false)).to(be_ok());
+    expect!("".matches_with("SGVsbG8gV29ybGQ=", &matcher, false)).to(be_ok());
+    expect!("".matches_with("SGk=", &matcher, false)).to(be_err());
+    expect!("".matches_with("SGVsbG8gV29ybGQh", &matcher, false)).to(be_err());
+  }
+
+  #[test]
+  #[cfg(feature = "datetime")]
+  fn duration_matcher_test() {
+    let matcher = MatchingRule::Duration;
+    expect!("PT1H".matches_with("PT1H30M", &matcher, false)).to(be_ok());
+    expect!("PT1H".matches_with("P1Y2M10DT2H30M", &matcher, false)).to(be_ok());
+    expect!("PT1H".matches_with("abc", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected 'abc' to be a valid ISO-8601 duration"));
+    expect!(json!("PT1H").matches_with(&json!("P3W"), &matcher, false)).to(be_ok());
+    expect!(json!("PT1H").matches_with(&json!("1 hour"), &matcher, false)).to(be_err());
+    expect!(json!("PT1H").matches_with(&json!(100), &matcher, false)).to(be_err());
+  }
+
+  #[test]
+  #[cfg(feature = "datetime")]
+  fn datetime_order_matcher_test() {
+    let matcher = MatchingRule::DateTimeAfter {
+      format: "yyyy-MM-dd'T'HH:mm:ssXXX".to_string(),
+      reference: "2025-01-01T00:00:00+00:00".to_string()
+    };
+    expect!("".matches_with("2025-01-01T00:00:01+00:00", &matcher, false)).to(be_ok());
+    expect!("".matches_with("2025-01-01T10:00:00+10:00", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected '2025-01-01T10:00:00+10:00' to be after '2025-01-01T00:00:00+00:00'"));
+    expect!("".matches_with("2025-01-01", &matcher, false)).to(be_err());
+
+    let matcher = MatchingRule::DateTimeBefore {
+      format: "yyyy-MM-dd".to_string(),
+      reference: "2025-01-01".to_string()
+    };
+    expect!("".matches_with("2024-12-31", &matcher, false)).to(be_ok());
+    expect!("".matches_with("2025-01-01", &matcher, false)).to(be_err());
+    expect!(json!("").matches_with(&json!("2024-06-30"), &matcher, false)).to(be_ok());
+
+    let matcher = MatchingRule::DateTimeBefore {
+      format: "yyyy-MM-dd".to_string(),
+      reference: "${expiresAt}".to_string()
+    };
+    let error = "".matches_with("2024-12-31", &matcher, false).unwrap_err().to_string();
+    expect!(error.starts_with("Reference '${expiresAt}' must be 'now' or a literal date/time in the format 'yyyy-MM-dd'"))
+      .to(be_true());
+
+    let matcher = MatchingRule::DateTimeAfter { format: String::default(), reference: "now".to_string() };
+    let future = (chrono::Utc::now() + chrono::Duration::hours(1)).format("%Y-%m-%dT%H:%M:%S+00:00").to_string();
+    let past = (chrono::Utc::now() - chrono::Duration::hours(1)).format("%Y-%m-%dT%H:%M:%S+00:00").to_string();
+    expect!("".matches_with(future.as_str(), &matcher, false)).to(be_ok());
+    expect!("".matches_with(past.as_str(), &matcher, false)).to(be_err());
+  }
+
+  #[test]
+  fn integer_range_matcher_test() {
+    let matcher = MatchingRule::IntegerRange { min: 0, max: 100 };
+    expect!("50".matches_with("0", &matcher, false)).to(be_ok());
+    expect!("50".matches_with("100", &matcher, false)).to(be_ok());
+    expect!("50".matches_with("-1", &matcher, false)).to(be_err());
+    expect!("50".matches_with("150", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected 150 to be an integer between 0 and 100"));
+    expect!("50".matches_with("50.5", &matcher, false)).to(be_err());
+  }
+
+  #[test]
+  fn multiple_of_matcher_test() {
+    let matcher = MatchingRule::MultipleOf(5.0);
+    expect!("10".matches_with("15", &matcher, false)).to(be_ok());
+    expect!("10".matches_with("0", &matcher, false)).to(be_ok());
+    expect!("10".matches_with("-20", &matcher, false)).to(be_ok());
+    expect!("10".matches_with("7", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected 7 to be a multiple of 5"));
+    expect!("10".matches_with("abc", &matcher, false)).to(be_err());
+
+    let matcher = MatchingRule::MultipleOf(0.25);
+    expect!("1".matches_with("1.75", &matcher, false)).to(be_ok());
+    expect!("1".matches_with("1.3", &matcher, false)).to(be_err());
+
+    expect!("10".matches_with("10", &MatchingRule::MultipleOf(0.0), false).unwrap_err().to_string())
+      .to(be_equal_to("MultipleOf matcher requires a positive base, but was 0"));
+    expect!("10".matches_with("10", &MatchingRule::MultipleOf(-5.0), false)).to(be_err());
+  }
+
+  #[test]
+  fn numeric_string_matcher_test() {
+    let matcher = MatchingRule::NumericString { tolerance: None };
+    expect!("100.50".matches_with("100.5", &matcher, false)).to(be_ok());
+    expect!("100".matches_with("1e2", &matcher, false)).to(be_ok());
+    expect!("100.50".matches_with("100.51", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected '100.51' to be numerically equal to '100.50'"));
+    expect!("100.50".matches_with("abc", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected 'abc' to be a number"));
+    expect!("abc".matches_with("100", &matcher, false)).to(be_err());
+
+    let matcher = MatchingRule::NumericString { tolerance: Some(0.05) };
+    expect!("100.50".matches_with("100.54", &matcher, false)).to(be_ok());
+    expect!("100.50".matches_with("100.6", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected '100.6' to be numerically equal to '100.50' within a tolerance of 0.05"));
+  }
+
+  #[test]
+  fn hex_matcher_test() {
+    let matcher = MatchingRule::Hex { length: None };
+    expect!("".matches_with("00ff", &matcher, false)).to(be_ok());
+    expect!("".matches_with("DEADbeef", &matcher, false)).to(be_ok());
+    expect!("".matches_with("0x00ff", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected '0x00ff' to be a hexadecimal string"));
+    expect!("".matches_with("", &matcher, false)).to(be_err());
+
+    let matcher = MatchingRule::Hex { length: Some(6) };
+    expect!("".matches_with("ff00aa", &matcher, false)).to(be_ok());
+    expect!("".matches_with("ff00a", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected 'ff00a' to be a hexadecimal string with 6 digits but it has 5"));
+  }
+
+  #[test]
+  fn one_of_matcher_test() {
+    let matcher = MatchingRule::OneOf(vec!["RED".to_string(), "GREEN".to_string(), "BLUE".to_string()]);
+    expect!("RED".matches_with("GREEN", &matcher, false)).to(be_ok());
+    expect!("RED".matches_with("green", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected 'green' to be one of [RED, GREEN, BLUE]"));
+    expect!(json!("RED").matches_with(&json!("BLUE"), &matcher, false)).to(be_ok());
+    expect!(json!("RED").matches_with(&json!("YELLOW"), &matcher, false)).to(be_err());
+    expect!(json!("RED").matches_with(&json!(1), &matcher, false)).to(be_err());
+  }
+
+  #[test]
+  fn format_matcher_test() {
+    let matcher = MatchingRule::Format("email".to_string());
+    expect!("a@b.com".matches_with("fred.smith+test@example.co.nz", &matcher, false)).to(be_ok());
+    expect!("a@b.com".matches_with("fred.smith", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected 'fred.smith' to be in the 'email' format - it does not contain an '@'"));
+    expect!("a@b.com".matches_with("fred@localhost", &matcher, false)).to(be_err());
+    expect!("a@b.com".matches_with("fred smith@example.com", &matcher, false)).to(be_err());
+
+    let matcher = MatchingRule::Format("phone-e164".to_string());
+    expect!("+14155552671".matches_with("+442071838750", &matcher, false)).to(be_ok());
+    expect!("+14155552671".matches_with("0207 183 8750", &matcher, false)).to(be_err());
+    expect!("+14155552671".matches_with("+0442071838750", &matcher, false)).to(be_err());
+    expect!("+14155552671".matches_with("+1234567890123456", &matcher, false)).to(be_err());
+
+    let matcher = MatchingRule::Format("format_matcher_test-unknown".to_string());
+    expect!("a".matches_with("a", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Unknown format 'format_matcher_test-unknown', no validator has been registered for it"));
+
+    expect!(json!("a@b.com").matches_with(&json!("c@d.org"), &MatchingRule::Format("email".to_string()), false)).to(be_ok());
+    expect!(json!("a@b.com").matches_with(&json!(100), &MatchingRule::Format("email".to_string()), false)).to(be_err());
+  }
+
+  #[test]
+  fn format_matcher_with_a_registered_validator() {
+    let matcher = MatchingRule::Format("format_matcher_test-upper-case".to_string());
+    register_format_validator("format_matcher_test-upper-case", Arc::new(|value: &str| {
+      if value.chars().all(|ch| ch.is_uppercase()) {
+        Ok(())
+      } else {
+        Err(anyhow!("it is not all upper case"))
+      }
+    }));
+
+    expect!("ABC".matches_with("DEF", &matcher, false)).to(be_ok());
+    expect!("ABC".matches_with("Def", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected 'Def' to be in the 'format_matcher_test-upper-case' format - it is not all upper case"));
+
+    expect!(unregister_format_validator("format_matcher_test-upper-case")).to(be_true());
+    expect!("ABC".matches_with("DEF", &matcher, false)).to(be_err());
+  }
+
+  #[test]
+  fn ip_address_matcher_test() {
+    let matcher = MatchingRule::IpAddress { version: None };
+    expect!("127.0.0.1".matches_with("10.0.0.254", &matcher, false)).to(be_ok());
+    expect!("127.0.0.1".matches_with("fe80::1", &matcher, false)).to(be_ok());
+    expect!("127.0.0.1".matches_with("256.0.0.1", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected '256.0.0.1' to be a valid IP address"));
+    expect!("127.0.0.1".matches_with("localhost", &matcher, false)).to(be_err());
+
+    let matcher = MatchingRule::IpAddress { version: Some(4) };
+    expect!("127.0.0.1".matches_with("192.168.1.1", &matcher, false)).to(be_ok());
+    expect!("127.0.0.1".matches_with("::1", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected '::1' to be an IPv4 address, but it is an IPv6 address"));
+
+    let matcher = MatchingRule::IpAddress { version: Some(6) };
+    expect!("::1".matches_with("2001:db8::8a2e:370:7334", &matcher, false)).to(be_ok());
+    expect!("::1".matches_with("10.1.1.1", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected '10.1.1.1' to be an IPv6 address, but it is an IPv4 address"));
+    expect!("::1".matches_with("not-an-ip", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected 'not-an-ip' to be a valid IPv6 address"));
+
+    expect!(json!("::1").matches_with(&json!("10.1.1.1"), &MatchingRule::IpAddress { version: None }, false)).to(be_ok());
+    expect!(json!("::1").matches_with(&json!(100), &MatchingRule::IpAddress { version: None }, false)).to(be_err());
+  }
+
+  #[test]
+  fn string_length_matcher_test() {
+    let matcher = MatchingRule::StringLength { min: Some(60), max: Some(60) };
+    let hash = "$2a$12$R9h/cIPz0gi.URNNX3kh2OPST9/PgBkqquzi.Ss7KIUgO2t0jWMUW";
+    expect!("".matches_with(hash, &matcher, false)).to(be_ok());
+    expect!("".matches_with(&hash[2..], &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected string length 60 but was 58"));
+
+    let matcher = MatchingRule::StringLength { min: Some(2), max: Some(4) };
+    expect!("".matches_with("ab", &matcher, false)).to(be_ok());
+    expect!("".matches_with("äöüß", &matcher, false)).to(be_ok());
+    expect!("".matches_with("a", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected string length of at least 2 but was 1"));
+    expect!("".matches_with("abcde", &matcher, false).unwrap_err().to_string())
+      .to(be_equal_to("Expected string length of at most 4 but was 5"));
+
+    let matcher = MatchingRule::StringLength { min: None, max: Some(3) };
+    expect!(json!("").matches_with(&json!("abc"), &matcher, false)).to(be_ok());
+    expect!(json!("").matches_with(&json!("abcd"), &matcher, false)).to(be_err());
+    expect!(json!("").matches_with(&json!(100), &matcher, false)).to(be_err());
+  }
+
   #[test]
   fn content_type_matcher_test() {
     let matcher = MatchingRule::ContentType("text/plain".to_string());
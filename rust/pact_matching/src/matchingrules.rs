@@ -75,16 +75,53 @@ impl <T: Debug + Display + PartialEq + Clone> Matches<&[T]> for &[T] {
           Ok(())
         }
       }
+      MatchingRule::Empty => {
+        if actual.is_empty() {
+          Ok(())
+        } else {
+          Err(anyhow!("Expected an empty Array but found {} item(s): {}", actual.len(), display(actual)))
+        }
+      }
       MatchingRule::ArrayContains(_) => Ok(()),
+      MatchingRule::ArrayContainsInOrder => Ok(()),
+      MatchingRule::ArrayDistinct(_) => Ok(()),
       MatchingRule::EachKey(_) => Ok(()),
       MatchingRule::EachValue(_) => Ok(()),
       MatchingRule::Values => Ok(()),
       MatchingRule::Number | MatchingRule::Decimal | MatchingRule::Integer => Ok(()),
+      MatchingRule::IntegerRange { .. } => Ok(()),
       MatchingRule::Time(_) | MatchingRule::Date(_) | MatchingRule::Timestamp(_) => Ok(()),
+      MatchingRule::DateTimeAfter { .. } | MatchingRule::DateTimeBefore { .. } => Ok(()),
       MatchingRule::Include(_) => Ok(()),
+      MatchingRule::OneOf(_) => Ok(()),
+      MatchingRule::IpAddress { .. } => Ok(()),
+      MatchingRule::Format(_) => Ok(()),
+      MatchingRule::Any => Ok(()),
+      MatchingRule::JsonRegex(_) => Ok(()),
+      MatchingRule::NoCascade => Ok(()),
+      MatchingRule::MultipleOf(_) => Ok(()),
+      MatchingRule::Cookie => Ok(()),
+      MatchingRule::RawTarget => Ok(()),
+      MatchingRule::UnorderedValues => Ok(()),
+      MatchingRule::NumericString { .. } => Ok(()),
+      MatchingRule::Hex { .. } => Ok(()),
+      MatchingRule::Sensitive => Ok(()),
+      MatchingRule::JsonValue => Ok(()),
+      MatchingRule::Nullable(rule) => self.matches_with(actual, rule.as_ref(), cascaded),
       MatchingRule::ContentType(_) => Ok(()),
       MatchingRule::Boolean => Ok(()),
       MatchingRule::Semver => Ok(()),
+      MatchingRule::PathExpression => Ok(()),
+      MatchingRule::Base64(_, _) => Ok(()),
+      MatchingRule::RetryAfter(_, _) => Ok(()),
+      MatchingRule::ImageSimilarity { .. } => Ok(()),
+      MatchingRule::Duration => Ok(()),
+      MatchingRule::StringLength { .. } => Ok(()),
+      MatchingRule::AllowedKeys(_) | MatchingRule::Keys(_) => if cascaded {
+        Ok(())
+      } else {
+        Err(anyhow!("Expected {} (Array) to be a Map", actual.for_mismatch()))
+      }
       _ => Err(anyhow!("Unable to match {} using {:?}", self.for_mismatch(), matcher))
     };
     debug!("Comparing '{:?}' to '{:?}' using {:?} -> {:?}", self, actual, matcher, result);
@@ -163,6 +200,13 @@ impl Matches<&[u8]> for Vec<u8> {
           Ok(())
         }
       }
+      MatchingRule::Empty => {
+        if actual.is_empty() {
+          Ok(())
+        } else {
+          Err(anyhow!("Expected {} bytes to be empty", actual.len()))
+        }
+      }
       _ => Err(anyhow!("Unable to match {:?} using {:?}", self, matcher))
     };
     debug!("Comparing list with {} items to one with {} items using {:?} -> {:?}", self.len(), actual.len(), matcher, result);
@@ -220,10 +264,27 @@ impl <T: Debug + Display + Clone + PartialEq> Matches<&BTreeMap<String, T>> for
           Ok(())
         }
       }
+      MatchingRule::Empty => {
+        if actual.is_empty() {
+          Ok(())
+        } else {
+          Err(anyhow!("Expected an empty Object but found {} key(s): {}", actual.len(),
+            actual.keys().map(|k| format!("'{}'", k)).join(", ")))
+        }
+      }
       MatchingRule::ArrayContains(_) => Ok(()),
+      MatchingRule::ArrayContainsInOrder => Ok(()),
+      MatchingRule::ArrayDistinct(_) => Ok(()),
       MatchingRule::EachKey(_) => Ok(()),
       MatchingRule::EachValue(_) => Ok(()),
       MatchingRule::Values => Ok(()),
+      MatchingRule::AllowedKeys(keys) => if cascaded {
+        Ok(())
+      } else {
+        match_allowed_keys(actual.keys(), keys)
+      }
+      MatchingRule::Keys(_) => Ok(()),
+      MatchingRule::Nullable(rule) => self.matches_with(actual, rule.as_ref(), cascaded),
       _ => Err(anyhow!("Unable to match {} using {:?}", self.for_mismatch(), matcher))
     };
     debug!("Comparing '{:?}' to '{:?}' using {:?} -> {:?}", self, actual, matcher, result);
@@ -231,6 +292,24 @@ impl <T: Debug + Display + Clone + PartialEq> Matches<&BTreeMap<String, T>> for
   }
 }
 
+/// Checks that all the actual keys are in the list of allowed keys, returning an error listing
+/// any that are not.
+pub(crate) fn match_allowed_keys<'a>(
+  actual_keys: impl Iterator<Item = &'a String>,
+  allowed_keys: &[String]
+) -> anyhow::Result<()> {
+  let disallowed = actual_keys
+    .filter(|key| !allowed_keys.contains(key))
+    .cloned()
+    .collect::<Vec<String>>();
+  if disallowed.is_empty() {
+    Ok(())
+  } else {
+    Err(anyhow!("Expected the keys to be one of {} but found key(s) that are not allowed {}",
+      allowed_keys.to_vec().for_mismatch(), disallowed.for_mismatch()))
+  }
+}
+
 /// Trait to convert a expected or actual complex object into a string that can be used for a mismatch
 pub trait DisplayForMismatch {
   /// Return a string representation that can be used in a mismatch to display to the user
@@ -307,15 +386,14 @@ pub fn compare_maps_with_matchingrule<T: Display + Debug + Clone + PartialEq>(
           }
         }
       }).collect();
-      let rules = MatchingRuleCategory {
-        name: Category::BODY,
-        rules: hashmap! {
-            path.join("*") => RuleList {
-              rules: associated_rules,
-              rule_logic: RuleLogic::And,
-              cascaded: false
-            }
-          }
+      let mut rules = MatchingRuleCategory::empty(Category::BODY)
+        .with_definitions(context.matchers().definitions().clone());
+      rules.rules = hashmap! {
+        path.join("*") => RuleList {
+          rules: associated_rules,
+          rule_logic: RuleLogic::And,
+          cascaded: false
+        }
       };
       context.clone_with(&rules)
     } else {
@@ -330,6 +408,11 @@ pub fn compare_maps_with_matchingrule<T: Display + Debug + Clone + PartialEq>(
         result = merge_result(result, callback(&p, first, value, context.as_ref()));
       }
     }
+  } else if !cascaded && matches!(rule, MatchingRule::Keys(_)) {
+    debug!("Keys matcher is defined for path {}, ignoring the values", path);
+    let expected_keys = expected.keys().cloned().collect();
+    let actual_keys = actual.keys().cloned().collect();
+    result = merge_result(result, context.match_keys(path, &expected_keys, &actual_keys));
   } else {
     if let Err(mismatch) = expected.matches_with(actual, rule, cascaded) {
       result = merge_result(result, Err(vec![CommonMismatch {
@@ -343,15 +426,31 @@ pub fn compare_maps_with_matchingrule<T: Display + Debug + Clone + PartialEq>(
     let actual_keys = actual.keys().cloned().collect();
     result = merge_result(result, context.match_keys(path, &expected_keys, &actual_keys));
     for (key, value) in expected.iter() {
-      if actual.contains_key(key) {
+      if let Some(actual_value) = actual_value_for_key(key, actual, context) {
         let p = path.join(key);
-        result = merge_result(result, callback(&p, value, &actual[key], context));
+        result = merge_result(result, callback(&p, value, actual_value, context));
       }
     }
   }
   result
 }
 
+/// Returns the value from the actual map for the expected key. If the context has been
+/// configured with case-insensitive keys and there is no key that is exactly equal, the value of
+/// the first key that is equal ignoring case is returned.
+pub(crate) fn actual_value_for_key<'a, T>(
+  key: &str,
+  actual: &'a BTreeMap<String, T>,
+  context: &(dyn MatchingContext + Send + Sync)
+) -> Option<&'a T> {
+  actual.get(key).or_else(|| if context.matching_config().case_insensitive_keys {
+    let key = key.to_lowercase();
+    actual.iter().find(|(actual_key, _)| actual_key.to_lowercase() == key).map(|(_, value)| value)
+  } else {
+    None
+  })
+}
+
 /// Compare the expected and actual lists using the matching rule's logic
 #[tracing::instrument(ret, skip_all, fields(path, rule, cascaded), level = "trace")]
 pub fn compare_lists_with_matchingrule<T: Display + Debug + PartialEq + Clone + Sized>(
@@ -409,6 +508,29 @@ pub fn compare_lists_with_matchingrule<T: Display + Debug + PartialEq + Clone +
           }
         }
       }
+      MatchingRule::ArrayContainsInOrder => {
+        debug!("Matching {} with ArrayContainsInOrder", path);
+        let context = context.clone_with(&MatchingRuleCategory::equality("body"));
+        let mut actual_index = 0;
+        for (index, expected_value) in expected.iter().enumerate() {
+          let found = actual.iter().enumerate().skip(actual_index).find(|&(i, value)| {
+            debug!("Comparing list item {} with value '{:?}' to '{:?}'", i, value, expected_value);
+            callback(&DocPath::root(), expected_value, value, context.as_ref()).is_ok()
+          });
+          match found {
+            Some((i, _)) => actual_index = i + 1,
+            None => {
+              result.push(CommonMismatch {
+                path: path.to_string(),
+                expected: expected_value.to_string(),
+                actual: actual.for_mismatch(),
+                description: format!("Expected item at index {} ({}) was not found in the actual list in order", index, expected_value)
+              });
+              break;
+            }
+          }
+        }
+      }
       MatchingRule::EachValue(definition) => if !cascaded {
         debug!("Matching {} with EachValue", path);
         let associated_rules = definition.rules.iter().filter_map(|rule| {
@@ -425,14 +547,13 @@ pub fn compare_lists_with_matchingrule<T: Display + Debug + PartialEq + Clone +
             }
           }
         }).collect();
-        let rules = MatchingRuleCategory {
-          name: Category::BODY,
-          rules: hashmap! {
-            path.join("*") => RuleList {
-              rules: associated_rules,
-              rule_logic: RuleLogic::And,
-              cascaded: false
-            }
+        let mut rules = MatchingRuleCategory::empty(Category::BODY)
+          .with_definitions(context.matchers().definitions().clone());
+        rules.rules = hashmap! {
+          path.join("*") => RuleList {
+            rules: associated_rules,
+            rule_logic: RuleLogic::And,
+            cascaded: false
           }
         };
         let context = context.clone_with(&rules);
@@ -451,6 +572,15 @@ pub fn compare_lists_with_matchingrule<T: Display + Debug + PartialEq + Clone +
         result.extend(match_list_contents(path, expected, actual, context, callback));
       }
     }
+  } else if *rule == MatchingRule::Empty {
+    if let Err(mismatch) = expected.matches_with(actual, rule, cascaded) {
+      result.push(CommonMismatch {
+        path: path.to_string(),
+        expected: expected.for_mismatch(),
+        actual: actual.for_mismatch(),
+        description: mismatch.to_string()
+      });
+    }
   }
 
   if result.is_empty() {
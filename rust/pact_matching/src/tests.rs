@@ -6,7 +6,7 @@ use pretty_assertions::{assert_eq, assert_ne};
 use pact_models::{matchingrules, matchingrules_list};
 use pact_models::matchingrules::expressions::{MatchingRuleDefinition, ValueType};
 use pact_models::bodies::OptionalBody;
-use pact_models::content_types::{JSON, TEXT};
+use pact_models::content_types::{JSON, TEXT, XML};
 use pact_models::HttpStatus;
 use pact_models::request::Request;
 
@@ -60,6 +60,22 @@ fn match_status_using_matchers() {
     be_equal_to("Expected status code 500 to be a Successful response (200–299)"));
 }
 
+#[test]
+fn match_status_using_a_list_of_allowed_codes() {
+  let rules = matchingrules_list! {
+    "status"; "" => [ MatchingRule::StatusCode(HttpStatus::StatusCodes(vec![200, 201, 204])) ]
+  };
+  let context = CoreMatchingContext::new(
+    DiffConfig::AllowUnexpectedKeys,
+    &rules, &hashmap!{}
+  );
+  expect!(match_status(200, 201, &context)).to(be_ok());
+  expect!(match_status(200, 204, &context)).to(be_ok());
+  let result = match_status(200, 202, &context);
+  expect!(result.unwrap_err().first().unwrap().description()).to(
+    be_equal_to("Expected status code 202 to be one of 200, 201, 204"));
+}
+
 #[test]
 fn match_query_returns_nothing_if_there_are_no_query_strings() {
   let expected = None;
@@ -527,6 +543,180 @@ async fn body_matching_uses_any_matcher_for_content_type_header() {
   expect!(mismatches.iter()).to(be_empty());
 }
 
+#[tokio::test]
+async fn body_does_not_match_a_different_sub_type_if_strict_content_type_is_set() {
+  let expected = Request {
+    headers: Some(hashmap! { "Content-Type".to_string() => vec!["application/json".to_string()] }),
+    body: OptionalBody::Present(Bytes::from("100"), None, None),
+    ..Request::default()
+  };
+  let actual = Request {
+    headers: Some(hashmap! { "Content-Type".to_string() => vec!["application/hal+json".to_string()] }),
+    body: OptionalBody::Present(Bytes::from("100"), None, None),
+    ..Request::default()
+  };
+  let actual2 = Request {
+    headers: Some(hashmap! { "Content-Type".to_string() => vec!["application/json;charset=UTF-8".to_string()] }),
+    body: OptionalBody::Present(Bytes::from("100"), None, None),
+    ..Request::default()
+  };
+
+  let result = match_body(&expected, &actual, &CoreMatchingContext::default(),
+    &HeaderMatchingContext::default()).await;
+  expect!(result.mismatches().iter()).to(be_empty());
+
+  let header_context = HeaderMatchingContext::default()
+    .with_matching_config(MatchingConfig { strict_content_type: true, .. MatchingConfig::default() });
+  let result = match_body(&expected, &actual, &CoreMatchingContext::default(), &header_context).await;
+  expect!(result).to(be_equal_to(BodyMatchResult::BodyTypeMismatch {
+    expected_type: "application/json".to_string(),
+    actual_type: "application/hal+json".to_string(),
+    message: "Expected a body of 'application/json' but the actual content type was 'application/hal+json'".to_string(),
+    expected: Some(Bytes::from("100")),
+    actual: Some(Bytes::from("100"))
+  }));
+
+  let result = match_body(&expected, &actual2, &CoreMatchingContext::default(), &header_context).await;
+  expect!(result.mismatches().iter()).to(be_empty());
+}
+
+#[tokio::test]
+async fn body_with_any_matcher_at_the_root_ignores_the_contents() {
+  let expected = Request {
+    headers: Some(hashmap! { "Content-Type".to_string() => vec!["application/octet-stream".to_string()] }),
+    body: OptionalBody::Present(Bytes::from_static(&[1, 2, 3, 4]), None, None),
+    ..Request::default()
+  };
+  let actual = Request {
+    headers: Some(hashmap! { "Content-Type".to_string() => vec!["application/octet-stream".to_string()] }),
+    body: OptionalBody::Present(Bytes::from_static(&[255, 0, 10]), None, None),
+    ..Request::default()
+  };
+  let actual_json = Request {
+    headers: Some(hashmap! { "Content-Type".to_string() => vec!["application/json".to_string()] }),
+    body: OptionalBody::Present(Bytes::from_static(&[255, 0, 10]), None, None),
+    ..Request::default()
+  };
+  let actual_empty = Request {
+    headers: Some(hashmap! { "Content-Type".to_string() => vec!["application/octet-stream".to_string()] }),
+    body: OptionalBody::Empty,
+    ..Request::default()
+  };
+  let context = CoreMatchingContext::new(
+    DiffConfig::AllowUnexpectedKeys,
+    &matchingrules! {
+      "body" => { "$" => [ MatchingRule::Any ] }
+    }.rules_for_category("body").unwrap_or_default(), &hashmap!{}
+  );
+
+  let result = match_body(&expected, &actual, &context, &HeaderMatchingContext::default()).await;
+  expect!(result.mismatches().iter()).to(be_empty());
+
+  let result = match_body(&expected, &actual, &CoreMatchingContext::default(), &HeaderMatchingContext::default()).await;
+  expect!(result.mismatches().iter()).to_not(be_empty());
+
+  let result = match_body(&expected, &actual_json, &context, &HeaderMatchingContext::default()).await;
+  expect!(result).to(be_equal_to(BodyMatchResult::BodyTypeMismatch {
+    expected_type: "application/octet-stream".to_string(),
+    actual_type: "application/json".to_string(),
+    message: "Expected a body of 'application/octet-stream' but the actual content type was 'application/json'".to_string(),
+    expected: Some(Bytes::from_static(&[1, 2, 3, 4])),
+    actual: Some(Bytes::from_static(&[255, 0, 10]))
+  }));
+
+  let result = match_body(&expected, &actual_empty, &context, &HeaderMatchingContext::default()).await;
+  expect!(result.mismatches().iter()).to_not(be_empty());
+}
+
+#[tokio::test]
+async fn json_body_with_any_matcher_ignores_the_value_at_that_path() {
+  let expected = Request {
+    headers: Some(hashmap! { "Content-Type".to_string() => vec!["application/json".to_string()] }),
+    body: OptionalBody::Present(Bytes::from(r#"{"id": 1, "payload": {"a": [1, 2]}}"#), None, None),
+    ..Request::default()
+  };
+  let actual = Request {
+    headers: Some(hashmap! { "Content-Type".to_string() => vec!["application/json".to_string()] }),
+    body: OptionalBody::Present(Bytes::from(r#"{"id": 1, "payload": "opaque"}"#), None, None),
+    ..Request::default()
+  };
+  let context = CoreMatchingContext::new(
+    DiffConfig::AllowUnexpectedKeys,
+    &matchingrules! {
+      "body" => { "$.payload" => [ MatchingRule::Any ] }
+    }.rules_for_category("body").unwrap_or_default(), &hashmap!{}
+  );
+
+  let result = match_body(&expected, &actual, &context, &HeaderMatchingContext::default()).await;
+  expect!(result.mismatches().iter()).to(be_empty());
+}
+
+#[tokio::test]
+async fn json_body_with_no_cascade_marker_does_not_apply_the_parent_rules_to_the_children() {
+  let expected = Request {
+    headers: Some(hashmap! { "Content-Type".to_string() => vec!["application/json".to_string()] }),
+    body: OptionalBody::Present(Bytes::from(r#"{"item": {"id": 1, "status": "ACTIVE"}}"#), None, None),
+    ..Request::default()
+  };
+  let actual = Request {
+    headers: Some(hashmap! { "Content-Type".to_string() => vec!["application/json".to_string()] }),
+    body: OptionalBody::Present(Bytes::from(r#"{"item": {"id": 2, "status": "ACTIVE"}}"#), None, None),
+    ..Request::default()
+  };
+
+  let context = CoreMatchingContext::new(
+    DiffConfig::AllowUnexpectedKeys,
+    &matchingrules! {
+      "body" => { "$.item" => [ MatchingRule::Type ] }
+    }.rules_for_category("body").unwrap_or_default(), &hashmap!{}
+  );
+  let result = match_body(&expected, &actual, &context, &HeaderMatchingContext::default()).await;
+  expect!(result.mismatches().iter()).to(be_empty());
+
+  let context = CoreMatchingContext::new(
+    DiffConfig::AllowUnexpectedKeys,
+    &matchingrules! {
+      "body" => { "$.item" => [ MatchingRule::Type, MatchingRule::NoCascade ] }
+    }.rules_for_category("body").unwrap_or_default(), &hashmap!{}
+  );
+  let result = match_body(&expected, &actual, &context, &HeaderMatchingContext::default()).await;
+  let paths = result.mismatches().iter().map(|m| match m {
+    Mismatch::BodyMismatch { path, .. } => path.clone(),
+    _ => m.mismatch_type().to_string()
+  }).collect::<Vec<_>>();
+  expect!(paths).to(be_equal_to(vec!["$.item.id".to_string()]));
+}
+
+#[tokio::test]
+async fn json_body_with_no_cascade_marker_does_not_apply_the_parent_rules_to_any_descendants() {
+  let expected = Request {
+    headers: Some(hashmap! { "Content-Type".to_string() => vec!["application/json".to_string()] }),
+    body: OptionalBody::Present(Bytes::from(r#"{"item": {"name": "a", "details": {"id": 1}}}"#), None, None),
+    ..Request::default()
+  };
+  let actual = Request {
+    headers: Some(hashmap! { "Content-Type".to_string() => vec!["application/json".to_string()] }),
+    body: OptionalBody::Present(Bytes::from(r#"{"item": {"name": "b", "details": {"id": 2}}}"#), None, None),
+    ..Request::default()
+  };
+
+  let context = CoreMatchingContext::new(
+    DiffConfig::AllowUnexpectedKeys,
+    &matchingrules! {
+      "body" => {
+        "$.item" => [ MatchingRule::Type ],
+        "$.item.details" => [ MatchingRule::NoCascade ]
+      }
+    }.rules_for_category("body").unwrap_or_default(), &hashmap!{}
+  );
+  let result = match_body(&expected, &actual, &context, &HeaderMatchingContext::default()).await;
+  let paths = result.mismatches().iter().map(|m| match m {
+    Mismatch::BodyMismatch { path, .. } => path.clone(),
+    _ => m.mismatch_type().to_string()
+  }).sorted().collect::<Vec<_>>();
+  expect!(paths).to(be_equal_to(vec!["$.item.details".to_string(), "$.item.details.id".to_string()]));
+}
+
 #[tokio::test]
 async fn body_matches_if_expected_is_missing() {
   let expected = Request {
@@ -591,6 +781,45 @@ async fn body_matches_with_extended_mime_types() {
   expect!(result3.mismatches().iter()).to(be_empty());
 }
 
+#[tokio::test]
+async fn body_matches_using_a_registered_custom_body_matcher() {
+  let content_type = ContentType::parse("application/x-custom-format").unwrap();
+  register_body_matcher(&content_type, Arc::new(|_: &ContentType, expected: &Bytes, actual: &Bytes| {
+    if expected.to_ascii_lowercase() == actual.to_ascii_lowercase() {
+      vec![]
+    } else {
+      vec![Mismatch::BodyMismatch {
+        path: "$".to_string(),
+        expected: Some(expected.clone()),
+        actual: Some(actual.clone()),
+        mismatch: "Bodies are not equal ignoring case".to_string()
+      }]
+    }
+  }));
+
+  let expected = Request {
+    body: OptionalBody::Present("HELLO".into(), Some(content_type.clone()), None),
+    ..Request::default()
+  };
+  let actual = Request {
+    body: OptionalBody::Present("hello".into(), Some(content_type.clone()), None),
+    ..Request::default()
+  };
+  let actual2 = Request {
+    body: OptionalBody::Present("goodbye".into(), Some(content_type.clone()), None),
+    ..Request::default()
+  };
+  let result = match_body(&expected, &actual, &CoreMatchingContext::default(), &CoreMatchingContext::default()).await;
+  expect!(result.mismatches().iter()).to(be_empty());
+  let result2 = match_body(&expected, &actual2, &CoreMatchingContext::default(), &CoreMatchingContext::default()).await;
+  expect!(result2.mismatches().first().unwrap().description()).to(be_equal_to("$ -> Bodies are not equal ignoring case"));
+
+  expect!(unregister_body_matcher(&content_type)).to(be_true());
+  expect!(unregister_body_matcher(&content_type)).to(be_false());
+  let result = match_body(&expected, &actual, &CoreMatchingContext::default(), &CoreMatchingContext::default()).await;
+  expect!(result.mismatches().iter()).to_not(be_empty());
+}
+
 #[tokio::test]
 async fn body_matches_with_nested_matchers() {
   let expected = Request {
@@ -851,6 +1080,192 @@ fn match_path_returns_a_mismatch_if_the_path_does_not_match_with_a_matcher() {
   }]));
 }
 
+#[test]
+fn match_path_with_matchers_for_the_path_segments() {
+  let context = CoreMatchingContext::new(
+    DiffConfig::AllowUnexpectedKeys,
+    &matchingrules! {
+        "path" => {
+          "$[1]" => [ MatchingRule::Regex(s!("^\\d+$")) ],
+          "$[3]" => [ MatchingRule::Regex(s!("^[a-f0-9-]+$")) ]
+        }
+    }.rules_for_category("path").unwrap_or_default(), &hashmap!{}
+  );
+  let expected = "/users/100/orders/a1-b2";
+  expect!(match_path(expected, "/users/200/orders/ff-00", &context)).to(be_ok());
+
+  let result = match_path(expected, "/users/abc/orders/ff-00", &context);
+  let mismatches = result.unwrap_err();
+  expect!(mismatches.len()).to(be_equal_to(1));
+  expect!(mismatches[0].description()).to(be_equal_to(
+    "Expected 'abc' to match '^\\d+$' for path segment 1".to_string()));
+
+  let result = match_path(expected, "/accounts/200/orders/ff-00", &context);
+  let mismatches = result.unwrap_err();
+  expect!(mismatches[0].description()).to(be_equal_to(
+    "Expected 'accounts' to be equal to 'users' for path segment 0".to_string()));
+
+  let result = match_path(expected, "/users/200/orders", &context);
+  let mismatches = result.unwrap_err();
+  expect!(mismatches[0].description()).to(be_equal_to(
+    "Expected a path with 4 segment(s) but '/users/200/orders' has 3 segment(s)".to_string()));
+}
+
+#[test]
+fn match_path_applies_matchers_for_the_whole_path_and_the_path_segments() {
+  let context = CoreMatchingContext::new(
+    DiffConfig::AllowUnexpectedKeys,
+    &matchingrules! {
+        "path" => {
+          "" => [ MatchingRule::Regex(s!("^/users/\\w+/\\w+$")) ],
+          "$[1]" => [ MatchingRule::Regex(s!("^\\d+$")) ]
+        }
+    }.rules_for_category("path").unwrap_or_default(), &hashmap!{}
+  );
+  expect!(match_path("/users/100/profile", "/users/200/settings", &context)).to(be_ok());
+  expect!(match_path("/users/100/profile", "/users/abc/settings", &context)).to(be_err());
+}
+
+#[test]
+fn match_raw_target_matches_the_path_and_query_string_as_a_single_string() {
+  let context = CoreMatchingContext::new(
+    DiffConfig::NoUnexpectedKeys,
+    &matchingrules! {
+        "path" => { "" => [ MatchingRule::RawTarget, MatchingRule::Regex(s!("^/path/\\d+\\?(page=\\d+)?$")) ] }
+    }.rules_for_category("path").unwrap_or_default(), &hashmap!{}
+  );
+  let expected = HttpRequest {
+    path: "/path/1234".to_string(),
+    query: Some(hashmap!{ "page".to_string() => vec![Some("1".to_string())] }),
+    .. HttpRequest::default()
+  };
+  let actual = HttpRequest {
+    path: "/path/5678".to_string(),
+    query: Some(hashmap!{ "page".to_string() => vec![Some("22".to_string())] }),
+    .. HttpRequest::default()
+  };
+  expect!(raw_target_matching_enabled(&context)).to(be_true());
+  expect!(raw_request_target(&expected)).to(be_equal_to("/path/1234?page=1".to_string()));
+  expect!(match_raw_target(&expected, &actual, None, &context)).to(be_ok());
+
+  let actual = HttpRequest {
+    path: "/path/5678".to_string(),
+    query: Some(hashmap!{ "size".to_string() => vec![Some("22".to_string())] }),
+    .. HttpRequest::default()
+  };
+  let result = match_raw_target(&expected, &actual, None, &context);
+  expect!(result.clone()).to(be_err());
+  let mismatches = result.unwrap_err();
+  expect!(mismatches.len()).to(be_equal_to(1));
+  match &mismatches[0] {
+    Mismatch::PathMismatch { expected, actual, .. } => {
+      expect!(expected.as_str()).to(be_equal_to("/path/1234?page=1"));
+      expect!(actual.as_str()).to(be_equal_to("/path/5678?size=22"));
+    }
+    mismatch => panic!("Expected a path mismatch, got {:?}", mismatch)
+  }
+}
+
+#[test]
+fn match_raw_target_defaults_to_equality_with_only_the_marker() {
+  let context = CoreMatchingContext::new(
+    DiffConfig::NoUnexpectedKeys,
+    &matchingrules! {
+        "path" => { "" => [ MatchingRule::RawTarget ] }
+    }.rules_for_category("path").unwrap_or_default(), &hashmap!{}
+  );
+  let expected = HttpRequest {
+    path: "/path".to_string(),
+    query: Some(hashmap!{ "a".to_string() => vec![Some("1".to_string())] }),
+    .. HttpRequest::default()
+  };
+  let actual = HttpRequest {
+    path: "/path".to_string(),
+    query: Some(hashmap!{ "a".to_string() => vec![Some("2".to_string())] }),
+    .. HttpRequest::default()
+  };
+  expect!(match_raw_target(&expected, &expected, None, &context)).to(be_ok());
+  expect!(match_raw_target(&expected, &actual, None, &context)).to(be_err());
+}
+
+#[test]
+fn match_raw_target_uses_the_target_as_it_was_received() {
+  let context = CoreMatchingContext::new(
+    DiffConfig::NoUnexpectedKeys,
+    &matchingrules! {
+        "path" => { "" => [ MatchingRule::RawTarget, MatchingRule::Regex(s!("^/path\\?z=\\d+&a=\\d+$")) ] }
+    }.rules_for_category("path").unwrap_or_default(), &hashmap!{}
+  );
+  let request = HttpRequest {
+    path: "/path".to_string(),
+    query: Some(hashmap!{
+      "a".to_string() => vec![Some("1".to_string())],
+      "z".to_string() => vec![Some("2".to_string())]
+    }),
+    .. HttpRequest::default()
+  };
+  expect!(match_raw_target(&request, &request, Some("/path?z=2&a=1"), &context)).to(be_ok());
+  expect!(match_raw_target(&request, &request, None, &context)).to(be_err());
+}
+
+#[tokio::test]
+async fn match_request_with_raw_target_uses_the_target_as_it_was_received() {
+  let expected = HttpRequest {
+    path: "/orders".to_string(),
+    query: Some(hashmap!{
+      "page".to_string() => vec![Some("1".to_string())],
+      "id".to_string() => vec![Some("100".to_string())]
+    }),
+    matching_rules: matchingrules! {
+      "path" => { "" => [ MatchingRule::RawTarget, MatchingRule::Regex(s!("^/orders\\?page=\\d+&id=\\d+$")) ] }
+    },
+    .. HttpRequest::default()
+  };
+  let pact = pact_models::v4::pact::V4Pact::default().boxed();
+  let interaction = pact_models::v4::synch_http::SynchronousHttp::default().boxed();
+
+  let result = match_request_with_raw_target(expected.clone(), expected.clone(), Some("/orders?page=2&id=200"),
+    &pact, &interaction, MatchMode::AllMismatches, &MatchingConfig::default()).await;
+  expect!(result.all_matched()).to(be_true());
+
+  let result = match_request_with_raw_target(expected.clone(), expected, None,
+    &pact, &interaction, MatchMode::AllMismatches, &MatchingConfig::default()).await;
+  expect!(result.path.is_some()).to(be_true());
+}
+
+#[tokio::test]
+async fn match_request_does_not_compare_the_query_separately_with_raw_target_matching() {
+  let expected = HttpRequest {
+    path: "/orders".to_string(),
+    query: Some(hashmap!{ "id".to_string() => vec![Some("100".to_string())] }),
+    matching_rules: matchingrules! {
+      "path" => { "" => [ MatchingRule::RawTarget, MatchingRule::Regex(s!("^/orders\\?id=\\d+$")) ] }
+    },
+    .. HttpRequest::default()
+  };
+  let actual = HttpRequest {
+    path: "/orders".to_string(),
+    query: Some(hashmap!{ "id".to_string() => vec![Some("200".to_string())] }),
+    .. HttpRequest::default()
+  };
+  let pact = pact_models::v4::pact::V4Pact::default().boxed();
+  let interaction = pact_models::v4::synch_http::SynchronousHttp::default().boxed();
+
+  let result = match_request(expected.clone(), actual, &pact, &interaction).await;
+  expect!(result.path).to(be_none());
+  expect!(result.query.is_empty()).to(be_true());
+  expect!(result.all_matched()).to(be_true());
+
+  let actual = HttpRequest {
+    path: "/orders".to_string(),
+    query: Some(hashmap!{ "id".to_string() => vec![Some("abc".to_string())] }),
+    .. HttpRequest::default()
+  };
+  let result = match_request(expected, actual, &pact, &interaction).await;
+  expect!(result.path.is_some()).to(be_true());
+  expect!(result.query.is_empty()).to(be_true());
+}
+
 macro_rules! request {
   ($e:expr) => (Request { body: OptionalBody::Present($e.into(), None, None), .. Request::default() })
 }
@@ -962,6 +1377,55 @@ fn compare_bodies_core_should_check_for_content_type_matcher() {
   expect!(result.first().unwrap().description()).to(be_equal_to("$ -> Expected binary contents to have content type 'application/gif' but detected contents was 'image/gif'"));
 }
 
+#[test]
+#[cfg(feature = "json_schema")]
+fn compare_bodies_core_should_validate_the_body_against_a_json_schema_matcher() {
+  let schema = json!({
+    "type": "object",
+    "required": ["id", "items"],
+    "properties": {
+      "id": { "type": "integer" },
+      "items": { "type": "array", "items": { "type": "string" } }
+    }
+  });
+  let matching_rules = matchingrules!{ "body" => { "$" => [ MatchingRule::JsonSchema(schema) ] } };
+  let expected = Request {
+    body: OptionalBody::from(json!({ "id": 1, "items": ["a"] })),
+    matching_rules: matching_rules.clone(),
+    .. Request::default()
+  };
+  let context = CoreMatchingContext::new(DiffConfig::AllowUnexpectedKeys,
+    &matching_rules.rules_for_category("body").unwrap(),
+    &hashmap!{}
+  );
+
+  let actual = Request {
+    body: OptionalBody::from(json!({ "id": 100, "items": ["b", "c"], "other": true })),
+    .. Request::default()
+  };
+  expect!(compare_bodies_core(&JSON, &expected, &actual, &context).iter()).to(be_empty());
+
+  let actual = Request {
+    body: OptionalBody::from(json!({ "id": "100", "items": ["b", 2] })),
+    .. Request::default()
+  };
+  let result = compare_bodies_core(&JSON, &expected, &actual, &context);
+  let paths = result.iter().map(|m| match m {
+    Mismatch::BodyMismatch { path, .. } => path.clone(),
+    _ => String::default()
+  }).sorted().collect::<Vec<_>>();
+  expect!(paths).to(be_equal_to(vec!["$.id".to_string(), "$.items[1]".to_string()]));
+
+  let actual = Request {
+    body: OptionalBody::Present(Bytes::from("not json"), Some(JSON.clone()), None),
+    .. Request::default()
+  };
+  let result = compare_bodies_core(&JSON, &expected, &actual, &context);
+  expect!(result.len()).to(be_equal_to(1));
+  expect!(result.first().unwrap().description()).to(be_equal_to(
+    "$ -> Failed to parse the actual body as JSON: expected ident at line 1 column 2"));
+}
+
 #[test_log::test]
 fn match_metadata_value_test() {
   let expected = json!("value-a");
@@ -1012,3 +1476,458 @@ fn match_metadata_value_with_content_type_test() {
   let result = match_metadata_value("key", &expected, &actual, &context);
   expect!(result).to(be_err());
 }
+
+#[tokio::test]
+async fn compare_interaction_with_http_interactions() {
+  let expected = correlation_interaction("5", "6");
+  let pact = pact_models::v4::pact::V4Pact::default();
+
+  let result = compare_interaction(&expected, &correlation_interaction("10", "11"), &pact).await.unwrap();
+  expect!(result.all_matched()).to(be_true());
+  expect!(result.request.is_some()).to(be_true());
+  expect!(result.response.is_some()).to(be_true());
+  expect!(result.message).to(be_none());
+
+  let mut actual = correlation_interaction("10", "10");
+  actual.response.status = 500;
+  let result = compare_interaction(&expected, &actual, &pact).await.unwrap();
+  expect!(result.all_matched()).to(be_false());
+  expect!(result.request.unwrap().all_matched()).to(be_true());
+  expect!(result.response.unwrap().iter().map(|m| m.mismatch_type()).collect::<Vec<_>>())
+    .to(be_equal_to(vec!["StatusMismatch", "HeaderMismatch"]));
+}
+
+#[tokio::test]
+async fn compare_interaction_with_message_interactions() {
+  let message = |contents: Value| pact_models::v4::async_message::AsynchronousMessage {
+    contents: MessageContents {
+      contents: OptionalBody::from(contents),
+      .. MessageContents::default()
+    },
+    .. pact_models::v4::async_message::AsynchronousMessage::default()
+  };
+  let expected = message(json!({ "id": 100 }));
+  let pact = pact_models::v4::pact::V4Pact::default();
+
+  let result = compare_interaction(&expected, &message(json!({ "id": 100 })), &pact).await.unwrap();
+  expect!(result.all_matched()).to(be_true());
+  expect!(result.request).to(be_none());
+
+  let result = compare_interaction(&expected, &message(json!({ "id": 200 })), &pact).await.unwrap();
+  expect!(result.all_matched()).to(be_false());
+  expect!(result.message.unwrap().iter().map(|m| m.mismatch_type()).collect::<Vec<_>>())
+    .to(be_equal_to(vec!["BodyMismatch"]));
+
+  let result = compare_interaction(&expected, &correlation_interaction("5", "6"), &pact).await;
+  expect!(result).to(be_err());
+}
+
+fn correlation_interaction(request_count: &str, response_total: &str) -> pact_models::v4::synch_http::SynchronousHttp {
+  pact_models::v4::synch_http::SynchronousHttp {
+    request: HttpRequest {
+      headers: Some(hashmap!{ "X-Count".to_string() => vec![request_count.to_string()] }),
+      matching_rules: matchingrules!{
+        "header" => { "X-Count" => [ MatchingRule::Integer ] }
+      },
+      .. HttpRequest::default()
+    },
+    response: HttpResponse {
+      headers: Some(hashmap!{ "X-Total".to_string() => vec![response_total.to_string()] }),
+      matching_rules: matchingrules!{
+        "header" => { "X-Total" => [ MatchingRule::Correlation("X-Count".to_string(), Some("add:1".to_string())) ] }
+      },
+      .. HttpResponse::default()
+    },
+    .. pact_models::v4::synch_http::SynchronousHttp::default()
+  }
+}
+
+#[tokio::test]
+async fn match_interaction_with_transformed_header_correlation() {
+  let expected = correlation_interaction("5", "6");
+  let actual = correlation_interaction("10", "11");
+  let pact = pact_models::v4::pact::V4Pact::default().boxed();
+
+  let result = match_interaction(expected.boxed(), actual.boxed(), pact,
+    &PactSpecification::V4).await.unwrap();
+  expect!(result.iter()).to(be_empty());
+}
+
+#[tokio::test]
+async fn match_response_checks_header_correlations_against_the_request() {
+  let expected = correlation_interaction("5", "6");
+  let pact = pact_models::v4::pact::V4Pact::default().boxed();
+  let actual = HttpResponse {
+    headers: Some(hashmap!{ "X-Total".to_string() => vec!["11".to_string()] }),
+    .. HttpResponse::default()
+  };
+  let request = |count: &str| HttpRequest {
+    headers: Some(hashmap!{ "X-Count".to_string() => vec![count.to_string()] }),
+    .. HttpRequest::default()
+  };
+
+  let result = match_response_to_request(expected.response.clone(), actual.clone(), &request("10"),
+    &pact, &expected.boxed(), &MatchingConfig::default()).await;
+  expect!(result.iter()).to(be_empty());
+
+  let result = match_response_to_request(expected.response.clone(), actual.clone(), &request("5"),
+    &pact, &expected.boxed(), &MatchingConfig::default()).await;
+  expect!(result.iter().map(|m| m.description()).collect::<Vec<_>>()).to(be_equal_to(vec![
+    "Mismatch with header 'X-Total': Expected '11' to be equal to '6' (from request header 'X-Count' with 'add:1' applied)".to_string()
+  ]));
+
+  // Without the actual request, the correlation is checked against the request of the interaction
+  let result = match_response(expected.response.clone(), actual.clone(), &pact, &expected.boxed()).await;
+  expect!(result.len()).to(be_equal_to(1));
+
+  // And is reported as a mismatch if there is no request to check it against
+  let result = match_response(expected.response.clone(), actual, &pact,
+    &pact_models::v4::async_message::AsynchronousMessage::default().boxed()).await;
+  expect!(result.iter().map(|m| m.description()).collect::<Vec<_>>()).to(be_equal_to(vec![
+    "Mismatch with header 'X-Total': Expected a request header 'X-Count' to correlate with but was missing".to_string()
+  ]));
+}
+
+#[tokio::test]
+async fn match_interaction_with_incorrect_header_correlation() {
+  let expected = correlation_interaction("5", "6");
+  let actual = correlation_interaction("10", "10");
+  let pact = pact_models::v4::pact::V4Pact::default().boxed();
+
+  let result = match_interaction(expected.boxed(), actual.boxed(), pact,
+    &PactSpecification::V4).await.unwrap();
+  expect!(result.iter().map(|m| m.description()).collect::<Vec<_>>()).to(be_equal_to(vec![
+    "Mismatch with header 'X-Total': Expected '10' to be equal to '11' (from request header 'X-Count' with 'add:1' applied)".to_string()
+  ]));
+}
+
+#[tokio::test]
+async fn match_request_with_first_mismatch_mode_stops_at_the_first_mismatch() {
+  let expected = HttpRequest {
+    method: "POST".to_string(),
+    path: "/orders".to_string(),
+    body: OptionalBody::Present("{\"id\": 100}".into(), Some(JSON.clone()), None),
+    .. HttpRequest::default()
+  };
+  let actual = HttpRequest {
+    method: "PUT".to_string(),
+    path: "/orders".to_string(),
+    body: OptionalBody::Present("{\"id\": 200}".into(), Some(JSON.clone()), None),
+    .. HttpRequest::default()
+  };
+  let pact = pact_models::v4::pact::V4Pact::default().boxed();
+  let interaction = pact_models::v4::synch_http::SynchronousHttp::default().boxed();
+
+  let result = match_request(expected.clone(), actual.clone(), &pact, &interaction).await;
+  expect!(result.method.is_some()).to(be_true());
+  expect!(result.body.all_matched()).to(be_false());
+
+  let result = match_request_with_mode(expected.clone(), actual.clone(), &pact, &interaction,
+    MatchMode::FirstMismatch).await;
+  expect!(result.all_matched()).to(be_false());
+  expect!(result.method.is_some()).to(be_true());
+  expect!(result.body).to(be_equal_to(BodyMatchResult::Ok));
+
+  expect!(request_matches(expected.clone(), actual, &pact, &interaction).await).to(be_false());
+  expect!(request_matches(expected.clone(), expected, &pact, &interaction).await).to(be_true());
+}
+
+#[tokio::test]
+async fn match_response_with_strict_config_does_not_allow_unexpected_keys() {
+  let expected = HttpResponse {
+    headers: Some(hashmap!{ "Content-Type".to_string() => vec!["application/json".to_string()] }),
+    body: OptionalBody::Present("{\"id\": 100}".into(), Some(JSON.clone()), None),
+    .. HttpResponse::default()
+  };
+  let actual = HttpResponse {
+    headers: Some(hashmap!{ "Content-Type".to_string() => vec!["application/json".to_string()] }),
+    body: OptionalBody::Present("{\"id\": 100, \"name\": \"test\"}".into(), Some(JSON.clone()), None),
+    .. HttpResponse::default()
+  };
+  let pact = pact_models::v4::pact::V4Pact::default().boxed();
+  let interaction = pact_models::v4::synch_http::SynchronousHttp::default().boxed();
+
+  let result = match_response(expected.clone(), actual.clone(), &pact, &interaction).await;
+  expect!(result.iter()).to(be_empty());
+
+  let result = match_response_with_config(expected.clone(), actual.clone(), &pact, &interaction,
+    &MatchingConfig::default()).await;
+  expect!(result.iter()).to(be_empty());
+
+  let result = match_response_with_config(expected, actual, &pact, &interaction,
+    &MatchingConfig::strict()).await;
+  expect!(result.iter()).to_not(be_empty());
+  expect!(result.iter().all(|m| m.mismatch_type() == "BodyMismatch")).to(be_true());
+}
+
+#[tokio::test]
+async fn match_message_with_strict_config_does_not_allow_unexpected_keys() {
+  let expected = pact_models::v4::async_message::AsynchronousMessage {
+    contents: MessageContents {
+      contents: OptionalBody::Present("{\"id\": 100}".into(), Some(JSON.clone()), None),
+      .. MessageContents::default()
+    },
+    .. pact_models::v4::async_message::AsynchronousMessage::default()
+  }.boxed();
+  let actual = pact_models::v4::async_message::AsynchronousMessage {
+    contents: MessageContents {
+      contents: OptionalBody::Present("{\"id\": 100, \"name\": \"test\"}".into(), Some(JSON.clone()), None),
+      .. MessageContents::default()
+    },
+    .. pact_models::v4::async_message::AsynchronousMessage::default()
+  }.boxed();
+  let pact = pact_models::v4::pact::V4Pact::default().boxed();
+
+  let result = match_message(&expected, &actual, &pact).await;
+  expect!(result.iter()).to(be_empty());
+
+  let result = match_message_with_config(&expected, &actual, &pact, &MatchingConfig::strict()).await;
+  expect!(result.iter()).to_not(be_empty());
+  expect!(result.iter().all(|m| m.mismatch_type() == "BodyMismatch")).to(be_true());
+}
+
+#[tokio::test]
+async fn match_sync_message_response_with_strict_config_does_not_allow_unexpected_keys() {
+  let expected = MessageContents {
+    contents: OptionalBody::Present("{\"id\": 100}".into(), Some(JSON.clone()), None),
+    .. MessageContents::default()
+  };
+  let actual = MessageContents {
+    contents: OptionalBody::Present("{\"id\": 100, \"name\": \"test\"}".into(), Some(JSON.clone()), None),
+    .. MessageContents::default()
+  };
+  let message = SynchronousMessage {
+    response: vec![ expected.clone() ],
+    .. SynchronousMessage::default()
+  };
+  let pact = pact_models::v4::pact::V4Pact::default().boxed();
+
+  let result = match_sync_message_response(&message, &[ expected.clone() ], &[ actual.clone() ], &pact).await;
+  expect!(result.iter()).to(be_empty());
+
+  let result = match_sync_message_response_with_config(&message, &[ expected ], &[ actual ], &pact,
+    &MatchingConfig::strict()).await;
+  expect!(result.iter()).to_not(be_empty());
+}
+
+#[tokio::test]
+async fn match_interaction_with_first_mismatch_mode_does_not_compare_the_response_if_the_request_mismatches() {
+  let expected = correlation_interaction("5", "6");
+  let mut actual = correlation_interaction("five", "6");
+  actual.response.status = 500;
+  let pact = pact_models::v4::pact::V4Pact::default().boxed();
+
+  let result = match_interaction(expected.boxed(), actual.boxed(), pact.boxed(),
+    &PactSpecification::V4).await.unwrap();
+  expect!(result.iter().any(|m| m.mismatch_type() == "StatusMismatch")).to(be_true());
+
+  let result = match_interaction_with_mode(expected.boxed(), actual.boxed(), pact.boxed(),
+    &PactSpecification::V4, MatchMode::FirstMismatch).await.unwrap();
+  expect!(result.iter().map(|m| m.mismatch_type()).collect::<Vec<_>>()).to(be_equal_to(vec!["HeaderMismatch"]));
+
+  let matches = interaction_request_matches(expected.boxed(), actual.boxed(), pact.boxed(),
+    &PactSpecification::V4).await.unwrap();
+  expect!(matches).to(be_false());
+  let matches = interaction_request_matches(expected.boxed(), expected.boxed(), pact,
+    &PactSpecification::V4).await.unwrap();
+  expect!(matches).to(be_true());
+}
+
+#[test]
+fn matching_config_is_read_from_the_pact_metadata() {
+  let mut pact = pact_models::sync_pact::RequestResponsePact::default();
+  expect!(MatchingConfig::from_pact(&pact)).to(be_equal_to(MatchingConfig::default()));
+
+  pact.add_md_value(CASE_INSENSITIVE_KEYS, "true");
+  pact.add_md_value(STRICT_CONTENT_TYPE, "true");
+  pact.add_md_value(STRICT_NUMBER_TYPES, "false");
+  expect!(MatchingConfig::from_pact(&pact)).to(be_equal_to(MatchingConfig {
+    case_insensitive_keys: true,
+    strict_content_type: true,
+    .. MatchingConfig::default()
+  }));
+  expect!(pact_rust_flag(&pact, CASE_INSENSITIVE_KEYS)).to(be_true());
+  expect!(pact_rust_flag(&pact, STRICT_NUMBER_TYPES)).to(be_false());
+}
+
+#[test]
+fn matching_config_metadata_keys_round_trip_through_the_pact_metadata() {
+  let mut pact = pact_models::sync_pact::RequestResponsePact::default();
+  let config = MatchingConfig { form_bracket_notation: true, .. MatchingConfig::strict() };
+  for key in config.metadata_keys() {
+    pact.add_md_value(key, "true");
+  }
+  expect!(MatchingConfig::from_pact(&pact)).to(be_equal_to(config));
+}
+
+#[test]
+fn is_backward_compatible_allows_the_new_response_to_add_fields_and_headers() {
+  let old_response = HttpResponse {
+    headers: Some(hashmap!{ "Content-Type".to_string() => vec!["application/json".to_string()] }),
+    body: OptionalBody::Present(r#"{"id": 100, "price": 10}"#.into(), Some(JSON.clone()), None),
+    .. HttpResponse::default()
+  };
+  let new_response = HttpResponse {
+    headers: Some(hashmap!{
+      "content-type".to_string() => vec!["application/json".to_string()],
+      "X-Request-Id".to_string() => vec!["1234".to_string()]
+    }),
+    body: OptionalBody::Present(r#"{"id": 200, "price": 10.5, "name": "Widget"}"#.into(), Some(JSON.clone()), None),
+    .. HttpResponse::default()
+  };
+  expect!(is_backward_compatible(&old_response, &new_response)).to(be_empty());
+}
+
+#[test]
+fn is_backward_compatible_returns_the_breaking_changes() {
+  let old_response = HttpResponse {
+    headers: Some(hashmap!{
+      "Content-Type".to_string() => vec!["application/json".to_string()],
+      "X-Request-Id".to_string() => vec!["1234".to_string()]
+    }),
+    body: OptionalBody::Present(r#"{"id": 100, "name": "Widget"}"#.into(), Some(JSON.clone()), None),
+    .. HttpResponse::default()
+  };
+  let new_response = HttpResponse {
+    status: 201,
+    headers: Some(hashmap!{ "Content-Type".to_string() => vec!["application/json".to_string()] }),
+    body: OptionalBody::Present(r#"{"id": "100"}"#.into(), Some(JSON.clone()), None),
+    .. HttpResponse::default()
+  };
+  let mismatches = is_backward_compatible(&old_response, &new_response);
+  expect!(mismatches.iter().map(|m| m.description()).collect::<Vec<_>>()).to(be_equal_to(vec![
+    "Status has been changed from 200 to 201".to_string(),
+    "Header 'X-Request-Id' has been removed".to_string(),
+    "$.id -> Type has been changed from Integer to String".to_string(),
+    "$.name -> Field 'name' has been removed".to_string()
+  ]));
+
+  let new_response = HttpResponse {
+    headers: Some(hashmap!{
+      "Content-Type".to_string() => vec!["application/xml".to_string()],
+      "X-Request-Id".to_string() => vec!["1234".to_string()]
+    }),
+    body: OptionalBody::Present("<id>100</id>".into(), Some(XML.clone()), None),
+    .. HttpResponse::default()
+  };
+  let mismatches = is_backward_compatible(&old_response, &new_response);
+  expect!(mismatches.iter().map(|m| m.mismatch_type()).collect::<Vec<_>>()).to(be_equal_to(vec!["BodyTypeMismatch"]));
+}
+
+#[test]
+fn mismatches_equivalent_ignores_the_order_of_the_mismatches() {
+  let status = Mismatch::StatusMismatch { expected: 200, actual: 404, mismatch: "".into() };
+  let header = Mismatch::HeaderMismatch { key: "X-Id".into(), expected: "1".into(), actual: "2".into(), mismatch: "".into() };
+  let body = Mismatch::BodyMismatch { path: "$.id".into(), expected: Some("1".into()), actual: Some("2".into()), mismatch: "".into() };
+
+  expect!(mismatches_equivalent(&[], &[])).to(be_true());
+  expect!(mismatches_equivalent(&[status.clone(), header.clone(), body.clone()],
+    &[body.clone(), status.clone(), header.clone()])).to(be_true());
+  expect!(mismatches_equivalent(&[status.clone(), header.clone()], &[status.clone(), body.clone()])).to(be_false());
+  expect!(mismatches_equivalent(&[status.clone(), header.clone()], &[header.clone()])).to(be_false());
+  expect!(mismatches_equivalent(&[status.clone(), status.clone(), header.clone()],
+    &[status.clone(), header.clone(), header.clone()])).to(be_false());
+}
+
+#[test]
+fn mismatches_equivalent_does_not_compare_the_descriptions() {
+  let body1 = Mismatch::BodyMismatch { path: "$.id".into(), expected: Some("1".into()), actual: Some("2".into()), mismatch: "first".into() };
+  let body2 = Mismatch::BodyMismatch { path: "$.id".into(), expected: Some("1".into()), actual: Some("2".into()), mismatch: "second".into() };
+  let body3 = Mismatch::BodyMismatch { path: "$.name".into(), expected: None, actual: None, mismatch: "".into() };
+
+  expect!(mismatches_equivalent(&[body1.clone(), body3.clone()], &[body3.clone(), body2.clone()])).to(be_true());
+}
+
+#[test]
+fn redact_sensitive_values_only_redacts_the_values_at_sensitive_paths() {
+  let rules = matchingrules!{
+    "body" => { "$.token" => [ MatchingRule::Sensitive ] },
+    "header" => { "$.Authorization" => [ MatchingRule::Sensitive ] }
+  };
+  let token = Mismatch::BodyMismatch { path: "$.token".into(), expected: Some("abc".into()),
+    actual: Some("xyz".into()), mismatch: "Expected 'abc' to be equal to 'xyz'".into() };
+  let id = Mismatch::BodyMismatch { path: "$.id".into(), expected: Some("1".into()),
+    actual: Some("2".into()), mismatch: "Expected 1 to be equal to 2".into() };
+  let header = Mismatch::HeaderMismatch { key: "authorization".into(), expected: "Bearer abc".into(),
+    actual: "Bearer xyz".into(), mismatch: "Expected 'Bearer abc' to be equal to 'Bearer xyz'".into() };
+
+  let redacted = redact_sensitive_values(token.clone(), &rules);
+  expect!(redacted.clone()).to(be_equal_to(Mismatch::BodyMismatch { path: "$.token".into(),
+    expected: Some("***".into()), actual: Some("***".into()), mismatch: "".into() }));
+  expect!(redacted.description().contains("abc")).to(be_false());
+  expect!(redacted.to_json().to_string().contains("xyz")).to(be_false());
+
+  let redacted = redact_sensitive_values(header.clone(), &rules);
+  expect!(redacted.description().contains("abc")).to(be_false());
+  expect!(redacted.ansi_description().contains("xyz")).to(be_false());
+
+  expect!(redact_sensitive_values(id.clone(), &rules).description()).to(be_equal_to(id.description()));
+  expect!(redact_sensitive_values(token.clone(), &matchingrules!{}).description())
+    .to(be_equal_to(token.description()));
+
+  let root = Mismatch::BodyMismatch { path: "$".into(), expected: Some("{\"token\":\"abc\"}".into()),
+    actual: Some("{\"token\":\"xyz\"}".into()), mismatch: "Expected {\"token\":\"abc\"}".into() };
+  let redacted = redact_sensitive_values(root, &rules);
+  expect!(redacted.description().contains("abc")).to(be_false());
+  expect!(redacted.to_json().to_string().contains("xyz")).to(be_false());
+
+  let body_type = Mismatch::BodyTypeMismatch { expected: "application/json".into(), actual: "text/plain".into(),
+    mismatch: "Expected a body of 'application/json' but the actual content type was 'text/plain'".into(),
+    expected_body: Some("{\"token\":\"abc\"}".into()), actual_body: Some("xyz".into()) };
+  match redact_sensitive_values(body_type, &rules) {
+    Mismatch::BodyTypeMismatch { expected, expected_body, actual_body, .. } => {
+      expect!(expected).to(be_equal_to("application/json".to_string()));
+      expect!(expected_body).to(be_some().value(Bytes::from("***")));
+      expect!(actual_body).to(be_some().value(Bytes::from("***")));
+    }
+    mismatch => panic!("Expected a body type mismatch, got {:?}", mismatch)
+  }
+}
+
+#[tokio::test]
+async fn sensitive_marker_does_not_change_how_values_are_matched() {
+  let expected = HttpResponse {
+    headers: Some(hashmap!{ "Authorization".to_string() => vec!["Bearer abc".to_string()] }),
+    body: OptionalBody::from(json!({ "id": 100, "token": "abc" })),
+    matching_rules: matchingrules!{
+      "body" => { "$" => [ MatchingRule::Type ], "$.token" => [ MatchingRule::Sensitive ] },
+      "header" => { "$.Authorization" => [ MatchingRule::Sensitive ] }
+    },
+    .. HttpResponse::default()
+  };
+  let actual = HttpResponse {
+    headers: Some(hashmap!{ "Authorization".to_string() => vec!["Bearer abc ".to_string()] }),
+    body: OptionalBody::from(json!({ "id": 200, "token": "xyz" })),
+    .. HttpResponse::default()
+  };
+  let pact = pact_models::v4::pact::V4Pact::default().boxed();
+  let interaction = pact_models::v4::synch_http::SynchronousHttp::default().boxed();
+
+  let result = match_response(expected, actual, &pact, &interaction).await;
+  expect!(result.iter()).to(be_empty());
+}
+
+#[tokio::test]
+async fn match_response_redacts_sensitive_values_from_the_mismatches() {
+  let expected = HttpResponse {
+    headers: Some(hashmap!{ "Authorization".to_string() => vec!["Bearer abc".to_string()] }),
+    body: OptionalBody::from(json!({ "id": 100, "token": "abc" })),
+    matching_rules: matchingrules!{
+      "body" => { "$.token" => [ MatchingRule::Sensitive ] },
+      "header" => { "$.Authorization" => [ MatchingRule::Sensitive ] }
+    },
+    .. HttpResponse::default()
+  };
+  let actual = HttpResponse {
+    headers: Some(hashmap!{ "Authorization".to_string() => vec!["Bearer xyz".to_string()] }),
+    body: OptionalBody::from(json!({ "id": 200, "token": "xyz" })),
+    .. HttpResponse::default()
+  };
+  let pact = pact_models::v4::pact::V4Pact::default().boxed();
+  let interaction = pact_models::v4::synch_http::SynchronousHttp::default().boxed();
+
+  let result = match_response(expected, actual, &pact, &interaction).await;
+  expect!(result.len()).to(be_equal_to(3));
+  expect!(result.iter().any(|m| m.description().contains("abc") || m.description().contains("xyz"))).to(be_false());
+  expect!(result.iter().any(|m| m.description().contains("200"))).to(be_true());
+}
@@ -138,6 +138,12 @@ impl<'a> Matches<&'a Element<'a>> for &'a Element<'a> {
           } else {
             Ok(())
           },
+          MatchingRule::Empty => if actual.children().is_empty() {
+            Ok(())
+          } else {
+            Err(anyhow!("Expected '{}' to have no children but found {} child(ren)", name(actual.name()),
+              actual.children().len()))
+          },
           _ => Err(anyhow!("Unable to match {:?} using {:?}", self, matcher))
         };
         debug!("Comparing '{:?}' to '{:?}' using {:?} -> {:?}", self, actual, matcher, result);
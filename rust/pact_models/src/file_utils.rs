@@ -1,12 +1,16 @@
 //! Functions for dealing with file locks while reading/writing pact files
 
+use std::fs;
 use std::fs::File;
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::thread::sleep;
 use std::time::Duration;
 
 use anyhow::bail;
 use fs2::FileExt;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
 use tracing::{error, trace, warn};
 
 /// Attempts to get a read lock on the open file before proceeding with the provided closure.
@@ -90,3 +94,62 @@ pub fn with_write_lock<T>(
   error!("{}", msg);
   bail!(msg)
 }
+
+/// Attempts to get a write lock on a separate lock file for the given path (`<path>.lock`) before
+/// proceeding with the provided closure. As the lock is not held on the file itself, the closure
+/// can replace the file (i.e. with `write_file_atomically`) without a process waiting for the lock
+/// ending up with the replaced file. The lock file is not removed afterwards, as another process
+/// could then lock a different lock file.
+pub fn with_lock_file<T>(
+  path: &Path,
+  attempts: u32,
+  cl: &mut dyn FnMut() -> anyhow::Result<T>
+) -> anyhow::Result<T> {
+  let lock_path = lock_file_path(path);
+  let mut lock_file = fs::OpenOptions::new()
+    .read(true)
+    .write(true)
+    .create(true)
+    .truncate(false)
+    .open(&lock_path)?;
+  with_write_lock(&lock_path, &mut lock_file, attempts, &mut |_| cl())
+}
+
+fn lock_file_path(path: &Path) -> PathBuf {
+  let file_name = path.file_name()
+    .map(|name| name.to_string_lossy().to_string())
+    .unwrap_or_default();
+  path.with_file_name(format!("{}.lock", file_name))
+}
+
+/// Writes the contents to the file at the given path atomically. The contents are first written to
+/// a temporary file in the same directory, which is then renamed over the target. Readers will
+/// either see the previous file or the complete new one, never a partially written file.
+pub fn write_file_atomically(path: &Path, contents: &[u8]) -> anyhow::Result<()> {
+  let temp_path = temp_file_path(path);
+  trace!("Writing '{}' via temporary file '{}'", path.to_string_lossy(), temp_path.to_string_lossy());
+  let result = File::create(&temp_path)
+    .and_then(|mut file| {
+      file.write_all(contents)?;
+      file.sync_all()
+    })
+    .and_then(|_| fs::rename(&temp_path, path));
+  if let Err(err) = result {
+    let _ = fs::remove_file(&temp_path);
+    error!("Failed to write '{}' - {}", path.to_string_lossy(), err);
+    return Err(err.into());
+  }
+  Ok(())
+}
+
+fn temp_file_path(path: &Path) -> PathBuf {
+  let file_name = path.file_name()
+    .map(|name| name.to_string_lossy().to_string())
+    .unwrap_or_default();
+  let suffix: String = rand::thread_rng()
+    .sample_iter(&Alphanumeric)
+    .take(8)
+    .map(char::from)
+    .collect();
+  path.with_file_name(format!(".{}.{}.tmp", file_name, suffix))
+}
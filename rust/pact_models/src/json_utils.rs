@@ -216,6 +216,23 @@ pub fn json_deep_merge(value: &Value, other: &Value) -> Value {
   }
 }
 
+/// Returns a copy of the JSON value with the keys of every object (at any depth) in sorted
+/// order. This makes the serialised form stable regardless of how the underlying map orders
+/// its entries.
+pub fn sort_json_keys(value: &Value) -> Value {
+  match value {
+    Value::Array(items) => Value::Array(items.iter().map(sort_json_keys).collect()),
+    Value::Object(entries) => {
+      let map = entries.iter()
+        .sorted_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(k, v)| (k.clone(), sort_json_keys(v)))
+        .collect::<Map<String, Value>>();
+      Value::Object(map)
+    },
+    _ => value.clone()
+  }
+}
+
 /// If the JSON value is empty
 pub fn is_empty(value: &Value) -> bool {
   match value {
@@ -516,4 +533,17 @@ mod tests {
       "Date".to_string() => vec!["Sun, 12 Mar 2023 01:21:35 GMT".to_string()]
     }));
   }
+
+  #[test]
+  fn sort_json_keys_test() {
+    let value = json!({
+      "z": 1,
+      "a": [{ "y": true, "b": null }],
+      "m": { "x": "x", "c": { "q": 1, "d": 2 } }
+    });
+    let sorted = sort_json_keys(&value);
+    expect!(sorted.clone()).to(be_equal_to(value));
+    expect!(sorted.to_string()).to(be_equal_to(
+      r#"{"a":[{"b":null,"y":true}],"m":{"c":{"d":2,"q":1},"x":"x"},"z":1}"#.to_string()));
+  }
 }
@@ -11,7 +11,7 @@ use anyhow::{anyhow, Context as _};
 use itertools::{Either, Itertools};
 use maplit::hashmap;
 use serde_json::{json, Map, Value};
-use tracing::{error, trace};
+use tracing::{error, trace, warn};
 
 use crate::{HttpStatus, PactSpecification};
 use crate::generators::{Generator, GeneratorCategory, Generators};
@@ -21,6 +21,13 @@ use crate::path_exp::{DocPath, PathToken};
 
 pub mod expressions;
 
+/// Key used for the named matcher definitions of a category in the JSON form. This is not part of
+/// the Pact specification, so it is only read and never written (references are inlined instead)
+const DEFINITIONS_KEY: &str = "$definitions";
+
+/// Key used for a reference to a named matcher definition in the JSON form
+const REFERENCE_KEY: &str = "$ref";
+
 fn generator_from_json(json: &Map<String, Value>) -> Option<Generator> {
   if let Some(generator_json) = json.get("generator") {
     match generator_json {
@@ -39,16 +46,26 @@ fn generator_from_json(json: &Map<String, Value>) -> Option<Generator> {
   }
 }
 
+fn rule_or_reference_to_json(rule: &Either<MatchingRule, MatchingReference>) -> Value {
+  match rule {
+    Either::Left(rule) => rule.to_json(),
+    Either::Right(reference) => json!({ REFERENCE_KEY: reference.name })
+  }
+}
+
 fn rules_from_json(attributes: &Map<String, Value>) -> anyhow::Result<Vec<Either<MatchingRule, MatchingReference>>> {
   match attributes.get("rules") {
     Some(rules) => match rules {
       Value::Array(rules) => {
         let rules = rules.iter()
-          .map(|rule| MatchingRule::from_json(rule));
+          .map(|rule| match rule.get(REFERENCE_KEY) {
+            Some(name) => Ok(Either::Right(MatchingReference { name: json_to_string(name) })),
+            None => MatchingRule::from_json(rule).map(Either::Left)
+          });
         if let Some(err) = rules.clone().find(|rule| rule.is_err()) {
           Err(anyhow!("Matching rule configuration is not correct - {}", err.unwrap_err()))
         } else {
-          Ok(rules.map(|rule| Either::Left(rule.unwrap())).collect())
+          Ok(rules.map(|rule| rule.unwrap()).collect())
         }
       }
       _ => Err(anyhow!("EachKey matcher config is not valid. Was expected an array but got {}", rules))
@@ -57,8 +74,13 @@ fn rules_from_json(attributes: &Map<String, Value>) -> anyhow::Result<Vec<Either
   }
 }
 
+/// Tolerance used by the `MultipleOf` matcher. A value is a multiple of the base if dividing it by
+/// the base is within this amount of a whole number, as decimal bases (i.e. `0.01`) can not be
+/// represented exactly as floating point numbers.
+pub const MULTIPLE_OF_TOLERANCE: f64 = 1e-9;
+
 /// Set of all matching rules
-#[derive(Debug, Clone, Eq)]
+#[derive(Debug, Clone)]
 pub enum MatchingRule {
   /// Matcher using equals
   Equality,
@@ -105,7 +127,137 @@ pub enum MatchingRule {
   /// Matcher for keys in a map
   EachKey(MatchingRuleDefinition),
   /// Matcher for values in a collection. This delegates to the Values matcher for maps.
-  EachValue(MatchingRuleDefinition)
+  EachValue(MatchingRuleDefinition),
+  /// Matches maps where all the keys are in the given set of allowed keys
+  AllowedKeys(Vec<String>),
+  /// Header value must be a strong ETag of the body, being the Base64 encoded hash of the body
+  /// using the given algorithm (sha256, sha512 or md5)
+  BodyHash(String),
+  /// Response header value must correlate with the value of the given request header, after
+  /// applying the optional transform (e.g. `add:1` or `prefix:abc`)
+  Correlation(String, Option<String>),
+  /// Value must be a valid path expression (i.e. `$.a.b[0]`)
+  PathExpression,
+  /// Value must be a valid Base64 encoded string, with the optional minimum and maximum lengths
+  /// of the decoded data
+  Base64(Option<usize>, Option<usize>),
+  /// Header value must be a valid `Retry-After` value (a number of seconds or an HTTP date), with
+  /// the delay in seconds within the optional minimum and maximum
+  RetryAfter(Option<usize>, Option<usize>),
+  /// Body must be an image that is perceptually similar to the expected image. The threshold is
+  /// the maximum allowed difference between the perceptual hashes of the images, from 0.0
+  /// (identical) to 1.0 (completely different)
+  ImageSimilarity {
+    /// Maximum allowed difference between the images
+    threshold: f64
+  },
+  /// Value must be an ISO-8601 duration (i.e. `PT1H30M`)
+  Duration,
+  /// String value must have a length (in characters) within the optional minimum and maximum
+  StringLength {
+    /// Minimum length of the string
+    min: Option<usize>,
+    /// Maximum length of the string
+    max: Option<usize>
+  },
+  /// Match arrays where the expected items appear in the actual array in the same relative order,
+  /// with any other items allowed between them
+  ArrayContainsInOrder,
+  /// Match arrays where all the items are unique, optionally compared by the value at the given
+  /// path relative to each item (i.e. `$.id`)
+  ArrayDistinct(Option<String>),
+  /// Value must be an integer number between the minimum and maximum (inclusive)
+  IntegerRange {
+    /// Minimum value of the integer
+    min: i64,
+    /// Maximum value of the integer
+    max: i64
+  },
+  /// Body must be valid against the JSON Schema
+  JsonSchema(Value),
+  /// Value must be a date/time in the format that is after the reference date/time. The
+  /// reference is either `now` (the current time when matching) or a literal date/time in the same
+  /// format. References to values from the matching context (i.e. `${expiresAt}`) are not
+  /// supported.
+  DateTimeAfter {
+    /// Format of the date/time values (an empty format uses the default timestamp format)
+    format: String,
+    /// Reference date/time to compare to
+    reference: String
+  },
+  /// Value must be a date/time in the format that is before the reference date/time. The
+  /// reference is either `now` (the current time when matching) or a literal date/time in the same
+  /// format. References to values from the matching context (i.e. `${expiresAt}`) are not
+  /// supported.
+  DateTimeBefore {
+    /// Format of the date/time values (an empty format uses the default timestamp format)
+    format: String,
+    /// Reference date/time to compare to
+    reference: String
+  },
+  /// Matches maps that contain exactly the given keys, ignoring the values
+  Keys(Vec<String>),
+  /// Value must be one of the given set of strings
+  OneOf(Vec<String>),
+  /// Value must either be null, or match the given matching rule
+  Nullable(Box<MatchingRule>),
+  /// String value must be a valid IP address, optionally of the given version (4 or 6)
+  IpAddress {
+    /// IP version the address must be (4 or 6). Any version is accepted if not set.
+    version: Option<u8>
+  },
+  /// String value must be valid for the named format (i.e. `email`), using the validator
+  /// registered for the format
+  Format(String),
+  /// Matches any value. When applied to the root of a body, the contents of the body are not
+  /// compared at all, only that a body is present with a matching content type
+  Any,
+  /// Regex applied to the value serialised as canonical JSON text (i.e. a string value includes
+  /// its quotes, and numbers are written in their shortest form)
+  JsonRegex(String),
+  /// Marker that stops rules from cascading past the path it is defined for. The other rules at
+  /// the same path, and any rules defined for the parents of the path, are not applied to any of
+  /// the descendants of the path (only rules defined below the path are). If there are no other
+  /// rules at the path, the value at the path is compared for equality.
+  NoCascade,
+  /// Value must be a number that is a multiple of the base (i.e. a price in cents that must be
+  /// a multiple of 5). The base must be a positive number. The value is compared with a tolerance
+  /// of `MULTIPLE_OF_TOLERANCE`, so `0.3` is a multiple of `0.1`
+  MultipleOf(f64),
+  /// Marker for a `Cookie` or `Set-Cookie` header that causes the header values to be parsed into
+  /// structured cookies, so that matching rules can be applied to the individual cookies (and
+  /// attributes of the cookies for `Set-Cookie` headers)
+  Cookie,
+  /// Marker for the request path that causes the path and query string to be matched as a single
+  /// string (the raw request target, i.e. `/path?a=1&b=2`) with the other path matching rules,
+  /// instead of matching the path and query parameters separately
+  RawTarget,
+  /// Value must be present and empty (an empty object, array or string). A missing value is not
+  /// treated as empty
+  Empty,
+  /// Marker for a header with multiple values that causes the values to be compared ignoring
+  /// their order (i.e. for the `Vary` header). By default, the values are compared by index
+  UnorderedValues,
+  /// Value must be a string containing a number that is numerically equal to the expected one
+  /// (i.e. `"100.50"` matches `"100.5"`), optionally within the given tolerance
+  NumericString {
+    /// Maximum allowed difference between the expected and actual numbers
+    tolerance: Option<f64>
+  },
+  /// Value must be a string of hexadecimal digits (i.e. a hash or colour), optionally with the
+  /// given number of digits
+  Hex {
+    /// Required number of hexadecimal digits
+    length: Option<usize>
+  },
+  /// Marker for a value that is sensitive (i.e. a token or personal information), which causes
+  /// the expected and actual values to be redacted from any mismatches for the path and its
+  /// children. The marker is ignored when selecting the matchers for a path, so it does not change
+  /// how the value is matched
+  Sensitive,
+  /// Marker for a header with a JSON value that causes the header value to be parsed as JSON, so
+  /// that matching rules can be applied to the JSON value the same way as for bodies
+  JsonValue
 }
 
 impl MatchingRule {
@@ -197,7 +349,7 @@ impl MatchingRule {
         let mut json = json!({
           "match": "eachKey",
           "rules": definition.rules.iter()
-            .map(|rule| rule.as_ref().expect_left("Expected a matching rule, found an unresolved reference").to_json())
+            .map(rule_or_reference_to_json)
           .collect::<Vec<Value>>()
         });
         let map = json.as_object_mut().unwrap();
@@ -216,7 +368,7 @@ impl MatchingRule {
         let mut json = json!({
           "match": "eachValue",
           "rules": definition.rules.iter()
-            .map(|rule| rule.as_ref().expect_left("Expected a matching rule, found an unresolved reference").to_json())
+            .map(rule_or_reference_to_json)
           .collect::<Vec<Value>>()
         });
         let map = json.as_object_mut().unwrap();
@@ -231,6 +383,93 @@ impl MatchingRule {
 
         Value::Object(map.clone())
       }
+      MatchingRule::AllowedKeys(keys) => json!({ "match": "allowedKeys", "keys": keys }),
+      MatchingRule::BodyHash(algorithm) => json!({ "match": "bodyHash", "algorithm": algorithm }),
+      MatchingRule::Correlation(header, transform) => if let Some(transform) = transform {
+        json!({ "match": "correlation", "header": header, "transform": transform })
+      } else {
+        json!({ "match": "correlation", "header": header })
+      }
+      MatchingRule::PathExpression => json!({ "match": "pathExpression" }),
+      MatchingRule::Base64(min, max) => {
+        let mut json = json!({ "match": "base64" });
+        let map = json.as_object_mut().unwrap();
+        if let Some(min) = min {
+          map.insert("min".to_string(), json!(min));
+        }
+        if let Some(max) = max {
+          map.insert("max".to_string(), json!(max));
+        }
+        json
+      }
+      MatchingRule::RetryAfter(min, max) => {
+        let mut json = json!({ "match": "retryAfter" });
+        let map = json.as_object_mut().unwrap();
+        if let Some(min) = min {
+          map.insert("min".to_string(), json!(min));
+        }
+        if let Some(max) = max {
+          map.insert("max".to_string(), json!(max));
+        }
+        json
+      }
+      MatchingRule::ImageSimilarity { threshold } => json!({ "match": "imageSimilarity", "threshold": threshold }),
+      MatchingRule::Duration => json!({ "match": "duration" }),
+      MatchingRule::StringLength { min, max } => {
+        let mut json = json!({ "match": "stringLength" });
+        let map = json.as_object_mut().unwrap();
+        if let Some(min) = min {
+          map.insert("min".to_string(), json!(min));
+        }
+        if let Some(max) = max {
+          map.insert("max".to_string(), json!(max));
+        }
+        json
+      }
+      MatchingRule::ArrayContainsInOrder => json!({ "match": "arrayContainsInOrder" }),
+      MatchingRule::ArrayDistinct(by) => if let Some(by) = by {
+        json!({ "match": "arrayDistinct", "by": by })
+      } else {
+        json!({ "match": "arrayDistinct" })
+      }
+      MatchingRule::IntegerRange { min, max } => json!({ "match": "integerRange", "min": min, "max": max }),
+      MatchingRule::JsonSchema(schema) => json!({ "match": "jsonSchema", "schema": schema }),
+      MatchingRule::DateTimeAfter { format, reference } => if format.is_empty() {
+        json!({ "match": "datetimeAfter", "reference": reference })
+      } else {
+        json!({ "match": "datetimeAfter", "format": format, "reference": reference })
+      }
+      MatchingRule::DateTimeBefore { format, reference } => if format.is_empty() {
+        json!({ "match": "datetimeBefore", "reference": reference })
+      } else {
+        json!({ "match": "datetimeBefore", "format": format, "reference": reference })
+      }
+      MatchingRule::Keys(keys) => json!({ "match": "keys", "value": keys }),
+      MatchingRule::OneOf(values) => json!({ "match": "oneOf", "values": values }),
+      MatchingRule::Nullable(rule) => json!({ "match": "nullable", "rule": rule.to_json() }),
+      MatchingRule::IpAddress { version } => match version {
+        Some(version) => json!({ "match": "ipAddress", "version": version }),
+        None => json!({ "match": "ipAddress" })
+      },
+      MatchingRule::Format(format) => json!({ "match": "format", "format": format }),
+      MatchingRule::Any => json!({ "match": "any" }),
+      MatchingRule::JsonRegex(regex) => json!({ "match": "jsonRegex", "regex": regex }),
+      MatchingRule::NoCascade => json!({ "match": "noCascade" }),
+      MatchingRule::MultipleOf(base) => json!({ "match": "multipleOf", "base": base }),
+      MatchingRule::Cookie => json!({ "match": "cookie" }),
+      MatchingRule::RawTarget => json!({ "match": "rawTarget" }),
+      MatchingRule::Empty => json!({ "match": "empty" }),
+      MatchingRule::UnorderedValues => json!({ "match": "unorderedValues" }),
+      MatchingRule::NumericString { tolerance } => match tolerance {
+        Some(tolerance) => json!({ "match": "numericString", "tolerance": tolerance }),
+        None => json!({ "match": "numericString" })
+      },
+      MatchingRule::Hex { length } => match length {
+        Some(length) => json!({ "match": "hex", "length": length }),
+        None => json!({ "match": "hex" })
+      },
+      MatchingRule::Sensitive => json!({ "match": "sensitive" }),
+      MatchingRule::JsonValue => json!({ "match": "jsonValue" })
     }
   }
 
@@ -276,7 +515,39 @@ impl MatchingRule {
       MatchingRule::NotEmpty => "not-empty",
       MatchingRule::Semver => "semver",
       MatchingRule::EachKey(_) => "each-key",
-      MatchingRule::EachValue(_) => "each-value"
+      MatchingRule::EachValue(_) => "each-value",
+      MatchingRule::AllowedKeys(_) => "allowed-keys",
+      MatchingRule::BodyHash(_) => "body-hash",
+      MatchingRule::Correlation(_, _) => "correlation",
+      MatchingRule::PathExpression => "path-expression",
+      MatchingRule::Base64(_, _) => "base64",
+      MatchingRule::RetryAfter(_, _) => "retry-after",
+      MatchingRule::ImageSimilarity { .. } => "image-similarity",
+      MatchingRule::Duration => "duration",
+      MatchingRule::StringLength { .. } => "string-length",
+      MatchingRule::ArrayContainsInOrder => "array-contains-in-order",
+      MatchingRule::ArrayDistinct(_) => "array-distinct",
+      MatchingRule::IntegerRange { .. } => "integer-range",
+      MatchingRule::JsonSchema(_) => "json-schema",
+      MatchingRule::DateTimeAfter { .. } => "datetime-after",
+      MatchingRule::DateTimeBefore { .. } => "datetime-before",
+      MatchingRule::Keys(_) => "keys",
+      MatchingRule::OneOf(_) => "one-of",
+      MatchingRule::Nullable(_) => "nullable",
+      MatchingRule::IpAddress { .. } => "ip-address",
+      MatchingRule::Format(_) => "format",
+      MatchingRule::Any => "any",
+      MatchingRule::JsonRegex(_) => "json-regex",
+      MatchingRule::NoCascade => "no-cascade",
+      MatchingRule::MultipleOf(_) => "multiple-of",
+      MatchingRule::Cookie => "cookie",
+      MatchingRule::RawTarget => "raw-target",
+      MatchingRule::Empty => "empty",
+      MatchingRule::UnorderedValues => "unordered-values",
+      MatchingRule::NumericString { .. } => "numeric-string",
+      MatchingRule::Hex { .. } => "hex",
+      MatchingRule::Sensitive => "sensitive",
+      MatchingRule::JsonValue => "json-value"
     }.to_string()
   }
 
@@ -314,7 +585,7 @@ impl MatchingRule {
       MatchingRule::EachKey(definition) | MatchingRule::EachValue(definition) => {
         let mut map = hashmap! {
           "rules" => Value::Array(definition.rules.iter()
-            .map(|rule| rule.as_ref().expect_left("Expected a matching rule, found an unresolved reference").to_json())
+            .map(rule_or_reference_to_json)
             .collect())
         };
 
@@ -328,6 +599,73 @@ impl MatchingRule {
 
         map
       }
+      MatchingRule::AllowedKeys(keys) => hashmap!{ "keys" => json!(keys) },
+      MatchingRule::BodyHash(algorithm) => hashmap!{ "algorithm" => Value::String(algorithm.clone()) },
+      MatchingRule::Correlation(header, transform) => if let Some(transform) = transform {
+        hashmap!{
+          "header" => Value::String(header.clone()),
+          "transform" => Value::String(transform.clone())
+        }
+      } else {
+        hashmap!{ "header" => Value::String(header.clone()) }
+      }
+      MatchingRule::PathExpression => empty,
+      MatchingRule::Base64(min, max) | MatchingRule::RetryAfter(min, max) |
+      MatchingRule::StringLength { min, max } => {
+        let mut map = hashmap!{};
+        if let Some(min) = min {
+          map.insert("min", json!(min));
+        }
+        if let Some(max) = max {
+          map.insert("max", json!(max));
+        }
+        map
+      }
+      MatchingRule::ImageSimilarity { threshold } => hashmap!{ "threshold" => json!(threshold) },
+      MatchingRule::Duration => empty,
+      MatchingRule::ArrayContainsInOrder => empty,
+      MatchingRule::ArrayDistinct(by) => if let Some(by) = by {
+        hashmap!{ "by" => Value::String(by.clone()) }
+      } else {
+        empty
+      }
+      MatchingRule::IntegerRange { min, max } => hashmap!{ "min" => json!(min), "max" => json!(max) },
+      MatchingRule::JsonSchema(schema) => hashmap!{ "schema" => schema.clone() },
+      MatchingRule::DateTimeAfter { format, reference } |
+      MatchingRule::DateTimeBefore { format, reference } => if format.is_empty() {
+        hashmap!{ "reference" => Value::String(reference.clone()) }
+      } else {
+        hashmap!{
+          "format" => Value::String(format.clone()),
+          "reference" => Value::String(reference.clone())
+        }
+      }
+      MatchingRule::Keys(keys) => hashmap!{ "value" => json!(keys) },
+      MatchingRule::OneOf(values) => hashmap!{ "values" => json!(values) },
+      MatchingRule::Nullable(rule) => hashmap!{ "rule" => rule.to_json() },
+      MatchingRule::IpAddress { version } => match version {
+        Some(version) => hashmap!{ "version" => json!(version) },
+        None => hashmap!{}
+      },
+      MatchingRule::Format(format) => hashmap!{ "format" => Value::String(format.clone()) },
+      MatchingRule::Any => empty,
+      MatchingRule::JsonRegex(regex) => hashmap!{ "regex" => Value::String(regex.clone()) },
+      MatchingRule::NoCascade => empty,
+      MatchingRule::MultipleOf(base) => hashmap!{ "base" => json!(base) },
+      MatchingRule::Cookie => empty,
+      MatchingRule::RawTarget => empty,
+      MatchingRule::Empty => empty,
+      MatchingRule::UnorderedValues => empty,
+      MatchingRule::NumericString { tolerance } => match tolerance {
+        Some(tolerance) => hashmap!{ "tolerance" => json!(tolerance) },
+        None => empty
+      },
+      MatchingRule::Hex { length } => match length {
+        Some(length) => hashmap!{ "length" => json!(length) },
+        None => empty
+      },
+      MatchingRule::Sensitive => empty,
+      MatchingRule::JsonValue => empty
     }
   }
 
@@ -435,7 +773,7 @@ impl MatchingRule {
         None => Err(anyhow!("ArrayContains matcher missing 'variants' field")),
       }
       "values" => Ok(MatchingRule::Values),
-      "statusCode" | "status-code" => match attributes.get("status") {
+      "statusCode" | "status-code" => match attributes.get("status").or_else(|| attributes.get("codes")) {
         Some(s) => {
           let status = HttpStatus::from_json(s)
             .context("Unable to parse status code for StatusCode matcher")?;
@@ -445,6 +783,99 @@ impl MatchingRule {
       },
       "notEmpty" | "not-empty" => Ok(MatchingRule::NotEmpty),
       "semver" => Ok(MatchingRule::Semver),
+      "pathExpression" | "path-expression" => Ok(MatchingRule::PathExpression),
+      "duration" => Ok(MatchingRule::Duration),
+      "arrayContainsInOrder" | "array-contains-in-order" => Ok(MatchingRule::ArrayContainsInOrder),
+      "arrayDistinct" | "array-distinct" => match attributes.get("by") {
+        Some(Value::String(by)) => Ok(MatchingRule::ArrayDistinct(Some(by.clone()))),
+        Some(_) => Err(anyhow!("ArrayDistinct matcher 'by' field is not a String")),
+        None => Ok(MatchingRule::ArrayDistinct(None))
+      },
+      "integerRange" | "integer-range" => match (attributes.get("min").and_then(|v| v.as_i64()),
+                                                 attributes.get("max").and_then(|v| v.as_i64())) {
+        (Some(min), Some(max)) => Ok(MatchingRule::IntegerRange { min, max }),
+        _ => Err(anyhow!("IntegerRange matcher requires integer 'min' and 'max' fields"))
+      },
+      "jsonSchema" | "json-schema" => match attributes.get("schema") {
+        Some(schema) => Ok(MatchingRule::JsonSchema(schema.clone())),
+        None => Err(anyhow!("JsonSchema matcher missing 'schema' field"))
+      },
+      "datetimeAfter" | "datetime-after" => datetime_order_attributes(&attributes)
+        .map(|(format, reference)| MatchingRule::DateTimeAfter { format, reference }),
+      "datetimeBefore" | "datetime-before" => datetime_order_attributes(&attributes)
+        .map(|(format, reference)| MatchingRule::DateTimeBefore { format, reference }),
+      "keys" => match attributes.get("value") {
+        Some(Value::Array(keys)) => Ok(MatchingRule::Keys(keys.iter().map(json_to_string).collect())),
+        Some(_) => Err(anyhow!("Keys matcher 'value' field is not an Array")),
+        None => Err(anyhow!("Keys matcher missing 'value' field")),
+      },
+      "oneOf" | "one-of" => match attributes.get("values") {
+        Some(Value::Array(values)) => Ok(MatchingRule::OneOf(values.iter().map(json_to_string).collect())),
+        Some(_) => Err(anyhow!("OneOf matcher 'values' field is not an Array")),
+        None => Err(anyhow!("OneOf matcher missing 'values' field")),
+      },
+      "nullable" => match attributes.get("rule") {
+        Some(rule) => Ok(MatchingRule::Nullable(Box::new(MatchingRule::from_json(rule)?))),
+        None => Err(anyhow!("Nullable matcher missing 'rule' field")),
+      },
+      "ipAddress" | "ip-address" => match attributes.get("version") {
+        Some(Value::Number(version)) => match version.as_u64() {
+          Some(4) => Ok(MatchingRule::IpAddress { version: Some(4) }),
+          Some(6) => Ok(MatchingRule::IpAddress { version: Some(6) }),
+          _ => Err(anyhow!("IpAddress matcher 'version' field must be either 4 or 6, got {}", version))
+        },
+        Some(Value::Null) | None => Ok(MatchingRule::IpAddress { version: None }),
+        Some(version) => Err(anyhow!("IpAddress matcher 'version' field must be either 4 or 6, got {}", version))
+      },
+      "format" => match attributes.get("format") {
+        Some(Value::String(format)) if !format.is_empty() => Ok(MatchingRule::Format(format.clone())),
+        Some(_) => Err(anyhow!("Format matcher 'format' field is not a non-empty String")),
+        None => Err(anyhow!("Format matcher missing 'format' field")),
+      },
+      "any" => Ok(MatchingRule::Any),
+      "jsonRegex" | "json-regex" => match attributes.get("regex") {
+        Some(Value::String(regex)) => Ok(MatchingRule::JsonRegex(regex.clone())),
+        Some(_) => Err(anyhow!("JsonRegex matcher 'regex' field is not a String")),
+        None => Err(anyhow!("JsonRegex matcher missing 'regex' field")),
+      },
+      "noCascade" | "no-cascade" => Ok(MatchingRule::NoCascade),
+      "cookie" => Ok(MatchingRule::Cookie),
+      "rawTarget" | "raw-target" => Ok(MatchingRule::RawTarget),
+      "empty" => Ok(MatchingRule::Empty),
+      "unorderedValues" | "unordered-values" => Ok(MatchingRule::UnorderedValues),
+      "sensitive" => Ok(MatchingRule::Sensitive),
+      "jsonValue" | "json-value" => Ok(MatchingRule::JsonValue),
+      "numericString" | "numeric-string" => match attributes.get("tolerance") {
+        Some(tolerance) => match tolerance.as_f64() {
+          Some(tolerance) if tolerance >= 0.0 && tolerance.is_finite() => Ok(MatchingRule::NumericString { tolerance: Some(tolerance) }),
+          _ => Err(anyhow!("NumericString matcher 'tolerance' field must be a non-negative number, got {}", tolerance))
+        },
+        None => Ok(MatchingRule::NumericString { tolerance: None })
+      },
+      "hex" => match attributes.get("length") {
+        Some(length) => match length.as_u64() {
+          Some(length) if length > 0 => Ok(MatchingRule::Hex { length: Some(length as usize) }),
+          _ => Err(anyhow!("Hex matcher 'length' field must be a positive integer, got {}", length))
+        },
+        None => Ok(MatchingRule::Hex { length: None })
+      },
+      "multipleOf" | "multiple-of" => match attributes.get("base").and_then(|b| b.as_f64()) {
+        Some(base) if base > 0.0 && base.is_finite() => Ok(MatchingRule::MultipleOf(base)),
+        Some(base) => Err(anyhow!("MultipleOf matcher 'base' field must be a positive number, got {}", base)),
+        None => Err(anyhow!("MultipleOf matcher requires a numeric 'base' field"))
+      },
+      "stringLength" | "string-length" => Ok(MatchingRule::StringLength {
+        min: json_to_num(attributes.get("min").cloned()),
+        max: json_to_num(attributes.get("max").cloned())
+      }),
+      "base64" => Ok(MatchingRule::Base64(json_to_num(attributes.get("min").cloned()),
+        json_to_num(attributes.get("max").cloned()))),
+      "retryAfter" | "retry-after" => Ok(MatchingRule::RetryAfter(json_to_num(attributes.get("min").cloned()),
+        json_to_num(attributes.get("max").cloned()))),
+      "imageSimilarity" | "image-similarity" => match attributes.get("threshold").and_then(|t| t.as_f64()) {
+        Some(threshold) => Ok(MatchingRule::ImageSimilarity { threshold }),
+        None => Err(anyhow!("ImageSimilarity matcher missing 'threshold' field")),
+      }
       "eachKey" | "each-key" => {
         let generator = generator_from_json(&attributes);
         let value = attributes.get("value").cloned().unwrap_or_default();
@@ -469,6 +900,20 @@ impl MatchingRule {
         };
         Ok(MatchingRule::EachValue(definition))
       }
+      "allowedKeys" | "allowed-keys" => match attributes.get("keys") {
+        Some(Value::Array(keys)) => Ok(MatchingRule::AllowedKeys(keys.iter().map(json_to_string).collect())),
+        Some(_) => Err(anyhow!("AllowedKeys matcher 'keys' field is not an Array")),
+        None => Err(anyhow!("AllowedKeys matcher missing 'keys' field")),
+      }
+      "bodyHash" | "body-hash" => match attributes.get("algorithm") {
+        Some(algorithm) => Ok(MatchingRule::BodyHash(json_to_string(algorithm))),
+        None => Err(anyhow!("BodyHash matcher missing 'algorithm' field")),
+      }
+      "correlation" => match attributes.get("header") {
+        Some(header) => Ok(MatchingRule::Correlation(json_to_string(header),
+          attributes.get("transform").map(json_to_string))),
+        None => Err(anyhow!("Correlation matcher missing 'header' field")),
+      }
       _ => Err(anyhow!("{} is not a valid matching rule type", rule_type)),
     }
   }
@@ -488,11 +933,47 @@ impl MatchingRule {
       MatchingRule::Values => false,
       MatchingRule::EachValue(_) => false,
       MatchingRule::EachKey(_) => false,
+      MatchingRule::AllowedKeys(_) => false,
+      MatchingRule::Keys(_) => false,
+      MatchingRule::ArrayDistinct(_) => false,
+      MatchingRule::NoCascade => false,
+      MatchingRule::Cookie => false,
+      MatchingRule::RawTarget => false,
+      MatchingRule::Empty => false,
+      MatchingRule::UnorderedValues => false,
+      MatchingRule::Sensitive => false,
+      MatchingRule::JsonValue => false,
+      MatchingRule::Nullable(rule) => rule.can_cascade(),
       _ => true
     }
   }
 }
 
+fn datetime_order_attributes(attributes: &Map<String, Value>) -> anyhow::Result<(String, String)> {
+  let format = match attributes.get("format") {
+    Some(Value::String(format)) => format.clone(),
+    Some(_) => return Err(anyhow!("DateTime matcher 'format' field is not a String")),
+    None => String::default()
+  };
+  let reference = match attributes.get("reference") {
+    Some(Value::String(reference)) => reference.clone(),
+    _ => return Err(anyhow!("DateTime matcher requires a 'reference' String field"))
+  };
+  if reference != "now" {
+    if reference.contains("${") {
+      return Err(anyhow!("DateTime matcher reference '{}' is not supported, the reference must be 'now' or a literal date/time", reference));
+    }
+    #[cfg(feature = "datetime")]
+    {
+      let datetime_format = if format.is_empty() { "yyyy-MM-dd'T'HH:mm:ssXXX" } else { format.as_str() };
+      crate::time_utils::validate_datetime(reference.as_str(), datetime_format)
+        .map_err(|err| anyhow!("DateTime matcher reference '{}' is not 'now' or a date/time in the format '{}' - {}",
+          reference, datetime_format, err))?;
+    }
+  }
+  Ok((format, reference))
+}
+
 impl Hash for MatchingRule {
   fn hash<H: Hasher>(&self, state: &mut H) {
     mem::discriminant(self).hash(state);
@@ -509,6 +990,37 @@ impl Hash for MatchingRule {
       MatchingRule::Date(format) => format.hash(state),
       MatchingRule::Include(str) => str.hash(state),
       MatchingRule::ContentType(str) => str.hash(state),
+      MatchingRule::AllowedKeys(keys) | MatchingRule::Keys(keys) => keys.hash(state),
+      MatchingRule::OneOf(values) => values.hash(state),
+      MatchingRule::Nullable(rule) => rule.hash(state),
+      MatchingRule::IpAddress { version } => version.hash(state),
+      MatchingRule::Format(format) => format.hash(state),
+      MatchingRule::JsonRegex(regex) => regex.hash(state),
+      MatchingRule::BodyHash(algorithm) => algorithm.hash(state),
+      MatchingRule::Correlation(header, transform) => {
+        header.hash(state);
+        transform.hash(state);
+      }
+      MatchingRule::Base64(min, max) | MatchingRule::RetryAfter(min, max) |
+      MatchingRule::StringLength { min, max } => {
+        min.hash(state);
+        max.hash(state);
+      }
+      MatchingRule::ImageSimilarity { threshold } => threshold.to_bits().hash(state),
+      MatchingRule::MultipleOf(base) => base.to_bits().hash(state),
+      MatchingRule::NumericString { tolerance } => tolerance.map(|t| t.to_bits()).hash(state),
+      MatchingRule::Hex { length } => length.hash(state),
+      MatchingRule::ArrayDistinct(by) => by.hash(state),
+      MatchingRule::IntegerRange { min, max } => {
+        min.hash(state);
+        max.hash(state);
+      }
+      MatchingRule::JsonSchema(schema) => schema.to_string().hash(state),
+      MatchingRule::DateTimeAfter { format, reference } |
+      MatchingRule::DateTimeBefore { format, reference } => {
+        format.hash(state);
+        reference.hash(state);
+      }
       MatchingRule::ArrayContains(variants) => {
         for (index, rules, generators) in variants {
           index.hash(state);
@@ -524,6 +1036,8 @@ impl Hash for MatchingRule {
   }
 }
 
+impl Eq for MatchingRule {}
+
 impl PartialEq for MatchingRule {
   fn eq(&self, other: &Self) -> bool {
     match (self, other) {
@@ -539,6 +1053,36 @@ impl PartialEq for MatchingRule {
       (MatchingRule::ArrayContains(variants1), MatchingRule::ArrayContains(variants2)) => variants1 == variants2,
       (MatchingRule::EachKey(definition1), MatchingRule::EachKey(definition2)) => definition1 == definition2,
       (MatchingRule::EachValue(definition1), MatchingRule::EachValue(definition2)) => definition1 == definition2,
+      (MatchingRule::AllowedKeys(keys1), MatchingRule::AllowedKeys(keys2)) => keys1 == keys2,
+      (MatchingRule::Keys(keys1), MatchingRule::Keys(keys2)) => keys1 == keys2,
+      (MatchingRule::OneOf(values1), MatchingRule::OneOf(values2)) => values1 == values2,
+      (MatchingRule::Nullable(rule1), MatchingRule::Nullable(rule2)) => rule1 == rule2,
+      (MatchingRule::IpAddress { version: version1 }, MatchingRule::IpAddress { version: version2 }) => version1 == version2,
+      (MatchingRule::Format(format1), MatchingRule::Format(format2)) => format1 == format2,
+      (MatchingRule::JsonRegex(regex1), MatchingRule::JsonRegex(regex2)) => regex1 == regex2,
+      (MatchingRule::BodyHash(algorithm1), MatchingRule::BodyHash(algorithm2)) => algorithm1 == algorithm2,
+      (MatchingRule::Correlation(header1, transform1), MatchingRule::Correlation(header2, transform2)) =>
+        header1 == header2 && transform1 == transform2,
+      (MatchingRule::Base64(min1, max1), MatchingRule::Base64(min2, max2)) => min1 == min2 && max1 == max2,
+      (MatchingRule::RetryAfter(min1, max1), MatchingRule::RetryAfter(min2, max2)) => min1 == min2 && max1 == max2,
+      (MatchingRule::StringLength { min: min1, max: max1 }, MatchingRule::StringLength { min: min2, max: max2 }) =>
+        min1 == min2 && max1 == max2,
+      (MatchingRule::ImageSimilarity { threshold: threshold1 }, MatchingRule::ImageSimilarity { threshold: threshold2 }) =>
+        threshold1.to_bits() == threshold2.to_bits(),
+      (MatchingRule::MultipleOf(base1), MatchingRule::MultipleOf(base2)) => base1.to_bits() == base2.to_bits(),
+      (MatchingRule::NumericString { tolerance: tolerance1 }, MatchingRule::NumericString { tolerance: tolerance2 }) =>
+        tolerance1.map(|t| t.to_bits()) == tolerance2.map(|t| t.to_bits()),
+      (MatchingRule::Hex { length: length1 }, MatchingRule::Hex { length: length2 }) => length1 == length2,
+      (MatchingRule::ArrayDistinct(by1), MatchingRule::ArrayDistinct(by2)) => by1 == by2,
+      (MatchingRule::IntegerRange { min: min1, max: max1 }, MatchingRule::IntegerRange { min: min2, max: max2 }) =>
+        min1 == min2 && max1 == max2,
+      (MatchingRule::JsonSchema(schema1), MatchingRule::JsonSchema(schema2)) => schema1 == schema2,
+      (MatchingRule::DateTimeAfter { format: format1, reference: reference1 },
+        MatchingRule::DateTimeAfter { format: format2, reference: reference2 }) =>
+        format1 == format2 && reference1 == reference2,
+      (MatchingRule::DateTimeBefore { format: format1, reference: reference1 },
+        MatchingRule::DateTimeBefore { format: format2, reference: reference2 }) =>
+        format1 == format2 && reference1 == reference2,
       _ => mem::discriminant(self) == mem::discriminant(other)
     }
   }
@@ -656,6 +1200,51 @@ impl RuleList {
       self.add_rule(rule);
     }
   }
+
+  /// If the rules in this list should cascade to the children of the path they are defined for.
+  /// This will be false if the list contains a `NoCascade` marker.
+  pub fn can_cascade(&self) -> bool {
+    !self.rules.contains(&MatchingRule::NoCascade)
+  }
+
+  /// Returns a copy of this rule list with any `NoCascade` markers removed. A list that only
+  /// contained markers is replaced with an equality matcher.
+  pub fn without_cascade_markers(&self) -> RuleList {
+    if self.can_cascade() {
+      self.clone()
+    } else {
+      let rules = self.rules.iter()
+        .filter(|rule| **rule != MatchingRule::NoCascade)
+        .cloned()
+        .collect_vec();
+      RuleList {
+        rules: if rules.is_empty() { vec![ MatchingRule::Equality ] } else { rules },
+        .. self.clone()
+      }
+    }
+  }
+
+  /// Returns a copy of this rule list with any `Sensitive` markers removed. A list that only
+  /// contained the marker will be empty.
+  pub fn without_sensitive_markers(&self) -> RuleList {
+    RuleList {
+      rules: self.rules.iter()
+        .filter(|rule| **rule != MatchingRule::Sensitive)
+        .cloned()
+        .collect_vec(),
+      .. self.clone()
+    }
+  }
+
+  /// If this rule list contains a `Sensitive` marker
+  pub fn is_sensitive(&self) -> bool {
+    self.rules.contains(&MatchingRule::Sensitive)
+  }
+
+  /// If this rule list only contains `Sensitive` markers, and so has no rules to match with
+  fn only_sensitive_markers(&self) -> bool {
+    !self.rules.is_empty() && self.rules.iter().all(|rule| *rule == MatchingRule::Sensitive)
+  }
 }
 
 impl Hash for RuleList {
@@ -780,7 +1369,10 @@ pub struct MatchingRuleCategory {
   /// Name of the category
   pub name: Category,
   /// Matching rules for this category
-  pub rules: HashMap<DocPath, RuleList>
+  pub rules: HashMap<DocPath, RuleList>,
+  /// Named matcher definitions that can be referenced from the `EachKey` and `EachValue` rules
+  /// in this category (i.e. `eachValue(matching($'name'))`)
+  pub(crate) definitions: HashMap<String, RuleList>
 }
 
 impl MatchingRuleCategory {
@@ -791,6 +1383,7 @@ impl MatchingRuleCategory {
     MatchingRuleCategory {
       name: name.into(),
       rules: hashmap! {},
+      definitions: Default::default()
     }
   }
 
@@ -802,7 +1395,8 @@ impl MatchingRuleCategory {
       name: name.into(),
       rules: hashmap! {
         DocPath::empty() => RuleList::equality()
-      }
+      },
+      definitions: Default::default()
     }
   }
 
@@ -849,33 +1443,57 @@ impl MatchingRuleCategory {
     MatchingRuleCategory {
       name: self.name.clone(),
       rules: self.rules.iter().filter(predicate)
-        .map(|(path, rules)| (path.clone(), rules.clone())).collect()
+        .map(|(path, rules)| (path.clone(), rules.clone())).collect(),
+      definitions: self.definitions.clone()
     }
   }
 
   fn max_by_path(&self, path: &[&str]) -> RuleList {
-    self.rules.iter().map(|(k, v)| (k, v, k.path_weight(path)))
-      .filter(|&(_, _, (w, _))| w > 0)
-      .max_by_key(|&(_, _, (w, t))| w * t)
+    self.weighted_max_by_path(path)
       .map(|(_, v, (_, t))| v.as_cascaded(t != path.len()))
       .unwrap_or_default()
   }
 
-  /// Returns a JSON Value representation in V3 format
+  fn weighted_max_by_path(&self, path: &[&str]) -> Option<(&DocPath, &RuleList, (usize, usize))> {
+    let no_cascade_depth = self.no_cascade_depth(path);
+    self.rules.iter()
+      .filter(|(_, v)| !v.only_sensitive_markers())
+      .map(|(k, v)| (k, v, k.path_weight(path)))
+      .filter(|&(_, v, (w, t))| w > 0 && (t == path.len() || (v.can_cascade() && t > no_cascade_depth)))
+      .max_by_key(|&(_, _, (w, t))| w * t)
+  }
+
+  /// Returns the length of the deepest parent of the path that has a `NoCascade` marker, or zero
+  /// if there is none. Rules defined for paths that are not longer than this do not cascade to the
+  /// path.
+  fn no_cascade_depth(&self, path: &[&str]) -> usize {
+    self.rules.iter()
+      .filter(|(k, v)| !v.can_cascade() && k.len() < path.len() && k.matches_path(path))
+      .map(|(k, _)| k.len())
+      .max()
+      .unwrap_or(0)
+  }
+
+  /// Returns a JSON Value representation in V3 format. Named matcher definitions are not part
+  /// of the Pact specification, so any references to them are replaced with the rules they refer to.
   pub fn to_v3_json(&self) -> Value {
-    Value::Object(self.rules.iter().fold(serde_json::Map::new(), |mut map, (category, rulelist)| {
+    let json = self.rules.iter().fold(serde_json::Map::new(), |mut map, (category, rulelist)| {
       match self.name {
         Category::HEADER | Category::QUERY => {
-          let name = category.first_field().map(|v| v.to_string())
+          // Paths under the name (i.e. for headers with JSON values) are written out in full
+          let name = category.first_field()
+            .filter(|_| category.len() <= 2)
+            .map(|v| v.to_string())
             .unwrap_or_else(|| category.to_string());
-          map.insert(name, rulelist.to_v3_json());
+          map.insert(name, self.resolve_references(rulelist.clone()).to_v3_json());
         }
         _ => {
-          map.insert(String::from(category), rulelist.to_v3_json());
+          map.insert(String::from(category), self.resolve_references(rulelist.clone()).to_v3_json());
         }
       }
       map
-    }))
+    });
+    Value::Object(json)
   }
 
   /// Returns a JSON Value representation in V2 format
@@ -883,8 +1501,13 @@ impl MatchingRuleCategory {
     let mut map = hashmap!{};
 
     match &self.name {
-      Category::PATH => for (_, v) in self.rules.clone() {
-        map.insert("$.path".to_string(), v.to_v2_json());
+      Category::PATH => {
+        if self.rules.keys().any(|k| k.len() > 1) {
+          warn!("Matching rules for the individual path segments are not supported by V2 pacts, they will be ignored");
+        }
+        for (_, v) in self.rules.iter().filter(|(k, _)| k.len() <= 1) {
+          map.insert("$.path".to_string(), v.to_v2_json());
+        }
       }
       Category::BODY => for (k, v) in self.rules.clone() {
         map.insert(String::from(k).replace("$", "$.body"), v.to_v2_json());
@@ -921,22 +1544,93 @@ impl MatchingRuleCategory {
   }
 
   /// filters this category with all rules that match the given path for categories that contain
-  /// collections (eg. bodies, headers, query parameters). Returns self otherwise.
+  /// collections (eg. bodies, headers, query parameters). Returns self otherwise. Rule lists that
+  /// only contain `Sensitive` markers are not returned, as they have no rules to match with.
   pub fn resolve_matchers_for_path(&self, path: &[&str]) -> MatchingRuleCategory {
     match self.name {
       Category::HEADER| Category::QUERY | Category::BODY |
-      Category::CONTENTS | Category::METADATA => self.filter(|(val, _)| {
-        val.matches_path(path)
-      }),
-      _ => self.clone()
+      Category::CONTENTS | Category::METADATA => {
+        let no_cascade_depth = self.no_cascade_depth(path);
+        self.filter(|(val, rules)| {
+          if rules.only_sensitive_markers() {
+            false
+          } else if rules.can_cascade() {
+            val.matches_path(path) && (val.len() == path.len() || val.len() > no_cascade_depth)
+          } else {
+            val.matches_path_exactly(path)
+          }
+        })
+      }
+      _ => self.filter(|(_, rules)| !rules.only_sensitive_markers())
     }
   }
 
   /// Selects the best matcher for the given path by calculating a weighting for each one
   pub fn select_best_matcher(&self, path: &[&str]) -> RuleList {
-    match self.name {
+    let rule_list = match self.name {
       Category::BODY | Category::METADATA => self.max_by_path(path),
       _ => self.resolve_matchers_for_path(path).as_rule_list()
+    };
+    self.resolve_references(rule_list.without_sensitive_markers().without_cascade_markers())
+  }
+
+  /// Selects the best matcher for the given path in the same way as `select_best_matcher`, but
+  /// also returns the path expression the winning matchers were defined for and the weighting
+  /// calculated for it. Only the body and metadata categories select matchers by weighting, so
+  /// this will return None for the other categories, or if no matcher applies to the path.
+  pub fn select_best_matcher_with_weighting(&self, path: &[&str]) -> Option<(DocPath, usize, RuleList)> {
+    match self.name {
+      Category::BODY | Category::METADATA => self.weighted_max_by_path(path)
+        .map(|(k, v, (w, t))| {
+          let rule_list = v.as_cascaded(t != path.len()).without_sensitive_markers().without_cascade_markers();
+          (k.clone(), w * t, self.resolve_references(rule_list))
+        }),
+      _ => None
+    }
+  }
+
+  /// Named matcher definitions that can be referenced from the `EachKey` and `EachValue` rules
+  pub fn definitions(&self) -> &HashMap<String, RuleList> {
+    &self.definitions
+  }
+
+  /// Returns this category with the named matcher definitions replaced with the given ones
+  pub fn with_definitions(mut self, definitions: HashMap<String, RuleList>) -> MatchingRuleCategory {
+    self.definitions = definitions;
+    self
+  }
+
+  /// Adds a named matcher definition to this category, which can then be referenced by name from
+  /// the `EachKey` and `EachValue` rules. Any existing definition with the same name is replaced.
+  pub fn add_definition<S: Into<String>>(&mut self, name: S, rules: RuleList) {
+    self.definitions.insert(name.into(), rules);
+  }
+
+  /// Replaces any references in the `EachKey` and `EachValue` rules in the rule list with the
+  /// rules from the named matcher definitions. References to definitions that do not exist are
+  /// left unresolved.
+  pub fn resolve_references(&self, rule_list: RuleList) -> RuleList {
+    if self.definitions.is_empty() {
+      return rule_list;
+    }
+
+    let resolve = |definition: &MatchingRuleDefinition| MatchingRuleDefinition {
+      rules: definition.rules.iter().flat_map(|rule| match rule {
+        Either::Right(reference) => match self.definitions.get(&reference.name) {
+          Some(rules) => rules.rules.iter().map(|rule| Either::Left(rule.clone())).collect(),
+          None => vec![ rule.clone() ]
+        },
+        Either::Left(_) => vec![ rule.clone() ]
+      }).collect(),
+      .. definition.clone()
+    };
+    RuleList {
+      rules: rule_list.rules.iter().map(|rule| match rule {
+        MatchingRule::EachKey(definition) => MatchingRule::EachKey(resolve(definition)),
+        MatchingRule::EachValue(definition) => MatchingRule::EachValue(resolve(definition)),
+        _ => rule.clone()
+      }).collect(),
+      .. rule_list
     }
   }
 
@@ -994,12 +1688,26 @@ impl MatchingRuleCategory {
         }
       }
     } else if let Value::Object(m) = rules {
-      if m.contains_key("matchers") {
+      if let Some(Value::Object(definitions)) = m.get(DEFINITIONS_KEY) {
+        for (name, v) in definitions {
+          let mut tmp = MatchingRuleCategory::empty(self.name.clone());
+          tmp.add_rule_list(DocPath::empty(), v)?;
+          self.add_definition(name, tmp.as_rule_list());
+        }
+      }
+      let m = m.iter().filter(|(k, _)| k.as_str() != DEFINITIONS_KEY);
+      if rules.get("matchers").is_some() {
         self.add_rule_list(DocPath::empty(), rules)?;
       } else if self.name == Category::QUERY || self.name == Category::HEADER {
         for (k, v) in m {
-          let mut path = DocPath::root();
-          path.push_field(k);
+          let path = match DocPath::new(k) {
+            Ok(path) if k.starts_with('$') && path.len() > 2 => path,
+            _ => {
+              let mut path = DocPath::root();
+              path.push_field(k);
+              path
+            }
+          };
           self.add_rule_list(path, v)?;
         }
       } else {
@@ -1063,6 +1771,7 @@ impl MatchingRuleCategory {
         self.rules.insert(path.clone(), rules.clone());
       }
     }
+    self.definitions.extend(category.definitions);
   }
 }
 
@@ -1074,16 +1783,21 @@ impl Hash for MatchingRuleCategory {
       k.hash(state);
       v.hash(state);
     }
+    for (k, v) in self.definitions.iter()
+      .sorted_by(|(a, _), (b, _)| Ord::cmp(a, b)) {
+      k.hash(state);
+      v.hash(state);
+    }
   }
 }
 
 impl PartialEq for MatchingRuleCategory {
   fn eq(&self, other: &Self) -> bool {
-    self.name == other.name && self.rules == other.rules
+    self.name == other.name && self.rules == other.rules && self.definitions == other.definitions
   }
 
   fn ne(&self, other: &Self) -> bool {
-    self.name != other.name || self.rules != other.rules
+    self.name != other.name || self.rules != other.rules || self.definitions != other.definitions
   }
 }
 
@@ -1255,11 +1969,35 @@ impl MatchingRules {
   }
 
   fn to_v3_json(&self) -> Value {
+    self.to_json(false)
+  }
+
+  fn to_v4_json(&self) -> Value {
+    self.to_json(true)
+  }
+
+  fn to_json(&self, path_segments: bool) -> Value {
     Value::Object(self.rules.iter()
       .fold(serde_json::Map::new(), |mut map, (name, sub_category)| {
       match name {
-        Category::PATH => if let Some(rules) = sub_category.rules.get(&DocPath::empty()).or_else(|| sub_category.rules.get(&DocPath::root())) {
-          map.insert(name.to_string(), rules.to_v3_json());
+        Category::PATH => {
+          let has_segment_rules = sub_category.rules.keys().any(|path| path.len() > 1);
+          if has_segment_rules && path_segments {
+            // Rules for the individual path segments are written out keyed by the segment path
+            map.insert(name.to_string(), Value::Object(sub_category.rules.iter()
+              .map(|(path, rules)| {
+                let key = if path.len() > 1 { path.to_string() } else { "$".to_string() };
+                (key, rules.to_v3_json())
+              })
+              .collect()));
+          } else {
+            if has_segment_rules {
+              warn!("Matching rules for the individual path segments are only supported by V4 pacts, they will be ignored");
+            }
+            if let Some(rules) = sub_category.rules.get(&DocPath::empty()).or_else(|| sub_category.rules.get(&DocPath::root())) {
+              map.insert(name.to_string(), rules.to_v3_json());
+            }
+          }
         }
         _ => {
           let value = sub_category.to_v3_json();
@@ -1376,7 +2114,8 @@ pub fn matchers_from_json(value: &Value, deprecated_name: &Option<String>
 /// Generates a Value structure for the provided matching rules
 pub fn matchers_to_json(matchers: &MatchingRules, spec_version: &PactSpecification) -> Value {
   match spec_version {
-    PactSpecification::V3 | PactSpecification::V4 => matchers.to_v3_json(),
+    PactSpecification::V3 => matchers.to_v3_json(),
+    PactSpecification::V4 => matchers.to_v4_json(),
     _ => matchers.to_v2_json()
   }
 }
@@ -1709,7 +2448,8 @@ mod tests {
                 rule_logic: RuleLogic::And,
                 cascaded: false
               }
-            }
+            },
+            definitions: Default::default()
         },
       }
     }.is_empty()).to(be_false());
@@ -1743,16 +2483,19 @@ mod tests {
     }));
     expect!(matching_rules.rules_for_category("path")).to(be_some().value(MatchingRuleCategory {
       name: "path".into(),
-      rules: hashmap! { DocPath::empty() => RuleList { rules: vec![ MatchingRule::Regex("\\w+".to_string()) ], rule_logic: RuleLogic::And, cascaded: false } }
+      rules: hashmap! { DocPath::empty() => RuleList { rules: vec![ MatchingRule::Regex("\\w+".to_string()) ], rule_logic: RuleLogic::And, cascaded: false } },
+      definitions: Default::default()
     }));
     expect!(matching_rules.rules_for_category("query")).to(be_some().value(MatchingRuleCategory {
       name: "query".into(),
-      rules: hashmap!{ DocPath::new_unwrap("Q1") => RuleList { rules: vec![ MatchingRule::Regex("\\d+".to_string()) ], rule_logic: RuleLogic::And, cascaded: false } }
+      rules: hashmap!{ DocPath::new_unwrap("Q1") => RuleList { rules: vec![ MatchingRule::Regex("\\d+".to_string()) ], rule_logic: RuleLogic::And, cascaded: false } },
+      definitions: Default::default()
     }));
     expect!(matching_rules.rules_for_category("header")).to(be_some().value(MatchingRuleCategory {
       name: "header".into(),
       rules: hashmap!{ DocPath::new_unwrap("HEADERY") => RuleList { rules: vec![
-        MatchingRule::Include("ValueA".to_string()) ], rule_logic: RuleLogic::And, cascaded: false } }
+        MatchingRule::Include("ValueA".to_string()) ], rule_logic: RuleLogic::And, cascaded: false } },
+      definitions: Default::default()
     }));
     expect!(matching_rules.rules_for_category("body")).to(be_some().value(MatchingRuleCategory {
       name: "body".into(),
@@ -1761,7 +2504,8 @@ mod tests {
         DocPath::new_unwrap("$.animals[*].*") => RuleList { rules: vec![ MatchingRule::Type ], rule_logic: RuleLogic::And, cascaded: false },
         DocPath::new_unwrap("$.animals[*].children") => RuleList { rules: vec![ MatchingRule::MinType(1) ], rule_logic: RuleLogic::And, cascaded: false },
         DocPath::new_unwrap("$.animals[*].children[*].*") => RuleList { rules: vec![ MatchingRule::Type ], rule_logic: RuleLogic::And, cascaded: false }
-      }
+      },
+      definitions: Default::default()
     }));
   }
 
@@ -1778,7 +2522,8 @@ mod tests {
     expect!(matching_rules.rules_for_category("header")).to(be_some().value(MatchingRuleCategory {
       name: "header".into(),
       rules: hashmap!{ DocPath::new_unwrap("HEADERY") => RuleList { rules: vec![
-        MatchingRule::Include("ValueA".to_string()) ], rule_logic: RuleLogic::And, cascaded: false } }
+        MatchingRule::Include("ValueA".to_string()) ], rule_logic: RuleLogic::And, cascaded: false } },
+      definitions: Default::default()
     }));
   }
 
@@ -1807,7 +2552,8 @@ mod tests {
         DocPath::new_unwrap("HEADERY") => RuleList { rules: vec![ MatchingRule::Regex("4".to_string()) ], rule_logic: RuleLogic::And, cascaded: false },
         DocPath::new_unwrap("x-test") => RuleList { rules: vec![ MatchingRule::Regex("5".to_string()) ], rule_logic: RuleLogic::And, cascaded: false },
         DocPath::new_unwrap("x-test-2") => RuleList { rules: vec![ MatchingRule::Regex("6".to_string()) ], rule_logic: RuleLogic::And, cascaded: false }
-      }
+      },
+      definitions: Default::default()
     });
     assert_eq!(matching_rules.rules_for_category("query").unwrap(), MatchingRuleCategory {
       name: "query".into(),
@@ -1815,7 +2561,8 @@ mod tests {
         DocPath::new_unwrap("Q1") => RuleList { rules: vec![ MatchingRule::Regex("1".to_string()) ], rule_logic: RuleLogic::And, cascaded: false },
         DocPath::new_unwrap("x-test") => RuleList { rules: vec![ MatchingRule::Regex("2".to_string()) ], rule_logic: RuleLogic::And, cascaded: false },
         DocPath::new_unwrap("x-test-2") => RuleList { rules: vec![ MatchingRule::Regex("3".to_string()) ], rule_logic: RuleLogic::And, cascaded: false }
-      }
+      },
+      definitions: Default::default()
     });
   }
 
@@ -1868,17 +2615,20 @@ mod tests {
     }));
     expect!(matching_rules.rules_for_category("path")).to(be_some().value(MatchingRuleCategory {
       name: "path".into(),
-      rules: hashmap! { DocPath::empty() => RuleList { rules: vec![ MatchingRule::Regex("\\w+".to_string()) ], rule_logic: RuleLogic::And, cascaded: false } }
+      rules: hashmap! { DocPath::empty() => RuleList { rules: vec![ MatchingRule::Regex("\\w+".to_string()) ], rule_logic: RuleLogic::And, cascaded: false } },
+      definitions: Default::default()
     }));
     expect!(matching_rules.rules_for_category("query")).to(be_some().value(MatchingRuleCategory {
       name: "query".into(),
-      rules: hashmap!{ DocPath::root().join("Q1") => RuleList { rules: vec![ MatchingRule::Regex("\\d+".to_string()) ], rule_logic: RuleLogic::And, cascaded: false } }
+      rules: hashmap!{ DocPath::root().join("Q1") => RuleList { rules: vec![ MatchingRule::Regex("\\d+".to_string()) ], rule_logic: RuleLogic::And, cascaded: false } },
+      definitions: Default::default()
     }));
     expect!(matching_rules.rules_for_category("header")).to(be_some().value(MatchingRuleCategory {
       name: "header".into(),
       rules: hashmap!{ DocPath::root().join("HEADERY") => RuleList { rules: vec![
         MatchingRule::Include("ValueA".to_string()),
-        MatchingRule::Include("ValueB".to_string()) ], rule_logic: RuleLogic::Or, cascaded: false } }
+        MatchingRule::Include("ValueB".to_string()) ], rule_logic: RuleLogic::Or, cascaded: false } },
+      definitions: Default::default()
     }));
     expect!(matching_rules.rules_for_category("body")).to(be_some().value(MatchingRuleCategory {
       name: "body".into(),
@@ -1887,7 +2637,8 @@ mod tests {
         DocPath::new_unwrap("$.animals[*].*") => RuleList { rules: vec![ MatchingRule::Type ], rule_logic: RuleLogic::And, cascaded: false },
         DocPath::new_unwrap("$.animals[*].children") => RuleList { rules: vec![ MatchingRule::MinType(1) ], rule_logic: RuleLogic::And, cascaded: false },
         DocPath::new_unwrap("$.animals[*].children[*].*") => RuleList { rules: vec![ MatchingRule::Type ], rule_logic: RuleLogic::And, cascaded: false }
-      }
+      },
+      definitions: Default::default()
     }));
   }
 
@@ -1910,10 +2661,40 @@ mod tests {
     expect!(matching_rules.categories()).to(be_equal_to(hashset!{ Category::PATH }));
     expect!(matching_rules.rules_for_category("path")).to(be_some().value(MatchingRuleCategory {
       name: "path".into(),
-      rules: hashmap! { DocPath::empty() => RuleList { rules: vec![ MatchingRule::Regex("\\w+".to_string()) ], rule_logic: RuleLogic::And, cascaded: false } }
+      rules: hashmap! { DocPath::empty() => RuleList { rules: vec![ MatchingRule::Regex("\\w+".to_string()) ], rule_logic: RuleLogic::And, cascaded: false } },
+      definitions: Default::default()
     }));
   }
 
+  #[test]
+  fn path_segment_matching_rules_round_trip_through_v4_json() {
+    let matchers = matchingrules!{
+      "path" => {
+        "" => [ MatchingRule::Regex("^/users/.*$".to_string()) ],
+        "$[1]" => [ MatchingRule::Regex("^\\d+$".to_string()) ]
+      }
+    };
+
+    let json = matchers.to_v4_json();
+    expect!(json.clone()).to(be_equal_to(json!({
+      "path": {
+        "$": { "combine": "AND", "matchers": [ { "match": "regex", "regex": "^/users/.*$" } ] },
+        "$[1]": { "combine": "AND", "matchers": [ { "match": "regex", "regex": "^\\d+$" } ] }
+      }
+    })));
+    let matching_rules = matchers_from_json(&json!({ "matchingRules": json }), &None).unwrap();
+    let path_rules = matching_rules.rules_for_category("path").unwrap();
+    expect!(path_rules.rules.get(&DocPath::root())).to(be_some().value(&RuleList::new(MatchingRule::Regex("^/users/.*$".to_string()))));
+    expect!(path_rules.rules.get(&DocPath::root().join_index(1))).to(be_some().value(&RuleList::new(MatchingRule::Regex("^\\d+$".to_string()))));
+
+    expect!(matchers.to_v3_json()).to(be_equal_to(json!({
+      "path": { "combine": "AND", "matchers": [ { "match": "regex", "regex": "^/users/.*$" } ] }
+    })));
+    expect!(matchers.to_v2_json()).to(be_equal_to(json!({
+      "$.path": { "match": "regex", "regex": "^/users/.*$" }
+    })));
+  }
+
   speculate! {
     describe "generating matcher JSON" {
       before {
@@ -1991,13 +2772,15 @@ mod tests {
     }));
     expect!(matching_rules.rules_for_category("query")).to(be_some().value(MatchingRuleCategory {
       name: "query".into(),
-      rules: hashmap!{ DocPath::root().join("Q[]") => RuleList { rules: vec![ MatchingRule::Regex("\\d+".to_string()) ], rule_logic: RuleLogic::And, cascaded: false } }
+      rules: hashmap!{ DocPath::root().join("Q[]") => RuleList { rules: vec![ MatchingRule::Regex("\\d+".to_string()) ], rule_logic: RuleLogic::And, cascaded: false } },
+      definitions: Default::default()
     }));
     expect!(matching_rules.rules_for_category("header")).to(be_some().value(MatchingRuleCategory {
       name: "header".into(),
       rules: hashmap!{ DocPath::root().join("Y[]") => RuleList { rules: vec![
         MatchingRule::Include("ValueA".to_string()),
-        MatchingRule::Include("ValueB".to_string()) ], rule_logic: RuleLogic::Or, cascaded: false } }
+        MatchingRule::Include("ValueB".to_string()) ], rule_logic: RuleLogic::Or, cascaded: false } },
+      definitions: Default::default()
     }));
   }
 
@@ -2144,6 +2927,193 @@ mod tests {
     expect!(MatchingRule::from_json(&json)).to(be_ok().value(
       MatchingRule::StatusCode(HttpStatus::StatusCodes(vec![200, 201, 204]))
     ));
+
+    let json = json!({
+      "match": "statusCode",
+      "codes": [200, 201, 204]
+    });
+    expect!(MatchingRule::from_json(&json)).to(be_ok().value(
+      MatchingRule::StatusCode(HttpStatus::StatusCodes(vec![200, 201, 204]))
+    ));
+
+    let json = json!({
+      "match": "allowedKeys",
+      "keys": ["a", "b"]
+    });
+    expect!(MatchingRule::from_json(&json)).to(be_ok().value(
+      MatchingRule::AllowedKeys(vec!["a".to_string(), "b".to_string()])
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "allowedKeys" }))).to(be_err());
+
+    let json = json!({
+      "match": "bodyHash",
+      "algorithm": "sha256"
+    });
+    expect!(MatchingRule::from_json(&json)).to(be_ok().value(
+      MatchingRule::BodyHash("sha256".to_string())
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "bodyHash" }))).to(be_err());
+
+    let json = json!({
+      "match": "correlation",
+      "header": "X-Count",
+      "transform": "add:1"
+    });
+    expect!(MatchingRule::from_json(&json)).to(be_ok().value(
+      MatchingRule::Correlation("X-Count".to_string(), Some("add:1".to_string()))
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "correlation", "header": "X-Id" }))).to(be_ok().value(
+      MatchingRule::Correlation("X-Id".to_string(), None)
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "correlation" }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "pathExpression" }))).to(be_ok().value(
+      MatchingRule::PathExpression
+    ));
+
+    expect!(MatchingRule::from_json(&json!({ "match": "base64" }))).to(be_ok().value(
+      MatchingRule::Base64(None, None)
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "base64", "min": 16, "max": 32 }))).to(be_ok().value(
+      MatchingRule::Base64(Some(16), Some(32))
+    ));
+
+    expect!(MatchingRule::from_json(&json!({ "match": "retryAfter", "min": 30, "max": 120 }))).to(be_ok().value(
+      MatchingRule::RetryAfter(Some(30), Some(120))
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "retry-after", "max": 60 }))).to(be_ok().value(
+      MatchingRule::RetryAfter(None, Some(60))
+    ));
+
+    expect!(MatchingRule::from_json(&json!({ "match": "imageSimilarity", "threshold": 0.1 }))).to(be_ok().value(
+      MatchingRule::ImageSimilarity { threshold: 0.1 }
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "imageSimilarity" }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "duration" }))).to(be_ok().value(
+      MatchingRule::Duration
+    ));
+
+    expect!(MatchingRule::from_json(&json!({ "match": "stringLength", "min": 60, "max": 60 }))).to(be_ok().value(
+      MatchingRule::StringLength { min: Some(60), max: Some(60) }
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "string-length", "max": 10 }))).to(be_ok().value(
+      MatchingRule::StringLength { min: None, max: Some(10) }
+    ));
+
+    expect!(MatchingRule::from_json(&json!({ "match": "arrayContainsInOrder" }))).to(be_ok().value(
+      MatchingRule::ArrayContainsInOrder
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "array-contains-in-order" }))).to(be_ok().value(
+      MatchingRule::ArrayContainsInOrder
+    ));
+
+    expect!(MatchingRule::from_json(&json!({ "match": "arrayDistinct" }))).to(be_ok().value(
+      MatchingRule::ArrayDistinct(None)
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "array-distinct", "by": "$.id" }))).to(be_ok().value(
+      MatchingRule::ArrayDistinct(Some("$.id".to_string()))
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "arrayDistinct", "by": 100 }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "integerRange", "min": 0, "max": 100 }))).to(be_ok().value(
+      MatchingRule::IntegerRange { min: 0, max: 100 }
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "integer-range", "min": -10, "max": 10 }))).to(be_ok().value(
+      MatchingRule::IntegerRange { min: -10, max: 10 }
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "integerRange", "min": 0 }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "integerRange", "min": 0, "max": 1.5 }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "jsonSchema", "schema": { "type": "object" } }))).to(be_ok().value(
+      MatchingRule::JsonSchema(json!({ "type": "object" }))
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "json-schema" }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "datetimeAfter", "reference": "now" }))).to(be_ok().value(
+      MatchingRule::DateTimeAfter { format: String::default(), reference: "now".to_string() }
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "datetime-before", "format": "yyyy-MM-dd", "reference": "2025-01-01" })))
+      .to(be_ok().value(MatchingRule::DateTimeBefore { format: "yyyy-MM-dd".to_string(), reference: "2025-01-01".to_string() }));
+    expect!(MatchingRule::from_json(&json!({ "match": "datetimeBefore" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "datetimeAfter", "reference": "${expiresAt}" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "datetimeAfter", "format": "yyyy-MM-dd", "reference": "tomorrow" })))
+      .to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "keys", "value": ["a", "b"] }))).to(be_ok().value(
+      MatchingRule::Keys(vec!["a".to_string(), "b".to_string()])
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "keys" }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "oneOf", "values": ["RED", "GREEN"] }))).to(be_ok().value(
+      MatchingRule::OneOf(vec!["RED".to_string(), "GREEN".to_string()])
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "one-of", "values": "RED" }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "nullable", "rule": { "match": "type" } }))).to(be_ok().value(
+      MatchingRule::Nullable(Box::new(MatchingRule::Type))
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "nullable" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "nullable", "rule": { "match": "unknown" } }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "ipAddress" }))).to(be_ok().value(
+      MatchingRule::IpAddress { version: None }
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "ip-address", "version": 6 }))).to(be_ok().value(
+      MatchingRule::IpAddress { version: Some(6) }
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "ipAddress", "version": 5 }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "ipAddress", "version": "4" }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "format", "format": "email" }))).to(be_ok().value(
+      MatchingRule::Format("email".to_string())
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "format" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "format", "format": "" }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "any" }))).to(be_ok().value(MatchingRule::Any));
+
+    expect!(MatchingRule::from_json(&json!({ "match": "jsonRegex", "regex": "^\\d+(\\.\\d{1,2})?$" }))).to(be_ok().value(
+      MatchingRule::JsonRegex("^\\d+(\\.\\d{1,2})?$".to_string())
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "jsonRegex" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "jsonRegex", "regex": 100 }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "noCascade" }))).to(be_ok().value(MatchingRule::NoCascade));
+
+    expect!(MatchingRule::from_json(&json!({ "match": "multipleOf", "base": 5 }))).to(be_ok().value(
+      MatchingRule::MultipleOf(5.0)
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "multiple-of", "base": 0.25 }))).to(be_ok().value(
+      MatchingRule::MultipleOf(0.25)
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "multipleOf" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "multipleOf", "base": "5" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "multipleOf", "base": 0 }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "multipleOf", "base": -5 }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "cookie" }))).to(be_ok().value(MatchingRule::Cookie));
+    expect!(MatchingRule::from_json(&json!({ "match": "rawTarget" }))).to(be_ok().value(MatchingRule::RawTarget));
+    expect!(MatchingRule::from_json(&json!({ "match": "empty" }))).to(be_ok().value(MatchingRule::Empty));
+    expect!(MatchingRule::from_json(&json!({ "match": "unorderedValues" }))).to(be_ok().value(MatchingRule::UnorderedValues));
+
+    expect!(MatchingRule::from_json(&json!({ "match": "numericString" }))).to(be_ok().value(
+      MatchingRule::NumericString { tolerance: None }
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "numeric-string", "tolerance": 0.01 }))).to(be_ok().value(
+      MatchingRule::NumericString { tolerance: Some(0.01) }
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "numericString", "tolerance": "0.01" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "numericString", "tolerance": -1 }))).to(be_err());
+
+    expect!(MatchingRule::from_json(&json!({ "match": "hex" }))).to(be_ok().value(MatchingRule::Hex { length: None }));
+    expect!(MatchingRule::from_json(&json!({ "match": "hex", "length": 64 }))).to(be_ok().value(
+      MatchingRule::Hex { length: Some(64) }
+    ));
+    expect!(MatchingRule::from_json(&json!({ "match": "hex", "length": "64" }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "hex", "length": 0 }))).to(be_err());
+    expect!(MatchingRule::from_json(&json!({ "match": "jsonValue" }))).to(be_ok().value(MatchingRule::JsonValue));
+    expect!(MatchingRule::from_json(&json!({ "match": "sensitive" }))).to(be_ok().value(MatchingRule::Sensitive));
   }
 
   #[test]
@@ -2205,6 +3175,132 @@ mod tests {
         "match": "time",
         "format": "HH"
       })));
+    expect!(MatchingRule::AllowedKeys(vec!["a".to_string(), "b".to_string()]).to_json()).to(
+      be_equal_to(json!({
+        "match": "allowedKeys",
+        "keys": ["a", "b"]
+      })));
+    expect!(MatchingRule::BodyHash("sha256".to_string()).to_json()).to(
+      be_equal_to(json!({
+        "match": "bodyHash",
+        "algorithm": "sha256"
+      })));
+    expect!(MatchingRule::Correlation("X-Count".to_string(), Some("add:1".to_string())).to_json()).to(
+      be_equal_to(json!({
+        "match": "correlation",
+        "header": "X-Count",
+        "transform": "add:1"
+      })));
+    expect!(MatchingRule::Correlation("X-Id".to_string(), None).to_json()).to(
+      be_equal_to(json!({
+        "match": "correlation",
+        "header": "X-Id"
+      })));
+    expect!(MatchingRule::PathExpression.to_json()).to(
+      be_equal_to(json!({
+        "match": "pathExpression"
+      })));
+    expect!(MatchingRule::Base64(None, None).to_json()).to(
+      be_equal_to(json!({
+        "match": "base64"
+      })));
+    expect!(MatchingRule::Base64(Some(16), None).to_json()).to(
+      be_equal_to(json!({
+        "match": "base64",
+        "min": 16
+      })));
+    expect!(MatchingRule::RetryAfter(Some(30), Some(120)).to_json()).to(
+      be_equal_to(json!({
+        "match": "retryAfter",
+        "min": 30,
+        "max": 120
+      })));
+    expect!(MatchingRule::ImageSimilarity { threshold: 0.1 }.to_json()).to(
+      be_equal_to(json!({
+        "match": "imageSimilarity",
+        "threshold": 0.1
+      })));
+    expect!(MatchingRule::Duration.to_json()).to(
+      be_equal_to(json!({
+        "match": "duration"
+      })));
+    expect!(MatchingRule::StringLength { min: Some(8), max: None }.to_json()).to(
+      be_equal_to(json!({
+        "match": "stringLength",
+        "min": 8
+      })));
+    expect!(MatchingRule::ArrayContainsInOrder.to_json()).to(
+      be_equal_to(json!({
+        "match": "arrayContainsInOrder"
+      })));
+    expect!(MatchingRule::ArrayDistinct(None).to_json()).to(
+      be_equal_to(json!({
+        "match": "arrayDistinct"
+      })));
+    expect!(MatchingRule::ArrayDistinct(Some("$.id".to_string())).to_json()).to(
+      be_equal_to(json!({
+        "match": "arrayDistinct",
+        "by": "$.id"
+      })));
+    expect!(MatchingRule::IntegerRange { min: 0, max: 100 }.to_json()).to(
+      be_equal_to(json!({
+        "match": "integerRange",
+        "min": 0,
+        "max": 100
+      })));
+    expect!(MatchingRule::JsonSchema(json!({ "type": "object" })).to_json()).to(
+      be_equal_to(json!({
+        "match": "jsonSchema",
+        "schema": { "type": "object" }
+      })));
+    expect!(MatchingRule::DateTimeAfter { format: String::default(), reference: "now".to_string() }.to_json()).to(
+      be_equal_to(json!({
+        "match": "datetimeAfter",
+        "reference": "now"
+      })));
+    expect!(MatchingRule::DateTimeBefore { format: "yyyy-MM-dd".to_string(), reference: "2025-01-01".to_string() }.to_json()).to(
+      be_equal_to(json!({
+        "match": "datetimeBefore",
+        "format": "yyyy-MM-dd",
+        "reference": "2025-01-01"
+      })));
+    expect!(MatchingRule::Keys(vec!["a".to_string(), "b".to_string()]).to_json()).to(
+      be_equal_to(json!({
+        "match": "keys",
+        "value": ["a", "b"]
+      })));
+    expect!(MatchingRule::OneOf(vec!["RED".to_string(), "GREEN".to_string()]).to_json()).to(
+      be_equal_to(json!({
+        "match": "oneOf",
+        "values": ["RED", "GREEN"]
+      })));
+    expect!(MatchingRule::Nullable(Box::new(MatchingRule::Regex("\\d+".to_string()))).to_json()).to(
+      be_equal_to(json!({
+        "match": "nullable",
+        "rule": { "match": "regex", "regex": "\\d+" }
+      })));
+    expect!(MatchingRule::IpAddress { version: None }.to_json()).to(
+      be_equal_to(json!({ "match": "ipAddress" })));
+    expect!(MatchingRule::IpAddress { version: Some(4) }.to_json()).to(
+      be_equal_to(json!({ "match": "ipAddress", "version": 4 })));
+    expect!(MatchingRule::Format("phone-e164".to_string()).to_json()).to(
+      be_equal_to(json!({ "match": "format", "format": "phone-e164" })));
+    expect!(MatchingRule::Any.to_json()).to(be_equal_to(json!({ "match": "any" })));
+    expect!(MatchingRule::JsonRegex("^\".*\"$".to_string()).to_json()).to(
+      be_equal_to(json!({ "match": "jsonRegex", "regex": "^\".*\"$" })));
+    expect!(MatchingRule::NoCascade.to_json()).to(be_equal_to(json!({ "match": "noCascade" })));
+    expect!(MatchingRule::MultipleOf(5.0).to_json()).to(be_equal_to(json!({ "match": "multipleOf", "base": 5.0 })));
+    expect!(MatchingRule::Cookie.to_json()).to(be_equal_to(json!({ "match": "cookie" })));
+    expect!(MatchingRule::RawTarget.to_json()).to(be_equal_to(json!({ "match": "rawTarget" })));
+    expect!(MatchingRule::Empty.to_json()).to(be_equal_to(json!({ "match": "empty" })));
+    expect!(MatchingRule::UnorderedValues.to_json()).to(be_equal_to(json!({ "match": "unorderedValues" })));
+    expect!(MatchingRule::NumericString { tolerance: None }.to_json()).to(be_equal_to(json!({ "match": "numericString" })));
+    expect!(MatchingRule::NumericString { tolerance: Some(0.01) }.to_json()).to(
+      be_equal_to(json!({ "match": "numericString", "tolerance": 0.01 })));
+    expect!(MatchingRule::Hex { length: None }.to_json()).to(be_equal_to(json!({ "match": "hex" })));
+    expect!(MatchingRule::Hex { length: Some(64) }.to_json()).to(be_equal_to(json!({ "match": "hex", "length": 64 })));
+    expect!(MatchingRule::Sensitive.to_json()).to(be_equal_to(json!({ "match": "sensitive" })));
+    expect!(MatchingRule::JsonValue.to_json()).to(be_equal_to(json!({ "match": "jsonValue" })));
   }
 
   #[test]
@@ -2346,7 +3442,8 @@ mod tests {
               rule_logic: RuleLogic::And,
               cascaded: false
             }
-          }
+          },
+          definitions: Default::default()
         }
       }
     };
@@ -2378,7 +3475,8 @@ mod tests {
               rule_logic: RuleLogic::And,
               cascaded: false
             }
-          }
+          },
+          definitions: Default::default()
         },
         Category::QUERY => MatchingRuleCategory {
           name: Category::QUERY,
@@ -2393,7 +3491,8 @@ mod tests {
               rule_logic: RuleLogic::And,
               cascaded: false
             }
-          }
+          },
+          definitions: Default::default()
         }
       }
     };
@@ -2439,6 +3538,256 @@ mod tests {
     }));
   }
 
+  #[test]
+  fn matching_rules_for_headers_and_query_parameters_round_trip() {
+    let matching_rules = matchingrules!{
+      "header" => {
+        "$['Content-Type']" => [ MatchingRule::Regex("^application/json".to_string()) ],
+        "$['se-token']" => [ MatchingRule::Type ],
+        "$['X.Dotted']" => [ MatchingRule::Type ]
+      },
+      "query" => {
+        "$.a" => [ MatchingRule::Type ],
+        "$['a[0]']" => [ MatchingRule::Integer ],
+        "$['b.c']" => [ MatchingRule::Type ]
+      }
+    };
+
+    for spec in [ PactSpecification::V3, PactSpecification::V4 ] {
+      let json = matchers_to_json(&matching_rules, &spec);
+      assert_eq!(json, json!({
+        "header": {
+          "Content-Type": {
+            "combine": "AND",
+            "matchers": [ { "match": "regex", "regex": "^application/json" } ]
+          },
+          "se-token": {
+            "combine": "AND",
+            "matchers": [ { "match": "type" } ]
+          },
+          "X.Dotted": {
+            "combine": "AND",
+            "matchers": [ { "match": "type" } ]
+          }
+        },
+        "query": {
+          "a": {
+            "combine": "AND",
+            "matchers": [ { "match": "type" } ]
+          },
+          "a[0]": {
+            "combine": "AND",
+            "matchers": [ { "match": "integer" } ]
+          },
+          "b.c": {
+            "combine": "AND",
+            "matchers": [ { "match": "type" } ]
+          }
+        }
+      }));
+      expect!(matchers_from_json(&json!({ "matchingRules": json }), &None).unwrap()).to(be_equal_to(matching_rules.clone()));
+    }
+  }
+
+  #[test]
+  fn matching_rules_for_json_header_values_round_trip() {
+    let matching_rules = matchingrules!{
+      "header" => {
+        "$['X-Data']" => [ MatchingRule::JsonValue ],
+        "$['X-Data'].id" => [ MatchingRule::Integer ]
+      }
+    };
+
+    let json = matchers_to_json(&matching_rules, &PactSpecification::V3);
+    assert_eq!(json, json!({
+      "header": {
+        "X-Data": {
+          "combine": "AND",
+          "matchers": [ { "match": "jsonValue" } ]
+        },
+        "$['X-Data'].id": {
+          "combine": "AND",
+          "matchers": [ { "match": "integer" } ]
+        }
+      }
+    }));
+    expect!(matchers_from_json(&json!({ "matchingRules": json }), &None).unwrap()).to(be_equal_to(matching_rules));
+  }
+
+  #[test]
+  fn matcher_definitions_references_are_inlined_when_written_to_json() {
+    let mut matching_rules = matchingrules!{
+      "body" => {
+        "$.items" => [ MatchingRule::EachValue(MatchingRuleDefinition {
+          value: "".to_string(),
+          value_type: ValueType::Unknown,
+          rules: vec![ Either::Right(MatchingReference { name: "item".to_string() }) ],
+          generator: None
+        }) ]
+      }
+    };
+    matching_rules.add_category("body").add_definition("item", RuleList::new(MatchingRule::Type));
+
+    let json = matchers_to_json(&matching_rules, &PactSpecification::V4);
+    assert_eq!(json, json!({
+      "body": {
+        "$.items": {
+          "combine": "AND",
+          "matchers": [ { "match": "eachValue", "rules": [ { "match": "type" } ] } ]
+        }
+      }
+    }));
+  }
+
+  #[test]
+  fn matcher_definitions_and_references_are_loaded_from_json() {
+    let json = json!({
+      "body": {
+        "$.items": {
+          "combine": "AND",
+          "matchers": [ { "match": "eachValue", "rules": [ { "$ref": "item" } ] } ]
+        },
+        "$definitions": {
+          "item": {
+            "combine": "AND",
+            "matchers": [ { "match": "type" } ]
+          }
+        }
+      }
+    });
+    let mut matching_rules = matchingrules!{
+      "body" => {
+        "$.items" => [ MatchingRule::EachValue(MatchingRuleDefinition {
+          value: "".to_string(),
+          value_type: ValueType::Unknown,
+          rules: vec![ Either::Right(MatchingReference { name: "item".to_string() }) ],
+          generator: None
+        }) ]
+      }
+    };
+    matching_rules.add_category("body").add_definition("item", RuleList::new(MatchingRule::Type));
+
+    expect!(matchers_from_json(&json!({ "matchingRules": json }), &None).unwrap()).to(be_equal_to(matching_rules));
+  }
+
+  #[test]
+  fn select_best_matcher_resolves_references_to_matcher_definitions() {
+    let reference = MatchingRuleDefinition {
+      value: "".to_string(),
+      value_type: ValueType::Unknown,
+      rules: vec![
+        Either::Right(MatchingReference { name: "id".to_string() }),
+        Either::Right(MatchingReference { name: "missing".to_string() })
+      ],
+      generator: None
+    };
+    let mut category = matchingrules_list!{
+      "body"; "$.a" => [ MatchingRule::EachKey(reference) ]
+    };
+    category.add_definition("id", RuleList::new(MatchingRule::Regex("\\d+".to_string())));
+
+    expect!(category.select_best_matcher(&["$", "a"]).rules).to(be_equal_to(vec![
+      MatchingRule::EachKey(MatchingRuleDefinition {
+        value: "".to_string(),
+        value_type: ValueType::Unknown,
+        rules: vec![
+          Either::Left(MatchingRule::Regex("\\d+".to_string())),
+          Either::Right(MatchingReference { name: "missing".to_string() })
+        ],
+        generator: None
+      })
+    ]));
+  }
+
+  #[test]
+  fn select_best_matcher_with_weighting_test() {
+    let category = matchingrules_list!{
+      "body";
+      "$.a" => [ MatchingRule::Type ],
+      "$.a.b" => [ MatchingRule::Regex("\\d+".to_string()) ],
+      "$.*.b" => [ MatchingRule::Integer ]
+    };
+
+    let (path, weight, rules) = category.select_best_matcher_with_weighting(&["$", "a", "b"]).unwrap();
+    expect!(path.to_string()).to(be_equal_to("$.a.b"));
+    expect!(weight).to(be_equal_to(24));
+    expect!(rules.rules).to(be_equal_to(vec![MatchingRule::Regex("\\d+".to_string())]));
+    expect!(rules.cascaded).to(be_false());
+
+    let (path, weight, rules) = category.select_best_matcher_with_weighting(&["$", "a", "c"]).unwrap();
+    expect!(path.to_string()).to(be_equal_to("$.a"));
+    expect!(weight).to(be_equal_to(8));
+    expect!(rules.cascaded).to(be_true());
+
+    expect!(category.select_best_matcher_with_weighting(&["$", "x"])).to(be_none());
+
+    let headers = matchingrules_list!{
+      "header";
+      "X-Id" => [ MatchingRule::Integer ]
+    };
+    expect!(headers.select_best_matcher_with_weighting(&["X-Id"])).to(be_none());
+  }
+
+  #[test]
+  fn no_cascade_marker_stops_rules_from_cascading_to_children() {
+    let category = matchingrules_list!{
+      "body";
+      "$.a" => [ MatchingRule::Type, MatchingRule::NoCascade ],
+      "$.b" => [ MatchingRule::Type ],
+      "$.b.c" => [ MatchingRule::NoCascade ]
+    };
+
+    expect!(category.matcher_is_defined(&["$", "a"])).to(be_true());
+    expect!(category.select_best_matcher(&["$", "a"]).rules).to(be_equal_to(vec![MatchingRule::Type]));
+    expect!(category.matcher_is_defined(&["$", "a", "x"])).to(be_false());
+    expect!(category.select_best_matcher(&["$", "a", "x"]).is_empty()).to(be_true());
+
+    expect!(category.select_best_matcher(&["$", "b", "x"]).rules).to(be_equal_to(vec![MatchingRule::Type]));
+    expect!(category.select_best_matcher(&["$", "b", "c"]).rules).to(be_equal_to(vec![MatchingRule::Equality]));
+    expect!(category.matcher_is_defined(&["$", "b", "c", "d"])).to(be_false());
+    expect!(category.select_best_matcher(&["$", "b", "c", "d"]).is_empty()).to(be_true());
+    expect!(category.select_best_matcher(&["$", "b", "c", "d", "e"]).is_empty()).to(be_true());
+    expect!(category.select_best_matcher(&["$", "b", "c", "e", "f"]).is_empty()).to(be_true());
+  }
+
+  #[test]
+  fn no_cascade_marker_allows_rules_defined_below_the_path() {
+    let category = matchingrules_list!{
+      "body";
+      "$" => [ MatchingRule::Type ],
+      "$.b.c" => [ MatchingRule::NoCascade ],
+      "$.b.c.d" => [ MatchingRule::Integer ]
+    };
+
+    expect!(category.select_best_matcher(&["$", "b", "c", "d"]).rules).to(be_equal_to(vec![MatchingRule::Integer]));
+    expect!(category.select_best_matcher(&["$", "b", "c", "d", "e"]).rules).to(be_equal_to(vec![MatchingRule::Integer]));
+    expect!(category.select_best_matcher(&["$", "b", "c", "x"]).is_empty()).to(be_true());
+    expect!(category.select_best_matcher(&["$", "b", "x"]).rules).to(be_equal_to(vec![MatchingRule::Type]));
+  }
+
+  #[test]
+  fn sensitive_marker_is_not_returned_as_a_matcher() {
+    let category = matchingrules_list!{
+      "body";
+      "$" => [ MatchingRule::Type ],
+      "$.a" => [ MatchingRule::Regex("\\d+".to_string()), MatchingRule::Sensitive ],
+      "$.b" => [ MatchingRule::Sensitive ]
+    };
+
+    expect!(category.select_best_matcher(&["$", "a"]).rules).to(be_equal_to(vec![MatchingRule::Regex("\\d+".to_string())]));
+    expect!(category.select_best_matcher(&["$", "b"]).rules).to(be_equal_to(vec![MatchingRule::Type]));
+    expect!(category.select_best_matcher(&["$", "b", "c"]).rules).to(be_equal_to(vec![MatchingRule::Type]));
+    expect!(category.select_best_matcher_with_weighting(&["$", "b"]).map(|(path, _, _)| path))
+      .to(be_some().value(DocPath::root()));
+
+    let headers = matchingrules_list!{
+      "header";
+      "$.Authorization" => [ MatchingRule::Sensitive ]
+    };
+    expect!(headers.matcher_is_defined(&["$", "Authorization"])).to(be_false());
+    expect!(headers.select_best_matcher(&["$", "Authorization"]).is_empty()).to(be_true());
+  }
+
   #[test]
   fn hash_test_for_matchingrules() {
     let m1 = MatchingRules::default();
@@ -2454,7 +3803,8 @@ mod tests {
               rule_logic: RuleLogic::And,
               cascaded: false
             }
-          }
+          },
+          definitions: Default::default()
         }
       }
     };
@@ -2484,7 +3834,8 @@ mod tests {
               rule_logic: RuleLogic::And,
               cascaded: false
             }
-          }
+          },
+          definitions: Default::default()
         }
       }
     };
@@ -2519,7 +3870,8 @@ mod tests {
           rule_logic: RuleLogic::And,
           cascaded: false
         }
-      }
+      },
+      definitions: Default::default()
     };
     expect!(h(&m2)).to(be_equal_to(5907609104530210046));
 
@@ -2542,7 +3894,8 @@ mod tests {
           rule_logic: RuleLogic::And,
           cascaded: false
         }
-      }
+      },
+      definitions: Default::default()
     };
     expect!(h(&m5)).to(be_equal_to(2282399821086239745));
 
@@ -2559,7 +3912,8 @@ mod tests {
           rule_logic: RuleLogic::And,
           cascaded: false
         }
-      }
+      },
+      definitions: Default::default()
     };
     expect!(h(&m6)).to(be_equal_to(2282399821086239745));
   }
@@ -2575,7 +3929,8 @@ mod tests {
           rule_logic: RuleLogic::And,
           cascaded: false
         }
-      }
+      },
+      definitions: Default::default()
     };
     let m3 = MatchingRuleCategory::empty(Category::PATH);
     let m4 = MatchingRuleCategory::empty(Category::HEADER);
@@ -4,7 +4,7 @@ use std::collections::{BTreeMap, HashMap};
 use std::fmt::Debug;
 use std::fs;
 use std::fs::File;
-use std::io::{Read, Seek, SeekFrom, Write};
+use std::io::Read;
 use std::ops::Deref;
 use std::panic::RefUnwindSafe;
 use std::path::Path;
@@ -17,7 +17,7 @@ use serde_json::{json, Value};
 use tracing::{debug, error, trace, warn};
 
 use crate::{Consumer, PactSpecification, Provider};
-#[cfg(not(target_family = "wasm"))] use crate::file_utils::{with_read_lock_for_open_file, with_write_lock};
+#[cfg(not(target_family = "wasm"))] use crate::file_utils::{with_lock_file, with_read_lock_for_open_file, write_file_atomically};
 #[cfg(not(target_family = "wasm"))] use crate::http_utils;
 #[cfg(not(target_family = "wasm"))] use crate::http_utils::HttpAuth;
 use crate::interaction::Interaction;
@@ -93,6 +93,11 @@ pub trait Pact: Debug + ReadWritePact {
 
   /// Adds some version info to the Pact-Rust metadata section
   fn add_md_version(&mut self, key: &str, version: &str);
+
+  /// Sets a configuration value in the Pact-Rust metadata section
+  fn add_md_value(&mut self, key: &str, value: &str) {
+    self.add_md_version(key, value)
+  }
 }
 
 impl Default for Box<dyn Pact> {
@@ -214,6 +219,10 @@ lazy_static!{
 /// Writes the pact out to the provided path. If there is an existing pact at the path, the two
 /// pacts will be merged together unless overwrite is true. Returns an error if the file can not
 /// be written or the pacts can not be merged.
+///
+/// The file is written to a temporary file and then renamed into place, so a partially written
+/// pact file is never visible. Writers are serialised with an exclusive lock on a separate
+/// `<path>.lock` file, as the pact file itself is replaced when it is written.
 #[cfg(not(target_family = "wasm"))]
 pub fn write_pact(
   pact: Box<dyn Pact>,
@@ -223,34 +232,26 @@ pub fn write_pact(
 ) -> anyhow::Result<()> {
   fs::create_dir_all(path.parent().unwrap())?;
   let _lock = WRITE_LOCK.lock().unwrap();
-  if !overwrite && path.exists() {
-    debug!("Merging pact with file {:?}", path);
-    let mut f = fs::OpenOptions::new().read(true).write(true).open(&path)?;
-    let existing_pact = read_pact_from_file(&mut f, path)?;
-
-    if existing_pact.specification_version() < pact.specification_version() {
-      warn!("Note: Existing pact is an older specification version ({:?}), and will be upgraded",
-            existing_pact.specification_version());
-    }
-
-    let merged_pact = pact.merge(existing_pact.deref())?;
-    let pact_json = serde_json::to_string_pretty(&merged_pact.to_json(pact_spec)?)?;
+  with_lock_file(path, 3, &mut || {
+    if !overwrite && path.exists() {
+      debug!("Merging pact with file {:?}", path);
+      let mut f = File::open(path)?;
+      let existing_pact = read_pact_from_file(&mut f, path)?;
+
+      if existing_pact.specification_version() < pact.specification_version() {
+        warn!("Note: Existing pact is an older specification version ({:?}), and will be upgraded",
+              existing_pact.specification_version());
+      }
 
-    with_write_lock(path, &mut f, 3, &mut |f| {
-      f.set_len(0)?;
-      f.seek(SeekFrom::Start(0))?;
-      f.write_all(pact_json.as_bytes())?;
-      Ok(())
-    })
-  } else {
-    debug!("Writing new pact file to {:?}", path);
-    let result = serde_json::to_string_pretty(&pact.to_json(pact_spec)?)?;
-    let mut file = File::create(path)?;
-    with_write_lock(path, &mut file, 3, &mut |f| {
-      f.write_all(result.as_bytes())?;
-      Ok(())
-    })
-  }
+      let merged_pact = pact.merge(existing_pact.deref())?;
+      let pact_json = serde_json::to_string_pretty(&merged_pact.to_json(pact_spec)?)?;
+      write_file_atomically(path, pact_json.as_bytes())
+    } else {
+      debug!("Writing new pact file to {:?}", path);
+      let result = serde_json::to_string_pretty(&pact.to_json(pact_spec)?)?;
+      write_file_atomically(path, result.as_bytes())
+    }
+  })
 }
 
 
@@ -931,6 +932,39 @@ mod tests {
 }}"#, PACT_RUST_VERSION.unwrap()));
   }
 
+  #[test]
+  fn write_pact_test_should_not_leave_temporary_files_behind() {
+    let pact = RequestResponsePact { consumer: Consumer { name: "write_pact_test_consumer".to_string() },
+      provider: Provider { name: "write_pact_test_provider".to_string() },
+      interactions: vec![
+        RequestResponseInteraction {
+          description: "Test Interaction".to_string(),
+          .. RequestResponseInteraction::default()
+        }
+      ],
+      .. RequestResponsePact::default() };
+    let mut dir = env::temp_dir();
+    let x = rand::random::<u16>();
+    dir.push(format!("pact_test_{}", x));
+    dir.push(pact.default_file_name());
+
+    let result = write_pact(pact.boxed(), dir.as_path(), PactSpecification::V3, true);
+    let result2 = write_pact(pact.boxed(), dir.as_path(), PactSpecification::V3, false);
+
+    let mut files = fs::read_dir(dir.parent().unwrap()).unwrap()
+      .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+      .collect::<Vec<_>>();
+    files.sort();
+    fs::remove_dir_all(dir.parent().unwrap()).unwrap_or(());
+
+    expect!(result).to(be_ok());
+    expect!(result2).to(be_ok());
+    expect!(files).to(be_equal_to(vec![
+      pact.default_file_name(),
+      format!("{}.lock", pact.default_file_name())
+    ]));
+  }
+
   #[test]
   fn write_pact_test_should_merge_pacts() {
     let pact = RequestResponsePact { consumer: Consumer { name: "merge_consumer".to_string() },
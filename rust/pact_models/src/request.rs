@@ -590,7 +590,8 @@ mod tests {
                 rule_logic: RuleLogic::And,
                 cascaded: false
               }
-            }
+            },
+            definitions: Default::default()
           }
         }
       },
@@ -510,7 +510,8 @@ mod tests {
                 rules: vec![MatchingRule::Type],
                 rule_logic: RuleLogic::And,
                 cascaded: false
-              }}
+              }},
+              definitions: Default::default()
             }
           }
         },
@@ -531,7 +532,8 @@ mod tests {
           rules: hashmap!{
             Category::HEADER => MatchingRuleCategory {
               name: Category::HEADER,
-              rules: hashmap!{}
+              rules: hashmap!{},
+              definitions: Default::default()
             }
           }
         },
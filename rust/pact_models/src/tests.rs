@@ -299,7 +299,8 @@ fn write_pact_file_with_provider_state_generator_test() {
                   rules: vec![MatchingRule::Type],
                   rule_logic: RuleLogic::And,
                   cascaded: false
-                }}
+                }},
+                definitions: Default::default()
               }
             }
           },
@@ -319,7 +320,8 @@ fn write_pact_file_with_provider_state_generator_test() {
             rules: hashmap!{
               Category::HEADER => MatchingRuleCategory {
                 name: Category::HEADER,
-                rules: hashmap!{}
+                rules: hashmap!{},
+                definitions: Default::default()
               }
             }
           },
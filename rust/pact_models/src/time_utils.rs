@@ -945,6 +945,46 @@ pub fn generate_string(format: &str) -> Result<String, String> {
   }
 }
 
+/// Validates that the value is an ISO-8601 duration (i.e. `PT1H30M` or `P1Y2M10DT2H30M`)
+pub fn validate_duration(value: &str) -> Result<(), String> {
+  let error = || format!("Expected '{}' to be a valid ISO-8601 duration", value);
+  let rest = value.strip_prefix('P').ok_or_else(error)?;
+  let (date, time) = match rest.split_once('T') {
+    Some((date, time)) => (date, Some(time)),
+    None => (rest, None)
+  };
+  if rest.is_empty() || time == Some("") {
+    Err(error())
+  } else if valid_duration_parts(date, &['Y', 'M', 'W', 'D']) &&
+    time.map(|time| valid_duration_parts(time, &['H', 'M', 'S'])).unwrap_or(true) {
+    Ok(())
+  } else {
+    Err(error())
+  }
+}
+
+/// Checks that the value is a sequence of numbers followed by one of the designators, with the
+/// designators in order and not repeated. Numbers can have a decimal fraction.
+fn valid_duration_parts(value: &str, designators: &[char]) -> bool {
+  let mut remaining = designators;
+  let mut number = String::new();
+  for ch in value.chars() {
+    if ch.is_ascii_digit() {
+      number.push(ch);
+    } else if (ch == '.' || ch == ',') && !number.is_empty() && !number.contains(['.', ',']) {
+      number.push(ch);
+    } else if number.is_empty() || number.ends_with(['.', ',']) {
+      return false;
+    } else if let Some(index) = remaining.iter().position(|designator| *designator == ch) {
+      remaining = &remaining[index + 1..];
+      number.clear();
+    } else {
+      return false;
+    }
+  }
+  number.is_empty()
+}
+
 fn validate_tz_abbreviation(tz: &str) -> bool {
   ZONES_ABBR.contains_key(tz)
 }
@@ -1327,6 +1367,28 @@ mod tests {
     expect!(validate_datetime("5th quarter", "QQQQ")).to(be_err());
   }
 
+  #[test]
+  fn validate_duration_test() {
+    expect!(validate_duration("PT1H30M")).to(be_ok());
+    expect!(validate_duration("P1Y2M10DT2H30M")).to(be_ok());
+    expect!(validate_duration("P3W")).to(be_ok());
+    expect!(validate_duration("P1D")).to(be_ok());
+    expect!(validate_duration("PT0.5S")).to(be_ok());
+    expect!(validate_duration("PT1,5H")).to(be_ok());
+
+    expect!(validate_duration("abc")).to(be_err().value("Expected 'abc' to be a valid ISO-8601 duration".to_string()));
+    expect!(validate_duration("P")).to(be_err());
+    expect!(validate_duration("PT")).to(be_err());
+    expect!(validate_duration("P1H")).to(be_err());
+    expect!(validate_duration("PT1D")).to(be_err());
+    expect!(validate_duration("P1M1Y")).to(be_err());
+    expect!(validate_duration("PT1H1H")).to(be_err());
+    expect!(validate_duration("PT1.5.5S")).to(be_err());
+    expect!(validate_duration("PT1.S")).to(be_err());
+    expect!(validate_duration("P1Y2")).to(be_err());
+    expect!(validate_duration("1Y")).to(be_err());
+  }
+
   #[test]
   fn timezone_abbreviations() {
     expect!(validate_tz_abbreviation("AEST")).to(be_true());
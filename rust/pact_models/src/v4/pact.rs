@@ -1806,7 +1806,8 @@ mod tests {
                     rules: vec![MatchingRule::Type],
                     rule_logic: RuleLogic::And,
                     cascaded: false
-                  }}
+                  }},
+                  definitions: Default::default()
                 }
               }
             },
@@ -1827,7 +1828,8 @@ mod tests {
               rules: hashmap!{
                 Category::HEADER => MatchingRuleCategory {
                   name: Category::HEADER,
-                  rules: hashmap!{}
+                  rules: hashmap!{},
+                  definitions: Default::default()
                 }
               }
             },
@@ -17,6 +17,7 @@ use pact_models::v4::http_parts::HttpRequest;
 use tracing::warn;
 
 use crate::provider_client::make_state_change_request;
+use crate::verification_result::VerificationInteractionResult;
 
 /// Trait for executors that call request filters
 pub trait RequestFilterExecutor: Debug {
@@ -55,6 +56,35 @@ impl RequestFilterExecutor for NullRequestFilterExecutor {
   }
 }
 
+/// Callback that is invoked with the result of each interaction as soon as it has been verified,
+/// so that progress can be reported for long running verifications. The callback is invoked in
+/// the order that the interactions are verified, and never concurrently for the same pact.
+#[derive(Clone)]
+pub struct InteractionResultCallback {
+  callback: Arc<dyn Fn(&VerificationInteractionResult) + Send + Sync>
+}
+
+impl InteractionResultCallback {
+  /// Create a new callback from the given function
+  pub fn new<F>(callback: F) -> Self
+    where F: Fn(&VerificationInteractionResult) + Send + Sync + 'static {
+    InteractionResultCallback {
+      callback: Arc::new(callback)
+    }
+  }
+
+  /// Invoke the callback with the result of an interaction
+  pub fn call(&self, result: &VerificationInteractionResult) {
+    (self.callback)(result)
+  }
+}
+
+impl Debug for InteractionResultCallback {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "InteractionResultCallback")
+  }
+}
+
 /// Struct for returning errors from executing a provider state
 #[derive(Debug, Clone)]
 pub struct ProviderStateError {
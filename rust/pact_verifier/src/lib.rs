@@ -44,11 +44,11 @@ use tracing::{debug, debug_span, error, info, Instrument, instrument, trace, war
 
 pub use callback_executors::NullRequestFilterExecutor;
 use callback_executors::RequestFilterExecutor;
-use pact_matching::{match_response, Mismatch};
+use pact_matching::{match_response_to_request, MatchingConfig, Mismatch};
 use pact_matching::logging::LOG_ID;
 use pact_matching::metrics::{MetricEvent, send_metrics_async};
 
-use crate::callback_executors::{ProviderStateError, ProviderStateExecutor};
+use crate::callback_executors::{InteractionResultCallback, ProviderStateError, ProviderStateExecutor};
 use crate::messages::{process_message_result, process_sync_message_result, verify_message_from_provider, verify_sync_message_from_provider};
 use crate::metrics::VerificationMetrics;
 use crate::pact_broker::{
@@ -63,6 +63,7 @@ use crate::provider_client::make_provider_request;
 use crate::request_response::process_request_response_result;
 use crate::utils::as_safe_ref;
 use crate::verification_result::{
+  InteractionPart,
   VerificationExecutionResult,
   VerificationInteractionResult,
   VerificationResult
@@ -369,7 +370,8 @@ async fn verify_response_from_provider<F: RequestFilterExecutor>(
   });
   match make_provider_request(provider, &request, options, client, transport).await {
     Ok(ref actual_response) => {
-      let mismatches = match_response(expected_response.clone(), actual_response.clone(), pact, &interaction.boxed()).await;
+      let mismatches = match_response_to_request(expected_response.clone(), actual_response.clone(),
+        &request, pact, &interaction.boxed(), &options.matching_config).await;
       if mismatches.is_empty() {
         Ok(interaction.id.clone())
       } else {
@@ -755,6 +757,8 @@ fn walkdir(
         let path = entry.path();
         if path.is_dir() {
             walkdir(&path, provider)?;
+        } else if path.extension().map(|ext| ext == "lock").unwrap_or(false) {
+          trace!("Ignoring lock file {:?}", path);
         } else {
           match timeit(|| read_pact(&path)) {
             Ok((pact, tm)) => {
@@ -919,7 +923,12 @@ pub struct VerificationOptions<F> where F: RequestFilterExecutor {
   /// If coloured output should be used (using ANSI escape codes)
   pub coloured_output: bool,
   /// If no pacts are found to verify, then this should be an error
-  pub no_pacts_is_error: bool
+  pub no_pacts_is_error: bool,
+  /// Callback invoked with the result of each interaction as soon as it has been verified
+  pub interaction_result_callback: Option<InteractionResultCallback>,
+  /// Matching configuration applied to all the responses from the provider (i.e.
+  /// `MatchingConfig::strict()` to make all the matching strict)
+  pub matching_config: MatchingConfig
 }
 
 impl <F: RequestFilterExecutor> Default for VerificationOptions<F> {
@@ -930,7 +939,9 @@ impl <F: RequestFilterExecutor> Default for VerificationOptions<F> {
       request_timeout: 5000,
       custom_headers: Default::default(),
       coloured_output: true,
-      no_pacts_is_error: true
+      no_pacts_is_error: true,
+      interaction_result_callback: None,
+      matching_config: MatchingConfig::default()
     }
   }
 }
@@ -1410,19 +1421,19 @@ pub async fn verify_pact_internal<'a, F: RequestFilterExecutor, S: ProviderState
   let interactions = pact.interactions();
   let mut output = vec![];
 
-  let results: Vec<(Box<dyn Interaction + Send + Sync + RefUnwindSafe>, Result<(Option<String>, Vec<String>, Duration), (MismatchResult, Vec<String>, Duration)>)> =
-    futures::stream::iter(interactions.iter().map(|i| (&pact, i)))
+  let results = futures::stream::iter(interactions.iter().map(|i| (&pact, i)))
     .filter(|(_, interaction)| futures::future::ready(filter_interaction(interaction.as_ref(), filter)))
     .then( |(pact, interaction)| async move {
       let interaction_desc = interaction.description();
       (interaction.boxed(), verify_interaction(provider_info, interaction.as_ref(), &pact.boxed(), options, provider_state_executor)
         .instrument(debug_span!("verify_interaction", interaction = interaction_desc.as_str())).await)
-    })
-    .collect()
-    .await;
+    });
+  futures::pin_mut!(results);
 
+  // The results are processed as each interaction is verified, so that any interaction result
+  // callback can report progress
   let mut errors: Vec<VerificationInteractionResult> = vec![];
-  for (interaction, match_result) in results {
+  while let Some((interaction, match_result)) = results.next().await {
     let mut description = format!("Verifying a pact between {} and {}",
       pact.consumer().name.clone(), pact.provider().name.clone());
 
@@ -1513,30 +1524,20 @@ pub async fn verify_pact_internal<'a, F: RequestFilterExecutor, S: ProviderState
       }
     }
 
-    match match_result {
-      Ok(_) => {
-        errors.push(VerificationInteractionResult {
-          interaction_id: interaction.id(),
-          interaction_key,
-          description: description.clone(),
-          interaction_description: interaction.description(),
-          result: Ok(()),
-          pending: pending || interaction.pending(),
-          duration
-        });
-      },
-      Err(err) => {
-        errors.push(VerificationInteractionResult {
-          interaction_id: interaction.id(),
-          interaction_key,
-          description: description.clone(),
-          interaction_description: interaction.description(),
-          result: Err(err.clone()),
-          pending: pending || interaction.pending(),
-          duration
-        });
-      }
+    let interaction_result = VerificationInteractionResult {
+      interaction_id: interaction.id(),
+      interaction_key,
+      description: description.clone(),
+      interaction_description: interaction.description(),
+      result: match_result.map(|_| ()),
+      pending: pending || interaction.pending(),
+      duration,
+      parts: InteractionPart::parts_for(interaction.as_ref())
+    };
+    if let Some(callback) = &options.interaction_result_callback {
+      callback.call(&interaction_result);
     }
+    errors.push(interaction_result);
   }
 
   output.push(String::default());
@@ -19,7 +19,7 @@ use pact_models::v4::sync_message::SynchronousMessage;
 use serde_json::{json, Value};
 use tracing::{debug, trace, warn};
 
-use pact_matching::{match_message, match_sync_message_response, Mismatch};
+use pact_matching::{match_message_with_config, match_sync_message_response_with_config, Mismatch};
 
 use crate::{MismatchResult, ProviderInfo, ProviderTransport, VerificationOptions};
 use crate::callback_executors::RequestFilterExecutor;
@@ -98,7 +98,8 @@ pub(crate) async fn verify_message_from_provider<'a, F: RequestFilterExecutor>(
 
       debug!("actual message = {:?}", actual);
 
-      let mismatches = match_message(interaction, &actual.boxed(), pact).await;
+      let mismatches = match_message_with_config(interaction, &actual.boxed(), pact,
+        &options.matching_config).await;
       if mismatches.is_empty() {
         Ok(interaction.id().clone())
       } else {
@@ -339,7 +340,8 @@ pub(crate) async fn verify_sync_message_from_provider<'a, F: RequestFilterExecut
 
         debug!("actual synchronous message = {:?}", actual);
 
-        let mismatches = match_sync_message_response(&message, &message.response, &actual.response, pact).await;
+        let mismatches = match_sync_message_response_with_config(&message, &message.response,
+          &actual.response, pact, &options.matching_config).await;
         if mismatches.is_empty() {
           Ok(message.id().clone())
         } else {
@@ -11,7 +11,7 @@ use anyhow::anyhow;
 use futures::future::*;
 use http::{HeaderMap, HeaderValue, Method};
 use http::header::{HeaderName, InvalidHeaderName, InvalidHeaderValue};
-use http::header::CONTENT_TYPE;
+use http::header::{CONTENT_TYPE, TRANSFER_ENCODING};
 use http::method::InvalidMethod;
 use itertools::Itertools;
 use maplit::hashmap;
@@ -161,8 +161,37 @@ fn extract_headers(headers: &HeaderMap) -> Option<HashMap<String, Vec<String>>>
   }
 }
 
+fn is_chunked(headers: &HeaderMap) -> bool {
+  headers.get_all(TRANSFER_ENCODING).iter()
+    .filter_map(|value| value.to_str().ok())
+    .flat_map(|value| value.split(','))
+    .any(|encoding| encoding.trim().eq_ignore_ascii_case("chunked"))
+}
+
+/// If the body still has the chunked transfer encoding framing, i.e. it starts with a chunk size
+/// line and ends with the last (zero length) chunk
+fn has_chunk_framing(body: &[u8]) -> bool {
+  let size_line = body.iter().position(|b| *b == b'\r')
+    .filter(|index| body.get(index + 1) == Some(&b'\n'))
+    .map(|index| &body[..index]);
+  match size_line {
+    Some(line) => {
+      let size = line.split(|b| *b == b';').next().unwrap_or_default();
+      !size.is_empty() && size.iter().all(|b| b.is_ascii_hexdigit()) &&
+        (body == b"0\r\n\r\n" || body.ends_with(b"\r\n0\r\n\r\n"))
+    }
+    None => false
+  }
+}
+
 async fn extract_body(response: reqwest::Response, pact_response: &HttpResponse) -> anyhow::Result<OptionalBody> {
+  let chunked = is_chunked(response.headers());
+  // The HTTP client removes the transfer encoding framing (i.e. the chunk sizes of a chunked
+  // response) as the body is read, so this is the complete decoded body that gets matched
   let body = response.bytes().await?;
+  if chunked && has_chunk_framing(&body) {
+    return Err(anyhow!("Response body from the provider still has the chunked transfer encoding framing"));
+  }
   if !body.is_empty() {
     Ok(OptionalBody::Present(body, pact_response.content_type(), None))
   } else {
@@ -224,7 +253,11 @@ pub async fn make_provider_request<F: RequestFilterExecutor>(
   let request = create_native_request(client, &base_url, &request, &options.custom_headers)?;
 
   let response = request.send()
-    .map_err(|err| anyhow!(err))
+    .map_err(|err| if err.is_timeout() {
+      anyhow!("Request to provider timed out after {} ms", options.request_timeout)
+    } else {
+      anyhow!(err)
+    })
     .and_then(native_response_to_pact_response)
     .await?;
 
@@ -289,8 +322,17 @@ mod tests {
   use http::HeaderMap;
   use itertools::Itertools;
   use maplit::*;
+  use bytes::Bytes;
+  use pact_matching::match_response;
   use pact_models::bodies::OptionalBody;
-  use pact_models::v4::http_parts::HttpRequest;
+  use pact_models::pact::Pact;
+  use pact_models::interaction::Interaction;
+  use pact_models::v4::http_parts::{HttpRequest, HttpResponse};
+  use pact_models::v4::pact::V4Pact;
+  use pact_models::v4::synch_http::SynchronousHttp;
+  use tokio::io::{AsyncReadExt, AsyncWriteExt};
+  use tokio::net::TcpListener;
+  use tracing::instrument::WithSubscriber;
 
   use pact_consumer::builders::{HttpPartBuilder, PactBuilderAsync};
   use pact_consumer::mock_server::StartMockServer;
@@ -302,7 +344,7 @@ mod tests {
     VerificationOptions
   };
 
-  use super::{create_native_request, extract_headers, join_paths};
+  use super::{create_native_request, extract_headers, has_chunk_framing, is_chunked, join_paths};
 
   #[test]
   fn extract_headers_tests() {
@@ -491,6 +533,93 @@ mod tests {
     super::make_provider_request(&provider, &request, &options, &client, None).await.unwrap();
   }
 
+  #[test]
+  fn is_chunked_test() {
+    let mut headers = HeaderMap::new();
+    expect!(is_chunked(&headers)).to(be_false());
+    headers.insert("Transfer-Encoding", "gzip, Chunked".parse().unwrap());
+    expect!(is_chunked(&headers)).to(be_true());
+    headers.insert("Transfer-Encoding", "gzip".parse().unwrap());
+    expect!(is_chunked(&headers)).to(be_false());
+  }
+
+  #[test]
+  fn has_chunk_framing_test() {
+    expect!(has_chunk_framing(b"")).to(be_false());
+    expect!(has_chunk_framing(b"{\"id\": 100}")).to(be_false());
+    expect!(has_chunk_framing(b"0\r\n\r\n")).to(be_true());
+    expect!(has_chunk_framing(b"7\r\n{\"id\": \r\n4\r\n100}\r\n0\r\n\r\n")).to(be_true());
+    expect!(has_chunk_framing(b"a;ext=1\r\n0123456789\r\n0\r\n\r\n")).to(be_true());
+    expect!(has_chunk_framing(b"line one\r\nline two\r\n0\r\n\r\n")).to(be_false());
+    expect!(has_chunk_framing(b"7\r\n{\"id\": 100}")).to(be_false());
+  }
+
+  #[tokio::test]
+  async fn chunked_response_body_is_assembled_before_matching() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+      let (mut socket, _) = listener.accept().await.unwrap();
+      let mut request = vec![];
+      let mut buffer = [0_u8; 1024];
+      while !request.ends_with(b"\r\n\r\n") {
+        let read = socket.read(&mut buffer).await.unwrap();
+        if read == 0 {
+          break;
+        }
+        request.extend_from_slice(&buffer[..read]);
+      }
+      socket.write_all(b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nTransfer-Encoding: chunked\r\n\r\n\
+        7\r\n{\"id\": \r\n4\r\n100,\r\nE\r\n \"name\": \"Fred\r\n2\r\n\"}\r\n0\r\n\r\n").await.unwrap();
+      socket.shutdown().await.unwrap();
+    }.with_current_subscriber());
+
+    let options = VerificationOptions::<NullRequestFilterExecutor>::default();
+    let client = configure_http_client(&options).unwrap();
+    #[allow(deprecated)]
+    let provider = ProviderInfo {
+      port: Some(port),
+      .. ProviderInfo::default()
+    };
+    let response = super::make_provider_request(&provider, &HttpRequest::default(), &options, &client, None)
+      .await.unwrap();
+
+    expect!(response.body.value()).to(be_some().value(Bytes::from("{\"id\": 100, \"name\": \"Fred\"}")));
+
+    let expected = HttpResponse {
+      headers: Some(hashmap!{ "Content-Type".to_string() => vec![ "application/json".to_string() ] }),
+      body: OptionalBody::Present(Bytes::from("{\"id\": 100, \"name\": \"Fred\"}"), None, None),
+      .. HttpResponse::default()
+    };
+    let mismatches = match_response(expected, response, &V4Pact::default().boxed(),
+      &SynchronousHttp::default().boxed()).await;
+    expect!(mismatches).to(be_equal_to(vec![]));
+  }
+
+  #[tokio::test]
+  async fn make_provider_request_fails_if_the_provider_does_not_respond_in_time() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+      let (_socket, _) = listener.accept().await.unwrap();
+      tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+    }.with_current_subscriber());
+
+    let options = VerificationOptions::<NullRequestFilterExecutor> {
+      request_timeout: 100,
+      .. VerificationOptions::<NullRequestFilterExecutor>::default()
+    };
+    let client = configure_http_client(&options).unwrap();
+    #[allow(deprecated)]
+    let provider = ProviderInfo {
+      port: Some(port),
+      .. ProviderInfo::default()
+    };
+    let result = super::make_provider_request(&provider, &HttpRequest::default(), &options, &client, None).await;
+
+    expect!(result.unwrap_err().to_string()).to(be_equal_to("Request to provider timed out after 100 ms"));
+  }
+
   #[test]
   fn convert_request_to_native_request_with_custom_headers() {
     let client = reqwest::Client::new();
@@ -333,6 +333,7 @@ async fn publish_successful_result_to_broker() {
       result: Ok(()),
       pending: false,
       duration: Default::default(),
+      parts: vec![]
     }], &source, &options, None
   ).await;
 
@@ -356,6 +357,7 @@ async fn publish_successful_result_to_broker() {
       result: Ok(()),
       pending: false,
       duration: Default::default(),
+      parts: vec![]
     }], &source, &options, None
   ).await;
 }
@@ -7,6 +7,110 @@ use itertools::Itertools;
 use serde_json::{json, Value};
 
 use pact_matching::Mismatch;
+use pact_models::interaction::Interaction;
+
+/// Part of an interaction that is checked during verification
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum InteractionPart {
+  /// Response status code
+  Status,
+  /// Response header with the given name
+  Header(String),
+  /// Message metadata with the given key
+  Metadata(String),
+  /// Response or message body
+  Body
+}
+
+impl InteractionPart {
+  /// Returns the parts of the interaction that will be checked when it is verified
+  pub fn parts_for(interaction: &dyn Interaction) -> Vec<InteractionPart> {
+    let mut parts = vec![];
+    if let Some(http) = interaction.as_v4_http() {
+      parts.push(InteractionPart::Status);
+      if let Some(headers) = &http.response.headers {
+        parts.extend(headers.keys().sorted().map(|key| InteractionPart::Header(key.clone())));
+      }
+      if http.response.body.is_present() {
+        parts.push(InteractionPart::Body);
+      }
+    } else if let Some(message) = interaction.as_v4_async_message() {
+      parts.extend(message.contents.metadata.keys().sorted().map(|key| InteractionPart::Metadata(key.clone())));
+      if message.contents.contents.is_present() {
+        parts.push(InteractionPart::Body);
+      }
+    } else if let Some(message) = interaction.as_v4_sync_message() {
+      if let Some(response) = message.response.first() {
+        parts.extend(response.metadata.keys().sorted().map(|key| InteractionPart::Metadata(key.clone())));
+        if response.contents.is_present() {
+          parts.push(InteractionPart::Body);
+        }
+      }
+    }
+    parts
+  }
+
+  /// If the mismatch is for this part of the interaction
+  pub fn is_for(&self, mismatch: &Mismatch) -> bool {
+    match (self, mismatch) {
+      (InteractionPart::Status, Mismatch::StatusMismatch { .. }) => true,
+      (InteractionPart::Header(name), Mismatch::HeaderMismatch { key, .. }) => name.eq_ignore_ascii_case(key),
+      (InteractionPart::Metadata(name), Mismatch::MetadataMismatch { key, .. }) => name.eq_ignore_ascii_case(key),
+      (InteractionPart::Body, Mismatch::BodyMismatch { .. }) => true,
+      (InteractionPart::Body, Mismatch::BodyTypeMismatch { .. }) => true,
+      _ => false
+    }
+  }
+
+  fn for_mismatch(mismatch: &Mismatch) -> Option<InteractionPart> {
+    match mismatch {
+      Mismatch::StatusMismatch { .. } => Some(InteractionPart::Status),
+      Mismatch::HeaderMismatch { key, .. } => Some(InteractionPart::Header(key.clone())),
+      Mismatch::MetadataMismatch { key, .. } => Some(InteractionPart::Metadata(key.clone())),
+      Mismatch::BodyMismatch { .. } | Mismatch::BodyTypeMismatch { .. } => Some(InteractionPart::Body),
+      _ => None
+    }
+  }
+}
+
+impl Into<Value> for &InteractionPart {
+  fn into(self) -> Value {
+    match self {
+      InteractionPart::Status => json!({ "part": "status" }),
+      InteractionPart::Header(name) => json!({ "part": "header", "name": name }),
+      InteractionPart::Metadata(name) => json!({ "part": "metadata", "name": name }),
+      InteractionPart::Body => json!({ "part": "body" })
+    }
+  }
+}
+
+/// Result of verifying a part of an interaction
+#[derive(Clone, Debug, PartialEq)]
+pub struct InteractionPartResult {
+  /// Part of the interaction that was verified
+  pub part: InteractionPart,
+  /// Mismatches that occurred for the part. This will be empty if the part matched.
+  pub mismatches: Vec<Mismatch>
+}
+
+impl InteractionPartResult {
+  /// If the part of the interaction matched
+  pub fn passed(&self) -> bool {
+    self.mismatches.is_empty()
+  }
+}
+
+impl Into<Value> for &InteractionPartResult {
+  fn into(self) -> Value {
+    let mut json: Value = (&self.part).into();
+    let map = json.as_object_mut().unwrap();
+    map.insert("result".to_string(), json!(if self.passed() { "success" } else { "failure" }));
+    if !self.passed() {
+      map.insert("mismatches".to_string(), Value::Array(self.mismatches.iter().map(|m| m.to_json()).collect()));
+    }
+    json
+  }
+}
 
 /// Result of verifying a Pact interaction
 #[derive(Clone, Debug)]
@@ -24,7 +128,69 @@ pub struct VerificationInteractionResult {
   /// If the Pact or interaction is pending
   pub pending: bool,
   /// Duration that the verification took
-  pub duration: Duration
+  pub duration: Duration,
+  /// Parts of the interaction that were checked
+  pub parts: Vec<InteractionPart>
+}
+
+impl VerificationInteractionResult {
+  /// Returns the result for each part of the interaction that was checked. Any mismatches that
+  /// are not for one of the expected parts (i.e. a header that was not expected) are returned as
+  /// an additional part. This will be empty if there was an error verifying the interaction.
+  pub fn part_results(&self) -> Vec<InteractionPartResult> {
+    let mismatches = match &self.result {
+      Ok(_) => vec![],
+      Err(crate::MismatchResult::Mismatches { mismatches, .. }) => mismatches.clone(),
+      Err(crate::MismatchResult::Error(_, _)) => return vec![]
+    };
+
+    let mut results = self.parts.iter().map(|part| InteractionPartResult {
+      part: part.clone(),
+      mismatches: mismatches.iter().filter(|m| part.is_for(m)).cloned().collect()
+    }).collect_vec();
+    for mismatch in &mismatches {
+      if !self.parts.iter().any(|part| part.is_for(mismatch)) {
+        if let Some(part) = InteractionPart::for_mismatch(mismatch) {
+          match results.iter_mut().find(|result| result.part.is_for(mismatch)) {
+            Some(result) => result.mismatches.push(mismatch.clone()),
+            None => results.push(InteractionPartResult { part, mismatches: vec![mismatch.clone()] })
+          }
+        }
+      }
+    }
+    results
+  }
+}
+
+impl Into<Value> for &VerificationInteractionResult {
+  fn into(self) -> Value {
+    let mut json = json!({
+      "description": self.description,
+      "interactionDescription": self.interaction_description,
+      "interactionId": self.interaction_id.clone().unwrap_or_default(),
+      "interactionKey": self.interaction_key.clone().unwrap_or_default(),
+      "pending": self.pending,
+      "duration": self.duration.as_millis() as u64,
+      "parts": self.part_results().iter().map(|result| {
+        let json: Value = result.into();
+        json
+      }).collect_vec()
+    });
+    let map = json.as_object_mut().unwrap();
+    match &self.result {
+      Ok(_) => {
+        map.insert("result".to_string(), json!("success"));
+      }
+      Err(crate::MismatchResult::Mismatches { .. }) => {
+        map.insert("result".to_string(), json!("failure"));
+      }
+      Err(crate::MismatchResult::Error(error, _)) => {
+        map.insert("result".to_string(), json!("error"));
+        map.insert("error".to_string(), json!(error));
+      }
+    }
+    json
+  }
 }
 
 /// Result of verifying a Pact
@@ -172,8 +338,18 @@ mod tests {
 
   use pact_matching::Mismatch;
 
-  use crate::VerificationExecutionResult;
-  use crate::verification_result::VerificationMismatchResult;
+  use pact_models::bodies::OptionalBody;
+  use pact_models::prelude::v4::SynchronousHttp;
+  use pact_models::sync_interaction::RequestResponseInteraction;
+  use pact_models::v4::http_parts::HttpResponse;
+
+  use crate::{MismatchResult, VerificationExecutionResult};
+  use crate::verification_result::{
+    InteractionPart,
+    InteractionPartResult,
+    VerificationInteractionResult,
+    VerificationMismatchResult
+  };
 
   #[test]
   fn match_result_to_json() {
@@ -286,4 +462,130 @@ mod tests {
       "result": false
     })));
   }
+
+  #[test]
+  fn interaction_parts_for_http_interaction() {
+    let interaction = SynchronousHttp {
+      response: HttpResponse {
+        status: 200,
+        headers: Some(hashmap!{
+          "X-Request-Id".to_string() => vec!["1234".to_string()],
+          "Content-Type".to_string() => vec!["application/json".to_string()]
+        }),
+        body: OptionalBody::from("{}"),
+        .. HttpResponse::default()
+      },
+      .. SynchronousHttp::default()
+    };
+    expect!(InteractionPart::parts_for(&interaction)).to(be_equal_to(vec![
+      InteractionPart::Status,
+      InteractionPart::Header("Content-Type".to_string()),
+      InteractionPart::Header("X-Request-Id".to_string()),
+      InteractionPart::Body
+    ]));
+  }
+
+  fn interaction_result(result: Result<(), MismatchResult>) -> VerificationInteractionResult {
+    VerificationInteractionResult {
+      interaction_id: None,
+      interaction_key: Some("abc123".to_string()),
+      description: "Verifying a pact between test and test".to_string(),
+      interaction_description: "a request".to_string(),
+      result,
+      pending: false,
+      duration: Default::default(),
+      parts: vec![
+        InteractionPart::Status,
+        InteractionPart::Header("Content-Type".to_string()),
+        InteractionPart::Body
+      ]
+    }
+  }
+
+  #[test]
+  fn part_results_for_a_successful_interaction() {
+    let result = interaction_result(Ok(()));
+    expect!(result.part_results().iter().all(|r| r.passed())).to(be_true());
+    expect!(result.part_results().len()).to(be_equal_to(3));
+  }
+
+  #[test]
+  fn part_results_for_a_failed_interaction() {
+    let body_mismatch = Mismatch::BodyMismatch {
+      path: "$.id".to_string(),
+      expected: Some("100".into()),
+      actual: Some("200".into()),
+      mismatch: "Expected 100 but got 200".to_string()
+    };
+    let header_mismatch = Mismatch::HeaderMismatch {
+      key: "X-Request-Id".to_string(),
+      expected: "1234".to_string(),
+      actual: "".to_string(),
+      mismatch: "Expected header 'X-Request-Id' but was missing".to_string()
+    };
+    let result = interaction_result(Err(MismatchResult::Mismatches {
+      mismatches: vec![body_mismatch.clone(), header_mismatch.clone()],
+      expected: Box::new(RequestResponseInteraction::default()),
+      actual: Box::new(RequestResponseInteraction::default()),
+      interaction_id: None
+    }));
+    expect!(result.part_results()).to(be_equal_to(vec![
+      InteractionPartResult { part: InteractionPart::Status, mismatches: vec![] },
+      InteractionPartResult { part: InteractionPart::Header("Content-Type".to_string()), mismatches: vec![] },
+      InteractionPartResult { part: InteractionPart::Body, mismatches: vec![body_mismatch] },
+      InteractionPartResult { part: InteractionPart::Header("X-Request-Id".to_string()), mismatches: vec![header_mismatch] }
+    ]));
+
+    let json: Value = (&result).into();
+    expect!(json).to(be_equal_to(json!({
+      "description": "Verifying a pact between test and test",
+      "interactionDescription": "a request",
+      "interactionId": "",
+      "interactionKey": "abc123",
+      "pending": false,
+      "duration": 0,
+      "result": "failure",
+      "parts": [
+        { "part": "status", "result": "success" },
+        { "part": "header", "name": "Content-Type", "result": "success" },
+        {
+          "part": "body",
+          "result": "failure",
+          "mismatches": [
+            {
+              "actual": "200",
+              "expected": "100",
+              "mismatch": "Expected 100 but got 200",
+              "path": "$.id",
+              "type": "BodyMismatch"
+            }
+          ]
+        },
+        {
+          "part": "header",
+          "name": "X-Request-Id",
+          "result": "failure",
+          "mismatches": [
+            {
+              "actual": "",
+              "expected": "1234",
+              "key": "X-Request-Id",
+              "mismatch": "Expected header 'X-Request-Id' but was missing",
+              "type": "HeaderMismatch"
+            }
+          ]
+        }
+      ]
+    })));
+  }
+
+  #[test]
+  fn part_results_for_an_interaction_with_an_error() {
+    let result = interaction_result(Err(MismatchResult::Error("Boom!".to_string(), None)));
+    expect!(result.part_results()).to(be_equal_to(vec![]));
+
+    let json: Value = (&result).into();
+    expect!(&json["result"]).to(be_equal_to(&json!("error")));
+    expect!(&json["error"]).to(be_equal_to(&json!("Boom!")));
+  }
 }